@@ -18,7 +18,7 @@ use frame_support::BoundedSlice;
 
 pub use frame_support::pallet_prelude::Weight;
 use frame_support::traits::Currency;
-use pallet_loans::{InterestRateModel, JumpModel, Market, MarketState};
+use pallet_loans::{InterestRateModel, JumpModel, LiquidationMode, Market, MarketState};
 use pallet_traits::{
     ump::{XcmCall, XcmWeightFeeMisc},
     xcm::AssetType,
@@ -100,6 +100,11 @@ pub const fn market_mock(ptoken_id: u32) -> Market<Balance> {
         supply_cap: 1_000_000_000_000_000_000_000u128, // set to 1B
         borrow_cap: 1_000_000_000_000_000_000_000u128, // set to 1B
         ptoken_id,
+        isolated: false,
+        debt_ceiling: 0,
+        borrowable_in_isolation: false,
+        flash_loan_fee: Ratio::from_parts(900),
+        liquidation_mode: LiquidationMode::Instant,
     }
 }
 
@@ -37,7 +37,9 @@ pub trait RuntimeApiCollection:
     + sp_offchain::OffchainWorkerApi<Block>
     + sp_session::SessionKeys<Block>
     + cumulus_primitives_core::CollectCollationInfo<Block>
+    + pallet_amm_rpc::AMMRuntimeApi<Block, BlockNumber>
     + pallet_loans_rpc::LoansRuntimeApi<Block, AccountId, Balance>
+    + pallet_liquid_staking_rpc::LiquidStakingRuntimeApi<Block, AccountId, Balance>
     + pallet_router_rpc::RouterRuntimeApi<Block, Balance>
     + fp_rpc::EthereumRuntimeRPCApi<Block>
     + fp_rpc::ConvertTransactionRuntimeApi<Block>
@@ -59,7 +61,9 @@ where
         + sp_offchain::OffchainWorkerApi<Block>
         + sp_session::SessionKeys<Block>
         + cumulus_primitives_core::CollectCollationInfo<Block>
+        + pallet_amm_rpc::AMMRuntimeApi<Block, BlockNumber>
         + pallet_loans_rpc::LoansRuntimeApi<Block, AccountId, Balance>
+        + pallet_liquid_staking_rpc::LiquidStakingRuntimeApi<Block, AccountId, Balance>
         + pallet_router_rpc::RouterRuntimeApi<Block, Balance>
         + fp_rpc::EthereumRuntimeRPCApi<Block>
         + fp_rpc::ConvertTransactionRuntimeApi<Block>,
@@ -46,6 +46,8 @@ use std::sync::Arc;
 use substrate_frame_rpc_system::{System, SystemApiServer};
 
 use orml_oracle_rpc::{Oracle, OracleApiServer};
+use pallet_amm_rpc::{AMMApiServer, AMM};
+use pallet_liquid_staking_rpc::{LiquidStaking, LiquidStakingApiServer};
 use pallet_loans_rpc::{Loans, LoansApiServer};
 use pallet_router_rpc::{Router, RouterApiServer};
 
@@ -167,7 +169,9 @@ where
         + pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
         + BlockBuilder<Block>
         + orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
+        + pallet_amm_rpc::AMMRuntimeApi<Block, BlockNumber>
         + pallet_loans_rpc::LoansRuntimeApi<Block, AccountId, Balance>
+        + pallet_liquid_staking_rpc::LiquidStakingRuntimeApi<Block, AccountId, Balance>
         + pallet_router_rpc::RouterRuntimeApi<Block, Balance>
         + fp_rpc::ConvertTransactionRuntimeApi<Block>
         + fp_rpc::EthereumRuntimeRPCApi<Block>,
@@ -258,7 +262,9 @@ where
 
     io.merge(Oracle::new(client.clone()).into_rpc())?;
     io.merge(Loans::new(client.clone()).into_rpc())?;
+    io.merge(LiquidStaking::new(client.clone()).into_rpc())?;
     io.merge(Router::new(client.clone()).into_rpc())?;
+    io.merge(AMM::new(client.clone()).into_rpc())?;
 
     Ok(io)
 }
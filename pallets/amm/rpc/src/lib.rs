@@ -0,0 +1,115 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+pub use pallet_amm_rpc_runtime_api::AMMApi as AMMRuntimeApi;
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use primitives::{CurrencyId, Price};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+#[rpc(client, server)]
+pub trait AMMApi<BlockHash, BlockNumber>
+where
+    BlockNumber: Codec,
+{
+    #[method(name = "amm_getTwap")]
+    fn get_twap(
+        &self,
+        asset_in: CurrencyId,
+        asset_out: CurrencyId,
+        window: BlockNumber,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Price>;
+}
+
+/// A struct that implements the [`AMMApi`].
+pub struct AMM<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> AMM<C, B> {
+    /// Create new `AMM` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+pub enum Error {
+    RuntimeError,
+    AMMError,
+}
+
+impl From<Error> for i32 {
+    fn from(e: Error) -> i32 {
+        match e {
+            Error::RuntimeError => 1,
+            Error::AMMError => 2,
+        }
+    }
+}
+
+#[async_trait]
+impl<C, Block, BlockNumber> AMMApiServer<<Block as BlockT>::Hash, BlockNumber> for AMM<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<Block>,
+    C: HeaderBackend<Block>,
+    C::Api: AMMRuntimeApi<Block, BlockNumber>,
+    BlockNumber: Codec + Send + Sync + 'static,
+{
+    fn get_twap(
+        &self,
+        asset_in: CurrencyId,
+        asset_out: CurrencyId,
+        window: BlockNumber,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Price> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(self.client.info().best_hash));
+        api.get_twap(&at, asset_in, asset_out, window)
+            .map_err(runtime_error_into_rpc_error)?
+            .map_err(amm_rpc_error)
+    }
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_error(err: impl std::fmt::Debug) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+        Error::RuntimeError.into(),
+        "Runtime trapped",
+        Some(format!("{:?}", err)),
+    )))
+}
+
+fn amm_rpc_error(err: impl std::fmt::Debug) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+        Error::AMMError.into(),
+        "AMM error",
+        Some(format!("{:?}", err)),
+    )))
+}
@@ -22,6 +22,19 @@ const QUOTE_ASSET: CurrencyId = DOT;
 const INITIAL_AMOUNT: u128 = 1_000_000_000_000_000;
 const ASSET_ID: u32 = 10;
 const MINIMUM_LIQUIDITY: u128 = 1_000u128;
+const TICK_SPACING: i32 = 60;
+const TICK_RANGE: (i32, i32) = (-TICK_SPACING, TICK_SPACING);
+const POSITION_AMOUNT: u128 = 10_000_000_000_000;
+
+fn create_concentrated_pool<T: Config<I>, I: 'static>() {
+    assert_ok!(AMM::<T, I>::create_concentrated_pool(
+        T::CreatePoolOrigin::try_successful_origin()
+            .expect("No origin exists which can satisfy the guard"),
+        (BASE_ASSET, QUOTE_ASSET),
+        TICK_SPACING as u32,
+        Price::one()
+    ));
+}
 
 fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::RuntimeEvent) {
     frame_system::Pallet::<T>::assert_last_event(generic_event.into());
@@ -175,6 +188,69 @@ benchmarks_instance_pallet! {
     }
     verify {
     }
+
+    create_concentrated_pool {
+        let origin = T::CreatePoolOrigin::try_successful_origin().expect("No origin exists which can satisfy the guard");
+        let call = Call::<T, I>::create_concentrated_pool {
+            pair: (BASE_ASSET, QUOTE_ASSET),
+            tick_spacing: TICK_SPACING as u32,
+            initial_price: Price::one(),
+        };
+    }: {
+        call.dispatch_bypass_filter(origin)?
+    }
+    verify {
+        assert!(AMM::<T, I>::concentrated_pools(BASE_ASSET, QUOTE_ASSET).is_some());
+    }
+
+    add_concentrated_liquidity {
+        let caller: T::AccountId = whitelisted_caller();
+        initial_set_up::<T, I>(caller.clone());
+        create_concentrated_pool::<T, I>();
+    }: _(
+        SystemOrigin::Signed(caller.clone()),
+        (BASE_ASSET, QUOTE_ASSET),
+        TICK_RANGE,
+        (POSITION_AMOUNT, POSITION_AMOUNT),
+        (0u128, 0u128)
+    )
+    verify {
+        assert!(AMM::<T, I>::positions((BASE_ASSET, QUOTE_ASSET, &caller, TICK_RANGE)).is_some());
+    }
+
+    remove_concentrated_liquidity {
+        let caller: T::AccountId = whitelisted_caller();
+        initial_set_up::<T, I>(caller.clone());
+        create_concentrated_pool::<T, I>();
+        assert_ok!(AMM::<T, I>::add_concentrated_liquidity(SystemOrigin::Signed(caller.clone()).into(),
+            (BASE_ASSET, QUOTE_ASSET), TICK_RANGE, (POSITION_AMOUNT, POSITION_AMOUNT), (0u128, 0u128)));
+        let liquidity = AMM::<T, I>::positions((BASE_ASSET, QUOTE_ASSET, &caller, TICK_RANGE)).unwrap().liquidity;
+    }: _(
+        SystemOrigin::Signed(caller.clone()),
+        (BASE_ASSET, QUOTE_ASSET),
+        TICK_RANGE,
+        liquidity
+    )
+    verify {
+        assert!(AMM::<T, I>::positions((BASE_ASSET, QUOTE_ASSET, &caller, TICK_RANGE)).is_none());
+    }
+
+    collect_fees {
+        let caller: T::AccountId = whitelisted_caller();
+        initial_set_up::<T, I>(caller.clone());
+        create_concentrated_pool::<T, I>();
+        assert_ok!(AMM::<T, I>::add_concentrated_liquidity(SystemOrigin::Signed(caller.clone()).into(),
+            (BASE_ASSET, QUOTE_ASSET), TICK_RANGE, (POSITION_AMOUNT, POSITION_AMOUNT), (0u128, 0u128)));
+        assert_ok!(AMM::<T, I>::do_swap(&caller, (BASE_ASSET, QUOTE_ASSET), 10_000u128));
+    }: _(
+        SystemOrigin::Signed(caller.clone()),
+        (BASE_ASSET, QUOTE_ASSET),
+        TICK_RANGE
+    )
+    verify {
+        let position = AMM::<T, I>::positions((BASE_ASSET, QUOTE_ASSET, &caller, TICK_RANGE)).unwrap();
+        assert_eq!(position.fees_owed_base, 0);
+    }
 }
 
 impl_benchmark_test_suite!(AMM, crate::mock::new_test_ext(), crate::mock::Test,);
@@ -0,0 +1,1002 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Concentrated liquidity pools
+//!
+//! Liquidity providers of a concentrated liquidity pool choose the price range,
+//! as a pair of ticks, in which their liquidity is used. The price of tick `i` is
+//! `1.0001^i` base asset in quote asset. A pool tracks the square root of its current
+//! price and the liquidity of the positions whose range contains it, trades follow
+//! the constant product curve of that liquidity and crossing an initialized tick
+//! adds or removes the liquidity of the positions starting or ending at it.
+//!
+//! Trading fees are shared by the positions in range, through the fees earned per
+//! unit of liquidity by the pool and outside of each initialized tick.
+
+use super::*;
+use num_bigint::BigUint;
+
+/// Lowest tick a position can start at
+pub const MIN_TICK: i32 = -400_000;
+/// Highest tick a position can end at
+pub const MAX_TICK: i32 = 400_000;
+
+/// Ratio between the square root prices of two consecutive ticks, `sqrt(1.0001)`,
+/// as a `FixedU128` inner value
+const SQRT_TICK_RATIO: u128 = 1_000_049_998_750_062_496;
+
+const SCALE: u128 = <FixedU128 as FixedPointNumber>::DIV;
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ConcentratedPool<Balance> {
+    /// Square root of the price of the base asset in the quote asset
+    pub sqrt_price: FixedU128,
+    /// Greatest tick whose price is not above the current price
+    pub current_tick: i32,
+    /// Ticks of positions must be multiples of the spacing
+    pub tick_spacing: u32,
+    /// Liquidity of the positions in range
+    pub liquidity: Balance,
+    /// Base asset fees earned per unit of liquidity, wrapping on overflow
+    pub fee_growth_base: FixedU128,
+    /// Quote asset fees earned per unit of liquidity, wrapping on overflow
+    pub fee_growth_quote: FixedU128,
+    /// Base asset provided by the positions, without their fees
+    pub base_amount: Balance,
+    /// Quote asset provided by the positions, without their fees
+    pub quote_amount: Balance,
+}
+
+impl<Balance: Zero> ConcentratedPool<Balance> {
+    pub fn new(tick_spacing: u32, sqrt_price: FixedU128, current_tick: i32) -> Self {
+        Self {
+            sqrt_price,
+            current_tick,
+            tick_spacing,
+            liquidity: Zero::zero(),
+            fee_growth_base: Zero::zero(),
+            fee_growth_quote: Zero::zero(),
+            base_amount: Zero::zero(),
+            quote_amount: Zero::zero(),
+        }
+    }
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct TickInfo<Balance> {
+    /// Liquidity of the positions starting or ending at the tick
+    pub liquidity_gross: Balance,
+    /// Liquidity added to the pool when the price crosses the tick upwards
+    pub liquidity_net: i128,
+    /// Base asset fees earned per unit of liquidity on the other side of the tick
+    pub fee_growth_outside_base: FixedU128,
+    /// Quote asset fees earned per unit of liquidity on the other side of the tick
+    pub fee_growth_outside_quote: FixedU128,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Position<Balance> {
+    /// Liquidity provided in the range of the position
+    pub liquidity: Balance,
+    /// Base asset fees earned per unit of liquidity in range, when last updated
+    pub fee_growth_inside_base_last: FixedU128,
+    /// Quote asset fees earned per unit of liquidity in range, when last updated
+    pub fee_growth_inside_quote_last: FixedU128,
+    /// Base asset fees not collected yet
+    pub fees_owed_base: Balance,
+    /// Quote asset fees not collected yet
+    pub fees_owed_quote: Balance,
+}
+
+impl<Balance: Zero> Position<Balance> {
+    pub fn new((fee_growth_inside_base, fee_growth_inside_quote): (FixedU128, FixedU128)) -> Self {
+        Self {
+            liquidity: Zero::zero(),
+            fee_growth_inside_base_last: fee_growth_inside_base,
+            fee_growth_inside_quote_last: fee_growth_inside_quote,
+            fees_owed_base: Zero::zero(),
+            fees_owed_quote: Zero::zero(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.liquidity.is_zero() && self.fees_owed_base.is_zero() && self.fees_owed_quote.is_zero()
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ConcentratedSwap {
+    /// Amount of the input asset traded, fees included
+    pub amount_in: Balance,
+    /// Amount of the output asset traded
+    pub amount_out: Balance,
+    /// Part of the fees sent to the protocol fee receiver
+    pub protocol_fee: Balance,
+}
+
+struct SwapStep {
+    sqrt_price_next: FixedU128,
+    amount_in: Balance,
+    amount_out: Balance,
+    fee_amount: Balance,
+}
+
+fn div_rounding(
+    numerator: BigUint,
+    denominator: BigUint,
+    round_up: bool,
+) -> Result<Balance, ArithmeticError> {
+    if denominator.is_zero() {
+        return Err(ArithmeticError::DivisionByZero);
+    }
+
+    let quotient = &numerator / &denominator;
+    let quotient = if round_up && &quotient * &denominator != numerator {
+        quotient + 1u32
+    } else {
+        quotient
+    };
+
+    quotient.to_u128().ok_or(ArithmeticError::Overflow)
+}
+
+fn wrapping_add(a: FixedU128, b: FixedU128) -> FixedU128 {
+    FixedU128::from_inner(a.into_inner().wrapping_add(b.into_inner()))
+}
+
+fn wrapping_sub(a: FixedU128, b: FixedU128) -> FixedU128 {
+    FixedU128::from_inner(a.into_inner().wrapping_sub(b.into_inner()))
+}
+
+fn add_delta(liquidity: Balance, delta: i128) -> Result<Balance, ArithmeticError> {
+    if delta >= 0 {
+        liquidity
+            .checked_add(delta.unsigned_abs())
+            .ok_or(ArithmeticError::Overflow)
+    } else {
+        liquidity
+            .checked_sub(delta.unsigned_abs())
+            .ok_or(ArithmeticError::Underflow)
+    }
+}
+
+/// Returns the square root of the price of `tick`, `sqrt(1.0001)^tick`
+pub fn sqrt_price_at_tick(tick: i32) -> Result<FixedU128, ArithmeticError> {
+    let mut ratio = SCALE.get_big_uint();
+    let mut power = SQRT_TICK_RATIO.get_big_uint();
+    let mut exponent = tick.unsigned_abs();
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            ratio = ratio * &power / SCALE;
+        }
+        power = &power * &power / SCALE;
+        exponent >>= 1;
+    }
+
+    let inner = if tick < 0 {
+        div_rounding(SCALE.get_big_uint() * SCALE, ratio, false)?
+    } else {
+        ratio.to_u128().ok_or(ArithmeticError::Overflow)?
+    };
+
+    Ok(FixedU128::from_inner(inner))
+}
+
+/// Returns the greatest tick whose square root price is not above `sqrt_price`,
+/// within the usable ticks
+pub fn tick_at_sqrt_price(sqrt_price: FixedU128) -> Result<i32, ArithmeticError> {
+    let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if sqrt_price_at_tick(mid)? <= sqrt_price {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    Ok(low)
+}
+
+/// Returns the square root of `price`
+pub fn sqrt_price_from_price(price: Price) -> Result<FixedU128, ArithmeticError> {
+    let inner = (price.into_inner().get_big_uint() * SCALE)
+        .sqrt()
+        .to_u128()
+        .ok_or(ArithmeticError::Overflow)?;
+
+    Ok(FixedU128::from_inner(inner))
+}
+
+// amount of base asset between two square root prices for `liquidity`
+//
+// baseDelta = liquidity * (sqrtPriceB - sqrtPriceA) / (sqrtPriceA * sqrtPriceB)
+fn base_delta(
+    sqrt_price_a: FixedU128,
+    sqrt_price_b: FixedU128,
+    liquidity: Balance,
+    round_up: bool,
+) -> Result<Balance, ArithmeticError> {
+    let (a, b) = if sqrt_price_a < sqrt_price_b {
+        (sqrt_price_a.into_inner(), sqrt_price_b.into_inner())
+    } else {
+        (sqrt_price_b.into_inner(), sqrt_price_a.into_inner())
+    };
+
+    div_rounding(
+        liquidity.get_big_uint() * (b - a) * SCALE,
+        a.get_big_uint() * b,
+        round_up,
+    )
+}
+
+// amount of quote asset between two square root prices for `liquidity`
+//
+// quoteDelta = liquidity * (sqrtPriceB - sqrtPriceA)
+fn quote_delta(
+    sqrt_price_a: FixedU128,
+    sqrt_price_b: FixedU128,
+    liquidity: Balance,
+    round_up: bool,
+) -> Result<Balance, ArithmeticError> {
+    let (a, b) = if sqrt_price_a < sqrt_price_b {
+        (sqrt_price_a.into_inner(), sqrt_price_b.into_inner())
+    } else {
+        (sqrt_price_b.into_inner(), sqrt_price_a.into_inner())
+    };
+
+    div_rounding(
+        liquidity.get_big_uint() * (b - a),
+        SCALE.get_big_uint(),
+        round_up,
+    )
+}
+
+// liquidity provided by `amount` of base asset between two square root prices
+fn liquidity_for_base(
+    sqrt_price_a: FixedU128,
+    sqrt_price_b: FixedU128,
+    amount: Balance,
+) -> Result<Balance, ArithmeticError> {
+    let (a, b) = (sqrt_price_a.into_inner(), sqrt_price_b.into_inner());
+    div_rounding(
+        amount.get_big_uint() * a * b,
+        (b - a).get_big_uint() * SCALE,
+        false,
+    )
+}
+
+// liquidity provided by `amount` of quote asset between two square root prices
+fn liquidity_for_quote(
+    sqrt_price_a: FixedU128,
+    sqrt_price_b: FixedU128,
+    amount: Balance,
+) -> Result<Balance, ArithmeticError> {
+    let (a, b) = (sqrt_price_a.into_inner(), sqrt_price_b.into_inner());
+    div_rounding(amount.get_big_uint() * SCALE, (b - a).get_big_uint(), false)
+}
+
+// square root price after adding `amount` of the input asset, rounded against the trader
+//
+// base in:  sqrtPrice' = liquidity * sqrtPrice / (liquidity + amount * sqrtPrice)
+// quote in: sqrtPrice' = sqrtPrice + amount / liquidity
+fn next_sqrt_price_from_input(
+    sqrt_price: FixedU128,
+    liquidity: Balance,
+    amount: Balance,
+    base_in: bool,
+) -> Result<FixedU128, ArithmeticError> {
+    let p = sqrt_price.into_inner();
+    let inner = if base_in {
+        div_rounding(
+            liquidity.get_big_uint() * p * SCALE,
+            liquidity.get_big_uint() * SCALE + amount.get_big_uint() * p,
+            true,
+        )?
+    } else {
+        p.checked_add(div_rounding(
+            amount.get_big_uint() * SCALE,
+            liquidity.get_big_uint(),
+            false,
+        )?)
+        .ok_or(ArithmeticError::Overflow)?
+    };
+
+    Ok(FixedU128::from_inner(inner))
+}
+
+// square root price after removing `amount` of the output asset, rounded against the trader
+//
+// quote out: sqrtPrice' = sqrtPrice - amount / liquidity
+// base out:  sqrtPrice' = liquidity * sqrtPrice / (liquidity - amount * sqrtPrice)
+fn next_sqrt_price_from_output(
+    sqrt_price: FixedU128,
+    liquidity: Balance,
+    amount: Balance,
+    base_in: bool,
+) -> Result<FixedU128, ArithmeticError> {
+    let p = sqrt_price.into_inner();
+    let inner = if base_in {
+        p.checked_sub(div_rounding(
+            amount.get_big_uint() * SCALE,
+            liquidity.get_big_uint(),
+            true,
+        )?)
+        .ok_or(ArithmeticError::Underflow)?
+    } else {
+        let denominator = (liquidity.get_big_uint() * SCALE)
+            .checked_sub(&(amount.get_big_uint() * p))
+            .ok_or(ArithmeticError::Underflow)?;
+        div_rounding(liquidity.get_big_uint() * p * SCALE, denominator, true)?
+    };
+
+    Ok(FixedU128::from_inner(inner))
+}
+
+// trades within a single range of constant liquidity, from `sqrt_price` towards
+// `sqrt_price_target`, until either the target is reached or `amount_remaining` of
+// the input asset if `exact_input`, or of the output asset otherwise, is traded
+fn compute_swap_step(
+    sqrt_price: FixedU128,
+    sqrt_price_target: FixedU128,
+    liquidity: Balance,
+    amount_remaining: Balance,
+    exact_input: bool,
+    fee: Ratio,
+) -> Result<SwapStep, ArithmeticError> {
+    let base_in = sqrt_price >= sqrt_price_target;
+
+    let (mut amount_in, mut amount_out) = (Zero::zero(), Zero::zero());
+    let sqrt_price_next = if exact_input {
+        let amount_remaining_less_fee = amount_remaining
+            .checked_sub(fee.mul_ceil(amount_remaining))
+            .ok_or(ArithmeticError::Underflow)?;
+        amount_in = if base_in {
+            base_delta(sqrt_price_target, sqrt_price, liquidity, true)?
+        } else {
+            quote_delta(sqrt_price, sqrt_price_target, liquidity, true)?
+        };
+        if amount_remaining_less_fee >= amount_in {
+            sqrt_price_target
+        } else {
+            next_sqrt_price_from_input(sqrt_price, liquidity, amount_remaining_less_fee, base_in)?
+        }
+    } else {
+        amount_out = if base_in {
+            quote_delta(sqrt_price_target, sqrt_price, liquidity, false)?
+        } else {
+            base_delta(sqrt_price, sqrt_price_target, liquidity, false)?
+        };
+        if amount_remaining >= amount_out {
+            sqrt_price_target
+        } else {
+            next_sqrt_price_from_output(sqrt_price, liquidity, amount_remaining, base_in)?
+        }
+    };
+
+    let reached_target = sqrt_price_next == sqrt_price_target;
+    if !(reached_target && exact_input) {
+        amount_in = if base_in {
+            base_delta(sqrt_price_next, sqrt_price, liquidity, true)?
+        } else {
+            quote_delta(sqrt_price, sqrt_price_next, liquidity, true)?
+        };
+    }
+    if !(reached_target && !exact_input) {
+        amount_out = if base_in {
+            quote_delta(sqrt_price_next, sqrt_price, liquidity, false)?
+        } else {
+            base_delta(sqrt_price, sqrt_price_next, liquidity, false)?
+        };
+    }
+
+    if !exact_input {
+        amount_out = min(amount_out, amount_remaining);
+    }
+
+    let fee_amount = if exact_input && !reached_target {
+        amount_remaining
+            .checked_sub(amount_in)
+            .ok_or(ArithmeticError::Underflow)?
+    } else {
+        Ratio::from_percent(100)
+            .checked_sub(&fee)
+            .ok_or(ArithmeticError::Underflow)?
+            .saturating_reciprocal_mul_ceil(amount_in)
+            .checked_sub(amount_in)
+            .ok_or(ArithmeticError::Underflow)?
+    };
+
+    Ok(SwapStep {
+        sqrt_price_next,
+        amount_in,
+        amount_out,
+        fee_amount,
+    })
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+    pub(crate) fn ensure_valid_tick_range(
+        pool: &ConcentratedPool<BalanceOf<T, I>>,
+        (tick_lower, tick_upper): (i32, i32),
+    ) -> DispatchResult {
+        let tick_spacing = pool.tick_spacing as i32;
+        ensure!(
+            tick_lower < tick_upper
+                && tick_lower >= MIN_TICK
+                && tick_upper <= MAX_TICK
+                && tick_lower % tick_spacing == 0
+                && tick_upper % tick_spacing == 0,
+            Error::<T, I>::InvalidTickRange
+        );
+
+        Ok(())
+    }
+
+    // given a tick range and the amounts a provider is willing to add, returns the
+    // greatest liquidity they can provide at the current price of the pool
+    pub(crate) fn get_liquidity_for_amounts(
+        pool: &ConcentratedPool<BalanceOf<T, I>>,
+        (tick_lower, tick_upper): (i32, i32),
+        (base_amount, quote_amount): (BalanceOf<T, I>, BalanceOf<T, I>),
+    ) -> Result<BalanceOf<T, I>, DispatchError> {
+        let sqrt_price_lower = sqrt_price_at_tick(tick_lower)?;
+        let sqrt_price_upper = sqrt_price_at_tick(tick_upper)?;
+
+        if pool.current_tick < tick_lower {
+            return Ok(liquidity_for_base(
+                sqrt_price_lower,
+                sqrt_price_upper,
+                base_amount,
+            )?);
+        }
+
+        if pool.current_tick >= tick_upper {
+            return Ok(liquidity_for_quote(
+                sqrt_price_lower,
+                sqrt_price_upper,
+                quote_amount,
+            )?);
+        }
+
+        // the price can sit on a tick of the range, where the position only holds
+        // one of the assets
+        let liquidity_base = if pool.sqrt_price < sqrt_price_upper {
+            Some(liquidity_for_base(
+                pool.sqrt_price,
+                sqrt_price_upper,
+                base_amount,
+            )?)
+        } else {
+            None
+        };
+        let liquidity_quote = if pool.sqrt_price > sqrt_price_lower {
+            Some(liquidity_for_quote(
+                sqrt_price_lower,
+                pool.sqrt_price,
+                quote_amount,
+            )?)
+        } else {
+            None
+        };
+
+        Ok(match (liquidity_base, liquidity_quote) {
+            (Some(liquidity_base), Some(liquidity_quote)) => min(liquidity_base, liquidity_quote),
+            (Some(liquidity), None) | (None, Some(liquidity)) => liquidity,
+            (None, None) => Zero::zero(),
+        })
+    }
+
+    // amounts of base and quote assets backing `liquidity` in a tick range at the
+    // current price of the pool
+    pub(crate) fn get_amounts_for_liquidity(
+        pool: &ConcentratedPool<BalanceOf<T, I>>,
+        (tick_lower, tick_upper): (i32, i32),
+        liquidity: BalanceOf<T, I>,
+        round_up: bool,
+    ) -> Result<(BalanceOf<T, I>, BalanceOf<T, I>), DispatchError> {
+        let sqrt_price_lower = sqrt_price_at_tick(tick_lower)?;
+        let sqrt_price_upper = sqrt_price_at_tick(tick_upper)?;
+
+        if pool.current_tick < tick_lower {
+            let base_amount = base_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?;
+            Ok((base_amount, Zero::zero()))
+        } else if pool.current_tick < tick_upper {
+            let base_amount = base_delta(pool.sqrt_price, sqrt_price_upper, liquidity, round_up)?;
+            let quote_amount = quote_delta(sqrt_price_lower, pool.sqrt_price, liquidity, round_up)?;
+            Ok((base_amount, quote_amount))
+        } else {
+            let quote_amount =
+                quote_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?;
+            Ok((Zero::zero(), quote_amount))
+        }
+    }
+
+    // adds `liquidity_delta` to the liquidity of the positions starting (or ending if
+    // `upper`) at `tick`, initializing the tick if needed
+    fn update_tick(
+        ticks: &mut Vec<(i32, TickInfo<BalanceOf<T, I>>)>,
+        pool: &ConcentratedPool<BalanceOf<T, I>>,
+        tick: i32,
+        liquidity_delta: i128,
+        upper: bool,
+    ) -> DispatchResult {
+        let index = match ticks.binary_search_by_key(&tick, |&(tick, _)| tick) {
+            Ok(index) => index,
+            Err(index) => {
+                ensure!(
+                    ticks.len() < T::MaxTicks::get() as usize,
+                    Error::<T, I>::TooManyTicks
+                );
+
+                // by convention, all the fees were earned below the tick
+                let (fee_growth_outside_base, fee_growth_outside_quote) =
+                    if tick <= pool.current_tick {
+                        (pool.fee_growth_base, pool.fee_growth_quote)
+                    } else {
+                        (Zero::zero(), Zero::zero())
+                    };
+                ticks.insert(
+                    index,
+                    (
+                        tick,
+                        TickInfo {
+                            liquidity_gross: Zero::zero(),
+                            liquidity_net: 0,
+                            fee_growth_outside_base,
+                            fee_growth_outside_quote,
+                        },
+                    ),
+                );
+                index
+            }
+        };
+
+        let info = &mut ticks[index].1;
+        info.liquidity_gross = add_delta(info.liquidity_gross, liquidity_delta)?;
+        info.liquidity_net = if upper {
+            info.liquidity_net.checked_sub(liquidity_delta)
+        } else {
+            info.liquidity_net.checked_add(liquidity_delta)
+        }
+        .ok_or(ArithmeticError::Overflow)?;
+
+        Ok(())
+    }
+
+    // fees earned per unit of liquidity between two ticks
+    fn get_fee_growth_inside(
+        ticks: &[(i32, TickInfo<BalanceOf<T, I>>)],
+        pool: &ConcentratedPool<BalanceOf<T, I>>,
+        (tick_lower, tick_upper): (i32, i32),
+    ) -> (FixedU128, FixedU128) {
+        let fee_growth_outside = |tick: i32| {
+            ticks.binary_search_by_key(&tick, |&(tick, _)| tick).map_or(
+                (Zero::zero(), Zero::zero()),
+                |index| {
+                    let info = &ticks[index].1;
+                    (info.fee_growth_outside_base, info.fee_growth_outside_quote)
+                },
+            )
+        };
+        let global = (pool.fee_growth_base, pool.fee_growth_quote);
+
+        let (lower_base, lower_quote) = fee_growth_outside(tick_lower);
+        let (below_base, below_quote) = if pool.current_tick >= tick_lower {
+            (lower_base, lower_quote)
+        } else {
+            (
+                wrapping_sub(global.0, lower_base),
+                wrapping_sub(global.1, lower_quote),
+            )
+        };
+
+        let (upper_base, upper_quote) = fee_growth_outside(tick_upper);
+        let (above_base, above_quote) = if pool.current_tick < tick_upper {
+            (upper_base, upper_quote)
+        } else {
+            (
+                wrapping_sub(global.0, upper_base),
+                wrapping_sub(global.1, upper_quote),
+            )
+        };
+
+        (
+            wrapping_sub(wrapping_sub(global.0, below_base), above_base),
+            wrapping_sub(wrapping_sub(global.1, below_quote), above_quote),
+        )
+    }
+
+    // credits a position with the fees earned in its range since its last update
+    fn accrue_position_fees(
+        position: &mut Position<BalanceOf<T, I>>,
+        (fee_growth_inside_base, fee_growth_inside_quote): (FixedU128, FixedU128),
+    ) -> DispatchResult {
+        let earned = |fee_growth_inside: FixedU128, fee_growth_inside_last: FixedU128| {
+            div_rounding(
+                position.liquidity.get_big_uint()
+                    * wrapping_sub(fee_growth_inside, fee_growth_inside_last).into_inner(),
+                SCALE.get_big_uint(),
+                false,
+            )
+        };
+
+        let earned_base = earned(fee_growth_inside_base, position.fee_growth_inside_base_last)?;
+        let earned_quote = earned(
+            fee_growth_inside_quote,
+            position.fee_growth_inside_quote_last,
+        )?;
+
+        position.fees_owed_base = position
+            .fees_owed_base
+            .checked_add(earned_base)
+            .ok_or(ArithmeticError::Overflow)?;
+        position.fees_owed_quote = position
+            .fees_owed_quote
+            .checked_add(earned_quote)
+            .ok_or(ArithmeticError::Overflow)?;
+        position.fee_growth_inside_base_last = fee_growth_inside_base;
+        position.fee_growth_inside_quote_last = fee_growth_inside_quote;
+
+        Ok(())
+    }
+
+    // adds `liquidity_delta` to the position of `who` in a tick range, after crediting
+    // it with its fees, and returns the amounts of base and quote assets to be
+    // added to the pool (or removed from it if `liquidity_delta` is negative)
+    pub(crate) fn do_update_position(
+        who: &T::AccountId,
+        (base_asset, quote_asset): (AssetIdOf<T, I>, AssetIdOf<T, I>),
+        (tick_lower, tick_upper): (i32, i32),
+        liquidity_delta: i128,
+    ) -> Result<(BalanceOf<T, I>, BalanceOf<T, I>), DispatchError> {
+        let position_key = (base_asset, quote_asset, who, (tick_lower, tick_upper));
+        if liquidity_delta <= 0 {
+            let position =
+                Self::positions(position_key).ok_or(Error::<T, I>::PositionDoesNotExist)?;
+            ensure!(
+                position.liquidity >= liquidity_delta.unsigned_abs(),
+                Error::<T, I>::InsufficientLiquidity
+            );
+        }
+
+        ConcentratedPools::<T, I>::try_mutate(
+            base_asset,
+            quote_asset,
+            |pool| -> Result<(BalanceOf<T, I>, BalanceOf<T, I>), DispatchError> {
+                let pool = pool.as_mut().ok_or(Error::<T, I>::PoolDoesNotExist)?;
+                let mut ticks = Self::ticks(base_asset, quote_asset).into_inner();
+
+                if liquidity_delta != 0 {
+                    Self::update_tick(&mut ticks, pool, tick_lower, liquidity_delta, false)?;
+                    Self::update_tick(&mut ticks, pool, tick_upper, liquidity_delta, true)?;
+                }
+
+                let fee_growth_inside =
+                    Self::get_fee_growth_inside(&ticks, pool, (tick_lower, tick_upper));
+                Positions::<T, I>::try_mutate(position_key, |maybe_position| -> DispatchResult {
+                    let position =
+                        maybe_position.get_or_insert_with(|| Position::new(fee_growth_inside));
+                    Self::accrue_position_fees(position, fee_growth_inside)?;
+                    position.liquidity = add_delta(position.liquidity, liquidity_delta)?;
+                    ensure!(
+                        position.liquidity.is_zero()
+                            || position.liquidity >= T::MinPositionLiquidity::get(),
+                        Error::<T, I>::PositionTooSmall
+                    );
+                    if position.is_empty() {
+                        *maybe_position = None;
+                    }
+                    Ok(())
+                })?;
+
+                if tick_lower <= pool.current_tick && pool.current_tick < tick_upper {
+                    pool.liquidity = add_delta(pool.liquidity, liquidity_delta)?;
+                }
+
+                let (base_amount, quote_amount) = Self::get_amounts_for_liquidity(
+                    pool,
+                    (tick_lower, tick_upper),
+                    liquidity_delta.unsigned_abs(),
+                    liquidity_delta > 0,
+                )?;
+                if liquidity_delta > 0 {
+                    pool.base_amount = pool
+                        .base_amount
+                        .checked_add(base_amount)
+                        .ok_or(ArithmeticError::Overflow)?;
+                    pool.quote_amount = pool
+                        .quote_amount
+                        .checked_add(quote_amount)
+                        .ok_or(ArithmeticError::Overflow)?;
+                } else {
+                    pool.base_amount = pool
+                        .base_amount
+                        .checked_sub(base_amount)
+                        .ok_or(ArithmeticError::Underflow)?;
+                    pool.quote_amount = pool
+                        .quote_amount
+                        .checked_sub(quote_amount)
+                        .ok_or(ArithmeticError::Underflow)?;
+                }
+
+                ticks.retain(|(_, info)| !info.liquidity_gross.is_zero());
+                Ticks::<T, I>::insert(
+                    base_asset,
+                    quote_asset,
+                    BoundedVec::try_from(ticks).map_err(|_| Error::<T, I>::TooManyTicks)?,
+                );
+
+                Ok((base_amount, quote_amount))
+            },
+        )
+    }
+
+    // trades `amount` of the input asset if `exact_input`, or for `amount` of the output
+    // asset otherwise, through the pool, crossing its initialized ticks as needed
+    pub(crate) fn compute_concentrated_swap(
+        pool: &mut ConcentratedPool<BalanceOf<T, I>>,
+        ticks: &mut [(i32, TickInfo<BalanceOf<T, I>>)],
+        base_in: bool,
+        amount: BalanceOf<T, I>,
+        exact_input: bool,
+    ) -> Result<ConcentratedSwap, DispatchError> {
+        let protocol_fee = if Self::protocol_fee_on() {
+            Self::protocol_fee()
+        } else {
+            Zero::zero()
+        };
+
+        let mut swap = ConcentratedSwap::default();
+        let mut amount_remaining = amount;
+        while !amount_remaining.is_zero() {
+            let next_index = if base_in {
+                ticks
+                    .iter()
+                    .rposition(|&(tick, _)| tick <= pool.current_tick)
+            } else {
+                ticks.iter().position(|&(tick, _)| tick > pool.current_tick)
+            };
+            let target_tick = match next_index {
+                Some(index) => ticks[index].0,
+                None if base_in => MIN_TICK,
+                None => MAX_TICK,
+            };
+            let sqrt_price_target = sqrt_price_at_tick(target_tick)?;
+
+            let step = compute_swap_step(
+                pool.sqrt_price,
+                sqrt_price_target,
+                pool.liquidity,
+                amount_remaining,
+                exact_input,
+                T::LpFee::get(),
+            )?;
+
+            // no position earns the fees of a step without liquidity, so the
+            // trader keeps them
+            let fee_amount = if pool.liquidity.is_zero() {
+                Zero::zero()
+            } else {
+                step.fee_amount
+            };
+            let step_amount_in = step
+                .amount_in
+                .checked_add(fee_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            amount_remaining = amount_remaining
+                .checked_sub(if exact_input {
+                    step_amount_in
+                } else {
+                    step.amount_out
+                })
+                .ok_or(ArithmeticError::Underflow)?;
+            swap.amount_in = swap
+                .amount_in
+                .checked_add(step_amount_in)
+                .ok_or(ArithmeticError::Overflow)?;
+            swap.amount_out = swap
+                .amount_out
+                .checked_add(step.amount_out)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            let step_protocol_fee = protocol_fee.mul_floor(fee_amount);
+            swap.protocol_fee = swap
+                .protocol_fee
+                .checked_add(step_protocol_fee)
+                .ok_or(ArithmeticError::Overflow)?;
+            if !fee_amount.is_zero() {
+                let fee_growth = FixedU128::from_inner(div_rounding(
+                    (fee_amount - step_protocol_fee).get_big_uint() * SCALE,
+                    pool.liquidity.get_big_uint(),
+                    false,
+                )?);
+                if base_in {
+                    pool.fee_growth_base = wrapping_add(pool.fee_growth_base, fee_growth);
+                } else {
+                    pool.fee_growth_quote = wrapping_add(pool.fee_growth_quote, fee_growth);
+                }
+            }
+
+            let (reserve_in, reserve_out) = if base_in {
+                (&mut pool.base_amount, &mut pool.quote_amount)
+            } else {
+                (&mut pool.quote_amount, &mut pool.base_amount)
+            };
+            *reserve_in = reserve_in
+                .checked_add(step.amount_in)
+                .ok_or(ArithmeticError::Overflow)?;
+            *reserve_out = reserve_out
+                .checked_sub(step.amount_out)
+                .ok_or(ArithmeticError::Underflow)?;
+
+            pool.sqrt_price = step.sqrt_price_next;
+            match next_index {
+                Some(index) if step.sqrt_price_next == sqrt_price_target => {
+                    let info = &mut ticks[index].1;
+                    info.fee_growth_outside_base =
+                        wrapping_sub(pool.fee_growth_base, info.fee_growth_outside_base);
+                    info.fee_growth_outside_quote =
+                        wrapping_sub(pool.fee_growth_quote, info.fee_growth_outside_quote);
+
+                    let liquidity_net = if base_in {
+                        info.liquidity_net
+                            .checked_neg()
+                            .ok_or(ArithmeticError::Overflow)?
+                    } else {
+                        info.liquidity_net
+                    };
+                    pool.liquidity = add_delta(pool.liquidity, liquidity_net)?;
+                    pool.current_tick = if base_in {
+                        target_tick - 1
+                    } else {
+                        target_tick
+                    };
+                }
+                None if step.sqrt_price_next == sqrt_price_target => {
+                    // no liquidity left in the direction of the trade
+                    ensure!(
+                        amount_remaining.is_zero(),
+                        Error::<T, I>::InsufficientSupplyOut
+                    );
+                    pool.current_tick = tick_at_sqrt_price(pool.sqrt_price)?;
+                }
+                _ => {
+                    pool.current_tick = tick_at_sqrt_price(pool.sqrt_price)?;
+                }
+            }
+        }
+
+        log::trace!(
+            target: "amm::compute_concentrated_swap",
+            "base_in: {:?}, amount: {:?}, exact_input: {:?}, amount_in: {:?}, amount_out: {:?},\
+             protocol_fee: {:?}, sqrt_price: {:?}, current_tick: {:?}",
+            &base_in,
+            &amount,
+            &exact_input,
+            &swap.amount_in,
+            &swap.amount_out,
+            &swap.protocol_fee,
+            &pool.sqrt_price,
+            &pool.current_tick,
+        );
+
+        Ok(swap)
+    }
+
+    // given an amount in (or out if not `exact_input`) of an asset, returns the amount
+    // out (or in) of the other asset when trading through their concentrated liquidity pool
+    pub(crate) fn get_concentrated_amount(
+        asset_in: AssetIdOf<T, I>,
+        asset_out: AssetIdOf<T, I>,
+        amount: BalanceOf<T, I>,
+        exact_input: bool,
+    ) -> Result<BalanceOf<T, I>, DispatchError> {
+        let (is_inverted, base_asset, quote_asset) = Self::sort_assets((asset_in, asset_out))?;
+        let mut pool = Self::concentrated_pools(base_asset, quote_asset)
+            .ok_or(Error::<T, I>::PoolDoesNotExist)?;
+        let mut ticks = Self::ticks(base_asset, quote_asset).into_inner();
+
+        let swap = Self::compute_concentrated_swap(
+            &mut pool,
+            &mut ticks,
+            !is_inverted,
+            amount,
+            exact_input,
+        )?;
+
+        Ok(if exact_input {
+            swap.amount_out
+        } else {
+            swap.amount_in
+        })
+    }
+
+    pub(crate) fn do_concentrated_swap(
+        who: &T::AccountId,
+        (asset_in, asset_out): (AssetIdOf<T, I>, AssetIdOf<T, I>),
+        amount_in: BalanceOf<T, I>,
+    ) -> Result<BalanceOf<T, I>, DispatchError> {
+        let (is_inverted, base_asset, quote_asset) = Self::sort_assets((asset_in, asset_out))?;
+
+        ensure!(
+            amount_in >= T::LpFee::get().saturating_reciprocal_mul_ceil(One::one()),
+            Error::<T, I>::InsufficientAmountIn
+        );
+
+        let mut pool = Self::concentrated_pools(base_asset, quote_asset)
+            .ok_or(Error::<T, I>::PoolDoesNotExist)?;
+        let mut ticks = Self::ticks(base_asset, quote_asset).into_inner();
+
+        let swap =
+            Self::compute_concentrated_swap(&mut pool, &mut ticks, !is_inverted, amount_in, true)?;
+        ensure!(
+            !swap.amount_out.is_zero(),
+            Error::<T, I>::InsufficientAmountOut
+        );
+
+        T::Assets::transfer(
+            asset_in,
+            who,
+            &Self::account_id(),
+            amount_in,
+            asset_in == T::GetNativeCurrencyId::get(), // should keep alive if is native
+        )?;
+        T::Assets::transfer(
+            asset_out,
+            &Self::account_id(),
+            who,
+            swap.amount_out,
+            asset_out == T::GetNativeCurrencyId::get(), // should keep alive if is native
+        )?;
+        if !swap.protocol_fee.is_zero() {
+            T::Assets::transfer(
+                asset_in,
+                &Self::account_id(),
+                &Self::protolcol_fee_receiver()?,
+                swap.protocol_fee,
+                false,
+            )?;
+        }
+
+        ConcentratedPools::<T, I>::insert(base_asset, quote_asset, &pool);
+        Ticks::<T, I>::insert(
+            base_asset,
+            quote_asset,
+            BoundedVec::try_from(ticks).map_err(|_| Error::<T, I>::TooManyTicks)?,
+        );
+
+        log::trace!(
+            target: "amm::do_concentrated_swap",
+            "who: {:?}, asset_in: {:?}, asset_out: {:?}, amount_in: {:?}, amount_out: {:?}",
+            &who,
+            &asset_in,
+            &asset_out,
+            &amount_in,
+            &swap.amount_out,
+        );
+
+        Self::deposit_event(Event::<T, I>::ConcentratedTraded(
+            who.clone(),
+            asset_in,
+            asset_out,
+            amount_in,
+            swap.amount_out,
+            pool.sqrt_price,
+            pool.current_tick,
+        ));
+
+        Ok(swap.amount_out)
+    }
+}
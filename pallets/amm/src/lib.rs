@@ -26,6 +26,8 @@ mod mock;
 mod tests;
 
 mod benchmarking;
+mod concentrated;
+pub mod migrations;
 pub mod weights;
 
 use frame_support::{
@@ -41,13 +43,14 @@ use frame_support::{
 };
 use frame_system::{ensure_signed, pallet_prelude::OriginFor};
 use pallet_traits::{ConvertToBigUint, Pool};
-use primitives::{Balance, CurrencyId, Ratio};
+use primitives::{Balance, CurrencyId, Price, Ratio};
 use sp_runtime::{
     traits::{AccountIdConversion, CheckedAdd, CheckedSub, One, Saturating, Zero},
     ArithmeticError, DispatchError, FixedPointNumber, FixedU128, SaturatedConversion,
 };
 use sp_std::{cmp::min, result::Result, vec::Vec};
 
+pub use concentrated::{ConcentratedPool, Position, TickInfo, MAX_TICK, MIN_TICK};
 pub use pallet::*;
 pub use weights::WeightInfo;
 
@@ -60,6 +63,16 @@ pub type AssetIdOf<T, I = ()> =
 pub type BalanceOf<T, I = ()> =
     <<T as Config<I>>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
+/// The cumulative prices of a pool at a block. The time weighted average price
+/// between two observations is the difference of their cumulative prices divided
+/// by the number of blocks between them.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Observation<Balance, BlockNumber> {
+    pub block_number: BlockNumber,
+    pub price_0_cumulative: Balance,
+    pub price_1_cumulative: Balance,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -113,6 +126,23 @@ pub mod pallet {
         #[pallet::constant]
         type MaxLengthRoute: Get<u32>;
 
+        /// How many price observations are kept per pool
+        #[pallet::constant]
+        type MaxObservations: Get<u32>;
+
+        /// Minimum number of blocks between two price observations of a pool
+        #[pallet::constant]
+        type ObservationPeriod: Get<Self::BlockNumber>;
+
+        /// How many ticks can be initialized in a concentrated liquidity pool
+        #[pallet::constant]
+        type MaxTicks: Get<u32>;
+
+        /// Minimum liquidity of a position in a concentrated liquidity pool, so that
+        /// initializing the `MaxTicks` ticks of a pool isn't cheap
+        #[pallet::constant]
+        type MinPositionLiquidity: Get<BalanceOf<Self, I>>;
+
         #[pallet::constant]
         type GetNativeCurrencyId: Get<AssetIdOf<Self, I>>;
     }
@@ -141,6 +171,22 @@ pub mod pallet {
         ConversionToU128Failed,
         /// Protocol fee receiver not set
         ProtocolFeeReceiverNotSet,
+        /// TWAP window must be greater than zero
+        InvalidTwapWindow,
+        /// No price observation is old enough to cover the TWAP window
+        InsufficientObservations,
+        /// Tick spacing must be greater than zero and not above the max tick
+        InvalidTickSpacing,
+        /// Price is out of the range of usable ticks
+        InvalidPrice,
+        /// Ticks must be ordered, usable and multiples of the tick spacing
+        InvalidTickRange,
+        /// Too many ticks are initialized in the pool
+        TooManyTicks,
+        /// Position does not exist
+        PositionDoesNotExist,
+        /// Position liquidity is below `MinPositionLiquidity`
+        PositionTooSmall,
     }
 
     #[pallet::event]
@@ -197,6 +243,56 @@ pub mod pallet {
 
         /// Protocol fee receiver updated
         ProtocolFeeReceiverUpdated(T::AccountId),
+
+        /// A concentrated liquidity pool has been created
+        /// [base_currency_id, quote_currency_id, tick_spacing, sqrt_price, current_tick]
+        ConcentratedPoolCreated(AssetIdOf<T, I>, AssetIdOf<T, I>, u32, FixedU128, i32),
+        /// Add liquidity into a price range of a concentrated liquidity pool
+        /// [sender, base_currency_id, quote_currency_id, tick_lower, tick_upper, liquidity, base_amount_added, quote_amount_added]
+        ConcentratedLiquidityAdded(
+            T::AccountId,
+            AssetIdOf<T, I>,
+            AssetIdOf<T, I>,
+            i32,
+            i32,
+            BalanceOf<T, I>,
+            BalanceOf<T, I>,
+            BalanceOf<T, I>,
+        ),
+        /// Remove liquidity from a price range of a concentrated liquidity pool
+        /// [sender, base_currency_id, quote_currency_id, tick_lower, tick_upper, liquidity, base_amount_removed, quote_amount_removed]
+        ConcentratedLiquidityRemoved(
+            T::AccountId,
+            AssetIdOf<T, I>,
+            AssetIdOf<T, I>,
+            i32,
+            i32,
+            BalanceOf<T, I>,
+            BalanceOf<T, I>,
+            BalanceOf<T, I>,
+        ),
+        /// Collect the fees earned by a position
+        /// [sender, base_currency_id, quote_currency_id, tick_lower, tick_upper, base_amount_collected, quote_amount_collected]
+        FeesCollected(
+            T::AccountId,
+            AssetIdOf<T, I>,
+            AssetIdOf<T, I>,
+            i32,
+            i32,
+            BalanceOf<T, I>,
+            BalanceOf<T, I>,
+        ),
+        /// Trade using the liquidity of a concentrated liquidity pool
+        /// [trader, currency_id_in, currency_id_out, amount_in, amount_out, sqrt_price, current_tick]
+        ConcentratedTraded(
+            T::AccountId,
+            AssetIdOf<T, I>,
+            AssetIdOf<T, I>,
+            BalanceOf<T, I>,
+            BalanceOf<T, I>,
+            FixedU128,
+            i32,
+        ),
     }
 
     #[pallet::pallet]
@@ -224,6 +320,91 @@ pub mod pallet {
     #[pallet::storage]
     pub type ProtocolFeeReceiver<T: Config<I>, I: 'static = ()> = StorageValue<_, T::AccountId>;
 
+    /// Price observations of each pool, oldest first, taken by trades at most
+    /// once every `ObservationPeriod` blocks
+    #[pallet::storage]
+    #[pallet::getter(fn observations)]
+    pub type Observations<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        BoundedVec<Observation<BalanceOf<T, I>, T::BlockNumber>, T::MaxObservations>,
+        ValueQuery,
+    >;
+
+    /// Concentrated liquidity pools, a pair has at most one pool of any kind
+    #[pallet::storage]
+    #[pallet::getter(fn concentrated_pools)]
+    pub type ConcentratedPools<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        ConcentratedPool<BalanceOf<T, I>>,
+        OptionQuery,
+    >;
+
+    /// Initialized ticks of each concentrated liquidity pool, sorted by tick
+    #[pallet::storage]
+    #[pallet::getter(fn ticks)]
+    pub type Ticks<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        BoundedVec<(i32, TickInfo<BalanceOf<T, I>>), T::MaxTicks>,
+        ValueQuery,
+    >;
+
+    /// Positions of accounts in concentrated liquidity pools, by tick range
+    #[pallet::storage]
+    #[pallet::getter(fn positions)]
+    pub type Positions<T: Config<I>, I: 'static = ()> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, AssetIdOf<T, I>>,
+            NMapKey<Blake2_128Concat, AssetIdOf<T, I>>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+            NMapKey<Blake2_128Concat, (i32, i32)>,
+        ),
+        Position<BalanceOf<T, I>>,
+        OptionQuery,
+    >;
+
+    /// The pools trading each asset, keyed by the asset and the other asset of the pool
+    #[pallet::storage]
+    pub type AssetPools<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        (),
+        OptionQuery,
+    >;
+
+    /// Utility type for managing upgrades/migrations.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+    pub enum Versions {
+        V0,
+        V1,
+    }
+
+    /// DefaultVersion is using for initialize the StorageVersion
+    #[pallet::type_value]
+    pub(super) fn DefaultVersion<T: Config<I>, I: 'static>() -> Versions {
+        Versions::V0
+    }
+
+    /// Storage version of the pallet.
+    #[pallet::storage]
+    pub(crate) type StorageVersion<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, Versions, ValueQuery, DefaultVersion<T, I>>;
+
     #[pallet::call]
     impl<T: Config<I>, I: 'static> Pallet<T, I> {
         /// Allow users to add liquidity to a given pool
@@ -381,7 +562,8 @@ pub mod pallet {
 
             let (is_inverted, base_asset, quote_asset) = Self::sort_assets(pair)?;
             ensure!(
-                !Pools::<T, I>::contains_key(base_asset, quote_asset),
+                !Pools::<T, I>::contains_key(base_asset, quote_asset)
+                    && !ConcentratedPools::<T, I>::contains_key(base_asset, quote_asset),
                 Error::<T, I>::PoolAlreadyExists
             );
 
@@ -415,6 +597,7 @@ pub mod pallet {
             )?;
 
             Pools::<T, I>::insert(base_asset, quote_asset, pool);
+            Self::index_pool(base_asset, quote_asset);
 
             log::trace!(
                 target: "amm::create_pool",
@@ -469,6 +652,323 @@ pub mod pallet {
             ));
             Ok(().into())
         }
+
+        /// Create of a new concentrated liquidity pool, governance only
+        ///
+        /// - `pair`: Currency pool, which must not have a pool of any kind yet
+        /// - `tick_spacing`: Ticks of positions must be multiples of the tick spacing
+        /// - `initial_price`: Price of `pair.0` in `pair.1`
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::AMMWeightInfo::create_concentrated_pool())]
+        #[transactional]
+        pub fn create_concentrated_pool(
+            origin: OriginFor<T>,
+            pair: (AssetIdOf<T, I>, AssetIdOf<T, I>),
+            tick_spacing: u32,
+            initial_price: Price,
+        ) -> DispatchResultWithPostInfo {
+            T::CreatePoolOrigin::ensure_origin(origin)?;
+
+            let (is_inverted, base_asset, quote_asset) = Self::sort_assets(pair)?;
+            ensure!(
+                !Pools::<T, I>::contains_key(base_asset, quote_asset)
+                    && !ConcentratedPools::<T, I>::contains_key(base_asset, quote_asset),
+                Error::<T, I>::PoolAlreadyExists
+            );
+            ensure!(
+                tick_spacing > 0 && tick_spacing <= MAX_TICK as u32,
+                Error::<T, I>::InvalidTickSpacing
+            );
+
+            let price = if is_inverted {
+                initial_price
+                    .reciprocal()
+                    .ok_or(Error::<T, I>::InvalidPrice)?
+            } else {
+                initial_price
+            };
+            let sqrt_price = concentrated::sqrt_price_from_price(price)?;
+            ensure!(
+                sqrt_price >= concentrated::sqrt_price_at_tick(MIN_TICK)?
+                    && sqrt_price < concentrated::sqrt_price_at_tick(MAX_TICK)?,
+                Error::<T, I>::InvalidPrice
+            );
+            let current_tick = concentrated::tick_at_sqrt_price(sqrt_price)?;
+
+            ConcentratedPools::<T, I>::insert(
+                base_asset,
+                quote_asset,
+                ConcentratedPool::new(tick_spacing, sqrt_price, current_tick),
+            );
+            Self::index_pool(base_asset, quote_asset);
+
+            log::trace!(
+                target: "amm::create_concentrated_pool",
+                "base_asset: {:?}, quote_asset: {:?}, tick_spacing: {:?}, initial_price: {:?},\
+                 sqrt_price: {:?}, current_tick: {:?}",
+                &base_asset,
+                &quote_asset,
+                &tick_spacing,
+                &initial_price,
+                &sqrt_price,
+                &current_tick
+            );
+
+            Self::deposit_event(Event::<T, I>::ConcentratedPoolCreated(
+                base_asset,
+                quote_asset,
+                tick_spacing,
+                sqrt_price,
+                current_tick,
+            ));
+
+            Ok(().into())
+        }
+
+        /// Allow users to add liquidity to a price range of a concentrated liquidity pool
+        ///
+        /// - `pair`: Currency pool, in which liquidity will be added
+        /// - `tick_range`: Lower and upper ticks of the price of the base asset in the
+        ///   quote asset, which is the asset with the smaller id
+        /// - `desired_amounts`: Maximum amounts to be added in pool
+        /// - `minimum_amounts`: specifying its "worst case" amounts
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::AMMWeightInfo::add_concentrated_liquidity())]
+        #[transactional]
+        pub fn add_concentrated_liquidity(
+            origin: OriginFor<T>,
+            pair: (AssetIdOf<T, I>, AssetIdOf<T, I>),
+            tick_range: (i32, i32),
+            desired_amounts: (BalanceOf<T, I>, BalanceOf<T, I>),
+            minimum_amounts: (BalanceOf<T, I>, BalanceOf<T, I>),
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let (is_inverted, base_asset, quote_asset) = Self::sort_assets(pair)?;
+
+            let (base_amount, quote_amount) = if is_inverted {
+                (desired_amounts.1, desired_amounts.0)
+            } else {
+                (desired_amounts.0, desired_amounts.1)
+            };
+
+            let (minimum_base_amount, minimum_quote_amount) = if is_inverted {
+                (minimum_amounts.1, minimum_amounts.0)
+            } else {
+                (minimum_amounts.0, minimum_amounts.1)
+            };
+
+            let pool = Self::concentrated_pools(base_asset, quote_asset)
+                .ok_or(Error::<T, I>::PoolDoesNotExist)?;
+            Self::ensure_valid_tick_range(&pool, tick_range)?;
+
+            let liquidity =
+                Self::get_liquidity_for_amounts(&pool, tick_range, (base_amount, quote_amount))?;
+            ensure!(!liquidity.is_zero(), Error::<T, I>::InsufficientAmountIn);
+
+            let (base_amount_added, quote_amount_added) = Self::do_update_position(
+                &who,
+                (base_asset, quote_asset),
+                tick_range,
+                i128::try_from(liquidity).map_err(|_| ArithmeticError::Overflow)?,
+            )?;
+
+            ensure!(
+                base_amount_added >= minimum_base_amount
+                    && quote_amount_added >= minimum_quote_amount,
+                Error::<T, I>::NotAnIdealPrice
+            );
+
+            for (asset, amount) in [
+                (base_asset, base_amount_added),
+                (quote_asset, quote_amount_added),
+            ] {
+                if !amount.is_zero() {
+                    T::Assets::transfer(
+                        asset,
+                        &who,
+                        &Self::account_id(),
+                        amount,
+                        asset == T::GetNativeCurrencyId::get(), // should keep alive if is native
+                    )?;
+                }
+            }
+
+            log::trace!(
+                target: "amm::add_concentrated_liquidity",
+                "who: {:?}, base_asset: {:?}, quote_asset: {:?}, tick_range: {:?}, liquidity: {:?},\
+                 desired_amounts: {:?}, minimum_amounts: {:?}",
+                &who,
+                &base_asset,
+                &quote_asset,
+                &tick_range,
+                &liquidity,
+                &desired_amounts,
+                &minimum_amounts
+            );
+
+            Self::deposit_event(Event::<T, I>::ConcentratedLiquidityAdded(
+                who,
+                base_asset,
+                quote_asset,
+                tick_range.0,
+                tick_range.1,
+                liquidity,
+                base_amount_added,
+                quote_amount_added,
+            ));
+
+            Ok(().into())
+        }
+
+        /// Allow users to remove liquidity from their position in a concentrated liquidity pool,
+        /// the fees earned by the position are kept until collected
+        ///
+        /// - `pair`: Currency pool, in which liquidity will be removed
+        /// - `tick_range`: Lower and upper ticks of the position
+        /// - `liquidity`: liquidity to be removed from the position
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::AMMWeightInfo::remove_concentrated_liquidity())]
+        #[transactional]
+        pub fn remove_concentrated_liquidity(
+            origin: OriginFor<T>,
+            pair: (AssetIdOf<T, I>, AssetIdOf<T, I>),
+            tick_range: (i32, i32),
+            #[pallet::compact] liquidity: BalanceOf<T, I>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let (_, base_asset, quote_asset) = Self::sort_assets(pair)?;
+
+            ensure!(!liquidity.is_zero(), Error::<T, I>::InsufficientLiquidity);
+            let liquidity_delta = i128::try_from(liquidity)
+                .ok()
+                .and_then(|liquidity| liquidity.checked_neg())
+                .ok_or(ArithmeticError::Overflow)?;
+
+            let (base_amount_removed, quote_amount_removed) = Self::do_update_position(
+                &who,
+                (base_asset, quote_asset),
+                tick_range,
+                liquidity_delta,
+            )?;
+
+            for (asset, amount) in [
+                (base_asset, base_amount_removed),
+                (quote_asset, quote_amount_removed),
+            ] {
+                if !amount.is_zero() {
+                    T::Assets::transfer(
+                        asset,
+                        &Self::account_id(),
+                        &who,
+                        amount,
+                        asset == T::GetNativeCurrencyId::get(), // should keep alive if is native
+                    )?;
+                }
+            }
+
+            log::trace!(
+                target: "amm::remove_concentrated_liquidity",
+                "who: {:?}, base_asset: {:?}, quote_asset: {:?}, tick_range: {:?}, liquidity: {:?}",
+                &who,
+                &base_asset,
+                &quote_asset,
+                &tick_range,
+                &liquidity
+            );
+
+            Self::deposit_event(Event::<T, I>::ConcentratedLiquidityRemoved(
+                who,
+                base_asset,
+                quote_asset,
+                tick_range.0,
+                tick_range.1,
+                liquidity,
+                base_amount_removed,
+                quote_amount_removed,
+            ));
+
+            Ok(())
+        }
+
+        /// Allow users to collect the fees earned by their position in a concentrated
+        /// liquidity pool
+        ///
+        /// - `pair`: Currency pool of the position
+        /// - `tick_range`: Lower and upper ticks of the position
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::AMMWeightInfo::collect_fees())]
+        #[transactional]
+        pub fn collect_fees(
+            origin: OriginFor<T>,
+            pair: (AssetIdOf<T, I>, AssetIdOf<T, I>),
+            tick_range: (i32, i32),
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let (_, base_asset, quote_asset) = Self::sort_assets(pair)?;
+
+            let position_key = (base_asset, quote_asset, &who, tick_range);
+            let position =
+                Self::positions(position_key).ok_or(Error::<T, I>::PositionDoesNotExist)?;
+
+            // credit the position with the fees earned since its last update
+            if !position.liquidity.is_zero() {
+                Self::do_update_position(&who, (base_asset, quote_asset), tick_range, 0)?;
+            }
+
+            let (base_amount_collected, quote_amount_collected) =
+                Positions::<T, I>::try_mutate_exists(
+                    position_key,
+                    |maybe_position| -> Result<_, DispatchError> {
+                        let position = maybe_position
+                            .as_mut()
+                            .ok_or(Error::<T, I>::PositionDoesNotExist)?;
+                        let fees_owed = (position.fees_owed_base, position.fees_owed_quote);
+                        position.fees_owed_base = Zero::zero();
+                        position.fees_owed_quote = Zero::zero();
+                        if position.is_empty() {
+                            *maybe_position = None;
+                        }
+                        Ok(fees_owed)
+                    },
+                )?;
+
+            for (asset, amount) in [
+                (base_asset, base_amount_collected),
+                (quote_asset, quote_amount_collected),
+            ] {
+                if !amount.is_zero() {
+                    T::Assets::transfer(
+                        asset,
+                        &Self::account_id(),
+                        &who,
+                        amount,
+                        asset == T::GetNativeCurrencyId::get(), // should keep alive if is native
+                    )?;
+                }
+            }
+
+            log::trace!(
+                target: "amm::collect_fees",
+                "who: {:?}, base_asset: {:?}, quote_asset: {:?}, tick_range: {:?}, fees: {:?}",
+                &who,
+                &base_asset,
+                &quote_asset,
+                &tick_range,
+                &(base_amount_collected, quote_amount_collected)
+            );
+
+            Self::deposit_event(Event::<T, I>::FeesCollected(
+                who,
+                base_asset,
+                quote_asset,
+                tick_range.0,
+                tick_range.1,
+                base_amount_collected,
+                quote_amount_collected,
+            ));
+
+            Ok(())
+        }
     }
 }
 
@@ -506,6 +1006,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
             .ok_or(ArithmeticError::Overflow)?)
     }
 
+    // Records the pool of `base_asset` and `quote_asset` under both of its assets
+    pub(crate) fn index_pool(base_asset: AssetIdOf<T, I>, quote_asset: AssetIdOf<T, I>) {
+        AssetPools::<T, I>::insert(base_asset, quote_asset, ());
+        AssetPools::<T, I>::insert(quote_asset, base_asset, ());
+    }
+
     fn sort_assets(
         (curr_a, curr_b): (AssetIdOf<T, I>, AssetIdOf<T, I>),
     ) -> Result<(bool, AssetIdOf<T, I>, AssetIdOf<T, I>), DispatchError> {
@@ -570,8 +1076,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
         amounts_out[0] = amount_in;
         for i in 0..(path.len() - 1) {
-            let (reserve_in, reserve_out) = Self::get_reserves(path[i], path[i + 1])?;
-            let amount_out = Self::get_amount_out(amounts_out[i], reserve_in, reserve_out)?;
+            let amount_out = if Self::is_concentrated(path[i], path[i + 1])? {
+                Self::get_concentrated_amount(path[i], path[i + 1], amounts_out[i], true)?
+            } else {
+                let (reserve_in, reserve_out) = Self::get_reserves(path[i], path[i + 1])?;
+                Self::get_amount_out(amounts_out[i], reserve_in, reserve_out)?
+            };
             amounts_out[i + 1] = amount_out;
         }
 
@@ -590,14 +1100,30 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
         amounts_in[amount_len - 1] = amount_out;
         for i in (1..(path.len())).rev() {
-            let (reserve_in, reserve_out) = Self::get_reserves(path[i - 1], path[i])?;
-            let amount_in = Self::get_amount_in(amounts_in[i], reserve_in, reserve_out)?;
+            let amount_in = if Self::is_concentrated(path[i - 1], path[i])? {
+                Self::get_concentrated_amount(path[i - 1], path[i], amounts_in[i], false)?
+            } else {
+                let (reserve_in, reserve_out) = Self::get_reserves(path[i - 1], path[i])?;
+                Self::get_amount_in(amounts_in[i], reserve_in, reserve_out)?
+            };
             amounts_in[i - 1] = amount_in;
         }
 
         Ok(amounts_in)
     }
 
+    // whether the pair of assets is traded through a concentrated liquidity pool
+    fn is_concentrated(
+        asset_in: AssetIdOf<T, I>,
+        asset_out: AssetIdOf<T, I>,
+    ) -> Result<bool, DispatchError> {
+        let (_, base_asset, quote_asset) = Self::sort_assets((asset_in, asset_out))?;
+        Ok(ConcentratedPools::<T, I>::contains_key(
+            base_asset,
+            quote_asset,
+        ))
+    }
+
     // extract the reserves from a pool after sorting assets
     fn get_reserves(
         asset_in: AssetIdOf<T, I>,
@@ -737,40 +1263,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         let block_timestamp = frame_system::Pallet::<T>::block_number();
 
         if pool.block_timestamp_last != block_timestamp {
-            let time_elapsed: BalanceOf<T, I> = block_timestamp
-                .saturating_sub(pool.block_timestamp_last)
-                .saturated_into();
-
-            // compute by multiplying the numerator with the time elapsed
-            let price0_fraction = FixedU128::saturating_from_rational(
-                time_elapsed
-                    .get_big_uint()
-                    .checked_mul(&pool.quote_amount.get_big_uint())
-                    .ok_or(Error::<T, I>::ConversionToU128Failed)?
-                    .to_u128()
-                    .ok_or(ArithmeticError::Overflow)?,
-                pool.base_amount,
-            );
-            let price1_fraction = FixedU128::saturating_from_rational(
-                time_elapsed
-                    .get_big_uint()
-                    .checked_mul(&pool.base_amount.get_big_uint())
-                    .ok_or(Error::<T, I>::ConversionToU128Failed)?
-                    .to_u128()
-                    .ok_or(ArithmeticError::Overflow)?,
-                pool.quote_amount,
-            );
-
-            // convert stored u128 into FixedU128 before add
-            pool.price_0_cumulative_last = FixedU128::from_inner(pool.price_0_cumulative_last)
-                .checked_add(&price0_fraction)
-                .ok_or(ArithmeticError::Overflow)?
-                .into_inner();
-
-            pool.price_1_cumulative_last = FixedU128::from_inner(pool.price_1_cumulative_last)
-                .checked_add(&price1_fraction)
-                .ok_or(ArithmeticError::Overflow)?
-                .into_inner();
+            let (price_0_cumulative, price_1_cumulative) =
+                Self::cumulative_prices(pool, block_timestamp)?;
+            pool.price_0_cumulative_last = price_0_cumulative;
+            pool.price_1_cumulative_last = price_1_cumulative;
 
             // updates timestamp last so `time_elapsed` is correctly calculated
             pool.block_timestamp_last = block_timestamp;
@@ -779,6 +1275,135 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         Ok(())
     }
 
+    // cumulative prices of the pool at `block_timestamp`, the current reserves are
+    // accounted for the blocks elapsed since the last update
+    fn cumulative_prices(
+        pool: &Pool<AssetIdOf<T, I>, BalanceOf<T, I>, T::BlockNumber>,
+        block_timestamp: T::BlockNumber,
+    ) -> Result<(BalanceOf<T, I>, BalanceOf<T, I>), DispatchError> {
+        let time_elapsed: BalanceOf<T, I> = block_timestamp
+            .saturating_sub(pool.block_timestamp_last)
+            .saturated_into();
+
+        if time_elapsed.is_zero() {
+            return Ok((pool.price_0_cumulative_last, pool.price_1_cumulative_last));
+        }
+
+        // compute by multiplying the numerator with the time elapsed
+        let price0_fraction = FixedU128::saturating_from_rational(
+            time_elapsed
+                .get_big_uint()
+                .checked_mul(&pool.quote_amount.get_big_uint())
+                .ok_or(Error::<T, I>::ConversionToU128Failed)?
+                .to_u128()
+                .ok_or(ArithmeticError::Overflow)?,
+            pool.base_amount,
+        );
+        let price1_fraction = FixedU128::saturating_from_rational(
+            time_elapsed
+                .get_big_uint()
+                .checked_mul(&pool.base_amount.get_big_uint())
+                .ok_or(Error::<T, I>::ConversionToU128Failed)?
+                .to_u128()
+                .ok_or(ArithmeticError::Overflow)?,
+            pool.quote_amount,
+        );
+
+        // convert stored u128 into FixedU128 before add
+        let price_0_cumulative = FixedU128::from_inner(pool.price_0_cumulative_last)
+            .checked_add(&price0_fraction)
+            .ok_or(ArithmeticError::Overflow)?
+            .into_inner();
+        let price_1_cumulative = FixedU128::from_inner(pool.price_1_cumulative_last)
+            .checked_add(&price1_fraction)
+            .ok_or(ArithmeticError::Overflow)?
+            .into_inner();
+
+        Ok((price_0_cumulative, price_1_cumulative))
+    }
+
+    // checkpoint the cumulative prices of the pool, at most once every
+    // `ObservationPeriod` blocks, dropping the oldest observation when full
+    fn do_record_observation(
+        base_asset: AssetIdOf<T, I>,
+        quote_asset: AssetIdOf<T, I>,
+        pool: &Pool<AssetIdOf<T, I>, BalanceOf<T, I>, T::BlockNumber>,
+    ) {
+        let block_number = pool.block_timestamp_last;
+        Observations::<T, I>::mutate(base_asset, quote_asset, |observations| {
+            if observations.last().map_or(false, |last| {
+                block_number
+                    < last
+                        .block_number
+                        .saturating_add(T::ObservationPeriod::get())
+            }) {
+                return;
+            }
+
+            if observations.len() >= T::MaxObservations::get() as usize {
+                observations.remove(0);
+            }
+            let _ = observations.try_push(Observation {
+                block_number,
+                price_0_cumulative: pool.price_0_cumulative_last,
+                price_1_cumulative: pool.price_1_cumulative_last,
+            });
+        });
+    }
+
+    /// Returns the time weighted average price of `asset_in` in `asset_out`. The
+    /// average is taken since the newest observation at least `window` blocks old,
+    /// so it covers at least `window` blocks.
+    pub fn get_twap(
+        (asset_in, asset_out): (AssetIdOf<T, I>, AssetIdOf<T, I>),
+        window: T::BlockNumber,
+    ) -> Result<Price, DispatchError> {
+        ensure!(!window.is_zero(), Error::<T, I>::InvalidTwapWindow);
+
+        let (is_inverted, base_asset, quote_asset) = Self::sort_assets((asset_in, asset_out))?;
+        let pool =
+            Pools::<T, I>::get(base_asset, quote_asset).ok_or(Error::<T, I>::PoolDoesNotExist)?;
+
+        let block_number = frame_system::Pallet::<T>::block_number();
+        let window_start = block_number
+            .checked_sub(&window)
+            .ok_or(Error::<T, I>::InsufficientObservations)?;
+        let observation = Self::observations(base_asset, quote_asset)
+            .into_iter()
+            .rev()
+            .find(|observation| observation.block_number <= window_start)
+            .ok_or(Error::<T, I>::InsufficientObservations)?;
+
+        let (price_0_cumulative, price_1_cumulative) =
+            Self::cumulative_prices(&pool, block_number)?;
+        let (cumulative, cumulative_start) = if is_inverted {
+            (price_1_cumulative, observation.price_1_cumulative)
+        } else {
+            (price_0_cumulative, observation.price_0_cumulative)
+        };
+
+        let time_elapsed: BalanceOf<T, I> = block_number
+            .saturating_sub(observation.block_number)
+            .saturated_into();
+        let twap = cumulative
+            .checked_sub(cumulative_start)
+            .ok_or(ArithmeticError::Underflow)?
+            .checked_div(time_elapsed)
+            .ok_or(ArithmeticError::DivisionByZero)?;
+
+        log::trace!(
+            target: "amm::get_twap",
+            "asset_in: {:?}, asset_out: {:?}, window: {:?}, observation: {:?}, twap: {:?}",
+            &asset_in,
+            &asset_out,
+            &window,
+            &observation,
+            &twap,
+        );
+
+        Ok(Price::from_inner(twap))
+    }
+
     #[require_transactional]
     fn do_add_liquidity(
         who: &T::AccountId,
@@ -833,6 +1458,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
             )
         };
 
+        // accumulate the prices of the current reserves before they change
+        if !total_supply.is_zero() {
+            Self::do_update_oracle(pool)?;
+            Self::do_record_observation(base_asset, quote_asset, pool);
+        }
+
         // update reserves after liquidity calculation
         pool.base_amount = pool
             .base_amount
@@ -915,6 +1546,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
     ) -> Result<(BalanceOf<T, I>, BalanceOf<T, I>), DispatchError> {
         let (base_amount, quote_amount) = Self::calculate_reserves_to_remove(pool, liquidity)?;
 
+        // accumulate the prices of the current reserves before they change
+        Self::do_update_oracle(pool)?;
+        Self::do_record_observation(base_asset, quote_asset, pool);
+
         pool.base_amount = pool
             .base_amount
             .checked_sub(base_amount)
@@ -1049,6 +1684,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         (asset_in, asset_out): (AssetIdOf<T, I>, AssetIdOf<T, I>),
         amount_in: BalanceOf<T, I>,
     ) -> Result<BalanceOf<T, I>, DispatchError> {
+        if Self::is_concentrated(asset_in, asset_out)? {
+            return Self::do_concentrated_swap(who, (asset_in, asset_out), amount_in);
+        }
+
         let (is_inverted, base_asset, quote_asset) = Self::sort_assets((asset_in, asset_out))?;
 
         Pools::<T, I>::try_mutate(
@@ -1089,6 +1728,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
                 }
 
                 Self::do_update_oracle(pool)?;
+                Self::do_record_observation(base_asset, quote_asset, pool);
 
                 T::Assets::transfer(
                     asset_in,
@@ -1173,7 +1813,41 @@ impl<T: Config<I>, I: 'static>
 
     /// Returns a vector of all of the pools in storage
     fn get_pools() -> Result<Vec<(AssetIdOf<T, I>, AssetIdOf<T, I>)>, DispatchError> {
-        Ok(Pools::<T, I>::iter_keys().collect())
+        Ok(Pools::<T, I>::iter_keys()
+            .chain(ConcentratedPools::<T, I>::iter_keys())
+            .collect())
+    }
+
+    fn get_pools_of(
+        asset: AssetIdOf<T, I>,
+    ) -> Result<Vec<(AssetIdOf<T, I>, AssetIdOf<T, I>)>, DispatchError> {
+        Ok(AssetPools::<T, I>::iter_key_prefix(asset)
+            .map(|other| {
+                if asset > other {
+                    (asset, other)
+                } else {
+                    (other, asset)
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the amounts of `asset_in` and `asset_out` provided to the pool of the pair
+    fn get_reserves(
+        asset_in: AssetIdOf<T, I>,
+        asset_out: AssetIdOf<T, I>,
+    ) -> Result<(BalanceOf<T, I>, BalanceOf<T, I>), DispatchError> {
+        let (is_inverted, base_asset, quote_asset) = Self::sort_assets((asset_in, asset_out))?;
+
+        if let Some(pool) = Self::concentrated_pools(base_asset, quote_asset) {
+            return if is_inverted {
+                Ok((pool.quote_amount, pool.base_amount))
+            } else {
+                Ok((pool.base_amount, pool.quote_amount))
+            };
+        }
+
+        Self::get_reserves(asset_in, asset_out)
     }
 
     //just iterate now and require improve later when Pools increased
@@ -1202,4 +1876,13 @@ impl<T: Config<I>, I: 'static>
         }
         None
     }
+
+    /// Returns the time weighted average price of `pair.0` in `pair.1`
+    /// over at least the last `window` blocks
+    fn get_twap(
+        pair: (AssetIdOf<T, I>, AssetIdOf<T, I>),
+        window: T::BlockNumber,
+    ) -> Result<Price, DispatchError> {
+        Self::get_twap(pair, window)
+    }
 }
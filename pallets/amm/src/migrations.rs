@@ -0,0 +1,91 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Index the pools of each asset
+use super::*;
+
+pub mod v1 {
+    use super::*;
+    use frame_support::{traits::OnRuntimeUpgrade, weights::Weight};
+    use sp_std::marker::PhantomData;
+
+    #[cfg(feature = "try-runtime")]
+    pub fn pre_migrate<T: Config<I>, I: 'static>() -> Result<(), &'static str> {
+        frame_support::ensure!(StorageVersion::<T, I>::get() == Versions::V0, "must be V0");
+        log::info!(
+            "pools to index: {:?}",
+            Pools::<T, I>::iter_keys()
+                .chain(ConcentratedPools::<T, I>::iter_keys())
+                .count()
+        );
+        Ok(())
+    }
+
+    /// Records every existing pool in `AssetPools`
+    pub fn migrate<T: Config<I>, I: 'static>() -> Weight {
+        if StorageVersion::<T, I>::get() != Versions::V0 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        log::info!("Migrating amm to Versions::V1");
+        let pools: Vec<_> = Pools::<T, I>::iter_keys()
+            .chain(ConcentratedPools::<T, I>::iter_keys())
+            .collect();
+        let count = pools.len() as u64;
+        for (base_asset, quote_asset) in pools {
+            Pallet::<T, I>::index_pool(base_asset, quote_asset);
+        }
+
+        StorageVersion::<T, I>::put(Versions::V1);
+        log::info!("👜 completed amm migration to Versions::V1");
+        T::DbWeight::get().reads_writes(1 + count, 1 + 2 * count)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    pub fn post_migrate<T: Config<I>, I: 'static>() -> Result<(), &'static str> {
+        frame_support::ensure!(
+            StorageVersion::<T, I>::get() == Versions::V1,
+            "must upgrade to V1"
+        );
+        frame_support::ensure!(
+            Pools::<T, I>::iter_keys()
+                .chain(ConcentratedPools::<T, I>::iter_keys())
+                .all(|(base_asset, quote_asset)| {
+                    AssetPools::<T, I>::contains_key(base_asset, quote_asset)
+                        && AssetPools::<T, I>::contains_key(quote_asset, base_asset)
+                }),
+            "every pool must be indexed"
+        );
+        log::info!("👜 amm migration passes POST migrate checks ✅");
+        Ok(())
+    }
+
+    pub struct MigrateToV1<T, I = ()>(PhantomData<(T, I)>);
+    impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV1<T, I> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            pre_migrate::<T, I>()?;
+            Ok(Default::default())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrate::<T, I>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            post_migrate::<T, I>()
+        }
+    }
+}
@@ -164,6 +164,10 @@ parameter_types! {
     pub const AMMPalletId: PalletId = PalletId(*b"par/ammp");
     pub DefaultLpFee: Ratio = Ratio::from_rational(25u32, 10000u32);        // 0.25%
     pub const MinimumLiquidity: u128 = 1_000u128;
+    pub const MaxObservations: u32 = 4;
+    pub const ObservationPeriod: BlockNumber = 10;
+    pub const MaxTicks: u32 = 8;
+    pub const MinPositionLiquidity: u128 = 1_000_000u128;
     pub const LockAccountId: AccountId = AccountId(1_u64);
     pub const MaxLengthRoute: u8 = 10;
 }
@@ -186,6 +190,10 @@ impl pallet_amm::Config for Test {
     type LpFee = DefaultLpFee;
     type MinimumLiquidity = MinimumLiquidity;
     type MaxLengthRoute = MaxLengthRoute;
+    type MaxObservations = MaxObservations;
+    type ObservationPeriod = ObservationPeriod;
+    type MaxTicks = MaxTicks;
+    type MinPositionLiquidity = MinPositionLiquidity;
     type GetNativeCurrencyId = NativeCurrencyId;
 }
 
@@ -835,6 +835,127 @@ fn update_oracle_should_work() {
     })
 }
 
+#[test]
+fn get_twap_should_work() {
+    new_test_ext().execute_with(|| {
+        let trader = EVE;
+
+        assert_ok!(AMM::create_pool(
+            RawOrigin::Signed(ALICE).into(), // Origin
+            (SDOT, DOT),                     // Currency pool, in which liquidity will be added
+            (100_000, 100_000),              // Liquidity amounts to be added in pool
+            BOB,                             // LPToken receiver
+            SAMPLE_LP_TOKEN,                 // Liquidity pool share representative token
+        ));
+
+        assert_noop!(
+            AMM::get_twap((DOT, SDOT), 0),
+            Error::<Test>::InvalidTwapWindow
+        );
+
+        // nothing is observed before the first trade
+        assert_noop!(
+            AMM::get_twap((DOT, SDOT), 1),
+            Error::<Test>::InsufficientObservations
+        );
+
+        run_to_block(2);
+
+        assert_ok!(AMM::swap(&trader, (DOT, SDOT), 1_000));
+        assert_eq!(AMM::observations(SDOT, DOT).len(), 1);
+
+        run_to_block(11);
+
+        // the only observation is too recent for the window
+        assert_noop!(
+            AMM::get_twap((DOT, SDOT), 10),
+            Error::<Test>::InsufficientObservations
+        );
+
+        run_to_block(12);
+
+        // reserves haven't changed since block 2 so the TWAP is the spot price
+        assert_eq!(
+            AMM::get_twap((DOT, SDOT), 10),
+            Ok(Price::from_inner(980_326_732_673_267_326))
+        );
+        assert_eq!(
+            AMM::get_twap((SDOT, DOT), 10),
+            Ok(Price::from_inner(1_020_068_071_869_350_489))
+        );
+    })
+}
+
+#[test]
+fn liquidity_changes_should_update_oracle() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(AMM::create_pool(
+            RawOrigin::Signed(ALICE).into(), // Origin
+            (SDOT, DOT),                     // Currency pool, in which liquidity will be added
+            (100_000, 100_000),              // Liquidity amounts to be added in pool
+            ALICE,                           // LPToken receiver
+            SAMPLE_LP_TOKEN,                 // Liquidity pool share representative token
+        ));
+
+        run_to_block(2);
+
+        assert_ok!(AMM::add_liquidity(
+            RawOrigin::Signed(ALICE).into(), // Origin
+            (SDOT, DOT),                     // Currency pool, in which liquidity will be added
+            (100_000, 100_000),              // Liquidity amounts to be added in pool
+            (5, 5),                          // specifying its worst case ratio
+        ));
+
+        // the prices of the reserves before the deposit are accumulated
+        let pool = AMM::pools(SDOT, DOT).unwrap();
+        assert_eq!(pool.block_timestamp_last, 2);
+        assert_eq!(pool.price_0_cumulative_last, 2_000000000000000000);
+        assert_eq!(pool.price_1_cumulative_last, 2_000000000000000000);
+        assert_eq!(AMM::observations(SDOT, DOT).len(), 1);
+
+        run_to_block(12);
+
+        assert_ok!(AMM::remove_liquidity(
+            RawOrigin::Signed(ALICE).into(), // Origin
+            (SDOT, DOT),                     // Currency pool, in which liquidity will be removed
+            50_000,                          // Liquidity to be removed from user's liquidity
+        ));
+
+        let pool = AMM::pools(SDOT, DOT).unwrap();
+        assert_eq!(pool.block_timestamp_last, 12);
+        assert_eq!(pool.price_0_cumulative_last, 12_000000000000000000);
+        assert_eq!(AMM::observations(SDOT, DOT).len(), 2);
+    })
+}
+
+#[test]
+fn observations_should_be_bounded() {
+    new_test_ext().execute_with(|| {
+        let trader = EVE;
+
+        assert_ok!(AMM::create_pool(
+            RawOrigin::Signed(ALICE).into(), // Origin
+            (SDOT, DOT),                     // Currency pool, in which liquidity will be added
+            (100_000, 100_000),              // Liquidity amounts to be added in pool
+            BOB,                             // LPToken receiver
+            SAMPLE_LP_TOKEN,                 // Liquidity pool share representative token
+        ));
+
+        for block in [2, 5, 12, 22, 32, 42] {
+            run_to_block(block);
+            assert_ok!(AMM::swap(&trader, (DOT, SDOT), 1_000));
+        }
+
+        // block 5 is within `ObservationPeriod` of block 2 and block 2 is
+        // dropped once `MaxObservations` is reached
+        let blocks: Vec<_> = AMM::observations(SDOT, DOT)
+            .into_iter()
+            .map(|observation| observation.block_number)
+            .collect();
+        assert_eq!(blocks, vec![12, 22, 32, 42]);
+    })
+}
+
 #[test]
 fn oracle_big_block_no_overflow() {
     new_test_ext().execute_with(|| {
@@ -1311,3 +1432,388 @@ fn glmr_add_liquidity_should_work() {
         ));
     })
 }
+
+fn create_concentrated_pool_with_liquidity() {
+    assert_ok!(AMM::create_concentrated_pool(
+        RawOrigin::Signed(ALICE).into(),
+        (SDOT, DOT),
+        60,
+        Price::one(),
+    ));
+
+    // in range of the current price
+    assert_ok!(AMM::add_concentrated_liquidity(
+        RawOrigin::Signed(BOB).into(),
+        (SDOT, DOT),
+        (-60, 60),
+        (1_000_000, 1_000_000),
+        (0, 0),
+    ));
+    // above the current price, only holds the base asset
+    assert_ok!(AMM::add_concentrated_liquidity(
+        RawOrigin::Signed(BOB).into(),
+        (SDOT, DOT),
+        (60, 180),
+        (1_000_000, 1_000_000),
+        (0, 0),
+    ));
+}
+
+#[test]
+fn create_concentrated_pool_should_work() {
+    new_test_ext().execute_with(|| {
+        // price of DOT in SDOT, SDOT being the base asset
+        assert_ok!(AMM::create_concentrated_pool(
+            RawOrigin::Signed(ALICE).into(),
+            (DOT, SDOT),
+            10,
+            Price::saturating_from_integer(2),
+        ));
+
+        let pool = AMM::concentrated_pools(SDOT, DOT).unwrap();
+        assert_eq!(
+            pool.sqrt_price,
+            FixedU128::from_inner(707_106_781_186_547_524)
+        );
+        assert_eq!(pool.current_tick, -6932);
+        assert_eq!(AMM::get_pools(), Ok(vec![(SDOT, DOT)]));
+
+        // a pair has at most one pool of any kind
+        assert_noop!(
+            AMM::create_pool(
+                RawOrigin::Signed(ALICE).into(),
+                (DOT, SDOT),
+                (1_000, 2_000),
+                BOB,
+                SAMPLE_LP_TOKEN,
+            ),
+            Error::<Test>::PoolAlreadyExists
+        );
+        assert_noop!(
+            AMM::create_concentrated_pool(
+                RawOrigin::Signed(ALICE).into(),
+                (SDOT, DOT),
+                10,
+                Price::one(),
+            ),
+            Error::<Test>::PoolAlreadyExists
+        );
+    })
+}
+
+#[test]
+fn create_concentrated_pool_with_invalid_parameters_should_not_work() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            AMM::create_concentrated_pool(
+                RawOrigin::Signed(BOB).into(),
+                (SDOT, DOT),
+                10,
+                Price::one(),
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            AMM::create_concentrated_pool(
+                RawOrigin::Signed(ALICE).into(),
+                (SDOT, DOT),
+                0,
+                Price::one(),
+            ),
+            Error::<Test>::InvalidTickSpacing
+        );
+        assert_noop!(
+            AMM::create_concentrated_pool(
+                RawOrigin::Signed(ALICE).into(),
+                (SDOT, DOT),
+                10,
+                Price::zero(),
+            ),
+            Error::<Test>::InvalidPrice
+        );
+    })
+}
+
+#[test]
+fn add_concentrated_liquidity_should_work() {
+    new_test_ext().execute_with(|| {
+        let sdot_before = Assets::balance(SDOT, BOB);
+        let dot_before = Assets::balance(DOT, BOB);
+
+        create_concentrated_pool_with_liquidity();
+
+        assert_eq!(Assets::balance(SDOT, BOB), sdot_before - 2_000_000);
+        assert_eq!(Assets::balance(DOT, BOB), dot_before - 1_000_000);
+        assert_eq!(
+            AMM::positions((SDOT, DOT, BOB, (-60, 60)))
+                .unwrap()
+                .liquidity,
+            333_850_249
+        );
+        assert_eq!(
+            AMM::positions((SDOT, DOT, BOB, (60, 180)))
+                .unwrap()
+                .liquidity,
+            167_677_754
+        );
+
+        // only the liquidity in range is active
+        let pool = AMM::concentrated_pools(SDOT, DOT).unwrap();
+        assert_eq!(pool.liquidity, 333_850_249);
+        assert_eq!(
+            (pool.base_amount, pool.quote_amount),
+            (2_000_000, 1_000_000)
+        );
+        assert_eq!(
+            AMM::ticks(SDOT, DOT)
+                .iter()
+                .map(|&(tick, _)| tick)
+                .collect::<Vec<_>>(),
+            vec![-60, 60, 180]
+        );
+    })
+}
+
+#[test]
+fn add_concentrated_liquidity_with_invalid_parameters_should_not_work() {
+    new_test_ext().execute_with(|| {
+        create_concentrated_pool_with_liquidity();
+
+        for tick_range in [(-61, 60), (60, -60), (MIN_TICK - 60, 60)] {
+            assert_noop!(
+                AMM::add_concentrated_liquidity(
+                    RawOrigin::Signed(BOB).into(),
+                    (SDOT, DOT),
+                    tick_range,
+                    (1_000_000, 1_000_000),
+                    (0, 0),
+                ),
+                Error::<Test>::InvalidTickRange
+            );
+        }
+
+        assert_noop!(
+            AMM::add_concentrated_liquidity(
+                RawOrigin::Signed(BOB).into(),
+                (SDOT, DOT),
+                (-60, 60),
+                (1_000_000, 1_000_000),
+                (1_000_000, 1_000_001),
+            ),
+            Error::<Test>::NotAnIdealPrice
+        );
+        assert_noop!(
+            AMM::add_concentrated_liquidity(
+                RawOrigin::Signed(BOB).into(),
+                (SDOT, KSM),
+                (-60, 60),
+                (1_000_000, 1_000_000),
+                (0, 0),
+            ),
+            Error::<Test>::PoolDoesNotExist
+        );
+        // below `MinPositionLiquidity`
+        assert_noop!(
+            AMM::add_concentrated_liquidity(
+                RawOrigin::Signed(BOB).into(),
+                (SDOT, DOT),
+                (-60, 60),
+                (1_000, 1_000),
+                (0, 0),
+            ),
+            Error::<Test>::PositionTooSmall
+        );
+
+        // `MaxTicks` is 8
+        for tick_range in [(-120, 120), (-180, 240)] {
+            assert_ok!(AMM::add_concentrated_liquidity(
+                RawOrigin::Signed(BOB).into(),
+                (SDOT, DOT),
+                tick_range,
+                (1_000_000, 1_000_000),
+                (0, 0),
+            ));
+        }
+        assert_noop!(
+            AMM::add_concentrated_liquidity(
+                RawOrigin::Signed(BOB).into(),
+                (SDOT, DOT),
+                (-240, 300),
+                (1_000_000, 1_000_000),
+                (0, 0),
+            ),
+            Error::<Test>::TooManyTicks
+        );
+    })
+}
+
+#[test]
+fn concentrated_swap_should_work() {
+    new_test_ext().execute_with(|| {
+        create_concentrated_pool_with_liquidity();
+
+        assert_eq!(
+            AMM::get_amounts_out(10_000, vec![DOT, SDOT]),
+            Ok(vec![10_000, 9_974])
+        );
+        assert_eq!(
+            AMM::get_amounts_in(9_000, vec![DOT, SDOT]),
+            Ok(vec![9_024, 9_000])
+        );
+
+        assert_ok!(AMM::swap(&CHARLIE, (DOT, SDOT), 10_000));
+
+        assert_eq!(Assets::balance(DOT, CHARLIE), 1_000_000_000 - 10_000);
+        assert_eq!(Assets::balance(SDOT, CHARLIE), 1_000_000_000 + 9_974);
+
+        let pool = AMM::concentrated_pools(SDOT, DOT).unwrap();
+        assert_eq!(
+            pool.sqrt_price,
+            FixedU128::from_inner(1_000_029_878_665_748_726)
+        );
+        assert_eq!(pool.current_tick, 0);
+        assert_eq!(pool.liquidity, 333_850_249);
+    })
+}
+
+#[test]
+fn concentrated_swap_should_cross_ticks() {
+    new_test_ext().execute_with(|| {
+        create_concentrated_pool_with_liquidity();
+
+        assert_ok!(AMM::swap(&CHARLIE, (DOT, SDOT), 1_500_000));
+
+        assert_eq!(Assets::balance(DOT, CHARLIE), 1_000_000_000 - 1_500_000);
+        assert_eq!(Assets::balance(SDOT, CHARLIE), 1_000_000_000 + 1_488_858);
+
+        // the price moved above the lower position into the upper one
+        let pool = AMM::concentrated_pools(SDOT, DOT).unwrap();
+        assert_eq!(pool.current_tick, 118);
+        assert_eq!(pool.liquidity, 167_677_754);
+        assert_eq!((pool.base_amount, pool.quote_amount), (511_142, 2_496_249));
+
+        // not enough liquidity left
+        assert_noop!(
+            AMM::swap(&CHARLIE, (DOT, SDOT), 100_000_000),
+            Error::<Test>::InsufficientSupplyOut
+        );
+    })
+}
+
+#[test]
+fn concentrated_swap_should_send_protocol_fee() {
+    new_test_ext().execute_with(|| {
+        create_concentrated_pool_with_liquidity();
+        assert_ok!(AMM::update_protocol_fee(
+            RawOrigin::Signed(ALICE).into(),
+            Ratio::from_percent(20),
+        ));
+        assert_ok!(AMM::update_protocol_fee_receiver(
+            RawOrigin::Signed(ALICE).into(),
+            PROTOCOL_FEE_RECEIVER,
+        ));
+
+        assert_ok!(AMM::swap(&CHARLIE, (DOT, SDOT), 10_000));
+
+        // 20% of the 25 DOT of fees
+        assert_eq!(Assets::balance(DOT, PROTOCOL_FEE_RECEIVER), 5);
+        assert_eq!(Assets::balance(SDOT, CHARLIE), 1_000_000_000 + 9_974);
+    })
+}
+
+#[test]
+fn collect_fees_should_work() {
+    new_test_ext().execute_with(|| {
+        create_concentrated_pool_with_liquidity();
+        assert_ok!(AMM::swap(&CHARLIE, (DOT, SDOT), 1_500_000));
+
+        let dot_before = Assets::balance(DOT, BOB);
+        assert_ok!(AMM::collect_fees(
+            RawOrigin::Signed(BOB).into(),
+            (SDOT, DOT),
+            (-60, 60),
+        ));
+        assert_ok!(AMM::collect_fees(
+            RawOrigin::Signed(BOB).into(),
+            (SDOT, DOT),
+            (60, 180),
+        ));
+
+        // 0.25% of the amount in, shared by the positions in range when it was traded
+        assert_eq!(Assets::balance(DOT, BOB), dot_before + 2_513 + 1_236);
+        let position = AMM::positions((SDOT, DOT, BOB, (-60, 60))).unwrap();
+        assert_eq!((position.fees_owed_base, position.fees_owed_quote), (0, 0));
+
+        assert_noop!(
+            AMM::collect_fees(RawOrigin::Signed(BOB).into(), (SDOT, DOT), (-120, 120)),
+            Error::<Test>::PositionDoesNotExist
+        );
+    })
+}
+
+#[test]
+fn remove_concentrated_liquidity_should_work() {
+    new_test_ext().execute_with(|| {
+        create_concentrated_pool_with_liquidity();
+        assert_ok!(AMM::swap(&CHARLIE, (DOT, SDOT), 1_500_000));
+
+        assert_noop!(
+            AMM::remove_concentrated_liquidity(
+                RawOrigin::Signed(BOB).into(),
+                (SDOT, DOT),
+                (-60, 60),
+                333_850_250,
+            ),
+            Error::<Test>::InsufficientLiquidity
+        );
+        assert_noop!(
+            AMM::remove_concentrated_liquidity(
+                RawOrigin::Signed(CHARLIE).into(),
+                (SDOT, DOT),
+                (-60, 60),
+                1,
+            ),
+            Error::<Test>::PositionDoesNotExist
+        );
+        // the position is either removed or kept above `MinPositionLiquidity`
+        assert_noop!(
+            AMM::remove_concentrated_liquidity(
+                RawOrigin::Signed(BOB).into(),
+                (SDOT, DOT),
+                (-60, 60),
+                333_850_248,
+            ),
+            Error::<Test>::PositionTooSmall
+        );
+
+        let sdot_before = Assets::balance(SDOT, BOB);
+        let dot_before = Assets::balance(DOT, BOB);
+        assert_ok!(AMM::remove_concentrated_liquidity(
+            RawOrigin::Signed(BOB).into(),
+            (SDOT, DOT),
+            (-60, 60),
+            333_850_249,
+        ));
+
+        // the price is above the range, which only holds the quote asset now
+        assert_eq!(Assets::balance(SDOT, BOB), sdot_before);
+        assert_eq!(Assets::balance(DOT, BOB), dot_before + 2_003_004);
+        assert_eq!(
+            AMM::ticks(SDOT, DOT)
+                .iter()
+                .map(|&(tick, _)| tick)
+                .collect::<Vec<_>>(),
+            vec![60, 180]
+        );
+
+        // the fees are kept until collected
+        let position = AMM::positions((SDOT, DOT, BOB, (-60, 60))).unwrap();
+        assert_eq!((position.liquidity, position.fees_owed_quote), (0, 2_513));
+        assert_ok!(AMM::collect_fees(
+            RawOrigin::Signed(BOB).into(),
+            (SDOT, DOT),
+            (-60, 60),
+        ));
+        assert_eq!(AMM::positions((SDOT, DOT, BOB, (-60, 60))), None);
+    })
+}
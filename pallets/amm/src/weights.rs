@@ -45,90 +45,210 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for pallet_amm.
 pub trait WeightInfo {
-  fn add_liquidity() -> Weight;
-  fn remove_liquidity() -> Weight;
-  fn create_pool() -> Weight;
-  fn update_protocol_fee() -> Weight;
-  fn update_protocol_fee_receiver() -> Weight;
+	fn add_liquidity() -> Weight;
+	fn remove_liquidity() -> Weight;
+	fn create_pool() -> Weight;
+	fn update_protocol_fee() -> Weight;
+	fn update_protocol_fee_receiver() -> Weight;
+	fn create_concentrated_pool() -> Weight;
+	fn add_concentrated_liquidity() -> Weight;
+	fn remove_concentrated_liquidity() -> Weight;
+	fn collect_fees() -> Weight;
 }
 
 /// Weights for pallet_amm using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM Observations (r:1 w:1)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:5 w:5)
 	fn add_liquidity() -> Weight {
 		Weight::from_ref_time(217_917_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(10 as u64))
-			.saturating_add(T::DbWeight::get().writes(10 as u64))
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
 	}
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM Observations (r:1 w:1)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:5 w:5)
 	fn remove_liquidity() -> Weight {
 		Weight::from_ref_time(238_051_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(10 as u64))
-			.saturating_add(T::DbWeight::get().writes(10 as u64))
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
 	}
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:0)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:6 w:6)
 	// Storage: System Account (r:2 w:2)
+	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM AssetPools (r:0 w:2)
 	fn create_pool() -> Weight {
 		Weight::from_ref_time(291_928_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(13 as u64))
-			.saturating_add(T::DbWeight::get().writes(13 as u64))
+			.saturating_add(T::DbWeight::get().reads(15 as u64))
+			.saturating_add(T::DbWeight::get().writes(15 as u64))
 	}
 	// Storage: AMM ProtocolFee (r:0 w:1)
-  fn update_protocol_fee() -> Weight {
-	Weight::from_ref_time(4_067_000 as u64).saturating_add(T::DbWeight::get().writes(1 as u64))
-  }
-  // Storage: AMM ProtocolFeeReceiver (r:0 w:1)
-  fn update_protocol_fee_receiver() -> Weight {
-	Weight::from_ref_time(4_114_000 as u64).saturating_add(T::DbWeight::get().writes(1 as u64))
-  }
+	fn update_protocol_fee() -> Weight {
+		Weight::from_ref_time(4_067_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: AMM ProtocolFeeReceiver (r:0 w:1)
+	fn update_protocol_fee_receiver() -> Weight {
+		Weight::from_ref_time(4_114_000 as u64)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM Pools (r:1 w:0)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM AssetPools (r:0 w:2)
+	fn create_concentrated_pool() -> Weight {
+		Weight::from_ref_time(54_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:1)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn add_concentrated_liquidity() -> Weight {
+		Weight::from_ref_time(229_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:1)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn remove_concentrated_liquidity() -> Weight {
+		Weight::from_ref_time(222_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:0)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn collect_fees() -> Weight {
+		Weight::from_ref_time(179_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM Observations (r:1 w:1)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:5 w:5)
 	fn add_liquidity() -> Weight {
 		Weight::from_ref_time(217_917_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(10 as u64))
-			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(11 as u64))
 	}
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM Observations (r:1 w:1)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:5 w:5)
 	fn remove_liquidity() -> Weight {
 		Weight::from_ref_time(238_051_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(10 as u64))
-			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(11 as u64))
 	}
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:0)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:6 w:6)
 	// Storage: System Account (r:2 w:2)
+	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM AssetPools (r:0 w:2)
 	fn create_pool() -> Weight {
 		Weight::from_ref_time(291_928_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(13 as u64))
-			.saturating_add(RocksDbWeight::get().writes(13 as u64))
+			.saturating_add(RocksDbWeight::get().reads(15 as u64))
+			.saturating_add(RocksDbWeight::get().writes(15 as u64))
 	}
 	// Storage: AMM ProtocolFee (r:0 w:1)
 	fn update_protocol_fee() -> Weight {
-		Weight::from_ref_time(4_067_000 as u64).saturating_add(RocksDbWeight::get().writes(1 as u64))
+		Weight::from_ref_time(4_067_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
 	// Storage: AMM ProtocolFeeReceiver (r:0 w:1)
 	fn update_protocol_fee_receiver() -> Weight {
-		Weight::from_ref_time(4_114_000 as u64).saturating_add(RocksDbWeight::get().writes(1 as u64))
+		Weight::from_ref_time(4_114_000 as u64)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM Pools (r:1 w:0)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM AssetPools (r:0 w:2)
+	fn create_concentrated_pool() -> Weight {
+		Weight::from_ref_time(54_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:1)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn add_concentrated_liquidity() -> Weight {
+		Weight::from_ref_time(229_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(10 as u64))
+			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:1)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn remove_concentrated_liquidity() -> Weight {
+		Weight::from_ref_time(222_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(10 as u64))
+			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:0)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn collect_fees() -> Weight {
+		Weight::from_ref_time(179_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(10 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
 	}
 }
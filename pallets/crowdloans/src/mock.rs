@@ -602,6 +602,13 @@ impl Loans<CurrencyId, AccountId, Balance> for MockLoans {
     ) -> Result<(), DispatchError> {
         Ok(())
     }
+    fn ensure_can_borrow(
+        borrower: &AccountId,
+        asset_id: CurrencyId,
+        amount: Balance,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
 }
 
 pub struct Decimal;
@@ -623,6 +630,7 @@ impl DecimalProvider<CurrencyId> for Decimal {
 parameter_types! {
     pub const XcmHelperPalletId: PalletId = PalletId(*b"par/fees");
     pub const NotifyTimeout: BlockNumber = 100;
+    pub RelayLocation: MultiLocation = MultiLocation::parent();
 }
 
 impl pallet_xcm_helper::Config for Test {
@@ -632,6 +640,7 @@ impl pallet_xcm_helper::Config for Test {
     type XcmSender = XcmRouter;
     type PalletId = XcmHelperPalletId;
     type RelayNetwork = RelayNetwork;
+    type RelayLocation = RelayLocation;
     type NotifyTimeout = NotifyTimeout;
     type AccountIdToMultiLocation = AccountIdToMultiLocation;
     type RefundLocation = RefundLocation;
@@ -0,0 +1,234 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+pub use pallet_liquid_staking_rpc_runtime_api::LiquidStakingApi as LiquidStakingRuntimeApi;
+use pallet_liquid_staking_rpc_runtime_api::{
+    ExchangeRateSnapshot, PendingUnstake, PendingUnstakes,
+};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorCode, ErrorObject},
+};
+use primitives::{DerivativeIndex, Rate};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+#[rpc(client, server)]
+pub trait LiquidStakingApi<BlockHash, AccountId, Balance>
+where
+    Balance: Codec + Copy + TryFrom<NumberOrHex>,
+{
+    #[method(name = "liquidStaking_getApy")]
+    fn get_apy(&self, at: Option<BlockHash>) -> RpcResult<Option<Rate>>;
+    #[method(name = "liquidStaking_getExchangeRateHistory")]
+    fn get_exchange_rate_history(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ExchangeRateSnapshot<NumberOrHex>>>;
+    #[method(name = "liquidStaking_getPendingUnstakes")]
+    fn get_pending_unstakes(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<PendingUnstakes<NumberOrHex>>;
+    #[method(name = "liquidStaking_getBondDistributions")]
+    fn get_bond_distributions(
+        &self,
+        amount: NumberOrHex,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(DerivativeIndex, NumberOrHex)>>;
+    #[method(name = "liquidStaking_getUnbondDistributions")]
+    fn get_unbond_distributions(
+        &self,
+        amount: NumberOrHex,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(DerivativeIndex, NumberOrHex)>>;
+}
+
+/// A struct that implements the [`LiquidStakingApi`].
+pub struct LiquidStaking<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> LiquidStaking<C, B> {
+    /// Create new `LiquidStaking` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+pub enum Error {
+    RuntimeError,
+}
+
+impl From<Error> for i32 {
+    fn from(e: Error) -> i32 {
+        match e {
+            Error::RuntimeError => 1,
+        }
+    }
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance>
+    LiquidStakingApiServer<<Block as BlockT>::Hash, AccountId, Balance> for LiquidStaking<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<Block>,
+    C: HeaderBackend<Block>,
+    C::Api: LiquidStakingRuntimeApi<Block, AccountId, Balance>,
+    AccountId: Codec,
+    Balance: Codec + Copy + TryFrom<NumberOrHex> + Into<NumberOrHex> + std::fmt::Display,
+{
+    fn get_apy(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Option<Rate>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_apy(&at).map_err(runtime_error_into_rpc_error)
+    }
+
+    fn get_exchange_rate_history(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ExchangeRateSnapshot<NumberOrHex>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_exchange_rate_history(&at)
+            .map_err(runtime_error_into_rpc_error)?
+            .into_iter()
+            .map(|snapshot| {
+                Ok(ExchangeRateSnapshot {
+                    era: snapshot.era,
+                    exchange_rate: snapshot.exchange_rate,
+                    total_bonded: try_into_rpc_balance(snapshot.total_bonded)?,
+                    total_liquid: try_into_rpc_balance(snapshot.total_liquid)?,
+                })
+            })
+            .collect()
+    }
+
+    fn get_pending_unstakes(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<PendingUnstakes<NumberOrHex>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        let pending_unstakes = api
+            .get_pending_unstakes(&at, account)
+            .map_err(runtime_error_into_rpc_error)?;
+        Ok(PendingUnstakes {
+            fast_unstake: try_into_rpc_balance(pending_unstakes.fast_unstake)?,
+            unstakes: pending_unstakes
+                .unstakes
+                .into_iter()
+                .map(|unstake| {
+                    Ok(PendingUnstake {
+                        value: try_into_rpc_balance(unstake.value)?,
+                        claimable_era: unstake.claimable_era,
+                        receipt_id: unstake.receipt_id,
+                    })
+                })
+                .collect::<RpcResult<_>>()?,
+        })
+    }
+
+    fn get_bond_distributions(
+        &self,
+        amount: NumberOrHex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(DerivativeIndex, NumberOrHex)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_bond_distributions(&at, decode_hex(amount, "balance")?)
+            .map_err(runtime_error_into_rpc_error)?
+            .into_iter()
+            .map(|(index, amount)| Ok((index, try_into_rpc_balance(amount)?)))
+            .collect()
+    }
+
+    fn get_unbond_distributions(
+        &self,
+        amount: NumberOrHex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(DerivativeIndex, NumberOrHex)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_unbond_distributions(&at, decode_hex(amount, "balance")?)
+            .map_err(runtime_error_into_rpc_error)?
+            .into_iter()
+            .map(|(index, amount)| Ok((index, try_into_rpc_balance(amount)?)))
+            .collect()
+    }
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_error(err: impl std::fmt::Debug) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+        Error::RuntimeError.into(),
+        "Runtime trapped",
+        Some(format!("{:?}", err)),
+    )))
+}
+
+fn decode_hex<H: std::fmt::Debug + Copy, T: TryFrom<H>>(
+    from: H,
+    name: &str,
+) -> Result<T, JsonRpseeError> {
+    from.try_into().map_err(|_| {
+        JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            ErrorCode::InvalidParams.code(),
+            format!("{:?} does not fit into the {} type", from, name),
+            None::<()>,
+        )))
+    })
+}
+
+fn try_into_rpc_balance<T: std::fmt::Display + Copy + TryInto<NumberOrHex>>(
+    value: T,
+) -> Result<NumberOrHex, JsonRpseeError> {
+    value.try_into().map_err(|_| {
+        JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            ErrorCode::InvalidParams.code(),
+            format!("{} doesn't fit in NumberOrHex representation", value),
+            None::<()>,
+        )))
+    })
+}
@@ -1,6 +1,7 @@
 //! Liquid staking pallet benchmarking.
 #![cfg(feature = "runtime-benchmarks")]
 
+use codec::Encode;
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
 use frame_support::{
     assert_ok,
@@ -10,17 +11,20 @@ use frame_support::{
 };
 use frame_system::{self, RawOrigin as SystemOrigin};
 use sp_runtime::{
-    traits::{One, Saturating, StaticLookup},
-    TransactionOutcome,
+    traits::{BlakeTwo256, Hash, One, Saturating, StaticLookup},
+    BoundedVec, TransactionOutcome,
 };
 use sp_std::{prelude::*, vec};
 use xcm::latest::prelude::*;
 
 use pallet_traits::ump::RewardDestination;
-use primitives::{Balance, CurrencyId, Rate, Ratio};
+use primitives::{Balance, CurrencyId, DerivativeIndex, Rate, Ratio};
 
 use crate::{
-    types::{StakingLedger, UnstakeProvider},
+    types::{
+        AccountData, AccountInfo, ActiveEraInfo, DistributionStrategyKind, StakingLedger,
+        UnstakeProvider,
+    },
     Pallet as LiquidStaking,
 };
 
@@ -37,6 +41,8 @@ const UNSTAKE_AMOUNT: u128 = 10000000000000u128;
 const BOND_AMOUNT: u128 = 10000000000000u128;
 const UNBOND_AMOUNT: u128 = 5000000000000u128;
 const REBOND_AMOUNT: u128 = 5000000000000u128;
+const REWARDS_AMOUNT: u128 = 1000000000000u128;
+const SLASH_AMOUNT: u128 = 1000000000000u128;
 
 const COMMISSION_RATE: Rate = Rate::from_rational(1, 100);
 
@@ -107,6 +113,80 @@ fn initial_set_up<
     ExchangeRate::<T>::mutate(|b| *b = Rate::one());
 }
 
+// Builds a relaychain storage containing only `Staking.ActiveEra`, then pads the proof
+// with unused nodes of max length, verifying it costs as much as the largest accepted proof
+fn active_era_proof<T: Config>(
+    active_era: &ActiveEraInfo,
+) -> (<BlakeTwo256 as Hash>::Output, BoundedStorageProof) {
+    storage_proof(
+        &LiquidStaking::<T>::get_active_era_key(),
+        &active_era.encode(),
+    )
+}
+
+// Same as `active_era_proof`, for both the `System.Account` and the `Staking.Ledger`
+// of a stash
+fn stash_proof<T: Config>(
+    derivative_index: DerivativeIndex,
+    account: &AccountInfo<Balance>,
+    staking_ledger: &StakingLedger<T::AccountId, Balance>,
+) -> (<BlakeTwo256 as Hash>::Output, BoundedStorageProof) {
+    storage_proof_of(&[
+        (
+            LiquidStaking::<T>::get_account_key(
+                &LiquidStaking::<T>::derivative_sovereign_account_id(derivative_index),
+            ),
+            account.encode(),
+        ),
+        (
+            LiquidStaking::<T>::get_staking_ledger_key(derivative_index),
+            staking_ledger.encode(),
+        ),
+    ])
+}
+
+// Same as `active_era_proof`, for the `Staking.Ledger` of a derivative index
+fn staking_ledger_proof<T: Config>(
+    derivative_index: DerivativeIndex,
+    staking_ledger: &StakingLedger<T::AccountId, Balance>,
+) -> (<BlakeTwo256 as Hash>::Output, BoundedStorageProof) {
+    storage_proof(
+        &LiquidStaking::<T>::get_staking_ledger_key(derivative_index),
+        &staking_ledger.encode(),
+    )
+}
+
+fn storage_proof(key: &[u8], value: &[u8]) -> (<BlakeTwo256 as Hash>::Output, BoundedStorageProof) {
+    storage_proof_of(&[(key.to_vec(), value.to_vec())])
+}
+
+fn storage_proof_of(
+    entries: &[(Vec<u8>, Vec<u8>)],
+) -> (<BlakeTwo256 as Hash>::Output, BoundedStorageProof) {
+    use sp_trie::{LayoutV1, MemoryDB, TrieDBMutBuilder, TrieMut};
+
+    let mut db = MemoryDB::<BlakeTwo256>::default();
+    let mut root = Default::default();
+    {
+        let mut trie = TrieDBMutBuilder::<LayoutV1<BlakeTwo256>>::new(&mut db, &mut root).build();
+        for (key, value) in entries {
+            trie.insert(key, value).unwrap();
+        }
+    }
+    let mut proof: Vec<_> = db
+        .drain()
+        .into_values()
+        .map(|(node, _)| BoundedVec::truncate_from(node))
+        .collect();
+    for i in proof.len()..MAX_PROOF_NODES as usize {
+        proof.push(BoundedVec::truncate_from(vec![
+            i as u8;
+            MAX_PROOF_NODE_LEN as usize
+        ]));
+    }
+    (root, BoundedVec::truncate_from(proof))
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
     frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
@@ -194,6 +274,31 @@ benchmarks! {
         assert_last_event::<T>(Event::<T>::StakingLedgerUpdated(0, staking_ledger).into());
     }
 
+    set_staking_ledger {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        initial_set_up::<T>(alice.clone());
+        LiquidStaking::<T>::stake(SystemOrigin::Signed(alice).into(), STAKE_AMOUNT).unwrap();
+        LiquidStaking::<T>::bond(SystemOrigin::Root.into(), 0, BOND_AMOUNT, RewardDestination::Stash).unwrap();
+        LiquidStaking::<T>::notification_received(
+            pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+            0u64,
+            Response::ExecutionResult(None)
+        ).unwrap();
+        // a slash is heavier than rewards, the reserves only cover a part of it
+        let mut staking_ledger = StakingLedgers::<T>::get(0).unwrap();
+        staking_ledger.total -= SLASH_AMOUNT;
+        staking_ledger.active -= SLASH_AMOUNT;
+        let (root, proof) = staking_ledger_proof::<T>(0, &staking_ledger);
+        ValidationData::<T>::put(PersistedValidationData {
+            relay_parent_storage_root: root,
+            ..Default::default()
+        });
+    }: _(SystemOrigin::Root, 0u16, staking_ledger.clone(), proof)
+    verify {
+        assert_eq!(StakingLedgers::<T>::get(0), Some(staking_ledger));
+        assert_eq!(Slashes::<T>::get(LiquidStaking::<T>::current_era(), 0).amount, SLASH_AMOUNT);
+    }
+
     unbond {
         let alice: T::AccountId = account("Sample", 100, SEED);
         initial_set_up::<T>(alice.clone());
@@ -353,6 +458,23 @@ benchmarks! {
         assert_last_event::<T>(Event::<T>::NewEra(1).into());
     }
 
+    advance_era {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        initial_set_up::<T>(alice.clone());
+        LiquidStaking::<T>::stake(SystemOrigin::Signed(alice.clone()).into(), STAKE_AMOUNT).unwrap();
+        let active_era = ActiveEraInfo { index: 1, start: None };
+        let (root, proof) = active_era_proof::<T>(&active_era);
+        ValidationData::<T>::put(PersistedValidationData {
+            relay_parent_storage_root: root,
+            ..Default::default()
+        });
+        let bounty = T::EraAdvanceBounty::get().min(TotalReserves::<T>::get());
+    }: _(SystemOrigin::Signed(alice.clone()), active_era, proof)
+    verify {
+        assert_eq!(CurrentEra::<T>::get(), 1);
+        assert_last_event::<T>(Event::<T>::EraAdvanced(alice, 1, bounty).into());
+    }
+
     force_matching {
         let alice: T::AccountId = account("Sample", 100, SEED);
         initial_set_up::<T>(alice.clone());
@@ -434,6 +556,144 @@ benchmarks! {
             STAKE_AMOUNT - xcm_fee - reserve - total_matched_amount
         );
     }
+
+    add_validator {
+        let validator: T::AccountId = account("validator", 0, SEED);
+    }: _(SystemOrigin::Root, validator.clone())
+    verify {
+        assert_last_event::<T>(Event::<T>::ValidatorAdded(validator).into());
+    }
+
+    remove_validator {
+        let validator: T::AccountId = account("validator", 0, SEED);
+        LiquidStaking::<T>::add_validator(SystemOrigin::Root.into(), validator.clone()).unwrap();
+    }: _(SystemOrigin::Root, validator.clone())
+    verify {
+        assert_last_event::<T>(Event::<T>::ValidatorRemoved(validator).into());
+    }
+
+    transfer_unstake_receipt {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        let bob: T::AccountId = account("Sample", 101, SEED);
+        initial_set_up::<T>(alice.clone());
+        LiquidStaking::<T>::stake(SystemOrigin::Signed(alice.clone()).into(), STAKE_AMOUNT).unwrap();
+        LiquidStaking::<T>::unstake(SystemOrigin::Signed(alice.clone()).into(), UNSTAKE_AMOUNT, Default::default()).unwrap();
+    }: _(SystemOrigin::Signed(alice.clone()), 0, T::Lookup::unlookup(bob.clone()))
+    verify {
+        assert_last_event::<T>(Event::<T>::UnstakeReceiptTransferRequested(0, alice, bob).into());
+    }
+
+    accept_unstake_receipt {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        let bob: T::AccountId = account("Sample", 101, SEED);
+        initial_set_up::<T>(alice.clone());
+        LiquidStaking::<T>::stake(SystemOrigin::Signed(alice.clone()).into(), STAKE_AMOUNT).unwrap();
+        LiquidStaking::<T>::unstake(SystemOrigin::Signed(alice.clone()).into(), UNSTAKE_AMOUNT, Default::default()).unwrap();
+        LiquidStaking::<T>::transfer_unstake_receipt(SystemOrigin::Signed(alice.clone()).into(), 0, T::Lookup::unlookup(bob.clone())).unwrap();
+    }: _(SystemOrigin::Signed(bob.clone()), 0)
+    verify {
+        assert_last_event::<T>(Event::<T>::UnstakeReceiptTransferred(0, alice, bob).into());
+    }
+
+    payout_stakers {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        let validator: T::AccountId = account("Sample", 101, SEED);
+        initial_set_up::<T>(alice);
+        PendingPayouts::<T>::put(BoundedVec::truncate_from(vec![(0, validator.clone())]));
+    }: {
+        assert_eq!(LiquidStaking::<T>::do_payout_stakers(), 1);
+    }
+    verify {
+        assert!(PendingPayouts::<T>::get().is_empty());
+        assert_last_event::<T>(Event::<T>::PayingOutStakers(validator, 0).into());
+    }
+
+    withdraw_rewards {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        initial_set_up::<T>(alice);
+        PendingRewards::<T>::insert(0, REWARDS_AMOUNT);
+    }: {
+        assert_eq!(LiquidStaking::<T>::do_withdraw_rewards(), 1);
+    }
+    verify {
+        assert_eq!(RewardsWithdrawals::<T>::get(0), Some(REWARDS_AMOUNT));
+        assert_last_event::<T>(Event::<T>::WithdrawingRewards(0, REWARDS_AMOUNT).into());
+    }
+
+    set_stash_rewards {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        initial_set_up::<T>(alice.clone());
+        LiquidStaking::<T>::stake(SystemOrigin::Signed(alice).into(), STAKE_AMOUNT).unwrap();
+        LiquidStaking::<T>::bond(SystemOrigin::Root.into(), 0, BOND_AMOUNT, RewardDestination::Stash).unwrap();
+        LiquidStaking::<T>::notification_received(
+            pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+            0u64,
+            Response::ExecutionResult(None)
+        ).unwrap();
+        let staking_ledger = StakingLedgers::<T>::get(0).unwrap();
+        let account = AccountInfo {
+            data: AccountData {
+                free: BOND_AMOUNT + REWARDS_AMOUNT,
+                misc_frozen: BOND_AMOUNT,
+                fee_frozen: BOND_AMOUNT,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (root, proof) = stash_proof::<T>(0, &account, &staking_ledger);
+        ValidationData::<T>::put(PersistedValidationData {
+            relay_parent_storage_root: root,
+            ..Default::default()
+        });
+    }: _(SystemOrigin::Root, 0u16, account, staking_ledger, proof)
+    verify {
+        assert_eq!(PendingRewards::<T>::get(0), Some(REWARDS_AMOUNT));
+        assert_last_event::<T>(Event::<T>::StashRewardsUpdated(0, REWARDS_AMOUNT).into());
+    }
+
+    set_distribution_strategy {
+        let n in 1 .. T::DerivativeIndexList::get().len() as u32;
+        // only the last weight isn't zero
+        let indexes: Vec<DerivativeIndex> = T::DerivativeIndexList::get()
+            .into_iter()
+            .take(n as usize)
+            .collect();
+        for (i, &index) in indexes.iter().enumerate() {
+            DistributionWeights::<T>::insert(index, (i + 1 == indexes.len()) as u32);
+        }
+    }: _(SystemOrigin::Root, DistributionStrategyKind::Weighted)
+    verify {
+        assert_eq!(CurrentDistributionStrategy::<T>::get(), DistributionStrategyKind::Weighted);
+    }
+
+    set_distribution_weights {
+        let n in 1 .. T::DerivativeIndexList::get().len() as u32;
+        let weights: Vec<(DerivativeIndex, u32)> = T::DerivativeIndexList::get()
+            .into_iter()
+            .take(n as usize)
+            .map(|index| (index, 1))
+            .collect();
+    }: _(SystemOrigin::Root, weights.clone())
+    verify {
+        assert_last_event::<T>(Event::<T>::DistributionWeightsUpdated(weights).into());
+    }
+
+    instant_unstake {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        let unstaker: T::AccountId = account("unstaker", 0, SEED);
+        let fast_unstake_amount = 50_000_000_000;
+        initial_set_up::<T>(alice.clone());
+        LiquidStaking::<T>::stake(SystemOrigin::Signed(alice).into(), STAKE_AMOUNT).unwrap();
+        <T as pallet_xcm_helper::Config>::Assets::mint_into(
+            T::LiquidCurrency::get(),
+            &unstaker,
+            INITIAL_AMOUNT,
+        )
+        .unwrap();
+    }: _(SystemOrigin::Signed(unstaker.clone()), fast_unstake_amount, 0)
+    verify {
+        assert_eq!(FastUnstakeRequests::<T>::get(&unstaker), 0);
+    }
 }
 
 impl_benchmark_test_suite!(LiquidStaking, crate::mock::para_ext(1), crate::mock::Test);
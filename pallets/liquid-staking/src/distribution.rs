@@ -1,8 +1,8 @@
-use frame_support::traits::tokens::Balance as BalanceT;
+use frame_support::traits::{tokens::Balance as BalanceT, Get};
 use pallet_traits::DistributionStrategy;
-use primitives::DerivativeIndex;
-use sp_runtime::FixedPointOperand;
-use sp_std::vec::Vec;
+use primitives::{DerivativeIndex, Rate};
+use sp_runtime::{FixedPointNumber, FixedPointOperand};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 pub struct AverageDistribution;
 impl<Balance: BalanceT + FixedPointOperand> DistributionStrategy<Balance> for AverageDistribution {
@@ -168,3 +168,150 @@ impl<Balance: BalanceT + FixedPointOperand> DistributionStrategy<Balance> for Ma
         distributions
     }
 }
+
+/// Moves the ledgers toward the target weights given by `W`, the derivative indexes
+/// without weight are left untouched.
+pub struct WeightedDistribution<W>(PhantomData<W>);
+
+impl<W: Get<Vec<(DerivativeIndex, u32)>>> WeightedDistribution<W> {
+    fn weight_of(weights: &[(DerivativeIndex, u32)], index: DerivativeIndex) -> u32 {
+        weights
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, weight)| *weight)
+            .unwrap_or_default()
+    }
+
+    // Shares `input` in proportion to `parts`, the rounding dust is not distributed
+    fn split<Balance: BalanceT + FixedPointOperand>(
+        parts: Vec<(DerivativeIndex, Balance)>,
+        input: Balance,
+    ) -> Vec<(DerivativeIndex, Balance)> {
+        let total = parts
+            .iter()
+            .fold(Balance::zero(), |acc, (_, part)| acc.saturating_add(*part));
+        if total.is_zero() {
+            return Default::default();
+        }
+        if total <= input {
+            return parts;
+        }
+
+        parts
+            .into_iter()
+            .map(|(index, part)| {
+                let amount = Rate::checked_from_rational(part, total)
+                    .map(|ratio| ratio.saturating_mul_int(input))
+                    .unwrap_or_default();
+                (index, amount)
+            })
+            .collect()
+    }
+}
+
+impl<W, Balance> DistributionStrategy<Balance> for WeightedDistribution<W>
+where
+    W: Get<Vec<(DerivativeIndex, u32)>>,
+    Balance: BalanceT + FixedPointOperand,
+{
+    fn get_bond_distributions(
+        bonded_amounts: Vec<(DerivativeIndex, Balance, Balance)>,
+        input: Balance,
+        cap: Balance,
+        min_nominator_bond: Balance,
+    ) -> Vec<(DerivativeIndex, Balance)> {
+        let weights = W::get();
+        let total_weight = weights
+            .iter()
+            .fold(0u32, |acc, (_, weight)| acc.saturating_add(*weight));
+        let total_bonded = bonded_amounts
+            .iter()
+            .fold(input, |acc, (_, _, total)| acc.saturating_add(*total));
+
+        // the amount each ledger misses to reach its target
+        let deficits = bonded_amounts
+            .iter()
+            .filter_map(|&(index, _, total_bonded_of)| {
+                let target =
+                    Rate::checked_from_rational(Self::weight_of(&weights, index), total_weight)?
+                        .saturating_mul_int(total_bonded);
+                let deficit = target
+                    .saturating_sub(total_bonded_of)
+                    .min(cap.saturating_sub(total_bonded_of));
+                (!deficit.is_zero()).then_some((index, deficit))
+            })
+            .collect();
+
+        Self::split(deficits, input)
+            .into_iter()
+            .filter(|&(index, amount)| {
+                let active_bonded = bonded_amounts
+                    .iter()
+                    .find(|(i, _, _)| *i == index)
+                    .map(|(_, active, _)| *active)
+                    .unwrap_or_default();
+                !amount.is_zero() && amount.saturating_add(active_bonded) >= min_nominator_bond
+            })
+            .collect()
+    }
+
+    fn get_unbond_distributions(
+        active_bonded_amounts: Vec<(DerivativeIndex, Balance)>,
+        input: Balance,
+        min_nominator_bond: Balance,
+    ) -> Vec<(DerivativeIndex, Balance)> {
+        let weights = W::get();
+        let total_weight = weights
+            .iter()
+            .fold(0u32, |acc, (_, weight)| acc.saturating_add(*weight));
+        let total_active = active_bonded_amounts
+            .iter()
+            .fold(Balance::zero(), |acc, (_, active)| {
+                acc.saturating_add(*active)
+            })
+            .saturating_sub(input);
+
+        // the amount each ledger exceeds its target by, an unbond can't leave less
+        // than `min_nominator_bond`
+        let excesses = active_bonded_amounts
+            .iter()
+            .filter_map(|&(index, active_bonded)| {
+                let target =
+                    Rate::checked_from_rational(Self::weight_of(&weights, index), total_weight)?
+                        .saturating_mul_int(total_active)
+                        .max(min_nominator_bond);
+                let excess = active_bonded.saturating_sub(target);
+                (!excess.is_zero()).then_some((index, excess))
+            })
+            .collect();
+
+        Self::split(excesses, input)
+            .into_iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .collect()
+    }
+
+    fn get_rebond_distributions(
+        unbonding_amounts: Vec<(DerivativeIndex, Balance)>,
+        input: Balance,
+    ) -> Vec<(DerivativeIndex, Balance)> {
+        let weights = W::get();
+        let total_weight = unbonding_amounts
+            .iter()
+            .filter(|(_, unbonding)| !unbonding.is_zero())
+            .fold(0u32, |acc, (index, _)| {
+                acc.saturating_add(Self::weight_of(&weights, *index))
+            });
+
+        unbonding_amounts
+            .into_iter()
+            .filter_map(|(index, unbonding)| {
+                let amount =
+                    Rate::checked_from_rational(Self::weight_of(&weights, index), total_weight)?
+                        .saturating_mul_int(input)
+                        .min(unbonding);
+                (!amount.is_zero()).then_some((index, amount))
+            })
+            .collect()
+    }
+}
@@ -17,6 +17,10 @@
 //! ## Overview
 //!
 //! This pallet manages the NPoS operations for relay chain asset.
+//!
+//! The pallet is instantiable, every instance runs an independent pool with its own
+//! staking and liquid currencies, exchange rate, matching pool, ledgers and eras. Each
+//! instance needs its own `PalletId` so that the pool accounts don't collide.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -28,11 +32,13 @@ use sp_runtime::{
 
 pub use pallet::*;
 use pallet_traits::{
-    DecimalProvider, DistributionStrategy, ExchangeRateProvider, LiquidStakingConvert,
-    LiquidStakingCurrenciesProvider, Loans, LoansMarketDataProvider, LoansPositionDataProvider,
-    ValidationDataProvider,
+    DecimalProvider, DistributionStrategy, ExchangeRateProvider, ExchangeRateSnapshot,
+    LiquidStakingConvert, LiquidStakingCurrenciesProvider, Loans, LoansMarketDataProvider,
+    LoansPositionDataProvider, NominationStrategy, PendingUnstake, PendingUnstakes, StableSwap,
+    ValidationDataProvider, ValidatorInfo, AMM,
 };
-use primitives::{PersistedValidationData, Rate};
+use primitives::{DerivativeIndex, PersistedValidationData, Rate};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 mod benchmarking;
 
@@ -44,10 +50,13 @@ mod mock;
 
 pub mod distribution;
 pub mod migrations;
+pub mod nomination;
 pub mod types;
 pub mod weights;
 pub use weights::WeightInfo;
 
+use distribution::WeightedDistribution;
+
 #[macro_use]
 extern crate primitives;
 
@@ -75,33 +84,46 @@ pub mod pallet {
     use pallet_xcm::ensure_response;
     use sp_runtime::{
         traits::{
-            AccountIdConversion, BlakeTwo256, BlockNumberProvider, CheckedDiv, CheckedSub,
-            Saturating, StaticLookup,
+            AccountIdConversion, BlakeTwo256, BlockNumberProvider, CheckedAdd, CheckedDiv,
+            CheckedMul, CheckedSub, SaturatedConversion, Saturating, StaticLookup,
         },
         ArithmeticError, FixedPointNumber, TransactionOutcome,
     };
-    use sp_std::{borrow::Borrow, boxed::Box, cmp::min, result::Result, vec::Vec};
+    use sp_std::{borrow::Borrow, boxed::Box, cmp::min, ops::Range, result::Result, vec, vec::Vec};
     use sp_trie::StorageProof;
     use xcm::latest::prelude::*;
 
     use pallet_traits::ump::*;
     use pallet_xcm_helper::XcmHelper;
-    use primitives::{Balance, CurrencyId, DerivativeIndex, EraIndex, ParaId, Rate, Ratio};
+    use primitives::{
+        Balance, CurrencyId, DerivativeIndex, EraIndex, ParaId, Rate, Ratio, SECONDS_PER_YEAR,
+    };
 
     use super::{types::*, *};
 
     pub const MAX_UNLOCKING_CHUNKS: usize = 32;
 
+    /// Max number of trie nodes in a relaychain storage proof provided by anyone
+    pub const MAX_PROOF_NODES: u32 = 32;
+
+    /// Max encoded length of a single trie node in such a storage proof
+    pub const MAX_PROOF_NODE_LEN: u32 = 2048;
+
+    /// Relaychain blocks are produced every 6 seconds
+    pub const RELAYCHAIN_BLOCK_SECONDS: u64 = 6;
+
     pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
-    pub type AssetIdOf<T> =
-        <<T as Config>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
-    pub type BalanceOf<T> =
-        <<T as Config>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+    pub type AssetIdOf<T, I = ()> =
+        <<T as Config<I>>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
+    pub type BalanceOf<T, I = ()> =
+        <<T as Config<I>>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+    pub type BoundedStorageProof =
+        BoundedVec<BoundedVec<u8, ConstU32<MAX_PROOF_NODE_LEN>>, ConstU32<MAX_PROOF_NODES>>;
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
     #[pallet::without_storage_info]
-    pub struct Pallet<T>(_);
+    pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
     /// Utility type for managing upgrades/migrations.
     #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
@@ -109,16 +131,20 @@ pub mod pallet {
         V1,
         V2,
         V3,
+        V4,
     }
 
     #[pallet::config]
-    pub trait Config: frame_system::Config + pallet_utility::Config + pallet_xcm::Config {
-        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+    pub trait Config<I: 'static = ()>:
+        frame_system::Config + pallet_utility::Config + pallet_xcm::Config
+    {
+        type RuntimeEvent: From<Event<Self, I>>
+            + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
         type RuntimeOrigin: IsType<<Self as frame_system::Config>::RuntimeOrigin>
-            + Into<Result<pallet_xcm::Origin, <Self as Config>::RuntimeOrigin>>;
+            + Into<Result<pallet_xcm::Origin, <Self as Config<I>>::RuntimeOrigin>>;
 
-        type RuntimeCall: IsType<<Self as pallet_xcm::Config>::RuntimeCall> + From<Call<Self>>;
+        type RuntimeCall: IsType<<Self as pallet_xcm::Config>::RuntimeCall> + From<Call<Self, I>>;
 
         /// Assets for deposit/withdraw assets to/from pallet account
         type Assets: Transfer<Self::AccountId, AssetId = CurrencyId>
@@ -152,7 +178,7 @@ pub mod pallet {
 
         /// Xcm fees
         #[pallet::constant]
-        type XcmFees: Get<BalanceOf<Self>>;
+        type XcmFees: Get<BalanceOf<Self, I>>;
 
         /// Loans instant unstake fee
         #[pallet::constant]
@@ -164,23 +190,23 @@ pub mod pallet {
 
         /// Staking currency
         #[pallet::constant]
-        type StakingCurrency: Get<AssetIdOf<Self>>;
+        type StakingCurrency: Get<AssetIdOf<Self, I>>;
 
         /// Liquid currency
         #[pallet::constant]
-        type LiquidCurrency: Get<AssetIdOf<Self>>;
+        type LiquidCurrency: Get<AssetIdOf<Self, I>>;
 
         /// Collateral currency
         #[pallet::constant]
-        type CollateralCurrency: Get<AssetIdOf<Self>>;
+        type CollateralCurrency: Get<AssetIdOf<Self, I>>;
 
         /// Minimum stake amount
         #[pallet::constant]
-        type MinStake: Get<BalanceOf<Self>>;
+        type MinStake: Get<BalanceOf<Self, I>>;
 
         /// Minimum unstake amount
         #[pallet::constant]
-        type MinUnstake: Get<BalanceOf<Self>>;
+        type MinUnstake: Get<BalanceOf<Self, I>>;
 
         /// Weight information
         type WeightInfo: WeightInfo;
@@ -191,7 +217,7 @@ pub mod pallet {
 
         /// The minimum active bond to become and maintain the role of a nominator.
         #[pallet::constant]
-        type MinNominatorBond: Get<BalanceOf<Self>>;
+        type MinNominatorBond: Get<BalanceOf<Self, I>>;
 
         /// Number of blocknumbers that each period contains.
         /// SessionsPerEra * EpochDuration / MILLISECS_PER_BLOCK
@@ -206,15 +232,16 @@ pub mod pallet {
             + BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
 
         /// Loans
-        type Loans: Loans<AssetIdOf<Self>, Self::AccountId, BalanceOf<Self>>
-            + LoansPositionDataProvider<AssetIdOf<Self>, Self::AccountId, BalanceOf<Self>>
-            + LoansMarketDataProvider<AssetIdOf<Self>, BalanceOf<Self>>;
+        type Loans: Loans<AssetIdOf<Self, I>, Self::AccountId, BalanceOf<Self, I>>
+            + LoansPositionDataProvider<AssetIdOf<Self, I>, Self::AccountId, BalanceOf<Self, I>>
+            + LoansMarketDataProvider<AssetIdOf<Self, I>, BalanceOf<Self, I>>;
 
         /// To expose XCM helper functions
-        type XCM: XcmHelper<Self, BalanceOf<Self>, Self::AccountId>;
+        type XCM: XcmHelper<Self, BalanceOf<Self, I>, Self::AccountId>;
 
-        /// Current strategy for distributing assets to multi-accounts
-        type DistributionStrategy: DistributionStrategy<BalanceOf<Self>>;
+        /// Default strategy for distributing assets to multi-accounts, governance can
+        /// switch to the weighted strategy at runtime
+        type DistributionStrategy: DistributionStrategy<BalanceOf<Self, I>>;
 
         /// Number of blocknumbers that do_matching after each era updated.
         /// Need to do_bond before relaychain store npos solution
@@ -227,36 +254,76 @@ pub mod pallet {
 
         /// Decimal provider.
         type Decimal: DecimalProvider<CurrencyId>;
+
+        /// Current strategy for nominating validators from the registry
+        type NominationStrategy: NominationStrategy<Self::AccountId, BalanceOf<Self, I>>;
+
+        /// Maximum number of validators in the registry
+        #[pallet::constant]
+        type MaxValidators: Get<u32>;
+
+        /// Maximum number of targets nominated by each derivative index
+        #[pallet::constant]
+        type MaxNominations: Get<u32>;
+
+        /// Number of eras kept in the validators' reward points history
+        #[pallet::constant]
+        type EraPointsDepth: Get<u32>;
+
+        /// Number of eras kept in the exchange rate history
+        #[pallet::constant]
+        type ExchangeRateHistoryDepth: Get<u32>;
+
+        /// Bounty paid from the reserves to whoever advances the era with a proof
+        #[pallet::constant]
+        type EraAdvanceBounty: Get<BalanceOf<Self, I>>;
+
+        /// Maximum number of staking.payout_stakers calls sent to relaychain in a block
+        #[pallet::constant]
+        type MaxPayoutsPerBlock: Get<u32>;
+
+        /// Maximum number of payouts waiting to be sent, the oldest are dropped beyond it
+        #[pallet::constant]
+        type MaxPendingPayouts: Get<u32>;
+
+        /// AMM used to sell the liquid currency on instant unstakes
+        type AMM: AMM<Self::AccountId, AssetIdOf<Self, I>, BalanceOf<Self, I>, BlockNumberFor<Self>>;
+
+        /// Stableswap pools used to sell the liquid currency on instant unstakes
+        type StableSwap: StableSwap<Self::AccountId, AssetIdOf<Self, I>, BalanceOf<Self, I>>;
     }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    pub enum Event<T: Config> {
+    pub enum Event<T: Config<I>, I: 'static = ()> {
         /// The assets get staked successfully
-        Staked(T::AccountId, BalanceOf<T>),
+        Staked(T::AccountId, BalanceOf<T, I>),
         /// The derivative get unstaked successfully
-        Unstaked(T::AccountId, BalanceOf<T>, BalanceOf<T>),
+        Unstaked(T::AccountId, BalanceOf<T, I>, BalanceOf<T, I>),
         /// Staking ledger updated
-        StakingLedgerUpdated(DerivativeIndex, StakingLedger<T::AccountId, BalanceOf<T>>),
+        StakingLedgerUpdated(
+            DerivativeIndex,
+            StakingLedger<T::AccountId, BalanceOf<T, I>>,
+        ),
         /// Sent staking.bond call to relaychain
         Bonding(
             DerivativeIndex,
             T::AccountId,
-            BalanceOf<T>,
+            BalanceOf<T, I>,
             RewardDestination<T::AccountId>,
         ),
         /// Sent staking.bond_extra call to relaychain
-        BondingExtra(DerivativeIndex, BalanceOf<T>),
+        BondingExtra(DerivativeIndex, BalanceOf<T, I>),
         /// Sent staking.unbond call to relaychain
-        Unbonding(DerivativeIndex, BalanceOf<T>),
+        Unbonding(DerivativeIndex, BalanceOf<T, I>),
         /// Sent staking.rebond call to relaychain
-        Rebonding(DerivativeIndex, BalanceOf<T>),
+        Rebonding(DerivativeIndex, BalanceOf<T, I>),
         /// Sent staking.withdraw_unbonded call to relaychain
         WithdrawingUnbonded(DerivativeIndex, u32),
         /// Sent staking.nominate call to relaychain
         Nominating(DerivativeIndex, Vec<T::AccountId>),
         /// Staking ledger's cap was updated
-        StakingLedgerCapUpdated(BalanceOf<T>),
+        StakingLedgerCapUpdated(BalanceOf<T, I>),
         /// Reserve_factor was updated
         ReserveFactorUpdated(Ratio),
         /// Exchange rate was updated
@@ -266,34 +333,92 @@ pub mod pallet {
         NotificationReceived(Box<MultiLocation>, QueryId, Option<(u32, XcmError)>),
         /// Claim user's unbonded staking assets
         /// [account_id, amount]
-        ClaimedFor(T::AccountId, BalanceOf<T>),
+        ClaimedFor(T::AccountId, BalanceOf<T, I>),
         /// New era
         /// [era_index]
         NewEra(EraIndex),
         /// Matching stakes & unstakes for optimizing operations to be done
         /// on relay chain
         /// [bond_amount, rebond_amount, unbond_amount]
-        Matching(BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+        Matching(BalanceOf<T, I>, BalanceOf<T, I>, BalanceOf<T, I>),
         /// Event emitted when the reserves are reduced
         /// [receiver, reduced_amount]
-        ReservesReduced(T::AccountId, BalanceOf<T>),
+        ReservesReduced(T::AccountId, BalanceOf<T, I>),
         /// Unstake cancelled
         /// [account_id, amount, liquid_amount]
-        UnstakeCancelled(T::AccountId, BalanceOf<T>, BalanceOf<T>),
+        UnstakeCancelled(T::AccountId, BalanceOf<T, I>, BalanceOf<T, I>),
         /// Commission rate was updated
         CommissionRateUpdated(Rate),
         /// Fast Unstake Matched
         /// [unstaker, received_staking_amount, matched_liquid_amount, fee_in_liquid_currency]
-        FastUnstakeMatched(T::AccountId, BalanceOf<T>, BalanceOf<T>, BalanceOf<T>),
+        FastUnstakeMatched(
+            T::AccountId,
+            BalanceOf<T, I>,
+            BalanceOf<T, I>,
+            BalanceOf<T, I>,
+        ),
         /// Unstake_reserve_factor was updated
         UnstakeReserveFactorUpdated(Ratio),
         /// Event emitted when the unstake reserves are reduced
         /// [receiver, reduced_amount]
-        UnstakeReservesReduced(T::AccountId, BalanceOf<T>),
+        UnstakeReservesReduced(T::AccountId, BalanceOf<T, I>),
+        /// Slash detected in a proven staking ledger
+        /// [derivative_index, era_index, slashed_amount, covered_by_reserves]
+        Slashed(DerivativeIndex, EraIndex, BalanceOf<T, I>, BalanceOf<T, I>),
+        /// Validator added to the registry
+        /// [validator]
+        ValidatorAdded(T::AccountId),
+        /// Validator removed from the registry
+        /// [validator]
+        ValidatorRemoved(T::AccountId),
+        /// Validator's preferences or stake updated from the relaychain
+        /// [validator, validator_info]
+        ValidatorUpdated(T::AccountId, ValidatorInfo<BalanceOf<T, I>>),
+        /// Validators' reward points updated from the relaychain
+        /// [era_index]
+        EraRewardPointsUpdated(EraIndex),
+        /// Unstake receipt minted for an unstake via the relaychain
+        /// [receipt_id, owner, amount, era_index]
+        UnstakeReceiptMinted(UnstakeReceiptId, T::AccountId, BalanceOf<T, I>, EraIndex),
+        /// Unstake receipt transferred
+        /// [receipt_id, from, to]
+        UnstakeReceiptTransferred(UnstakeReceiptId, T::AccountId, T::AccountId),
+        /// Unstake receipt transfer requested, waiting for the receiver to accept it
+        /// [receipt_id, from, to]
+        UnstakeReceiptTransferRequested(UnstakeReceiptId, T::AccountId, T::AccountId),
+        /// Sent staking.payout_stakers call to relaychain
+        /// [validator, era_index]
+        PayingOutStakers(T::AccountId, EraIndex),
+        /// Rewards paid out to a stash were proven, they are withdrawn in the next block
+        /// [derivative_index, amount]
+        StashRewardsUpdated(DerivativeIndex, BalanceOf<T, I>),
+        /// Sent the rewards paid to a stash back to the parachain's sovereign account
+        /// [derivative_index, amount]
+        WithdrawingRewards(DerivativeIndex, BalanceOf<T, I>),
+        /// Rewards withdrawn from a stash were re-bonded
+        /// [derivative_index, amount]
+        RewardsRebonded(DerivativeIndex, BalanceOf<T, I>),
+        /// Era advanced by providing a proof of the relaychain's active era
+        /// [who, era_index, bounty]
+        EraAdvanced(T::AccountId, EraIndex, BalanceOf<T, I>),
+        /// Distribution strategy was updated
+        /// [strategy]
+        DistributionStrategyUpdated(DistributionStrategyKind),
+        /// Target weights of the weighted distribution strategy were updated
+        /// [weights]
+        DistributionWeightsUpdated(Vec<(DerivativeIndex, u32)>),
+        /// Instant unstake done through the cheapest provider
+        /// [account_id, unstake_provider, liquid_amount, received_staking_amount]
+        InstantUnstaked(
+            T::AccountId,
+            UnstakeProvider,
+            BalanceOf<T, I>,
+            BalanceOf<T, I>,
+        ),
     }
 
     #[pallet::error]
-    pub enum Error<T> {
+    pub enum Error<T, I = ()> {
         /// Exchange rate is invalid.
         InvalidExchangeRate,
         /// The stake was below the minimum, `MinStake`.
@@ -335,17 +460,50 @@ pub mod pallet {
         NoUnlockings,
         /// Invalid commission rate
         InvalidCommissionRate,
+        /// Validator is already in the registry
+        ValidatorAlreadyAdded,
+        /// Validator is not in the registry
+        ValidatorNotFound,
+        /// The registry can not have more than `MaxValidators` validators
+        TooManyValidators,
+        /// Can not nominate more than `MaxNominations` targets
+        TooManyNominations,
+        /// The era is not proven yet or already proven
+        InvalidEra,
+        /// Unstake receipt doesn't exist
+        UnstakeReceiptNotFound,
+        /// Only the owner of the unstake receipt can transfer it
+        NotReceiptOwner,
+        /// Unstake receipt is worth less than `MinUnstake` and can't be transferred
+        UnstakeReceiptTooSmall,
+        /// Unstake receipt has no transfer pending for the caller
+        NoPendingReceiptTransfer,
+        /// The unstake provider can't be used by this call
+        InvalidUnstakeProvider,
+        /// None of the unstake providers can serve the instant unstake
+        NoInstantUnstakeProvider,
+        /// The received staking amount is below `min_amount_out`
+        TooLittleReceived,
+        /// Distribution weights must be set once for known derivative indexes and
+        /// not all be zero
+        InvalidDistributionWeights,
+        /// The stash has no rewards to withdraw
+        NoRewards,
+        /// The rewards of the stash are already waiting to be withdrawn
+        RewardsLocked,
+        /// The weighted distribution strategy can't be used without distribution weights
+        DistributionWeightsRequired,
     }
 
     /// The exchange rate between relaychain native asset and the voucher.
     #[pallet::storage]
     #[pallet::getter(fn exchange_rate)]
-    pub type ExchangeRate<T: Config> = StorageValue<_, Rate, ValueQuery>;
+    pub type ExchangeRate<T: Config<I>, I: 'static = ()> = StorageValue<_, Rate, ValueQuery>;
 
     /// The commission rate charge for staking total rewards.
     #[pallet::storage]
     #[pallet::getter(fn commission_rate)]
-    pub type CommissionRate<T: Config> = StorageValue<_, Rate, ValueQuery>;
+    pub type CommissionRate<T: Config<I>, I: 'static = ()> = StorageValue<_, Rate, ValueQuery>;
 
     /// ValidationData of previous block
     ///
@@ -354,98 +512,237 @@ pub mod pallet {
     /// extrinsics
     #[pallet::storage]
     #[pallet::getter(fn validation_data)]
-    pub type ValidationData<T: Config> = StorageValue<_, PersistedValidationData, OptionQuery>;
+    pub type ValidationData<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, PersistedValidationData, OptionQuery>;
 
     /// Fraction of reward currently set aside for reserves.
     #[pallet::storage]
     #[pallet::getter(fn reserve_factor)]
-    pub type ReserveFactor<T: Config> = StorageValue<_, Ratio, ValueQuery>;
+    pub type ReserveFactor<T: Config<I>, I: 'static = ()> = StorageValue<_, Ratio, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn total_reserves)]
-    pub type TotalReserves<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub type TotalReserves<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T, I>, ValueQuery>;
 
     /// Fraction of reward currently set aside for unstake reserves.
     #[pallet::storage]
     #[pallet::getter(fn unstake_reserve_factor)]
-    pub type UnstakeReserveFactor<T: Config> = StorageValue<_, Ratio, ValueQuery>;
+    pub type UnstakeReserveFactor<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, Ratio, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn total_unstake_reserves)]
-    pub type TotalUnstakeReserves<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub type TotalUnstakeReserves<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T, I>, ValueQuery>;
 
     /// Store total stake amount and unstake amount in each era,
     /// And will update when stake/unstake occurred.
     #[pallet::storage]
     #[pallet::getter(fn matching_pool)]
-    pub type MatchingPool<T: Config> = StorageValue<_, MatchingLedger<BalanceOf<T>>, ValueQuery>;
+    pub type MatchingPool<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, MatchingLedger<BalanceOf<T, I>>, ValueQuery>;
 
     /// Staking ledger's cap
     #[pallet::storage]
     #[pallet::getter(fn staking_ledger_cap)]
-    pub type StakingLedgerCap<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+    pub type StakingLedgerCap<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BalanceOf<T, I>, ValueQuery>;
 
     /// Flying & failed xcm requests
     #[pallet::storage]
     #[pallet::getter(fn xcm_request)]
-    pub type XcmRequests<T> = StorageMap<_, Blake2_128Concat, QueryId, XcmRequest<T>, OptionQuery>;
+    pub type XcmRequests<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Blake2_128Concat,
+        QueryId,
+        XcmRequest<T::AccountId, BalanceOf<T, I>>,
+        OptionQuery,
+    >;
 
     /// Users' fast unstake requests in liquid currency
     #[pallet::storage]
     #[pallet::getter(fn fast_unstake_requests)]
-    pub type FastUnstakeRequests<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+    pub type FastUnstakeRequests<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T, I>, ValueQuery>;
 
     /// Current era index
     /// Users can come to claim their unbonded staking assets back once this value arrived
     /// at certain height decided by `BondingDuration` and `EraLength`
     #[pallet::storage]
     #[pallet::getter(fn current_era)]
-    pub type CurrentEra<T: Config> = StorageValue<_, EraIndex, ValueQuery>;
+    pub type CurrentEra<T: Config<I>, I: 'static = ()> = StorageValue<_, EraIndex, ValueQuery>;
 
     /// Current era's start relaychain block
     #[pallet::storage]
     #[pallet::getter(fn era_start_block)]
-    pub type EraStartBlock<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+    pub type EraStartBlock<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BlockNumberFor<T>, ValueQuery>;
 
     /// Unbonding requests to be handled after arriving at target era
     #[pallet::storage]
     #[pallet::getter(fn unlockings)]
-    pub type Unlockings<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::AccountId, Vec<UnlockChunk<BalanceOf<T>>>, OptionQuery>;
+    pub type Unlockings<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Vec<UnlockChunk<BalanceOf<T, I>>>,
+        OptionQuery,
+    >;
+
+    /// The id of the next unstake receipt
+    #[pallet::storage]
+    #[pallet::getter(fn next_unstake_receipt_id)]
+    pub type NextUnstakeReceiptId<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, UnstakeReceiptId, ValueQuery>;
+
+    /// Transferable claims on the unbonding staking assets
+    #[pallet::storage]
+    #[pallet::getter(fn unstake_receipt)]
+    pub type UnstakeReceipts<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        UnstakeReceiptId,
+        UnstakeReceipt<T::AccountId, BalanceOf<T, I>>,
+        OptionQuery,
+    >;
+
+    /// The unstake receipts held by each account
+    #[pallet::storage]
+    pub type UnstakeReceiptsOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        UnstakeReceiptId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Unstake receipt transfers waiting for the receiver to accept them
+    #[pallet::storage]
+    #[pallet::getter(fn pending_receipt_transfer)]
+    pub type PendingReceiptTransfers<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, UnstakeReceiptId, T::AccountId, OptionQuery>;
 
     /// Platform's staking ledgers
     #[pallet::storage]
     #[pallet::getter(fn staking_ledger)]
-    pub type StakingLedgers<T: Config> = StorageMap<
+    pub type StakingLedgers<T: Config<I>, I: 'static = ()> = StorageMap<
         _,
         Blake2_128Concat,
         DerivativeIndex,
-        StakingLedger<T::AccountId, BalanceOf<T>>,
+        StakingLedger<T::AccountId, BalanceOf<T, I>>,
         OptionQuery,
     >;
 
+    /// Slashes of the platform's staking ledgers, recorded in the era they were detected
+    #[pallet::storage]
+    #[pallet::getter(fn slashes)]
+    pub type Slashes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        DerivativeIndex,
+        SlashRecord<BalanceOf<T, I>>,
+        ValueQuery,
+    >;
+
+    /// Validators registry, the nominations are picked from these validators
+    #[pallet::storage]
+    #[pallet::getter(fn validator_info)]
+    pub type Validators<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ValidatorInfo<BalanceOf<T, I>>, OptionQuery>;
+
+    /// The latest era whose validators' reward points were proven
+    #[pallet::storage]
+    #[pallet::getter(fn last_rewarded_era)]
+    pub type LastRewardedEra<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, EraIndex, OptionQuery>;
+
+    /// Current nominations of the platform's staking ledgers
+    #[pallet::storage]
+    #[pallet::getter(fn nominations)]
+    pub type Nominations<T: Config<I>, I: 'static = ()> = StorageMap<
+        _,
+        Twox64Concat,
+        DerivativeIndex,
+        BoundedVec<T::AccountId, T::MaxNominations>,
+        ValueQuery,
+    >;
+
+    /// Validators nominated when the current era started, their rewards of the era
+    /// are paid out once it ends
+    #[pallet::storage]
+    #[pallet::getter(fn era_nominations)]
+    pub type EraNominations<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxNominations>, ValueQuery>;
+
+    /// Validators and eras whose staking rewards are waiting to be paid out,
+    /// the oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn pending_payouts)]
+    pub type PendingPayouts<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, BoundedVec<(EraIndex, T::AccountId), T::MaxPendingPayouts>, ValueQuery>;
+
+    /// Rewards paid out to each stash and proven on the relaychain, they are
+    /// withdrawn in the next `on_initialize`
+    #[pallet::storage]
+    #[pallet::getter(fn pending_rewards)]
+    pub type PendingRewards<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, DerivativeIndex, BalanceOf<T, I>, OptionQuery>;
+
+    /// Rewards being withdrawn from each stash, until the withdrawal is notified
+    #[pallet::storage]
+    #[pallet::getter(fn rewards_withdrawal)]
+    pub type RewardsWithdrawals<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, DerivativeIndex, BalanceOf<T, I>, OptionQuery>;
+
+    /// Strategy currently used to distribute the bonds, unbonds and rebonds
+    #[pallet::storage]
+    #[pallet::getter(fn distribution_strategy)]
+    pub type CurrentDistributionStrategy<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, DistributionStrategyKind, ValueQuery>;
+
+    /// Target weight of each derivative index for the weighted distribution strategy
+    #[pallet::storage]
+    #[pallet::getter(fn distribution_weight)]
+    pub type DistributionWeights<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, DerivativeIndex, u32, OptionQuery>;
+
+    /// Snapshots of the pool taken at the beginning of the last
+    /// `ExchangeRateHistoryDepth` eras, the oldest first
+    #[pallet::storage]
+    #[pallet::getter(fn exchange_rate_history)]
+    pub type ExchangeRateHistory<T: Config<I>, I: 'static = ()> = StorageValue<
+        _,
+        BoundedVec<ExchangeRateSnapshot<BalanceOf<T, I>>, T::ExchangeRateHistoryDepth>,
+        ValueQuery,
+    >;
+
     /// Set to true if staking ledger has been modified in this block
     #[pallet::storage]
     #[pallet::getter(fn is_updated)]
-    pub type IsUpdated<T: Config> = StorageMap<_, Twox64Concat, DerivativeIndex, bool, ValueQuery>;
+    pub type IsUpdated<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, DerivativeIndex, bool, ValueQuery>;
 
     /// DefaultVersion is using for initialize the StorageVersion
     #[pallet::type_value]
-    pub(super) fn DefaultVersion<T: Config>() -> Versions {
+    pub(super) fn DefaultVersion<T: Config<I>, I: 'static>() -> Versions {
         Versions::V2
     }
 
     /// Storage version of the pallet.
     #[pallet::storage]
-    pub(crate) type StorageVersion<T: Config> =
-        StorageValue<_, Versions, ValueQuery, DefaultVersion<T>>;
+    pub(crate) type StorageVersion<T: Config<I>, I: 'static = ()> =
+        StorageValue<_, Versions, ValueQuery, DefaultVersion<T, I>>;
 
     /// Set to true if already do matching in current era
     /// clear after arriving at next era
     #[pallet::storage]
     #[pallet::getter(fn is_matched)]
-    pub type IsMatched<T: Config> = StorageValue<_, bool, ValueQuery>;
+    pub type IsMatched<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
 
     #[derive(Default)]
     #[pallet::genesis_config]
@@ -455,30 +752,30 @@ pub mod pallet {
     }
 
     #[pallet::genesis_build]
-    impl<T: Config> GenesisBuild<T> for GenesisConfig {
+    impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig {
         fn build(&self) {
-            ExchangeRate::<T>::put(self.exchange_rate);
-            ReserveFactor::<T>::put(self.reserve_factor);
+            ExchangeRate::<T, I>::put(self.exchange_rate);
+            ReserveFactor::<T, I>::put(self.reserve_factor);
         }
     }
 
     #[pallet::call]
-    impl<T: Config> Pallet<T> {
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
         /// Put assets under staking, the native assets will be transferred to the account
         /// owned by the pallet, user receive derivative in return, such derivative can be
         /// further used as collateral for lending.
         ///
         /// - `amount`: the amount of staking assets
         #[pallet::call_index(0)]
-        #[pallet::weight(<T as Config>::WeightInfo::stake())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::stake())]
         #[transactional]
         pub fn stake(
             origin: OriginFor<T>,
-            #[pallet::compact] amount: BalanceOf<T>,
+            #[pallet::compact] amount: BalanceOf<T, I>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
-            ensure!(amount >= T::MinStake::get(), Error::<T>::StakeTooSmall);
+            ensure!(amount >= T::MinStake::get(), Error::<T, I>::StakeTooSmall);
 
             let reserves = Self::reserve_factor().mul_floor(amount);
 
@@ -499,7 +796,7 @@ pub mod pallet {
                 .checked_sub(reserves)
                 .ok_or(ArithmeticError::Underflow)?;
             let liquid_amount =
-                Self::staking_to_liquid(amount).ok_or(Error::<T>::InvalidExchangeRate)?;
+                Self::staking_to_liquid(amount).ok_or(Error::<T, I>::InvalidExchangeRate)?;
             let liquid_currency = Self::liquid_currency()?;
             Self::ensure_market_cap(amount)?;
 
@@ -513,109 +810,48 @@ pub mod pallet {
                 &reserves
             );
 
-            MatchingPool::<T>::try_mutate(|p| -> DispatchResult { p.add_stake_amount(amount) })?;
-            TotalReserves::<T>::try_mutate(|b| -> DispatchResult {
+            MatchingPool::<T, I>::try_mutate(|p| -> DispatchResult { p.add_stake_amount(amount) })?;
+            TotalReserves::<T, I>::try_mutate(|b| -> DispatchResult {
                 *b = b.checked_add(reserves).ok_or(ArithmeticError::Overflow)?;
                 Ok(())
             })?;
 
-            Self::deposit_event(Event::<T>::Staked(who, amount));
+            Self::deposit_event(Event::<T, I>::Staked(who, amount));
             Ok(().into())
         }
 
         /// Unstake by exchange derivative for assets, the assets will not be available immediately.
         /// Instead, the request is recorded and pending for the nomination accounts on relaychain
-        /// chain to do the `unbond` operation.
+        /// chain to do the `unbond` operation. Unstaking via the relaychain mints a transferable
+        /// `UnstakeReceipt` for the unbonding assets.
         ///
         /// - `amount`: the amount of derivative
         #[pallet::call_index(1)]
-        #[pallet::weight(<T as Config>::WeightInfo::unstake())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::unstake())]
         #[transactional]
         pub fn unstake(
             origin: OriginFor<T>,
-            #[pallet::compact] liquid_amount: BalanceOf<T>,
+            #[pallet::compact] liquid_amount: BalanceOf<T, I>,
             unstake_provider: UnstakeProvider,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
             ensure!(
                 liquid_amount >= T::MinUnstake::get(),
-                Error::<T>::UnstakeTooSmall
+                Error::<T, I>::UnstakeTooSmall
             );
-
-            let reserves = Self::unstake_reserve_factor().mul_floor(liquid_amount);
-
-            TotalUnstakeReserves::<T>::try_mutate(|b| -> DispatchResult {
-                *b = b.checked_add(reserves).ok_or(ArithmeticError::Overflow)?;
-                Ok(())
-            })?;
-
-            let origin_liquid_amount = liquid_amount;
-            let liquid_amount = liquid_amount
-                .checked_sub(reserves)
-                .ok_or(ArithmeticError::Underflow)?;
-
-            if unstake_provider.is_matching_pool() {
-                FastUnstakeRequests::<T>::try_mutate(&who, |b| -> DispatchResult {
-                    let balance =
-                        T::Assets::reducible_balance(Self::liquid_currency()?, &who, false);
-                    *b = b.saturating_add(liquid_amount).min(balance);
-                    Ok(())
-                })?;
-                return Ok(().into());
-            }
-
-            let amount =
-                Self::liquid_to_staking(liquid_amount).ok_or(Error::<T>::InvalidExchangeRate)?;
-            let unlockings_key = if unstake_provider.is_loans() {
-                Self::loans_account_id()
-            } else {
-                who.clone()
-            };
-
-            Unlockings::<T>::try_mutate(&unlockings_key, |b| -> DispatchResult {
-                let mut chunks = b.take().unwrap_or_default();
-                let target_era = Self::target_era();
-                if let Some(mut chunk) = chunks.last_mut().filter(|chunk| chunk.era == target_era) {
-                    chunk.value = chunk.value.saturating_add(amount);
-                } else {
-                    chunks.push(UnlockChunk {
-                        value: amount,
-                        era: target_era,
-                    });
-                }
-                ensure!(
-                    chunks.len() <= MAX_UNLOCKING_CHUNKS,
-                    Error::<T>::NoMoreChunks
-                );
-                *b = Some(chunks);
-                Ok(())
-            })?;
-
-            T::Assets::burn_from(Self::liquid_currency()?, &who, origin_liquid_amount)?;
-            T::Assets::mint_into(Self::liquid_currency()?, &Self::account_id(), reserves)?;
-
-            if unstake_provider.is_loans() {
-                Self::do_loans_instant_unstake(&who, amount)?;
-            }
-
-            MatchingPool::<T>::try_mutate(|p| p.add_unstake_amount(amount))?;
-
-            log::trace!(
-                target: "liquidStaking::unstake",
-                "unstake_amount: {:?}, liquid_amount: {:?}, reserved: {:?}",
-                &amount,
-                &liquid_amount,
-                &reserves
+            ensure!(
+                !unstake_provider.is_amm() && !unstake_provider.is_stable_swap(),
+                Error::<T, I>::InvalidUnstakeProvider
             );
 
-            Self::deposit_event(Event::<T>::Unstaked(who, origin_liquid_amount, amount));
+            Self::do_unstake(&who, liquid_amount, unstake_provider)?;
             Ok(().into())
         }
 
         /// Update insurance pool's reserve_factor
         #[pallet::call_index(2)]
-        #[pallet::weight(<T as Config>::WeightInfo::update_reserve_factor())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::update_reserve_factor())]
         #[transactional]
         pub fn update_reserve_factor(
             origin: OriginFor<T>,
@@ -625,7 +861,7 @@ pub mod pallet {
 
             ensure!(
                 reserve_factor > Ratio::zero() && reserve_factor < Ratio::one(),
-                Error::<T>::InvalidFactor,
+                Error::<T, I>::InvalidFactor,
             );
 
             log::trace!(
@@ -634,41 +870,41 @@ pub mod pallet {
                 &reserve_factor,
             );
 
-            ReserveFactor::<T>::mutate(|v| *v = reserve_factor);
-            Self::deposit_event(Event::<T>::ReserveFactorUpdated(reserve_factor));
+            ReserveFactor::<T, I>::mutate(|v| *v = reserve_factor);
+            Self::deposit_event(Event::<T, I>::ReserveFactorUpdated(reserve_factor));
             Ok(().into())
         }
 
         /// Update ledger's max bonded cap
         #[pallet::call_index(3)]
-        #[pallet::weight(<T as Config>::WeightInfo::update_staking_ledger_cap())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::update_staking_ledger_cap())]
         #[transactional]
         pub fn update_staking_ledger_cap(
             origin: OriginFor<T>,
-            #[pallet::compact] cap: BalanceOf<T>,
+            #[pallet::compact] cap: BalanceOf<T, I>,
         ) -> DispatchResultWithPostInfo {
             T::UpdateOrigin::ensure_origin(origin)?;
 
-            ensure!(!cap.is_zero(), Error::<T>::InvalidCap);
+            ensure!(!cap.is_zero(), Error::<T, I>::InvalidCap);
 
             log::trace!(
                 target: "liquidStaking::update_staking_ledger_cap",
                 "cap: {:?}",
                 &cap,
             );
-            StakingLedgerCap::<T>::mutate(|v| *v = cap);
-            Self::deposit_event(Event::<T>::StakingLedgerCapUpdated(cap));
+            StakingLedgerCap::<T, I>::mutate(|v| *v = cap);
+            Self::deposit_event(Event::<T, I>::StakingLedgerCapUpdated(cap));
             Ok(().into())
         }
 
         /// Bond on relaychain via xcm.transact
         #[pallet::call_index(4)]
-        #[pallet::weight(<T as Config>::WeightInfo::bond())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::bond())]
         #[transactional]
         pub fn bond(
             origin: OriginFor<T>,
             derivative_index: DerivativeIndex,
-            #[pallet::compact] amount: BalanceOf<T>,
+            #[pallet::compact] amount: BalanceOf<T, I>,
             payee: RewardDestination<T::AccountId>,
         ) -> DispatchResult {
             T::RelayOrigin::ensure_origin(origin)?;
@@ -678,12 +914,12 @@ pub mod pallet {
 
         /// Bond_extra on relaychain via xcm.transact
         #[pallet::call_index(5)]
-        #[pallet::weight(<T as Config>::WeightInfo::bond_extra())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::bond_extra())]
         #[transactional]
         pub fn bond_extra(
             origin: OriginFor<T>,
             derivative_index: DerivativeIndex,
-            #[pallet::compact] amount: BalanceOf<T>,
+            #[pallet::compact] amount: BalanceOf<T, I>,
         ) -> DispatchResult {
             T::RelayOrigin::ensure_origin(origin)?;
             Self::do_bond_extra(derivative_index, amount)?;
@@ -692,12 +928,12 @@ pub mod pallet {
 
         /// Unbond on relaychain via xcm.transact
         #[pallet::call_index(6)]
-        #[pallet::weight(<T as Config>::WeightInfo::unbond())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::unbond())]
         #[transactional]
         pub fn unbond(
             origin: OriginFor<T>,
             derivative_index: DerivativeIndex,
-            #[pallet::compact] amount: BalanceOf<T>,
+            #[pallet::compact] amount: BalanceOf<T, I>,
         ) -> DispatchResult {
             T::RelayOrigin::ensure_origin(origin)?;
             Self::do_unbond(derivative_index, amount)?;
@@ -706,12 +942,12 @@ pub mod pallet {
 
         /// Rebond on relaychain via xcm.transact
         #[pallet::call_index(7)]
-        #[pallet::weight(<T as Config>::WeightInfo::rebond())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::rebond())]
         #[transactional]
         pub fn rebond(
             origin: OriginFor<T>,
             derivative_index: DerivativeIndex,
-            #[pallet::compact] amount: BalanceOf<T>,
+            #[pallet::compact] amount: BalanceOf<T, I>,
         ) -> DispatchResult {
             T::RelayOrigin::ensure_origin(origin)?;
             Self::do_rebond(derivative_index, amount)?;
@@ -720,7 +956,7 @@ pub mod pallet {
 
         /// Withdraw unbonded on relaychain via xcm.transact
         #[pallet::call_index(8)]
-        #[pallet::weight(<T as Config>::WeightInfo::withdraw_unbonded())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::withdraw_unbonded())]
         #[transactional]
         pub fn withdraw_unbonded(
             origin: OriginFor<T>,
@@ -734,7 +970,7 @@ pub mod pallet {
 
         /// Nominate on relaychain via xcm.transact
         #[pallet::call_index(9)]
-        #[pallet::weight(<T as Config>::WeightInfo::nominate())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::nominate())]
         #[transactional]
         pub fn nominate(
             origin: OriginFor<T>,
@@ -748,14 +984,14 @@ pub mod pallet {
 
         /// Internal call which is expected to be triggered only by xcm instruction
         #[pallet::call_index(10)]
-        #[pallet::weight(<T as Config>::WeightInfo::notification_received())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::notification_received())]
         #[transactional]
         pub fn notification_received(
             origin: OriginFor<T>,
             query_id: QueryId,
             response: Response,
         ) -> DispatchResultWithPostInfo {
-            let responder = ensure_response(<T as Config>::RuntimeOrigin::from(origin.clone()))
+            let responder = ensure_response(<T as Config<I>>::RuntimeOrigin::from(origin.clone()))
                 .or_else(|_| {
                     T::UpdateOrigin::ensure_origin(origin).map(|_| MultiLocation::here())
                 })?;
@@ -764,7 +1000,7 @@ pub mod pallet {
                     Self::do_notification_received(query_id, request, res)?;
                 }
 
-                Self::deposit_event(Event::<T>::NotificationReceived(
+                Self::deposit_event(Event::<T, I>::NotificationReceived(
                     Box::new(responder),
                     query_id,
                     res,
@@ -776,7 +1012,7 @@ pub mod pallet {
         /// Claim assets back when current era index arrived
         /// at target era
         #[pallet::call_index(11)]
-        #[pallet::weight(<T as Config>::WeightInfo::claim_for())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::claim_for())]
         #[transactional]
         pub fn claim_for(
             origin: OriginFor<T>,
@@ -786,76 +1022,93 @@ pub mod pallet {
             let who = T::Lookup::lookup(dest)?;
             let current_era = Self::current_era();
 
-            Unlockings::<T>::try_mutate_exists(&who, |b| -> DispatchResult {
-                let mut amount: BalanceOf<T> = Zero::zero();
-                let chunks = b.as_mut().ok_or(Error::<T>::NoUnlockings)?;
-                chunks.retain(|chunk| {
-                    if chunk.era > current_era {
-                        true
-                    } else {
-                        amount += chunk.value;
-                        false
-                    }
-                });
+            let receipt_ids: Vec<UnstakeReceiptId> =
+                UnstakeReceiptsOf::<T, I>::iter_key_prefix(&who).collect();
+            ensure!(
+                Unlockings::<T, I>::contains_key(&who) || !receipt_ids.is_empty(),
+                Error::<T, I>::NoUnlockings
+            );
 
-                let total_unclaimed = Self::get_total_unclaimed(Self::staking_currency()?);
+            let mut amount: BalanceOf<T, I> = Zero::zero();
+            Unlockings::<T, I>::mutate_exists(&who, |b| {
+                if let Some(chunks) = b {
+                    chunks.retain(|chunk| {
+                        if chunk.era > current_era {
+                            true
+                        } else {
+                            amount += chunk.value;
+                            false
+                        }
+                    });
+                    if chunks.is_empty() {
+                        *b = None;
+                    }
+                }
+            });
+            for receipt_id in receipt_ids {
+                if let Some(receipt) =
+                    Self::unstake_receipt(receipt_id).filter(|r| r.era <= current_era)
+                {
+                    amount += receipt.value;
+                    UnstakeReceipts::<T, I>::remove(receipt_id);
+                    UnstakeReceiptsOf::<T, I>::remove(&who, receipt_id);
+                    PendingReceiptTransfers::<T, I>::remove(receipt_id);
+                }
+            }
 
-                log::trace!(
-                    target: "liquidStaking::claim_for",
-                    "current_era: {:?}, beneficiary: {:?}, total_unclaimed: {:?}, amount: {:?}",
-                    &current_era,
-                    &who,
-                    &total_unclaimed,
-                    amount
-                );
+            let total_unclaimed = Self::get_total_unclaimed(Self::staking_currency()?);
 
-                if amount.is_zero() {
-                    return Err(Error::<T>::NothingToClaim.into());
-                }
+            log::trace!(
+                target: "liquidStaking::claim_for",
+                "current_era: {:?}, beneficiary: {:?}, total_unclaimed: {:?}, amount: {:?}",
+                &current_era,
+                &who,
+                &total_unclaimed,
+                amount
+            );
 
-                if total_unclaimed < amount {
-                    return Err(Error::<T>::NotWithdrawn.into());
-                }
+            if amount.is_zero() {
+                return Err(Error::<T, I>::NothingToClaim.into());
+            }
 
-                Self::do_claim_for(&who, amount)?;
+            if total_unclaimed < amount {
+                return Err(Error::<T, I>::NotWithdrawn.into());
+            }
 
-                if chunks.is_empty() {
-                    *b = None;
-                }
+            Self::do_claim_for(&who, amount)?;
 
-                Self::deposit_event(Event::<T>::ClaimedFor(who.clone(), amount));
-                Ok(())
-            })?;
+            Self::deposit_event(Event::<T, I>::ClaimedFor(who, amount));
             Ok(().into())
         }
 
         /// Force set era start block
         #[pallet::call_index(12)]
-        #[pallet::weight(<T as Config>::WeightInfo::force_set_era_start_block())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::force_set_era_start_block())]
         #[transactional]
         pub fn force_set_era_start_block(
             origin: OriginFor<T>,
             block_number: BlockNumberFor<T>,
         ) -> DispatchResult {
             T::UpdateOrigin::ensure_origin(origin)?;
-            EraStartBlock::<T>::put(block_number);
+            EraStartBlock::<T, I>::put(block_number);
             Ok(())
         }
 
         /// Force set current era
         #[pallet::call_index(13)]
-        #[pallet::weight(<T as Config>::WeightInfo::force_set_current_era())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::force_set_current_era())]
         #[transactional]
         pub fn force_set_current_era(origin: OriginFor<T>, era: EraIndex) -> DispatchResult {
             T::UpdateOrigin::ensure_origin(origin)?;
-            IsMatched::<T>::put(false);
-            CurrentEra::<T>::put(era);
+            IsMatched::<T, I>::put(false);
+            CurrentEra::<T, I>::put(era);
             Ok(())
         }
 
         /// Force advance era
         #[pallet::call_index(14)]
-        #[pallet::weight(<T as Config>::WeightInfo::force_advance_era())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::force_advance_era()
+            .saturating_add(Pallet::<T, I>::update_nominations_weight()))]
         #[transactional]
         pub fn force_advance_era(
             origin: OriginFor<T>,
@@ -870,7 +1123,7 @@ pub mod pallet {
 
         /// Force matching
         #[pallet::call_index(15)]
-        #[pallet::weight(<T as Config>::WeightInfo::force_matching())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::force_matching())]
         #[transactional]
         pub fn force_matching(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
             T::UpdateOrigin::ensure_origin(origin)?;
@@ -882,20 +1135,20 @@ pub mod pallet {
 
         /// Force set staking_ledger
         #[pallet::call_index(16)]
-        #[pallet::weight(<T as Config>::WeightInfo::force_set_staking_ledger())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::force_set_staking_ledger())]
         #[transactional]
         pub fn force_set_staking_ledger(
             origin: OriginFor<T>,
             derivative_index: DerivativeIndex,
-            staking_ledger: StakingLedger<T::AccountId, BalanceOf<T>>,
+            staking_ledger: StakingLedger<T::AccountId, BalanceOf<T, I>>,
         ) -> DispatchResultWithPostInfo {
             T::UpdateOrigin::ensure_origin(origin)?;
 
             Self::do_update_ledger(derivative_index, |ledger| {
                 ensure!(
                     !Self::is_updated(derivative_index)
-                        && XcmRequests::<T>::iter().count().is_zero(),
-                    Error::<T>::StakingLedgerLocked
+                        && XcmRequests::<T, I>::iter().count().is_zero(),
+                    Error::<T, I>::StakingLedgerLocked
                 );
                 *ledger = staking_ledger;
                 Ok(())
@@ -906,7 +1159,8 @@ pub mod pallet {
 
         /// Set current era by providing storage proof
         #[pallet::call_index(17)]
-        #[pallet::weight(<T as Config>::WeightInfo::force_set_current_era())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::force_set_current_era()
+            .saturating_add(Pallet::<T, I>::update_nominations_weight()))]
         #[transactional]
         pub fn set_current_era(
             origin: OriginFor<T>,
@@ -921,44 +1175,155 @@ pub mod pallet {
             let value = era.encode();
             ensure!(
                 Self::verify_merkle_proof(key, value, proof),
-                Error::<T>::InvalidProof
+                Error::<T, I>::InvalidProof
             );
 
             Self::do_advance_era(offset)?;
             Ok(().into())
         }
 
+        /// Advance era by providing storage proof of the relaychain's active era,
+        /// anyone can call it and gets `EraAdvanceBounty` from the reserves
+        #[pallet::call_index(32)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::advance_era()
+            .saturating_add(Pallet::<T, I>::update_nominations_weight()))]
+        #[transactional]
+        pub fn advance_era(
+            origin: OriginFor<T>,
+            active_era: ActiveEraInfo,
+            proof: BoundedStorageProof,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                active_era.index > Self::current_era(),
+                Error::<T, I>::InvalidEra
+            );
+            let key = Self::get_active_era_key();
+            let value = active_era.encode();
+            let proof = proof.into_iter().map(|node| node.into_inner()).collect();
+            ensure!(
+                Self::verify_merkle_proof(key, value, proof),
+                Error::<T, I>::InvalidProof
+            );
+
+            Self::do_advance_era(active_era.index.saturating_sub(Self::current_era()))?;
+
+            let bounty = T::EraAdvanceBounty::get().min(Self::total_reserves());
+            if !bounty.is_zero() {
+                TotalReserves::<T, I>::mutate(|b| *b = b.saturating_sub(bounty));
+                T::Assets::transfer(
+                    Self::staking_currency()?,
+                    &Self::account_id(),
+                    &who,
+                    bounty,
+                    false,
+                )?;
+            }
+
+            Self::deposit_event(Event::<T, I>::EraAdvanced(who, active_era.index, bounty));
+            Ok(().into())
+        }
+
+        /// Switch the strategy used to distribute the bonds, unbonds and rebonds
+        #[pallet::call_index(33)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_distribution_strategy(
+            T::DerivativeIndexList::get().len() as u32
+        ))]
+        #[transactional]
+        pub fn set_distribution_strategy(
+            origin: OriginFor<T>,
+            strategy: DistributionStrategyKind,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            let derivative_index_list = T::DerivativeIndexList::get();
+            ensure!(
+                strategy != DistributionStrategyKind::Weighted
+                    || DistributionWeights::<T, I>::iter().any(|(index, weight)| {
+                        derivative_index_list.contains(&index) && !weight.is_zero()
+                    }),
+                Error::<T, I>::DistributionWeightsRequired
+            );
+
+            CurrentDistributionStrategy::<T, I>::put(strategy);
+            Self::deposit_event(Event::<T, I>::DistributionStrategyUpdated(strategy));
+            Ok(())
+        }
+
+        /// Replace the target weights of the weighted distribution strategy,
+        /// the derivative indexes left out get no weight. An empty list clears
+        /// the weights unless the weighted distribution strategy is in use.
+        #[pallet::call_index(34)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_distribution_weights(weights.len() as u32))]
+        #[transactional]
+        pub fn set_distribution_weights(
+            origin: OriginFor<T>,
+            weights: Vec<(DerivativeIndex, u32)>,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !weights.is_empty()
+                    || Self::distribution_strategy() != DistributionStrategyKind::Weighted,
+                Error::<T, I>::DistributionWeightsRequired
+            );
+            let derivative_index_list = T::DerivativeIndexList::get();
+            let mut indexes: Vec<DerivativeIndex> =
+                weights.iter().map(|&(index, _)| index).collect();
+            indexes.sort_unstable();
+            indexes.dedup();
+            ensure!(
+                indexes.len() == weights.len()
+                    && indexes
+                        .iter()
+                        .all(|index| derivative_index_list.contains(index))
+                    && (weights.is_empty() || weights.iter().any(|(_, weight)| !weight.is_zero())),
+                Error::<T, I>::InvalidDistributionWeights
+            );
+
+            let _ = DistributionWeights::<T, I>::clear(u32::max_value(), None);
+            for &(index, weight) in weights.iter() {
+                DistributionWeights::<T, I>::insert(index, weight);
+            }
+
+            Self::deposit_event(Event::<T, I>::DistributionWeightsUpdated(weights));
+            Ok(())
+        }
+
         /// Set staking_ledger by providing storage proof
+        ///
+        /// The net change of the ledger's total since the last update is either minted as
+        /// rewards or treated as slashed, then the loss is made up by the reserves as far as
+        /// possible and the exchange rate is lowered for the rest.
         #[pallet::call_index(18)]
-        #[pallet::weight(<T as Config>::WeightInfo::force_set_staking_ledger())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_staking_ledger())]
         #[transactional]
         pub fn set_staking_ledger(
             origin: OriginFor<T>,
             derivative_index: DerivativeIndex,
-            staking_ledger: StakingLedger<T::AccountId, BalanceOf<T>>,
-            proof: Vec<Vec<u8>>,
+            staking_ledger: StakingLedger<T::AccountId, BalanceOf<T, I>>,
+            proof: BoundedStorageProof,
         ) -> DispatchResultWithPostInfo {
             Self::ensure_origin(origin)?;
 
+            let mut slashed_amount: BalanceOf<T, I> = Zero::zero();
             Self::do_update_ledger(derivative_index, |ledger| {
                 ensure!(
                     !Self::is_updated(derivative_index)
-                        && XcmRequests::<T>::iter().count().is_zero(),
-                    Error::<T>::StakingLedgerLocked
-                );
-                ensure!(
-                    staking_ledger.total > ledger.total
-                        && staking_ledger.active > ledger.active
-                        && staking_ledger.unlocking == ledger.unlocking,
-                    Error::<T>::InvalidStakingLedger
+                        && XcmRequests::<T, I>::iter().count().is_zero(),
+                    Error::<T, I>::StakingLedgerLocked
                 );
+                let (rewards, slashed) = ledger
+                    .rewards_and_slash(&staking_ledger)
+                    .ok_or(Error::<T, I>::InvalidStakingLedger)?;
                 let key = Self::get_staking_ledger_key(derivative_index);
                 let value = staking_ledger.encode();
+                let proof = proof.into_iter().map(|node| node.into_inner()).collect();
                 ensure!(
                     Self::verify_merkle_proof(key, value, proof),
-                    Error::<T>::InvalidProof
+                    Error::<T, I>::InvalidProof
                 );
-                let rewards = staking_ledger.total.saturating_sub(ledger.total);
 
                 let inflate_liquid_amount = Self::get_inflate_liquid_amount(rewards)?;
                 if !inflate_liquid_amount.is_zero() {
@@ -971,31 +1336,37 @@ pub mod pallet {
 
                 log::trace!(
                     target: "liquidStaking::set_staking_ledger",
-                    "index: {:?}, staking_ledger: {:?}, inflate_liquid_amount: {:?}",
+                    "index: {:?}, staking_ledger: {:?}, inflate_liquid_amount: {:?}, slashed: {:?}",
                     &derivative_index,
                     &staking_ledger,
                     inflate_liquid_amount,
+                    slashed,
                 );
+                slashed_amount = slashed;
                 *ledger = staking_ledger;
                 Ok(())
             })?;
 
+            if !slashed_amount.is_zero() {
+                Self::do_slash(derivative_index, slashed_amount)?;
+            }
+
             Ok(().into())
         }
 
         /// Reduces reserves by transferring to receiver.
         #[pallet::call_index(19)]
-        #[pallet::weight(<T as Config>::WeightInfo::reduce_reserves())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::reduce_reserves())]
         #[transactional]
         pub fn reduce_reserves(
             origin: OriginFor<T>,
             receiver: <T::Lookup as StaticLookup>::Source,
-            #[pallet::compact] reduce_amount: BalanceOf<T>,
+            #[pallet::compact] reduce_amount: BalanceOf<T, I>,
         ) -> DispatchResultWithPostInfo {
             T::UpdateOrigin::ensure_origin(origin)?;
             let receiver = T::Lookup::lookup(receiver)?;
 
-            TotalReserves::<T>::try_mutate(|b| -> DispatchResult {
+            TotalReserves::<T, I>::try_mutate(|b| -> DispatchResult {
                 *b = b
                     .checked_sub(reduce_amount)
                     .ok_or(ArithmeticError::Underflow)?;
@@ -1010,27 +1381,27 @@ pub mod pallet {
                 false,
             )?;
 
-            Self::deposit_event(Event::<T>::ReservesReduced(receiver, reduce_amount));
+            Self::deposit_event(Event::<T, I>::ReservesReduced(receiver, reduce_amount));
 
             Ok(().into())
         }
 
         /// Cancel unstake
         #[pallet::call_index(20)]
-        #[pallet::weight(<T as Config>::WeightInfo::cancel_unstake())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::cancel_unstake())]
         #[transactional]
         pub fn cancel_unstake(
             origin: OriginFor<T>,
-            #[pallet::compact] amount: BalanceOf<T>,
+            #[pallet::compact] amount: BalanceOf<T, I>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
-            FastUnstakeRequests::<T>::try_mutate(&who, |b| -> DispatchResultWithPostInfo {
+            FastUnstakeRequests::<T, I>::try_mutate(&who, |b| -> DispatchResultWithPostInfo {
                 let balance = T::Assets::reducible_balance(Self::liquid_currency()?, &who, false);
                 *b = (*b).min(balance).saturating_sub(amount);
 
                 // reserve two amounts in event
-                Self::deposit_event(Event::<T>::UnstakeCancelled(who.clone(), amount, amount));
+                Self::deposit_event(Event::<T, I>::UnstakeCancelled(who.clone(), amount, amount));
 
                 Ok(().into())
             })
@@ -1038,7 +1409,7 @@ pub mod pallet {
 
         /// Update commission rate
         #[pallet::call_index(21)]
-        #[pallet::weight(<T as Config>::WeightInfo::update_commission_rate())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::update_commission_rate())]
         #[transactional]
         pub fn update_commission_rate(
             origin: OriginFor<T>,
@@ -1048,7 +1419,7 @@ pub mod pallet {
 
             ensure!(
                 commission_rate > Rate::zero() && commission_rate < Rate::one(),
-                Error::<T>::InvalidCommissionRate,
+                Error::<T, I>::InvalidCommissionRate,
             );
 
             log::trace!(
@@ -1057,14 +1428,14 @@ pub mod pallet {
                 &commission_rate,
             );
 
-            CommissionRate::<T>::put(commission_rate);
-            Self::deposit_event(Event::<T>::CommissionRateUpdated(commission_rate));
+            CommissionRate::<T, I>::put(commission_rate);
+            Self::deposit_event(Event::<T, I>::CommissionRateUpdated(commission_rate));
             Ok(())
         }
 
         /// Fast match unstake through matching pool
         #[pallet::call_index(22)]
-        #[pallet::weight(<T as Config>::WeightInfo::fast_match_unstake(unstaker_list.len() as u32))]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::fast_match_unstake(unstaker_list.len() as u32))]
         #[transactional]
         pub fn fast_match_unstake(
             origin: OriginFor<T>,
@@ -1079,7 +1450,7 @@ pub mod pallet {
 
         /// Update insurance pool's unstake_reserve_factor
         #[pallet::call_index(23)]
-        #[pallet::weight(<T as Config>::WeightInfo::update_reserve_factor())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::update_reserve_factor())]
         #[transactional]
         pub fn update_unstake_reserve_factor(
             origin: OriginFor<T>,
@@ -1089,7 +1460,7 @@ pub mod pallet {
 
             ensure!(
                 reserve_factor >= Ratio::zero() && reserve_factor < Ratio::one(),
-                Error::<T>::InvalidFactor,
+                Error::<T, I>::InvalidFactor,
             );
 
             log::trace!(
@@ -1098,24 +1469,24 @@ pub mod pallet {
                 &reserve_factor,
             );
 
-            UnstakeReserveFactor::<T>::mutate(|v| *v = reserve_factor);
-            Self::deposit_event(Event::<T>::UnstakeReserveFactorUpdated(reserve_factor));
+            UnstakeReserveFactor::<T, I>::mutate(|v| *v = reserve_factor);
+            Self::deposit_event(Event::<T, I>::UnstakeReserveFactorUpdated(reserve_factor));
             Ok(().into())
         }
 
         /// Reduces unstake reserves by transferring to receiver.
         #[pallet::call_index(24)]
-        #[pallet::weight(<T as Config>::WeightInfo::reduce_reserves())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::reduce_reserves())]
         #[transactional]
         pub fn reduce_unstake_reserves(
             origin: OriginFor<T>,
             receiver: <T::Lookup as StaticLookup>::Source,
-            #[pallet::compact] reduce_amount: BalanceOf<T>,
+            #[pallet::compact] reduce_amount: BalanceOf<T, I>,
         ) -> DispatchResultWithPostInfo {
             T::UpdateOrigin::ensure_origin(origin)?;
             let receiver = T::Lookup::lookup(receiver)?;
 
-            TotalUnstakeReserves::<T>::try_mutate(|b| -> DispatchResult {
+            TotalUnstakeReserves::<T, I>::try_mutate(|b| -> DispatchResult {
                 *b = b
                     .checked_sub(reduce_amount)
                     .ok_or(ArithmeticError::Underflow)?;
@@ -1130,63 +1501,390 @@ pub mod pallet {
                 false,
             )?;
 
-            Self::deposit_event(Event::<T>::UnstakeReservesReduced(receiver, reduce_amount));
+            Self::deposit_event(Event::<T, I>::UnstakeReservesReduced(
+                receiver,
+                reduce_amount,
+            ));
 
             Ok(().into())
         }
-    }
-
-    #[pallet::hooks]
-    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
-        fn on_initialize(_block_number: T::BlockNumber) -> frame_support::weights::Weight {
-            let mut weight = <T as Config>::WeightInfo::on_initialize();
-            let relaychain_block_number =
-                T::RelayChainValidationDataProvider::current_block_number();
-            let mut do_on_initialize = || -> DispatchResult {
-                if !Self::is_matched()
-                    && T::ElectionSolutionStoredOffset::get()
-                        .saturating_add(Self::era_start_block())
-                        <= relaychain_block_number
-                {
-                    weight += <T as Config>::WeightInfo::force_matching();
-                    Self::do_matching()?;
-                }
 
-                let offset = Self::offset(relaychain_block_number);
-                if offset.is_zero() {
-                    return Ok(());
-                }
-                weight += <T as Config>::WeightInfo::force_advance_era();
-                Self::do_advance_era(offset)
-            };
-            let _ = with_transaction(|| match do_on_initialize() {
-                Ok(()) => TransactionOutcome::Commit(Ok(())),
-                Err(err) => TransactionOutcome::Rollback(Err(err)),
-            });
-            weight
-        }
+        /// Add a validator to the registry
+        #[pallet::call_index(25)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::add_validator())]
+        #[transactional]
+        pub fn add_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            ensure!(
+                !Validators::<T, I>::contains_key(&validator),
+                Error::<T, I>::ValidatorAlreadyAdded
+            );
+            ensure!(
+                (Validators::<T, I>::iter_keys().count() as u32) < T::MaxValidators::get(),
+                Error::<T, I>::TooManyValidators
+            );
 
-        fn on_finalize(_n: T::BlockNumber) {
-            let _ = IsUpdated::<T>::clear(u32::max_value(), None);
-            if let Some(data) = T::RelayChainValidationDataProvider::validation_data() {
-                ValidationData::<T>::put(data);
-            }
+            Validators::<T, I>::insert(&validator, ValidatorInfo::default());
+            Self::deposit_event(Event::<T, I>::ValidatorAdded(validator));
+            Ok(())
         }
-    }
 
-    impl<T: Config> Pallet<T> {
-        /// Staking pool account
-        pub fn account_id() -> T::AccountId {
-            T::PalletId::get().into_account_truncating()
-        }
+        /// Remove a validator from the registry, it won't be nominated from the next era
+        #[pallet::call_index(26)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::remove_validator())]
+        #[transactional]
+        pub fn remove_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            ensure!(
+                Validators::<T, I>::contains_key(&validator),
+                Error::<T, I>::ValidatorNotFound
+            );
 
-        /// Loans pool account
-        pub fn loans_account_id() -> T::AccountId {
-            T::LoansPalletId::get().into_account_truncating()
+            Validators::<T, I>::remove(&validator);
+            Self::deposit_event(Event::<T, I>::ValidatorRemoved(validator));
+            Ok(())
         }
 
-        /// Parachain's sovereign account
-        pub fn sovereign_account_id() -> T::AccountId {
+        /// Set validator's commission by providing storage proof of its preferences
+        #[pallet::call_index(27)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_validator_prefs())]
+        #[transactional]
+        pub fn set_validator_prefs(
+            origin: OriginFor<T>,
+            validator: T::AccountId,
+            prefs: ValidatorPrefs,
+            proof: Vec<Vec<u8>>,
+        ) -> DispatchResult {
+            Self::ensure_origin(origin)?;
+
+            let key = Self::get_validator_prefs_key(&validator);
+            let value = prefs.encode();
+            ensure!(
+                Self::verify_merkle_proof(key, value, proof),
+                Error::<T, I>::InvalidProof
+            );
+
+            Self::do_update_validator(&validator, |info| {
+                info.commission = prefs.commission;
+                info.blocked = prefs.blocked;
+            })
+        }
+
+        /// Set validator's self stake by providing storage proof of its exposure in
+        /// current era
+        #[pallet::call_index(28)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_validator_exposure())]
+        #[transactional]
+        pub fn set_validator_exposure(
+            origin: OriginFor<T>,
+            validator: T::AccountId,
+            exposure: Exposure<T::AccountId, BalanceOf<T, I>>,
+            proof: Vec<Vec<u8>>,
+        ) -> DispatchResult {
+            Self::ensure_origin(origin)?;
+
+            let key = Self::get_eras_stakers_key(Self::current_era(), &validator);
+            let value = exposure.encode();
+            ensure!(
+                Self::verify_merkle_proof(key, value, proof),
+                Error::<T, I>::InvalidProof
+            );
+
+            Self::do_update_validator(&validator, |info| {
+                info.self_stake = exposure.own;
+                info.total_stake = exposure.total;
+            })
+        }
+
+        /// Set validators' reward points of a finished era by providing storage proof,
+        /// the nominations follow them from the next era on
+        #[pallet::call_index(29)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_era_reward_points(T::MaxValidators::get()))]
+        #[transactional]
+        pub fn set_era_reward_points(
+            origin: OriginFor<T>,
+            era: EraIndex,
+            points: EraRewardPoints<T::AccountId>,
+            proof: Vec<Vec<u8>>,
+        ) -> DispatchResult {
+            Self::ensure_origin(origin)?;
+            ensure!(
+                era < Self::current_era() && Self::last_rewarded_era().map_or(true, |e| era > e),
+                Error::<T, I>::InvalidEra
+            );
+
+            let key = Self::get_eras_reward_points_key(era);
+            let value = points.encode();
+            ensure!(
+                Self::verify_merkle_proof(key, value, proof),
+                Error::<T, I>::InvalidProof
+            );
+
+            let depth = T::EraPointsDepth::get() as usize;
+            Validators::<T, I>::translate(|validator, mut info: ValidatorInfo<BalanceOf<T, I>>| {
+                let era_points = points
+                    .individual
+                    .get(&validator)
+                    .copied()
+                    .unwrap_or_default();
+                info.era_points.push((era, era_points));
+                if info.era_points.len() > depth {
+                    let excess = info.era_points.len() - depth;
+                    info.era_points.drain(..excess);
+                }
+                Some(info)
+            });
+            LastRewardedEra::<T, I>::put(era);
+            Self::deposit_event(Event::<T, I>::EraRewardPointsUpdated(era));
+            Ok(())
+        }
+
+        /// Request to transfer an unstake receipt, the receiver has to accept it
+        /// with `accept_unstake_receipt` before it becomes theirs
+        #[pallet::call_index(30)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::transfer_unstake_receipt())]
+        #[transactional]
+        pub fn transfer_unstake_receipt(
+            origin: OriginFor<T>,
+            receipt_id: UnstakeReceiptId,
+            dest: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(dest)?;
+
+            let receipt =
+                Self::unstake_receipt(receipt_id).ok_or(Error::<T, I>::UnstakeReceiptNotFound)?;
+            ensure!(receipt.owner == who, Error::<T, I>::NotReceiptOwner);
+            let min_value = Self::liquid_to_staking(T::MinUnstake::get())
+                .ok_or(Error::<T, I>::InvalidExchangeRate)?;
+            ensure!(
+                receipt.value >= min_value,
+                Error::<T, I>::UnstakeReceiptTooSmall
+            );
+
+            PendingReceiptTransfers::<T, I>::insert(receipt_id, dest.clone());
+            Self::deposit_event(Event::<T, I>::UnstakeReceiptTransferRequested(
+                receipt_id, who, dest,
+            ));
+            Ok(())
+        }
+
+        /// Accept an unstake receipt transferred to the caller, the caller will get
+        /// the staking assets back with `claim_for`
+        #[pallet::call_index(35)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::accept_unstake_receipt())]
+        #[transactional]
+        pub fn accept_unstake_receipt(
+            origin: OriginFor<T>,
+            receipt_id: UnstakeReceiptId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                PendingReceiptTransfers::<T, I>::take(receipt_id).as_ref() == Some(&who),
+                Error::<T, I>::NoPendingReceiptTransfer
+            );
+
+            let from = UnstakeReceipts::<T, I>::try_mutate(
+                receipt_id,
+                |receipt| -> Result<T::AccountId, DispatchError> {
+                    let receipt = receipt
+                        .as_mut()
+                        .ok_or(Error::<T, I>::UnstakeReceiptNotFound)?;
+                    Self::ensure_receipts_limit(&who)?;
+
+                    UnstakeReceiptsOf::<T, I>::remove(&receipt.owner, receipt_id);
+                    UnstakeReceiptsOf::<T, I>::insert(&who, receipt_id, ());
+                    Ok(sp_std::mem::replace(&mut receipt.owner, who.clone()))
+                },
+            )?;
+
+            Self::deposit_event(Event::<T, I>::UnstakeReceiptTransferred(
+                receipt_id, from, who,
+            ));
+            Ok(())
+        }
+
+        /// Unstake instantly through the provider giving back the most staking assets,
+        /// among the loans, the matching pool, the AMM and the stableswap pools
+        #[pallet::call_index(31)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::instant_unstake())]
+        #[transactional]
+        pub fn instant_unstake(
+            origin: OriginFor<T>,
+            #[pallet::compact] liquid_amount: BalanceOf<T, I>,
+            #[pallet::compact] min_amount_out: BalanceOf<T, I>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                liquid_amount >= T::MinUnstake::get(),
+                Error::<T, I>::UnstakeTooSmall
+            );
+
+            let (unstake_provider, quote) = Self::instant_unstake_quotes(liquid_amount)
+                .into_iter()
+                .max_by_key(|(_, amount)| *amount)
+                .ok_or(Error::<T, I>::NoInstantUnstakeProvider)?;
+            ensure!(quote >= min_amount_out, Error::<T, I>::TooLittleReceived);
+
+            let staking_currency = Self::staking_currency()?;
+            let balance_before = T::Assets::balance(staking_currency, &who);
+            if unstake_provider.is_amm() {
+                T::AMM::swap(
+                    &who,
+                    (Self::liquid_currency()?, staking_currency),
+                    liquid_amount,
+                )?;
+            } else if unstake_provider.is_stable_swap() {
+                T::StableSwap::swap(
+                    &who,
+                    (Self::liquid_currency()?, staking_currency),
+                    liquid_amount,
+                )?;
+            } else if unstake_provider.is_matching_pool() {
+                Self::do_instant_match_unstake(&who, liquid_amount)?;
+            } else {
+                Self::do_unstake(&who, liquid_amount, unstake_provider)?;
+            }
+            let received =
+                T::Assets::balance(staking_currency, &who).saturating_sub(balance_before);
+            ensure!(received >= min_amount_out, Error::<T, I>::TooLittleReceived);
+
+            log::trace!(
+                target: "liquidStaking::instant_unstake",
+                "unstake_provider: {:?}, liquid_amount: {:?}, received: {:?}",
+                &unstake_provider,
+                &liquid_amount,
+                &received,
+            );
+
+            Self::deposit_event(Event::<T, I>::InstantUnstaked(
+                who,
+                unstake_provider,
+                liquid_amount,
+                received,
+            ));
+            Ok(())
+        }
+
+        /// Set the rewards paid out to a stash by providing storage proof of its relaychain
+        /// account and staking ledger, the balance of the stash which isn't accounted by the
+        /// ledger. They are withdrawn in `on_initialize` and re-bonded once they reach the
+        /// parachain's sovereign account
+        #[pallet::call_index(37)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_stash_rewards())]
+        #[transactional]
+        pub fn set_stash_rewards(
+            origin: OriginFor<T>,
+            derivative_index: DerivativeIndex,
+            account: AccountInfo<BalanceOf<T, I>>,
+            staking_ledger: StakingLedger<T::AccountId, BalanceOf<T, I>>,
+            proof: BoundedStorageProof,
+        ) -> DispatchResult {
+            Self::ensure_origin(origin)?;
+
+            ensure!(
+                StakingLedgers::<T, I>::contains_key(derivative_index),
+                Error::<T, I>::NotBonded
+            );
+            ensure!(
+                !PendingRewards::<T, I>::contains_key(derivative_index)
+                    && !RewardsWithdrawals::<T, I>::contains_key(derivative_index),
+                Error::<T, I>::RewardsLocked
+            );
+            let proof: Vec<Vec<u8>> = proof.into_iter().map(|node| node.into_inner()).collect();
+            let key =
+                Self::get_account_key(&Self::derivative_sovereign_account_id(derivative_index));
+            ensure!(
+                Self::verify_merkle_proof(key, account.encode(), proof.clone()),
+                Error::<T, I>::InvalidProof
+            );
+            let key = Self::get_staking_ledger_key(derivative_index);
+            ensure!(
+                Self::verify_merkle_proof(key, staking_ledger.encode(), proof),
+                Error::<T, I>::InvalidProof
+            );
+
+            // both are proven at the same relaychain block, the unlocked chunks are
+            // transferred back as soon as they are withdrawn
+            let amount = account.data.free.saturating_sub(staking_ledger.total);
+            ensure!(!amount.is_zero(), Error::<T, I>::NoRewards);
+
+            log::trace!(
+                target: "liquidStaking::set_stash_rewards",
+                "index: {:?}, amount: {:?}",
+                &derivative_index,
+                &amount,
+            );
+
+            PendingRewards::<T, I>::insert(derivative_index, amount);
+            Self::deposit_event(Event::<T, I>::StashRewardsUpdated(derivative_index, amount));
+            Ok(())
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config<I>, I: 'static> Hooks<T::BlockNumber> for Pallet<T, I> {
+        fn on_initialize(_block_number: T::BlockNumber) -> frame_support::weights::Weight {
+            let mut weight = <T as Config<I>>::WeightInfo::on_initialize();
+            let relaychain_block_number =
+                T::RelayChainValidationDataProvider::current_block_number();
+            let mut do_on_initialize = || -> DispatchResult {
+                if !Self::is_matched()
+                    && T::ElectionSolutionStoredOffset::get()
+                        .saturating_add(Self::era_start_block())
+                        <= relaychain_block_number
+                {
+                    weight += <T as Config<I>>::WeightInfo::force_matching();
+                    Self::do_matching()?;
+                }
+
+                let offset = Self::offset(relaychain_block_number);
+                if offset.is_zero() {
+                    return Ok(());
+                }
+                weight += <T as Config<I>>::WeightInfo::force_advance_era()
+                    .saturating_add(Self::update_nominations_weight());
+                Self::do_advance_era(offset)
+            };
+            let _ = with_transaction(|| match do_on_initialize() {
+                Ok(()) => TransactionOutcome::Commit(Ok(())),
+                Err(err) => TransactionOutcome::Rollback(Err(err)),
+            });
+
+            let payouts = Self::do_payout_stakers();
+            let withdrawals = Self::do_withdraw_rewards();
+            weight
+                .saturating_add(
+                    <T as Config<I>>::WeightInfo::payout_stakers().saturating_mul(payouts as u64),
+                )
+                .saturating_add(
+                    <T as Config<I>>::WeightInfo::withdraw_rewards()
+                        .saturating_mul(withdrawals as u64),
+                )
+        }
+
+        fn on_finalize(_n: T::BlockNumber) {
+            let _ = IsUpdated::<T, I>::clear(u32::max_value(), None);
+            if let Some(data) = T::RelayChainValidationDataProvider::validation_data() {
+                ValidationData::<T, I>::put(data);
+            }
+        }
+    }
+
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
+        /// Staking pool account
+        pub fn account_id() -> T::AccountId {
+            T::PalletId::get().into_account_truncating()
+        }
+
+        /// Loans pool account
+        pub fn loans_account_id() -> T::AccountId {
+            T::LoansPalletId::get().into_account_truncating()
+        }
+
+        /// Parachain's sovereign account
+        pub fn sovereign_account_id() -> T::AccountId {
             T::SelfParaId::get().into_account_truncating()
         }
 
@@ -1198,21 +1896,219 @@ pub mod pallet {
         }
 
         /// Get staking currency or return back an error
-        pub fn staking_currency() -> Result<AssetIdOf<T>, DispatchError> {
+        pub fn staking_currency() -> Result<AssetIdOf<T, I>, DispatchError> {
             Self::get_staking_currency()
-                .ok_or(Error::<T>::InvalidStakingCurrency)
+                .ok_or(Error::<T, I>::InvalidStakingCurrency)
                 .map_err(Into::into)
         }
 
         /// Get liquid currency or return back an error
-        pub fn liquid_currency() -> Result<AssetIdOf<T>, DispatchError> {
+        pub fn liquid_currency() -> Result<AssetIdOf<T, I>, DispatchError> {
             Self::get_liquid_currency()
-                .ok_or(Error::<T>::InvalidLiquidCurrency)
+                .ok_or(Error::<T, I>::InvalidLiquidCurrency)
                 .map_err(Into::into)
         }
 
+        /// Annualized growth of the exchange rate over the eras kept in the exchange
+        /// rate history, `None` until two eras are recorded.
+        pub fn get_apy() -> Option<Rate> {
+            let history = Self::exchange_rate_history();
+            let (first, last) = (history.first()?, history.last()?);
+            let eras = last.era.checked_sub(first.era).filter(|e| !e.is_zero())?;
+            let era_seconds = T::EraLength::get()
+                .saturated_into::<u64>()
+                .saturating_mul(RELAYCHAIN_BLOCK_SECONDS);
+            let eras_per_year = Rate::checked_from_rational(SECONDS_PER_YEAR, era_seconds)?;
+            last.exchange_rate
+                .checked_div(&first.exchange_rate)?
+                .saturating_sub(Rate::one())
+                .checked_div(&Rate::saturating_from_integer(eras))?
+                .checked_mul(&eras_per_year)
+        }
+
+        /// The unstakes of `who` which are not claimed yet and the eras from which they
+        /// can be claimed
+        pub fn pending_unstakes(who: &T::AccountId) -> PendingUnstakes<BalanceOf<T, I>> {
+            let mut unstakes: Vec<PendingUnstake<BalanceOf<T, I>>> = Self::unlockings(who)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|chunk| PendingUnstake {
+                    value: chunk.value,
+                    claimable_era: chunk.era,
+                    receipt_id: None,
+                })
+                .collect();
+            unstakes.extend(UnstakeReceiptsOf::<T, I>::iter_key_prefix(who).filter_map(
+                |receipt_id| {
+                    Self::unstake_receipt(receipt_id).map(|receipt| PendingUnstake {
+                        value: receipt.value,
+                        claimable_era: receipt.era,
+                        receipt_id: Some(receipt_id),
+                    })
+                },
+            ));
+            unstakes.sort_by_key(|unstake| unstake.claimable_era);
+
+            PendingUnstakes {
+                fast_unstake: Self::fast_unstake_requests(who),
+                unstakes,
+            }
+        }
+
+        /// The staking amounts that each available provider would give back right now
+        /// for `liquid_amount`, fees included
+        pub fn instant_unstake_quotes(
+            liquid_amount: BalanceOf<T, I>,
+        ) -> Vec<(UnstakeProvider, BalanceOf<T, I>)> {
+            let mut quotes = Vec::new();
+            let (liquid_currency, staking_currency) =
+                match (Self::liquid_currency(), Self::staking_currency()) {
+                    (Ok(liquid_currency), Ok(staking_currency)) => {
+                        (liquid_currency, staking_currency)
+                    }
+                    _ => return quotes,
+                };
+
+            let reserves = Self::unstake_reserve_factor().mul_floor(liquid_amount);
+            let net_liquid_amount = liquid_amount.saturating_sub(reserves);
+
+            if let Some(amount) = Self::liquid_to_staking(net_liquid_amount) {
+                // The loans only provide what the market of the staking asset can lend
+                let fee = T::LoansInstantUnstakeFee::get().saturating_mul_int(amount);
+                let borrow_amount = amount.saturating_sub(fee);
+                if T::Loans::get_market_info(T::CollateralCurrency::get()).is_ok()
+                    && T::Loans::ensure_can_borrow(
+                        &Self::account_id(),
+                        staking_currency,
+                        borrow_amount,
+                    )
+                    .is_ok()
+                {
+                    quotes.push((UnstakeProvider::Loans, borrow_amount));
+                }
+
+                // The amount must be matched at once, otherwise a part of it would be
+                // left pending in the matching pool
+                let available_liquid_amount = Self::matching_pool()
+                    .total_stake_amount
+                    .free()
+                    .ok()
+                    .and_then(Self::staking_to_liquid)
+                    .unwrap_or_default();
+                if net_liquid_amount <= available_liquid_amount {
+                    let fee =
+                        T::MatchingPoolFastUnstakeFee::get().saturating_mul_int(net_liquid_amount);
+                    if let Some(amount) =
+                        Self::liquid_to_staking(net_liquid_amount.saturating_sub(fee))
+                    {
+                        quotes.push((UnstakeProvider::MatchingPool, amount));
+                    }
+                }
+            }
+
+            // The swaps are quoted on the pair that `instant_unstake` trades
+            let path = vec![liquid_currency, staking_currency];
+            if let Some(amount) = T::AMM::get_amounts_out(liquid_amount, path.clone())
+                .ok()
+                .and_then(|amounts| amounts.last().copied())
+            {
+                quotes.push((UnstakeProvider::Amm, amount));
+            }
+            if let Some(amount) = T::StableSwap::get_amounts_out(liquid_amount, path)
+                .ok()
+                .and_then(|amounts| amounts.last().copied())
+            {
+                quotes.push((UnstakeProvider::StableSwap, amount));
+            }
+
+            quotes
+        }
+
+        /// Distribution of a bond of `total_amount` with the current strategy, it can be
+        /// used to preview the bonds before matching
+        pub fn get_bond_distributions(
+            total_amount: BalanceOf<T, I>,
+        ) -> Vec<(DerivativeIndex, BalanceOf<T, I>)> {
+            let amounts: Vec<(DerivativeIndex, BalanceOf<T, I>, BalanceOf<T, I>)> =
+                T::DerivativeIndexList::get()
+                    .iter()
+                    .map(|&index| {
+                        (
+                            index,
+                            Self::active_bonded_of(index),
+                            Self::total_bonded_of(index),
+                        )
+                    })
+                    .collect();
+            let cap = Self::staking_ledger_cap();
+            let min_nominator_bond = T::MinNominatorBond::get();
+            match Self::distribution_strategy() {
+                DistributionStrategyKind::Default => {
+                    T::DistributionStrategy::get_bond_distributions(
+                        amounts,
+                        total_amount,
+                        cap,
+                        min_nominator_bond,
+                    )
+                }
+                DistributionStrategyKind::Weighted => {
+                    WeightedDistribution::<DistributionWeightsOf<T, I>>::get_bond_distributions(
+                        amounts,
+                        total_amount,
+                        cap,
+                        min_nominator_bond,
+                    )
+                }
+            }
+        }
+
+        /// Distribution of an unbond of `total_amount` with the current strategy
+        pub fn get_unbond_distributions(
+            total_amount: BalanceOf<T, I>,
+        ) -> Vec<(DerivativeIndex, BalanceOf<T, I>)> {
+            let amounts: Vec<(DerivativeIndex, BalanceOf<T, I>)> = T::DerivativeIndexList::get()
+                .iter()
+                .map(|&index| (index, Self::active_bonded_of(index)))
+                .collect();
+            let min_nominator_bond = T::MinNominatorBond::get();
+            match Self::distribution_strategy() {
+                DistributionStrategyKind::Default => {
+                    T::DistributionStrategy::get_unbond_distributions(
+                        amounts,
+                        total_amount,
+                        min_nominator_bond,
+                    )
+                }
+                DistributionStrategyKind::Weighted => WeightedDistribution::<
+                    DistributionWeightsOf<T, I>,
+                >::get_unbond_distributions(
+                    amounts, total_amount, min_nominator_bond
+                ),
+            }
+        }
+
+        /// Distribution of a rebond of `total_amount` with the current strategy
+        pub fn get_rebond_distributions(
+            total_amount: BalanceOf<T, I>,
+        ) -> Vec<(DerivativeIndex, BalanceOf<T, I>)> {
+            let amounts: Vec<(DerivativeIndex, BalanceOf<T, I>)> = T::DerivativeIndexList::get()
+                .iter()
+                .map(|&index| (index, Self::unbonding_of(index)))
+                .collect();
+            match Self::distribution_strategy() {
+                DistributionStrategyKind::Default => {
+                    T::DistributionStrategy::get_rebond_distributions(amounts, total_amount)
+                }
+                DistributionStrategyKind::Weighted => WeightedDistribution::<
+                    DistributionWeightsOf<T, I>,
+                >::get_rebond_distributions(
+                    amounts, total_amount
+                ),
+            }
+        }
+
         /// Get total unclaimed
-        pub fn get_total_unclaimed(staking_currency: AssetIdOf<T>) -> BalanceOf<T> {
+        pub fn get_total_unclaimed(staking_currency: AssetIdOf<T, I>) -> BalanceOf<T, I> {
             T::Assets::reducible_balance(staking_currency, &Self::account_id(), false)
                 .saturating_sub(Self::total_reserves())
                 .saturating_sub(Self::matching_pool().total_stake_amount.total)
@@ -1232,21 +2128,21 @@ pub mod pallet {
                 .unwrap_or_else(Zero::zero)
         }
 
-        fn total_bonded_of(index: DerivativeIndex) -> BalanceOf<T> {
+        fn total_bonded_of(index: DerivativeIndex) -> BalanceOf<T, I> {
             Self::staking_ledger(index).map_or(Zero::zero(), |ledger| ledger.total)
         }
 
-        fn active_bonded_of(index: DerivativeIndex) -> BalanceOf<T> {
+        fn active_bonded_of(index: DerivativeIndex) -> BalanceOf<T, I> {
             Self::staking_ledger(index).map_or(Zero::zero(), |ledger| ledger.active)
         }
 
-        fn unbonding_of(index: DerivativeIndex) -> BalanceOf<T> {
+        fn unbonding_of(index: DerivativeIndex) -> BalanceOf<T, I> {
             Self::staking_ledger(index).map_or(Zero::zero(), |ledger| {
                 ledger.total.saturating_sub(ledger.active)
             })
         }
 
-        fn unbonded_of(index: DerivativeIndex) -> BalanceOf<T> {
+        fn unbonded_of(index: DerivativeIndex) -> BalanceOf<T, I> {
             let current_era = Self::current_era();
             Self::staking_ledger(index).map_or(Zero::zero(), |ledger| {
                 ledger.unlocking.iter().fold(Zero::zero(), |acc, chunk| {
@@ -1259,49 +2155,49 @@ pub mod pallet {
             })
         }
 
-        fn get_total_unbonding() -> BalanceOf<T> {
-            StakingLedgers::<T>::iter_values().fold(Zero::zero(), |acc, ledger| {
+        fn get_total_unbonding() -> BalanceOf<T, I> {
+            StakingLedgers::<T, I>::iter_values().fold(Zero::zero(), |acc, ledger| {
                 acc.saturating_add(ledger.total.saturating_sub(ledger.active))
             })
         }
 
-        fn get_total_bonded() -> BalanceOf<T> {
-            StakingLedgers::<T>::iter_values()
+        fn get_total_bonded() -> BalanceOf<T, I> {
+            StakingLedgers::<T, I>::iter_values()
                 .fold(Zero::zero(), |acc, ledger| acc.saturating_add(ledger.total))
         }
 
-        fn get_total_active_bonded() -> BalanceOf<T> {
-            StakingLedgers::<T>::iter_values().fold(Zero::zero(), |acc, ledger| {
+        fn get_total_active_bonded() -> BalanceOf<T, I> {
+            StakingLedgers::<T, I>::iter_values().fold(Zero::zero(), |acc, ledger| {
                 acc.saturating_add(ledger.active)
             })
         }
 
-        fn get_market_cap() -> BalanceOf<T> {
+        fn get_market_cap() -> BalanceOf<T, I> {
             Self::staking_ledger_cap()
-                .saturating_mul(T::DerivativeIndexList::get().len() as BalanceOf<T>)
+                .saturating_mul(T::DerivativeIndexList::get().len() as BalanceOf<T, I>)
         }
 
         #[require_transactional]
         fn do_bond(
             derivative_index: DerivativeIndex,
-            amount: BalanceOf<T>,
+            amount: BalanceOf<T, I>,
             payee: RewardDestination<T::AccountId>,
         ) -> DispatchResult {
             if amount.is_zero() {
                 return Ok(());
             }
 
-            if StakingLedgers::<T>::contains_key(derivative_index) {
+            if StakingLedgers::<T, I>::contains_key(derivative_index) {
                 return Self::do_bond_extra(derivative_index, amount);
             }
 
             ensure!(
                 T::DerivativeIndexList::get().contains(&derivative_index),
-                Error::<T>::InvalidDerivativeIndex
+                Error::<T, I>::InvalidDerivativeIndex
             );
             ensure!(
                 amount >= T::MinNominatorBond::get(),
-                Error::<T>::InsufficientBond
+                Error::<T, I>::InsufficientBond
             );
             Self::ensure_staking_ledger_cap(derivative_index, amount)?;
 
@@ -1312,7 +2208,7 @@ pub mod pallet {
                 &amount,
             );
 
-            MatchingPool::<T>::try_mutate(|p| -> DispatchResult {
+            MatchingPool::<T, I>::try_mutate(|p| -> DispatchResult {
                 p.set_stake_amount_lock(amount)
             })?;
 
@@ -1325,7 +2221,7 @@ pub mod pallet {
                 Self::notify_placeholder(),
             )?;
 
-            XcmRequests::<T>::insert(
+            XcmRequests::<T, I>::insert(
                 query_id,
                 XcmRequest::Bond {
                     index: derivative_index,
@@ -1333,7 +2229,7 @@ pub mod pallet {
                 },
             );
 
-            Self::deposit_event(Event::<T>::Bonding(
+            Self::deposit_event(Event::<T, I>::Bonding(
                 derivative_index,
                 derivative_account_id,
                 amount,
@@ -1346,7 +2242,7 @@ pub mod pallet {
         #[require_transactional]
         fn do_bond_extra(
             derivative_index: DerivativeIndex,
-            amount: BalanceOf<T>,
+            amount: BalanceOf<T, I>,
         ) -> DispatchResult {
             if amount.is_zero() {
                 return Ok(());
@@ -1354,11 +2250,11 @@ pub mod pallet {
 
             ensure!(
                 T::DerivativeIndexList::get().contains(&derivative_index),
-                Error::<T>::InvalidDerivativeIndex
+                Error::<T, I>::InvalidDerivativeIndex
             );
             ensure!(
-                StakingLedgers::<T>::contains_key(derivative_index),
-                Error::<T>::NotBonded
+                StakingLedgers::<T, I>::contains_key(derivative_index),
+                Error::<T, I>::NotBonded
             );
             Self::ensure_staking_ledger_cap(derivative_index, amount)?;
 
@@ -1369,7 +2265,7 @@ pub mod pallet {
                 &amount,
             );
 
-            MatchingPool::<T>::try_mutate(|p| -> DispatchResult {
+            MatchingPool::<T, I>::try_mutate(|p| -> DispatchResult {
                 p.set_stake_amount_lock(amount)
             })?;
 
@@ -1380,7 +2276,7 @@ pub mod pallet {
                 Self::notify_placeholder(),
             )?;
 
-            XcmRequests::<T>::insert(
+            XcmRequests::<T, I>::insert(
                 query_id,
                 XcmRequest::BondExtra {
                     index: derivative_index,
@@ -1388,34 +2284,34 @@ pub mod pallet {
                 },
             );
 
-            Self::deposit_event(Event::<T>::BondingExtra(derivative_index, amount));
+            Self::deposit_event(Event::<T, I>::BondingExtra(derivative_index, amount));
 
             Ok(())
         }
 
         #[require_transactional]
-        fn do_unbond(derivative_index: DerivativeIndex, amount: BalanceOf<T>) -> DispatchResult {
+        fn do_unbond(derivative_index: DerivativeIndex, amount: BalanceOf<T, I>) -> DispatchResult {
             if amount.is_zero() {
                 return Ok(());
             }
 
             ensure!(
                 T::DerivativeIndexList::get().contains(&derivative_index),
-                Error::<T>::InvalidDerivativeIndex
+                Error::<T, I>::InvalidDerivativeIndex
             );
 
-            let ledger: StakingLedger<T::AccountId, BalanceOf<T>> =
-                Self::staking_ledger(derivative_index).ok_or(Error::<T>::NotBonded)?;
+            let ledger: StakingLedger<T::AccountId, BalanceOf<T, I>> =
+                Self::staking_ledger(derivative_index).ok_or(Error::<T, I>::NotBonded)?;
             ensure!(
                 ledger.unlocking.len() < MAX_UNLOCKING_CHUNKS,
-                Error::<T>::NoMoreChunks
+                Error::<T, I>::NoMoreChunks
             );
             ensure!(
                 ledger.active.saturating_sub(amount) >= T::MinNominatorBond::get(),
-                Error::<T>::InsufficientBond
+                Error::<T, I>::InsufficientBond
             );
 
-            MatchingPool::<T>::try_mutate(|p| -> DispatchResult {
+            MatchingPool::<T, I>::try_mutate(|p| -> DispatchResult {
                 p.set_unstake_amount_lock(amount)
             })?;
 
@@ -1428,7 +2324,7 @@ pub mod pallet {
 
             let query_id = T::XCM::do_unbond(amount, derivative_index, Self::notify_placeholder())?;
 
-            XcmRequests::<T>::insert(
+            XcmRequests::<T, I>::insert(
                 query_id,
                 XcmRequest::Unbond {
                     index: derivative_index,
@@ -1436,24 +2332,24 @@ pub mod pallet {
                 },
             );
 
-            Self::deposit_event(Event::<T>::Unbonding(derivative_index, amount));
+            Self::deposit_event(Event::<T, I>::Unbonding(derivative_index, amount));
 
             Ok(())
         }
 
         #[require_transactional]
-        fn do_rebond(derivative_index: DerivativeIndex, amount: BalanceOf<T>) -> DispatchResult {
+        fn do_rebond(derivative_index: DerivativeIndex, amount: BalanceOf<T, I>) -> DispatchResult {
             if amount.is_zero() {
                 return Ok(());
             }
 
             ensure!(
                 T::DerivativeIndexList::get().contains(&derivative_index),
-                Error::<T>::InvalidDerivativeIndex
+                Error::<T, I>::InvalidDerivativeIndex
             );
             ensure!(
-                StakingLedgers::<T>::contains_key(derivative_index),
-                Error::<T>::NotBonded
+                StakingLedgers::<T, I>::contains_key(derivative_index),
+                Error::<T, I>::NotBonded
             );
 
             log::trace!(
@@ -1463,13 +2359,13 @@ pub mod pallet {
                 &amount,
             );
 
-            MatchingPool::<T>::try_mutate(|p| -> DispatchResult {
+            MatchingPool::<T, I>::try_mutate(|p| -> DispatchResult {
                 p.set_stake_amount_lock(amount)
             })?;
 
             let query_id = T::XCM::do_rebond(amount, derivative_index, Self::notify_placeholder())?;
 
-            XcmRequests::<T>::insert(
+            XcmRequests::<T, I>::insert(
                 query_id,
                 XcmRequest::Rebond {
                     index: derivative_index,
@@ -1477,7 +2373,7 @@ pub mod pallet {
                 },
             );
 
-            Self::deposit_event(Event::<T>::Rebonding(derivative_index, amount));
+            Self::deposit_event(Event::<T, I>::Rebonding(derivative_index, amount));
 
             Ok(())
         }
@@ -1493,11 +2389,11 @@ pub mod pallet {
 
             ensure!(
                 T::DerivativeIndexList::get().contains(&derivative_index),
-                Error::<T>::InvalidDerivativeIndex
+                Error::<T, I>::InvalidDerivativeIndex
             );
             ensure!(
-                StakingLedgers::<T>::contains_key(derivative_index),
-                Error::<T>::NotBonded
+                StakingLedgers::<T, I>::contains_key(derivative_index),
+                Error::<T, I>::NotBonded
             );
 
             log::trace!(
@@ -1507,14 +2403,15 @@ pub mod pallet {
                 &num_slashing_spans,
             );
 
-            let query_id = T::XCM::do_withdraw_unbonded(
+            let query_id = T::XCM::do_withdraw_unbonded_amount(
                 num_slashing_spans,
                 Self::sovereign_account_id(),
+                Self::unbonded_of(derivative_index),
                 derivative_index,
                 Self::notify_placeholder(),
             )?;
 
-            XcmRequests::<T>::insert(
+            XcmRequests::<T, I>::insert(
                 query_id,
                 XcmRequest::WithdrawUnbonded {
                     index: derivative_index,
@@ -1522,7 +2419,7 @@ pub mod pallet {
                 },
             );
 
-            Self::deposit_event(Event::<T>::WithdrawingUnbonded(
+            Self::deposit_event(Event::<T, I>::WithdrawingUnbonded(
                 derivative_index,
                 num_slashing_spans,
             ));
@@ -1537,11 +2434,15 @@ pub mod pallet {
         ) -> DispatchResult {
             ensure!(
                 T::DerivativeIndexList::get().contains(&derivative_index),
-                Error::<T>::InvalidDerivativeIndex
+                Error::<T, I>::InvalidDerivativeIndex
             );
             ensure!(
-                StakingLedgers::<T>::contains_key(derivative_index),
-                Error::<T>::NotBonded
+                StakingLedgers::<T, I>::contains_key(derivative_index),
+                Error::<T, I>::NotBonded
+            );
+            ensure!(
+                targets.len() <= T::MaxNominations::get() as usize,
+                Error::<T, I>::TooManyNominations
             );
 
             log::trace!(
@@ -1556,7 +2457,7 @@ pub mod pallet {
                 Self::notify_placeholder(),
             )?;
 
-            XcmRequests::<T>::insert(
+            XcmRequests::<T, I>::insert(
                 query_id,
                 XcmRequest::Nominate {
                     index: derivative_index,
@@ -1564,39 +2465,21 @@ pub mod pallet {
                 },
             );
 
-            Self::deposit_event(Event::<T>::Nominating(derivative_index, targets));
+            Self::deposit_event(Event::<T, I>::Nominating(derivative_index, targets));
 
             Ok(())
         }
 
         #[require_transactional]
         fn do_multi_bond(
-            total_amount: BalanceOf<T>,
+            total_amount: BalanceOf<T, I>,
             payee: RewardDestination<T::AccountId>,
         ) -> DispatchResult {
             if total_amount.is_zero() {
                 return Ok(());
             }
 
-            let amounts: Vec<(DerivativeIndex, BalanceOf<T>, BalanceOf<T>)> =
-                T::DerivativeIndexList::get()
-                    .iter()
-                    .map(|&index| {
-                        (
-                            index,
-                            Self::active_bonded_of(index),
-                            Self::total_bonded_of(index),
-                        )
-                    })
-                    .collect();
-            let distributions = T::DistributionStrategy::get_bond_distributions(
-                amounts,
-                total_amount,
-                Self::staking_ledger_cap(),
-                T::MinNominatorBond::get(),
-            );
-
-            for (index, amount) in distributions.into_iter() {
+            for (index, amount) in Self::get_bond_distributions(total_amount).into_iter() {
                 Self::do_bond(index, amount, payee.clone())?;
             }
 
@@ -1604,22 +2487,12 @@ pub mod pallet {
         }
 
         #[require_transactional]
-        fn do_multi_unbond(total_amount: BalanceOf<T>) -> DispatchResult {
+        fn do_multi_unbond(total_amount: BalanceOf<T, I>) -> DispatchResult {
             if total_amount.is_zero() {
                 return Ok(());
             }
 
-            let amounts: Vec<(DerivativeIndex, BalanceOf<T>)> = T::DerivativeIndexList::get()
-                .iter()
-                .map(|&index| (index, Self::active_bonded_of(index)))
-                .collect();
-            let distributions = T::DistributionStrategy::get_unbond_distributions(
-                amounts,
-                total_amount,
-                T::MinNominatorBond::get(),
-            );
-
-            for (index, amount) in distributions.into_iter() {
+            for (index, amount) in Self::get_unbond_distributions(total_amount).into_iter() {
                 Self::do_unbond(index, amount)?;
             }
 
@@ -1627,19 +2500,12 @@ pub mod pallet {
         }
 
         #[require_transactional]
-        fn do_multi_rebond(total_amount: BalanceOf<T>) -> DispatchResult {
+        fn do_multi_rebond(total_amount: BalanceOf<T, I>) -> DispatchResult {
             if total_amount.is_zero() {
                 return Ok(());
             }
 
-            let amounts: Vec<(DerivativeIndex, BalanceOf<T>)> = T::DerivativeIndexList::get()
-                .iter()
-                .map(|&index| (index, Self::unbonding_of(index)))
-                .collect();
-            let distributions =
-                T::DistributionStrategy::get_rebond_distributions(amounts, total_amount);
-
-            for (index, amount) in distributions.into_iter() {
+            for (index, amount) in Self::get_rebond_distributions(total_amount).into_iter() {
                 Self::do_rebond(index, amount)?;
             }
 
@@ -1648,7 +2514,7 @@ pub mod pallet {
 
         #[require_transactional]
         fn do_multi_withdraw_unbonded(num_slashing_spans: u32) -> DispatchResult {
-            for derivative_index in StakingLedgers::<T>::iter_keys() {
+            for derivative_index in StakingLedgers::<T, I>::iter_keys() {
                 Self::do_withdraw_unbonded(derivative_index, num_slashing_spans)?;
             }
 
@@ -1658,7 +2524,7 @@ pub mod pallet {
         #[require_transactional]
         fn do_notification_received(
             query_id: QueryId,
-            req: XcmRequest<T>,
+            req: XcmRequest<T::AccountId, BalanceOf<T, I>>,
             res: Option<(u32, XcmError)>,
         ) -> DispatchResult {
             use XcmRequest::*;
@@ -1672,6 +2538,15 @@ pub mod pallet {
 
             let executed = res.is_none();
             if !executed {
+                // the rewards are left in the stash, they can be set again
+                if let WithdrawRewards {
+                    index: derivative_index,
+                    ..
+                } = req
+                {
+                    RewardsWithdrawals::<T, I>::remove(derivative_index);
+                    XcmRequests::<T, I>::remove(query_id);
+                }
                 return Ok(());
             }
 
@@ -1681,15 +2556,15 @@ pub mod pallet {
                     amount,
                 } => {
                     ensure!(
-                        !StakingLedgers::<T>::contains_key(derivative_index),
-                        Error::<T>::AlreadyBonded
+                        !StakingLedgers::<T, I>::contains_key(derivative_index),
+                        Error::<T, I>::AlreadyBonded
                     );
-                    let staking_ledger = <StakingLedger<T::AccountId, BalanceOf<T>>>::new(
+                    let staking_ledger = <StakingLedger<T::AccountId, BalanceOf<T, I>>>::new(
                         Self::derivative_sovereign_account_id(derivative_index),
                         amount,
                     );
-                    StakingLedgers::<T>::insert(derivative_index, staking_ledger);
-                    MatchingPool::<T>::try_mutate(|p| -> DispatchResult {
+                    StakingLedgers::<T, I>::insert(derivative_index, staking_ledger);
+                    MatchingPool::<T, I>::try_mutate(|p| -> DispatchResult {
                         p.consolidate_stake(amount)
                     })?;
                     T::Assets::burn_from(Self::staking_currency()?, &Self::account_id(), amount)?;
@@ -1702,7 +2577,7 @@ pub mod pallet {
                         ledger.bond_extra(amount);
                         Ok(())
                     })?;
-                    MatchingPool::<T>::try_mutate(|p| -> DispatchResult {
+                    MatchingPool::<T, I>::try_mutate(|p| -> DispatchResult {
                         p.consolidate_stake(amount)
                     })?;
                     T::Assets::burn_from(Self::staking_currency()?, &Self::account_id(), amount)?;
@@ -1716,7 +2591,7 @@ pub mod pallet {
                         ledger.unbond(amount, target_era);
                         Ok(())
                     })?;
-                    MatchingPool::<T>::try_mutate(|p| -> DispatchResult {
+                    MatchingPool::<T, I>::try_mutate(|p| -> DispatchResult {
                         p.consolidate_unstake(amount)
                     })?;
                 }
@@ -1728,7 +2603,7 @@ pub mod pallet {
                         ledger.rebond(amount);
                         Ok(())
                     })?;
-                    MatchingPool::<T>::try_mutate(|p| -> DispatchResult {
+                    MatchingPool::<T, I>::try_mutate(|p| -> DispatchResult {
                         p.consolidate_stake(amount)
                     })?;
                 }
@@ -1747,35 +2622,232 @@ pub mod pallet {
                         Ok(())
                     })?;
                 }
-                Nominate { targets: _, .. } => {}
+                Nominate {
+                    index: derivative_index,
+                    targets,
+                } => {
+                    let targets: BoundedVec<T::AccountId, T::MaxNominations> =
+                        targets
+                            .try_into()
+                            .map_err(|_| Error::<T, I>::TooManyNominations)?;
+                    Nominations::<T, I>::insert(derivative_index, targets);
+                }
+                // The rewards are paid to the stashes, see `set_stash_rewards`
+                PayoutStakers { .. } => {}
+                WithdrawRewards {
+                    index: derivative_index,
+                    amount,
+                } => {
+                    RewardsWithdrawals::<T, I>::remove(derivative_index);
+                    let inflate_liquid_amount = Self::get_inflate_liquid_amount(amount)?;
+                    if !inflate_liquid_amount.is_zero() {
+                        T::Assets::mint_into(
+                            Self::liquid_currency()?,
+                            &T::ProtocolFeeReceiver::get(),
+                            inflate_liquid_amount,
+                        )?;
+                    }
+                    // The rewards now back the staking currency minted to the pallet,
+                    // they are staked without issuing liquid currency then re-bonded
+                    T::Assets::mint_into(Self::staking_currency()?, &Self::account_id(), amount)?;
+                    MatchingPool::<T, I>::try_mutate(|p| -> DispatchResult {
+                        p.add_stake_amount(amount)
+                    })?;
+                    Self::do_multi_bond(amount, RewardDestination::Stash)?;
+                    Self::deposit_event(Event::<T, I>::RewardsRebonded(derivative_index, amount));
+                }
             }
-            XcmRequests::<T>::remove(query_id);
+            XcmRequests::<T, I>::remove(query_id);
             Ok(())
         }
 
-        #[require_transactional]
-        fn do_update_exchange_rate() -> DispatchResult {
+        // Returns `None` if there is no liquid currency issued yet
+        fn calc_exchange_rate() -> Result<Option<Rate>, DispatchError> {
             let matching_ledger = Self::matching_pool();
             let total_active_bonded = Self::get_total_active_bonded();
             let issuance = T::Assets::total_issuance(Self::liquid_currency()?);
             if issuance.is_zero() {
-                return Ok(());
+                return Ok(None);
             }
             // TODO: when one era has big amount of stakes, the exchange rate
             // will not look great
-            let new_exchange_rate = Rate::checked_from_rational(
+            let exchange_rate = Rate::checked_from_rational(
                 total_active_bonded
                     .checked_add(matching_ledger.total_stake_amount.total)
                     .and_then(|r| r.checked_sub(matching_ledger.total_unstake_amount.total))
                     .ok_or(ArithmeticError::Overflow)?,
                 issuance,
             )
-            .ok_or(Error::<T>::InvalidExchangeRate)?;
-            // slashes should be handled properly offchain
-            // by doing `bond_extra` using OrmlXcm or PolkadotXcm
-            if new_exchange_rate > Self::exchange_rate() {
-                ExchangeRate::<T>::put(new_exchange_rate);
-                Self::deposit_event(Event::<T>::ExchangeRateUpdated(new_exchange_rate));
+            .ok_or(Error::<T, I>::InvalidExchangeRate)?;
+            Ok(Some(exchange_rate))
+        }
+
+        #[require_transactional]
+        fn do_update_exchange_rate() -> DispatchResult {
+            // slashes are handled by `do_slash` once the shrunk ledger is proven,
+            // so the exchange rate only goes up here
+            match Self::calc_exchange_rate()? {
+                Some(new_exchange_rate) if new_exchange_rate > Self::exchange_rate() => {
+                    ExchangeRate::<T, I>::put(new_exchange_rate);
+                    Self::deposit_event(Event::<T, I>::ExchangeRateUpdated(new_exchange_rate));
+                }
+                _ => {}
+            }
+            Ok(())
+        }
+
+        // Makes up the `amount` slashed from `derivative_index` with the reserves, which
+        // get bonded again at the next matching, and lowers the exchange rate for the
+        // rest of the loss.
+        #[require_transactional]
+        fn do_slash(derivative_index: DerivativeIndex, amount: BalanceOf<T, I>) -> DispatchResult {
+            let covered_by_reserves = min(amount, Self::total_reserves());
+            if !covered_by_reserves.is_zero() {
+                TotalReserves::<T, I>::mutate(|b| *b = b.saturating_sub(covered_by_reserves));
+                MatchingPool::<T, I>::try_mutate(|p| -> DispatchResult {
+                    p.add_stake_amount(covered_by_reserves)
+                })?;
+            }
+
+            let era = Self::current_era();
+            Slashes::<T, I>::try_mutate(era, derivative_index, |record| -> DispatchResult {
+                record.amount = record
+                    .amount
+                    .checked_add(amount)
+                    .ok_or(ArithmeticError::Overflow)?;
+                record.covered_by_reserves = record
+                    .covered_by_reserves
+                    .checked_add(covered_by_reserves)
+                    .ok_or(ArithmeticError::Overflow)?;
+                Ok(())
+            })?;
+
+            match Self::calc_exchange_rate()? {
+                Some(new_exchange_rate) if new_exchange_rate < Self::exchange_rate() => {
+                    ExchangeRate::<T, I>::put(new_exchange_rate);
+                    Self::deposit_event(Event::<T, I>::ExchangeRateUpdated(new_exchange_rate));
+                }
+                _ => {}
+            }
+
+            log::trace!(
+                target: "liquidStaking::do_slash",
+                "index: {:?}, era: {:?}, amount: {:?}, covered_by_reserves: {:?}",
+                &derivative_index,
+                &era,
+                &amount,
+                &covered_by_reserves,
+            );
+
+            Self::deposit_event(Event::<T, I>::Slashed(
+                derivative_index,
+                era,
+                amount,
+                covered_by_reserves,
+            ));
+            Ok(())
+        }
+
+        #[require_transactional]
+        fn do_mint_unstake_receipt(
+            owner: &T::AccountId,
+            value: BalanceOf<T, I>,
+            era: EraIndex,
+        ) -> DispatchResult {
+            // Top up the owner's receipt of the same era instead of minting
+            // a new one, unless it is being transferred
+            let existing = UnstakeReceiptsOf::<T, I>::iter_key_prefix(owner).find(|id| {
+                !PendingReceiptTransfers::<T, I>::contains_key(id)
+                    && Self::unstake_receipt(id).map_or(false, |r| r.era == era)
+            });
+            let receipt_id = if let Some(receipt_id) = existing {
+                UnstakeReceipts::<T, I>::try_mutate(receipt_id, |receipt| -> DispatchResult {
+                    let receipt = receipt
+                        .as_mut()
+                        .ok_or(Error::<T, I>::UnstakeReceiptNotFound)?;
+                    receipt.value = receipt
+                        .value
+                        .checked_add(&value)
+                        .ok_or(ArithmeticError::Overflow)?;
+                    Ok(())
+                })?;
+                receipt_id
+            } else {
+                Self::ensure_receipts_limit(owner)?;
+                let receipt_id = NextUnstakeReceiptId::<T, I>::try_mutate(
+                    |id| -> Result<UnstakeReceiptId, DispatchError> {
+                        let receipt_id = *id;
+                        *id = id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+                        Ok(receipt_id)
+                    },
+                )?;
+
+                UnstakeReceipts::<T, I>::insert(
+                    receipt_id,
+                    UnstakeReceipt {
+                        owner: owner.clone(),
+                        value,
+                        era,
+                    },
+                );
+                UnstakeReceiptsOf::<T, I>::insert(owner, receipt_id, ());
+                receipt_id
+            };
+
+            Self::deposit_event(Event::<T, I>::UnstakeReceiptMinted(
+                receipt_id,
+                owner.clone(),
+                value,
+                era,
+            ));
+            Ok(())
+        }
+
+        #[require_transactional]
+        fn do_update_validator(
+            validator: &T::AccountId,
+            cb: impl FnOnce(&mut ValidatorInfo<BalanceOf<T, I>>),
+        ) -> DispatchResult {
+            Validators::<T, I>::try_mutate(validator, |info| -> DispatchResult {
+                let info = info.as_mut().ok_or(Error::<T, I>::ValidatorNotFound)?;
+                cb(info);
+                Self::deposit_event(Event::<T, I>::ValidatorUpdated(
+                    validator.clone(),
+                    info.clone(),
+                ));
+                Ok(())
+            })
+        }
+
+        // Reading the registry and nominating with every derivative index
+        fn update_nominations_weight() -> Weight {
+            T::DbWeight::get()
+                .reads(T::MaxValidators::get() as u64)
+                .saturating_add(
+                    <T as Config<I>>::WeightInfo::nominate()
+                        .saturating_mul(T::DerivativeIndexList::get().len() as u64),
+                )
+        }
+
+        // Nominates the targets picked by `NominationStrategy` for every bonded
+        // derivative index whose nominations changed
+        #[require_transactional]
+        fn do_update_nominations() -> DispatchResult {
+            let derivative_indexes = T::DerivativeIndexList::get()
+                .into_iter()
+                .filter(|index| StakingLedgers::<T, I>::contains_key(index))
+                .collect();
+            let nominations = T::NominationStrategy::get_nominations(
+                Validators::<T, I>::iter().collect(),
+                derivative_indexes,
+                T::MaxNominations::get(),
+            );
+
+            for (index, targets) in nominations.into_iter() {
+                if targets.is_empty() || targets == Self::nominations(index).into_inner() {
+                    continue;
+                }
+                Self::do_nominate(index, targets)?;
             }
             Ok(())
         }
@@ -1783,13 +2855,13 @@ pub mod pallet {
         #[require_transactional]
         fn do_update_ledger(
             derivative_index: DerivativeIndex,
-            cb: impl FnOnce(&mut StakingLedger<T::AccountId, BalanceOf<T>>) -> DispatchResult,
+            cb: impl FnOnce(&mut StakingLedger<T::AccountId, BalanceOf<T, I>>) -> DispatchResult,
         ) -> DispatchResult {
-            StakingLedgers::<T>::try_mutate(derivative_index, |ledger| -> DispatchResult {
-                let ledger = ledger.as_mut().ok_or(Error::<T>::NotBonded)?;
+            StakingLedgers::<T, I>::try_mutate(derivative_index, |ledger| -> DispatchResult {
+                let ledger = ledger.as_mut().ok_or(Error::<T, I>::NotBonded)?;
                 cb(ledger)?;
-                IsUpdated::<T>::insert(derivative_index, true);
-                Self::deposit_event(Event::<T>::StakingLedgerUpdated(
+                IsUpdated::<T, I>::insert(derivative_index, true);
+                Self::deposit_event(Event::<T, I>::StakingLedgerUpdated(
                     derivative_index,
                     ledger.clone(),
                 ));
@@ -1811,16 +2883,16 @@ pub mod pallet {
                 &unbond_amount
             );
 
-            IsMatched::<T>::put(true);
+            IsMatched::<T, I>::put(true);
 
-            Self::do_multi_bond(bond_amount, RewardDestination::Staked)?;
+            Self::do_multi_bond(bond_amount, RewardDestination::Stash)?;
             Self::do_multi_rebond(rebond_amount)?;
 
             Self::do_multi_unbond(unbond_amount)?;
 
             Self::do_multi_withdraw_unbonded(T::NumSlashingSpans::get())?;
 
-            Self::deposit_event(Event::<T>::Matching(
+            Self::deposit_event(Event::<T, I>::Matching(
                 bond_amount,
                 rebond_amount,
                 unbond_amount,
@@ -1841,21 +2913,200 @@ pub mod pallet {
                 &offset,
             );
 
-            EraStartBlock::<T>::put(T::RelayChainValidationDataProvider::current_block_number());
-            CurrentEra::<T>::mutate(|e| *e = e.saturating_add(offset));
+            let ended_era = Self::current_era();
+            EraStartBlock::<T, I>::put(T::RelayChainValidationDataProvider::current_block_number());
+            CurrentEra::<T, I>::mutate(|e| *e = e.saturating_add(offset));
 
             // ignore error
             if let Err(e) = Self::do_update_exchange_rate() {
                 log::error!(target: "liquidStaking::do_advance_era", "advance era error caught: {:?}", &e);
             }
+            if let Err(e) = Self::do_snapshot_exchange_rate() {
+                log::error!(target: "liquidStaking::do_advance_era", "exchange rate snapshot error caught: {:?}", &e);
+            }
+
+            Self::do_schedule_payouts(ended_era..Self::current_era());
+            if let Err(e) = Self::do_update_nominations() {
+                log::error!(target: "liquidStaking::do_advance_era", "nominations update error caught: {:?}", &e);
+            }
 
-            IsMatched::<T>::put(false);
-            Self::deposit_event(Event::<T>::NewEra(Self::current_era()));
+            IsMatched::<T, I>::put(false);
+            Self::deposit_event(Event::<T, I>::NewEra(Self::current_era()));
             Ok(())
         }
 
+        // Queues the payouts of the validators nominated when the ended `eras` started,
+        // the eras skipped over share the nominations of the first one
+        fn do_schedule_payouts(eras: Range<EraIndex>) {
+            let validators = Self::era_nominations();
+            let mut nominations: Vec<T::AccountId> =
+                Nominations::<T, I>::iter_values().flatten().collect();
+            nominations.sort();
+            nominations.dedup();
+            if nominations.len() > T::MaxNominations::get() as usize {
+                log::warn!(
+                    target: "liquidStaking::do_schedule_payouts",
+                    "too many nominations, only the first {:?} are paid out",
+                    T::MaxNominations::get(),
+                );
+            }
+            EraNominations::<T, I>::put(BoundedVec::truncate_from(nominations));
+            if validators.is_empty() {
+                return;
+            }
+
+            PendingPayouts::<T, I>::mutate(|payouts| {
+                let scheduled =
+                    eras.flat_map(|era| validators.iter().cloned().map(move |v| (era, v)));
+                for payout in scheduled {
+                    // the relaychain keeps a limited history of eras, the oldest payouts
+                    // are the first to become unclaimable
+                    if let Err(payout) = payouts.try_push(payout) {
+                        let dropped = payouts.remove(0);
+                        log::warn!(
+                            target: "liquidStaking::do_schedule_payouts",
+                            "pending payouts are full, dropped: {:?}",
+                            &dropped,
+                        );
+                        let _ = payouts.try_push(payout);
+                    }
+                }
+            });
+        }
+
+        /// Sends the staking.payout_stakers calls of the oldest pending payouts, at most
+        /// `MaxPayoutsPerBlock` of them. A payout which fails to be sent is dropped,
+        /// returns the number of payouts processed
+        pub fn do_payout_stakers() -> u32 {
+            let mut count = 0;
+            while count < T::MaxPayoutsPerBlock::get() {
+                let payout = PendingPayouts::<T, I>::mutate(|payouts| {
+                    (!payouts.is_empty()).then(|| payouts.remove(0))
+                });
+                let (era, validator) = match payout {
+                    Some(payout) => payout,
+                    None => break,
+                };
+                count += 1;
+
+                let _ = with_transaction(|| match Self::do_payout_validator(&validator, era) {
+                    Ok(()) => TransactionOutcome::Commit(Ok(())),
+                    Err(err) => {
+                        log::error!(
+                            target: "liquidStaking::do_payout_stakers",
+                            "payout of validator: {:?}, era: {:?} dropped: {:?}",
+                            &validator,
+                            &era,
+                            &err,
+                        );
+                        TransactionOutcome::Rollback(Err(err))
+                    }
+                });
+            }
+            count
+        }
+
+        /// Sends the rewards set by `set_stash_rewards` back to the parachain's sovereign
+        /// account. A withdrawal which fails to be sent is dropped, returns the number of
+        /// withdrawals processed
+        pub fn do_withdraw_rewards() -> u32 {
+            let mut count = 0;
+            // at most one entry for each of the `DerivativeIndexList`
+            for (derivative_index, amount) in PendingRewards::<T, I>::drain() {
+                count += 1;
+                let _ = with_transaction(|| {
+                    match Self::do_withdraw_stash_rewards(derivative_index, amount) {
+                        Ok(()) => TransactionOutcome::Commit(Ok(())),
+                        Err(err) => {
+                            log::error!(
+                                target: "liquidStaking::do_withdraw_rewards",
+                                "rewards withdrawal of index: {:?}, amount: {:?} dropped: {:?}",
+                                &derivative_index,
+                                &amount,
+                                &err,
+                            );
+                            TransactionOutcome::Rollback(Err(err))
+                        }
+                    }
+                });
+            }
+            count
+        }
+
         #[require_transactional]
-        fn do_claim_for(who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+        fn do_withdraw_stash_rewards(
+            derivative_index: DerivativeIndex,
+            amount: BalanceOf<T, I>,
+        ) -> DispatchResult {
+            let query_id = T::XCM::do_withdraw_rewards(
+                amount,
+                Self::sovereign_account_id(),
+                derivative_index,
+                Self::notify_placeholder(),
+            )?;
+
+            XcmRequests::<T, I>::insert(
+                query_id,
+                XcmRequest::WithdrawRewards {
+                    index: derivative_index,
+                    amount,
+                },
+            );
+            RewardsWithdrawals::<T, I>::insert(derivative_index, amount);
+
+            log::trace!(
+                target: "liquidStaking::do_withdraw_rewards",
+                "index: {:?}, amount: {:?}",
+                &derivative_index,
+                &amount,
+            );
+
+            Self::deposit_event(Event::<T, I>::WithdrawingRewards(derivative_index, amount));
+            Ok(())
+        }
+
+        #[require_transactional]
+        fn do_payout_validator(validator: &T::AccountId, era: EraIndex) -> DispatchResult {
+            let query_id =
+                T::XCM::do_payout_stakers(validator.clone(), era, Self::notify_placeholder())?;
+
+            XcmRequests::<T, I>::insert(
+                query_id,
+                XcmRequest::PayoutStakers {
+                    validator: validator.clone(),
+                    era,
+                },
+            );
+
+            log::trace!(
+                target: "liquidStaking::do_payout_stakers",
+                "validator: {:?}, era: {:?}",
+                validator,
+                &era,
+            );
+
+            Self::deposit_event(Event::<T, I>::PayingOutStakers(validator.clone(), era));
+            Ok(())
+        }
+
+        fn do_snapshot_exchange_rate() -> DispatchResult {
+            let snapshot = ExchangeRateSnapshot {
+                era: Self::current_era(),
+                exchange_rate: Self::exchange_rate(),
+                total_bonded: Self::get_total_bonded(),
+                total_liquid: T::Assets::total_issuance(Self::liquid_currency()?),
+            };
+            ExchangeRateHistory::<T, I>::mutate(|history| {
+                if history.len() >= T::ExchangeRateHistoryDepth::get() as usize {
+                    history.remove(0);
+                }
+                let _ = history.try_push(snapshot);
+            });
+            Ok(())
+        }
+
+        #[require_transactional]
+        fn do_claim_for(who: &T::AccountId, amount: BalanceOf<T, I>) -> DispatchResult {
             let module_id = Self::account_id();
             let collateral_currency = T::CollateralCurrency::get();
             let staking_currency = Self::staking_currency()?;
@@ -1881,7 +3132,91 @@ pub mod pallet {
         }
 
         #[require_transactional]
-        fn do_loans_instant_unstake(who: &AccountIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+        fn do_unstake(
+            who: &T::AccountId,
+            liquid_amount: BalanceOf<T, I>,
+            unstake_provider: UnstakeProvider,
+        ) -> DispatchResult {
+            let reserves = Self::unstake_reserve_factor().mul_floor(liquid_amount);
+
+            TotalUnstakeReserves::<T, I>::try_mutate(|b| -> DispatchResult {
+                *b = b.checked_add(reserves).ok_or(ArithmeticError::Overflow)?;
+                Ok(())
+            })?;
+
+            let origin_liquid_amount = liquid_amount;
+            let liquid_amount = liquid_amount
+                .checked_sub(reserves)
+                .ok_or(ArithmeticError::Underflow)?;
+
+            if unstake_provider.is_matching_pool() {
+                FastUnstakeRequests::<T, I>::try_mutate(who, |b| -> DispatchResult {
+                    let balance =
+                        T::Assets::reducible_balance(Self::liquid_currency()?, who, false);
+                    *b = b.saturating_add(liquid_amount).min(balance);
+                    Ok(())
+                })?;
+                return Ok(());
+            }
+
+            let amount =
+                Self::liquid_to_staking(liquid_amount).ok_or(Error::<T, I>::InvalidExchangeRate)?;
+
+            if unstake_provider.is_loans() {
+                Unlockings::<T, I>::try_mutate(Self::loans_account_id(), |b| -> DispatchResult {
+                    let mut chunks = b.take().unwrap_or_default();
+                    let target_era = Self::target_era();
+                    if let Some(mut chunk) =
+                        chunks.last_mut().filter(|chunk| chunk.era == target_era)
+                    {
+                        chunk.value = chunk.value.saturating_add(amount);
+                    } else {
+                        chunks.push(UnlockChunk {
+                            value: amount,
+                            era: target_era,
+                        });
+                    }
+                    ensure!(
+                        chunks.len() <= MAX_UNLOCKING_CHUNKS,
+                        Error::<T, I>::NoMoreChunks
+                    );
+                    *b = Some(chunks);
+                    Ok(())
+                })?;
+            } else {
+                Self::do_mint_unstake_receipt(who, amount, Self::target_era())?;
+            }
+
+            T::Assets::burn_from(Self::liquid_currency()?, who, origin_liquid_amount)?;
+            T::Assets::mint_into(Self::liquid_currency()?, &Self::account_id(), reserves)?;
+
+            if unstake_provider.is_loans() {
+                Self::do_loans_instant_unstake(who, amount)?;
+            }
+
+            MatchingPool::<T, I>::try_mutate(|p| p.add_unstake_amount(amount))?;
+
+            log::trace!(
+                target: "liquidStaking::unstake",
+                "unstake_amount: {:?}, liquid_amount: {:?}, reserved: {:?}",
+                &amount,
+                &liquid_amount,
+                &reserves
+            );
+
+            Self::deposit_event(Event::<T, I>::Unstaked(
+                who.clone(),
+                origin_liquid_amount,
+                amount,
+            ));
+            Ok(())
+        }
+
+        #[require_transactional]
+        fn do_loans_instant_unstake(
+            who: &AccountIdOf<T>,
+            amount: BalanceOf<T, I>,
+        ) -> DispatchResult {
             let loans_instant_unstake_fee = T::LoansInstantUnstakeFee::get()
                 .checked_mul_int(amount)
                 .ok_or(ArithmeticError::Overflow)?;
@@ -1905,7 +3240,9 @@ pub mod pallet {
         }
 
         // liquid_amount_to_fee=TotalLiquidCurrency * (commission_rate*total_rewards/(TotalStakeCurrency+(1-commission_rate)*total_rewards))
-        fn get_inflate_liquid_amount(rewards: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+        fn get_inflate_liquid_amount(
+            rewards: BalanceOf<T, I>,
+        ) -> Result<BalanceOf<T, I>, DispatchError> {
             let issuance = T::Assets::total_issuance(Self::liquid_currency()?);
             let commission_rate = Self::commission_rate();
             if issuance.is_zero() || commission_rate.is_zero() || rewards.is_zero() {
@@ -1934,7 +3271,7 @@ pub mod pallet {
 
         #[require_transactional]
         fn do_fast_match_unstake(unstaker: &T::AccountId) -> DispatchResult {
-            FastUnstakeRequests::<T>::try_mutate_exists(unstaker, |b| -> DispatchResult {
+            FastUnstakeRequests::<T, I>::try_mutate_exists(unstaker, |b| -> DispatchResult {
                 if b.is_none() {
                     return Ok(());
                 }
@@ -1945,44 +3282,8 @@ pub mod pallet {
                     .expect("Could not be none, qed;")
                     .min(current_liquid_amount);
 
-                let available_liquid_amount =
-                    Self::staking_to_liquid(Self::matching_pool().total_stake_amount.free()?)
-                        .ok_or(Error::<T>::InvalidExchangeRate)?;
-
-                let matched_liquid_amount = request_liquid_amount.min(available_liquid_amount);
-
-                if !matched_liquid_amount.is_zero() {
-                    let matched_fee = T::MatchingPoolFastUnstakeFee::get()
-                        .saturating_mul_int(matched_liquid_amount);
-                    let liquid_to_burn = matched_liquid_amount.saturating_sub(matched_fee);
-                    T::Assets::burn_from(Self::liquid_currency()?, unstaker, liquid_to_burn)?;
-                    T::Assets::transfer(
-                        Self::liquid_currency()?,
-                        unstaker,
-                        &T::ProtocolFeeReceiver::get(),
-                        matched_fee,
-                        false,
-                    )?;
-
-                    let staking_to_receive = Self::liquid_to_staking(liquid_to_burn)
-                        .ok_or(Error::<T>::InvalidExchangeRate)?;
-
-                    MatchingPool::<T>::try_mutate(|p| p.sub_stake_amount(staking_to_receive))?;
-                    T::Assets::transfer(
-                        Self::staking_currency()?,
-                        &Self::account_id(),
-                        unstaker,
-                        staking_to_receive,
-                        false,
-                    )?;
-
-                    Self::deposit_event(Event::<T>::FastUnstakeMatched(
-                        unstaker.clone(),
-                        staking_to_receive,
-                        matched_liquid_amount,
-                        matched_fee,
-                    ));
-                }
+                let matched_liquid_amount =
+                    Self::do_match_liquid_amount(unstaker, request_liquid_amount)?;
 
                 let unmatched_amount = request_liquid_amount.saturating_sub(matched_liquid_amount);
                 if !unmatched_amount.is_zero() {
@@ -2001,6 +3302,85 @@ pub mod pallet {
             })
         }
 
+        // Unstakes `liquid_amount` of `unstaker` from the matching pool at once, leaving
+        // its pending fast unstake requests untouched
+        #[require_transactional]
+        fn do_instant_match_unstake(
+            unstaker: &T::AccountId,
+            liquid_amount: BalanceOf<T, I>,
+        ) -> DispatchResult {
+            let reserves = Self::unstake_reserve_factor().mul_floor(liquid_amount);
+            TotalUnstakeReserves::<T, I>::try_mutate(|b| -> DispatchResult {
+                *b = b.checked_add(reserves).ok_or(ArithmeticError::Overflow)?;
+                Ok(())
+            })?;
+            T::Assets::transfer(
+                Self::liquid_currency()?,
+                unstaker,
+                &Self::account_id(),
+                reserves,
+                false,
+            )?;
+
+            let liquid_amount = liquid_amount
+                .checked_sub(reserves)
+                .ok_or(ArithmeticError::Underflow)?;
+            ensure!(
+                Self::do_match_liquid_amount(unstaker, liquid_amount)? == liquid_amount,
+                Error::<T, I>::NoInstantUnstakeProvider
+            );
+            Ok(())
+        }
+
+        // Matches up to `request_liquid_amount` of `unstaker` with the free stake of the
+        // matching pool and returns the matched liquid amount
+        #[require_transactional]
+        fn do_match_liquid_amount(
+            unstaker: &T::AccountId,
+            request_liquid_amount: BalanceOf<T, I>,
+        ) -> Result<BalanceOf<T, I>, DispatchError> {
+            let available_liquid_amount =
+                Self::staking_to_liquid(Self::matching_pool().total_stake_amount.free()?)
+                    .ok_or(Error::<T, I>::InvalidExchangeRate)?;
+
+            let matched_liquid_amount = request_liquid_amount.min(available_liquid_amount);
+
+            if !matched_liquid_amount.is_zero() {
+                let matched_fee =
+                    T::MatchingPoolFastUnstakeFee::get().saturating_mul_int(matched_liquid_amount);
+                let liquid_to_burn = matched_liquid_amount.saturating_sub(matched_fee);
+                T::Assets::burn_from(Self::liquid_currency()?, unstaker, liquid_to_burn)?;
+                T::Assets::transfer(
+                    Self::liquid_currency()?,
+                    unstaker,
+                    &T::ProtocolFeeReceiver::get(),
+                    matched_fee,
+                    false,
+                )?;
+
+                let staking_to_receive = Self::liquid_to_staking(liquid_to_burn)
+                    .ok_or(Error::<T, I>::InvalidExchangeRate)?;
+
+                MatchingPool::<T, I>::try_mutate(|p| p.sub_stake_amount(staking_to_receive))?;
+                T::Assets::transfer(
+                    Self::staking_currency()?,
+                    &Self::account_id(),
+                    unstaker,
+                    staking_to_receive,
+                    false,
+                )?;
+
+                Self::deposit_event(Event::<T, I>::FastUnstakeMatched(
+                    unstaker.clone(),
+                    staking_to_receive,
+                    matched_liquid_amount,
+                    matched_fee,
+                ));
+            }
+
+            Ok(matched_liquid_amount)
+        }
+
         fn ensure_origin(origin: OriginFor<T>) -> DispatchResult {
             if T::RelayOrigin::ensure_origin(origin.clone()).is_ok() {
                 return Ok(());
@@ -2012,28 +3392,37 @@ pub mod pallet {
             Ok(())
         }
 
-        fn ensure_market_cap(amount: BalanceOf<T>) -> DispatchResult {
+        // Keeps `claim_for` bounded the same way as `Unlockings`
+        fn ensure_receipts_limit(owner: &T::AccountId) -> DispatchResult {
+            ensure!(
+                UnstakeReceiptsOf::<T, I>::iter_key_prefix(owner).count() < MAX_UNLOCKING_CHUNKS,
+                Error::<T, I>::NoMoreChunks
+            );
+            Ok(())
+        }
+
+        fn ensure_market_cap(amount: BalanceOf<T, I>) -> DispatchResult {
             ensure!(
                 Self::get_total_bonded().saturating_add(amount) <= Self::get_market_cap(),
-                Error::<T>::CapExceeded
+                Error::<T, I>::CapExceeded
             );
             Ok(())
         }
 
         fn ensure_staking_ledger_cap(
             derivative_index: DerivativeIndex,
-            amount: BalanceOf<T>,
+            amount: BalanceOf<T, I>,
         ) -> DispatchResult {
             ensure!(
                 Self::total_bonded_of(derivative_index).saturating_add(amount)
                     <= Self::staking_ledger_cap(),
-                Error::<T>::CapExceeded
+                Error::<T, I>::CapExceeded
             );
             Ok(())
         }
 
-        fn notify_placeholder() -> <T as Config>::RuntimeCall {
-            <T as Config>::RuntimeCall::from(Call::<T>::notification_received {
+        fn notify_placeholder() -> <T as Config<I>>::RuntimeCall {
+            <T as Config<I>>::RuntimeCall::from(Call::<T, I>::notification_received {
                 query_id: Default::default(),
                 response: Default::default(),
             })
@@ -2085,20 +3474,67 @@ pub mod pallet {
             final_key
         }
 
+        pub(crate) fn get_account_key(who: &T::AccountId) -> Vec<u8> {
+            let storage_prefix = storage_prefix("System".as_bytes(), "Account".as_bytes());
+            let mut final_key = storage_prefix.to_vec();
+            final_key.extend_from_slice(who.using_encoded(Blake2_128Concat::hash).as_ref());
+            final_key
+        }
+
         pub(crate) fn get_current_era_key() -> Vec<u8> {
             storage_prefix("Staking".as_bytes(), "CurrentEra".as_bytes()).to_vec()
         }
+
+        pub(crate) fn get_active_era_key() -> Vec<u8> {
+            storage_prefix("Staking".as_bytes(), "ActiveEra".as_bytes()).to_vec()
+        }
+
+        pub(crate) fn get_validator_prefs_key(validator: &T::AccountId) -> Vec<u8> {
+            let storage_prefix = storage_prefix("Staking".as_bytes(), "Validators".as_bytes());
+            let mut final_key = storage_prefix.to_vec();
+            final_key.extend_from_slice(validator.using_encoded(Twox64Concat::hash).as_ref());
+            final_key
+        }
+
+        pub(crate) fn get_eras_stakers_key(era: EraIndex, validator: &T::AccountId) -> Vec<u8> {
+            let storage_prefix = storage_prefix("Staking".as_bytes(), "ErasStakers".as_bytes());
+            let mut final_key = storage_prefix.to_vec();
+            final_key.extend_from_slice(era.using_encoded(Twox64Concat::hash).as_ref());
+            final_key.extend_from_slice(validator.using_encoded(Twox64Concat::hash).as_ref());
+            final_key
+        }
+
+        pub(crate) fn get_eras_reward_points_key(era: EraIndex) -> Vec<u8> {
+            let storage_prefix =
+                storage_prefix("Staking".as_bytes(), "ErasRewardPoints".as_bytes());
+            let mut final_key = storage_prefix.to_vec();
+            final_key.extend_from_slice(era.using_encoded(Twox64Concat::hash).as_ref());
+            final_key
+        }
     }
 }
 
-impl<T: Config> ExchangeRateProvider<AssetIdOf<T>> for Pallet<T> {
-    fn get_exchange_rate(_: &AssetIdOf<T>) -> Option<Rate> {
-        Some(ExchangeRate::<T>::get())
+/// Target weights of the weighted distribution strategy, read from `DistributionWeights`
+pub struct DistributionWeightsOf<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> Get<Vec<(DerivativeIndex, u32)>> for DistributionWeightsOf<T, I> {
+    fn get() -> Vec<(DerivativeIndex, u32)> {
+        DistributionWeights::<T, I>::iter().collect()
+    }
+}
+
+impl<T: Config<I>, I: 'static> ExchangeRateProvider<AssetIdOf<T, I>> for Pallet<T, I> {
+    fn get_exchange_rate(asset_id: &AssetIdOf<T, I>) -> Option<Rate> {
+        if asset_id == &T::LiquidCurrency::get() {
+            Some(ExchangeRate::<T, I>::get())
+        } else {
+            None
+        }
     }
 }
 
-impl<T: Config> LiquidStakingCurrenciesProvider<AssetIdOf<T>> for Pallet<T> {
-    fn get_staking_currency() -> Option<AssetIdOf<T>> {
+impl<T: Config<I>, I: 'static> LiquidStakingCurrenciesProvider<AssetIdOf<T, I>> for Pallet<T, I> {
+    fn get_staking_currency() -> Option<AssetIdOf<T, I>> {
         let asset_id = T::StakingCurrency::get();
         if T::Decimal::get_decimal(&asset_id).is_some() {
             Some(asset_id)
@@ -2107,7 +3543,7 @@ impl<T: Config> LiquidStakingCurrenciesProvider<AssetIdOf<T>> for Pallet<T> {
         }
     }
 
-    fn get_liquid_currency() -> Option<AssetIdOf<T>> {
+    fn get_liquid_currency() -> Option<AssetIdOf<T, I>> {
         let asset_id = T::LiquidCurrency::get();
         if T::Decimal::get_decimal(&asset_id).is_some() {
             Some(asset_id)
@@ -2117,7 +3553,9 @@ impl<T: Config> LiquidStakingCurrenciesProvider<AssetIdOf<T>> for Pallet<T> {
     }
 }
 
-impl<T: Config, Balance: BalanceT + FixedPointOperand> LiquidStakingConvert<Balance> for Pallet<T> {
+impl<T: Config<I>, I: 'static, Balance: BalanceT + FixedPointOperand> LiquidStakingConvert<Balance>
+    for Pallet<T, I>
+{
     fn staking_to_liquid(amount: Balance) -> Option<Balance> {
         Self::exchange_rate()
             .reciprocal()
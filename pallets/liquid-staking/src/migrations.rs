@@ -111,3 +111,104 @@ pub mod v3 {
         Ok(())
     }
 }
+
+pub mod v4 {
+    use crate::{
+        types::UnstakeReceipt, Config, NextUnstakeReceiptId, Pallet, StorageVersion, Unlockings,
+        UnstakeReceipts, UnstakeReceiptsOf, Versions,
+    };
+    use frame_support::{
+        log,
+        traits::{Get, OnRuntimeUpgrade},
+        weights::Weight,
+    };
+    use sp_std::{marker::PhantomData, vec::Vec};
+
+    #[cfg(feature = "try-runtime")]
+    pub fn pre_migrate<T: Config<I>, I: 'static>() -> Result<(), &'static str> {
+        frame_support::ensure!(
+            matches!(StorageVersion::<T, I>::get(), Versions::V2 | Versions::V3),
+            "must be V2 or V3"
+        );
+        log::info!(
+            "Unlockings to convert into unstake receipts: {:?}",
+            Unlockings::<T, I>::iter_keys()
+                .filter(|who| who != &Pallet::<T, I>::loans_account_id())
+                .count()
+        );
+        Ok(())
+    }
+
+    /// Turns the unlocking chunks of every account but the loans account into
+    /// unstake receipts
+    pub fn migrate<T: Config<I>, I: 'static>() -> Weight {
+        if !matches!(StorageVersion::<T, I>::get(), Versions::V2 | Versions::V3) {
+            return T::DbWeight::get().reads(1);
+        }
+
+        log::info!("Migrating liquidStaking to Versions::V4");
+        let loans_account = Pallet::<T, I>::loans_account_id();
+        let accounts: Vec<_> = Unlockings::<T, I>::iter_keys()
+            .filter(|who| who != &loans_account)
+            .collect();
+
+        let mut next_id = NextUnstakeReceiptId::<T, I>::get();
+        let mut reads: u64 = 2 + accounts.len() as u64;
+        let mut writes: u64 = 2;
+        for who in accounts {
+            let chunks = Unlockings::<T, I>::take(&who).unwrap_or_default();
+            writes += 1 + 2 * chunks.len() as u64;
+            for chunk in chunks {
+                UnstakeReceipts::<T, I>::insert(
+                    next_id,
+                    UnstakeReceipt {
+                        owner: who.clone(),
+                        value: chunk.value,
+                        era: chunk.era,
+                    },
+                );
+                UnstakeReceiptsOf::<T, I>::insert(&who, next_id, ());
+                next_id = next_id.saturating_add(1);
+            }
+            reads += 1;
+        }
+        NextUnstakeReceiptId::<T, I>::put(next_id);
+
+        StorageVersion::<T, I>::put(Versions::V4);
+        log::info!("👜 completed liquidStaking migration to Versions::V4");
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    pub fn post_migrate<T: Config<I>, I: 'static>() -> Result<(), &'static str> {
+        frame_support::ensure!(
+            StorageVersion::<T, I>::get() == Versions::V4,
+            "must upgrade to V4"
+        );
+        let loans_account = Pallet::<T, I>::loans_account_id();
+        frame_support::ensure!(
+            Unlockings::<T, I>::iter_keys().all(|who| who == loans_account),
+            "only the loans account can have unlockings"
+        );
+        log::info!("👜 liquidStaking migration passes POST migrate checks ✅");
+        Ok(())
+    }
+
+    pub struct MigrateToV4<T, I = ()>(PhantomData<(T, I)>);
+    impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV4<T, I> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            pre_migrate::<T, I>()?;
+            Ok(Default::default())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrate::<T, I>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            post_migrate::<T, I>()
+        }
+    }
+}
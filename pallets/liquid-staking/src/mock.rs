@@ -16,7 +16,7 @@ use orml_xcm_support::IsNativeConcrete;
 use pallet_xcm::XcmPassthrough;
 use polkadot_parachain::primitives::{IsSystem, Sibling};
 
-use pallet_loans::{InterestRateModel, JumpModel, Market, MarketState};
+use pallet_loans::{InterestRateModel, JumpModel, LiquidationMode, Market, MarketState};
 use pallet_traits::{
     xcm::MultiCurrencyAdapter, DecimalProvider, PriceFeeder, ValidationDataProvider,
 };
@@ -48,7 +48,12 @@ use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chai
 
 pub type AccountId = AccountId32;
 pub type CurrencyId = u32;
-use crate::{distribution::AverageDistribution, types::StakingLedger, BalanceOf};
+use crate::{
+    distribution::AverageDistribution,
+    nomination::EraPointsNomination,
+    types::{AccountInfo, ActiveEraInfo, StakingLedger},
+    BalanceOf, BoundedStorageProof,
+};
 pub use kusama_runtime;
 
 parameter_types! {
@@ -346,6 +351,10 @@ impl pallet_loans::Config for Test {
     type Assets = Assets;
     type RewardAssetId = RewardAssetId;
     type LiquidationFreeAssetId = LiquidationFreeAssetId;
+    type FlashLoanReceiver = ();
+    type InsuranceAccount = ();
+    type MaxBatchActions = ConstU32<8>;
+    type CollateralDustValue = frame_support::traits::ConstU128<0>;
 }
 
 parameter_types! {
@@ -394,6 +403,7 @@ type Block = frame_system::mocking::MockBlock<Test>;
 type BlockNumber = u32;
 
 pub const KSM_DECIMAL: u128 = 10u128.pow(12);
+pub const DOT_DECIMAL: u128 = 10u128.pow(10);
 
 parameter_types! {
     pub const BlockHashCount: BlockNumber = 250;
@@ -473,6 +483,7 @@ impl pallet_utility::Config for Test {
 parameter_types! {
     pub const XcmHelperPalletId: PalletId = PalletId(*b"par/fees");
     pub const NotifyTimeout: BlockNumber = 100;
+    pub RelayLocation: MultiLocation = MultiLocation::parent();
     pub RefundLocation: AccountId = para_a_id().into_account_truncating();
 }
 
@@ -483,6 +494,7 @@ impl pallet_xcm_helper::Config for Test {
     type XcmSender = XcmRouter;
     type PalletId = XcmHelperPalletId;
     type RelayNetwork = RelayNetwork;
+    type RelayLocation = RelayLocation;
     type NotifyTimeout = NotifyTimeout;
     type AccountIdToMultiLocation = AccountIdToMultiLocation;
     type RefundLocation = RefundLocation;
@@ -491,6 +503,26 @@ impl pallet_xcm_helper::Config for Test {
     type RelayCurrency = StakingCurrency;
 }
 
+parameter_types! {
+    pub const XcmHelperPalletId2: PalletId = PalletId(*b"par/fee2");
+}
+
+impl pallet_xcm_helper::Config<pallet_xcm_helper::Instance2> for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type UpdateOrigin = UpdateOrigin;
+    type Assets = Assets;
+    type XcmSender = XcmRouter;
+    type PalletId = XcmHelperPalletId2;
+    type RelayNetwork = RelayNetwork;
+    type RelayLocation = RelayLocation;
+    type NotifyTimeout = NotifyTimeout;
+    type AccountIdToMultiLocation = AccountIdToMultiLocation;
+    type RefundLocation = RefundLocation;
+    type BlockNumberProvider = frame_system::Pallet<Test>;
+    type WeightInfo = ();
+    type RelayCurrency = StakingCurrency2;
+}
+
 impl BlockNumberProvider for RelayChainValidationDataProvider {
     type BlockNumber = BlockNumber;
 
@@ -504,9 +536,7 @@ impl ValidationDataProvider for RelayChainValidationDataProvider {
         Some(PersistedValidationData {
             parent_head: Default::default(),
             relay_parent_number: 100,
-            relay_parent_storage_root: sp_core::hash::H256::from_slice(
-                &hex::decode(ROOT_HASH).unwrap(),
-            ),
+            relay_parent_storage_root: RelayParentStorageRoot::get(),
             max_pov_size: Default::default(),
         })
     }
@@ -537,6 +567,72 @@ pub fn get_mock_proof_bytes() -> Vec<Vec<u8>> {
     ].to_vec()
 }
 
+// Builds a relaychain storage containing only `Staking.ActiveEra`
+// and returns its root together with the proof of that key
+pub fn get_mock_active_era_proof(active_era: &ActiveEraInfo) -> (H256, Vec<Vec<u8>>) {
+    get_mock_storage_proof(&LiquidStaking::get_active_era_key(), &active_era.encode())
+}
+
+// Same as `get_mock_active_era_proof`, for both the `System.Account` and the
+// `Staking.Ledger` of the stash of `derivative_index`
+pub fn get_mock_stash_proof(
+    derivative_index: u16,
+    account: &AccountInfo<Balance>,
+    staking_ledger: &StakingLedger<AccountId, Balance>,
+) -> (H256, Vec<Vec<u8>>) {
+    get_mock_storage_proof_of(&[
+        (
+            LiquidStaking::get_account_key(&LiquidStaking::derivative_sovereign_account_id(
+                derivative_index,
+            )),
+            account.encode(),
+        ),
+        (
+            LiquidStaking::get_staking_ledger_key(derivative_index),
+            staking_ledger.encode(),
+        ),
+    ])
+}
+
+// Same as `get_mock_active_era_proof`, for the `Staking.Ledger` of `derivative_index`
+pub fn get_mock_staking_ledger_proof(
+    derivative_index: u16,
+    staking_ledger: &StakingLedger<AccountId, Balance>,
+) -> (H256, Vec<Vec<u8>>) {
+    get_mock_storage_proof(
+        &LiquidStaking::get_staking_ledger_key(derivative_index),
+        &staking_ledger.encode(),
+    )
+}
+
+fn get_mock_storage_proof(key: &[u8], value: &[u8]) -> (H256, Vec<Vec<u8>>) {
+    get_mock_storage_proof_of(&[(key.to_vec(), value.to_vec())])
+}
+
+fn get_mock_storage_proof_of(entries: &[(Vec<u8>, Vec<u8>)]) -> (H256, Vec<Vec<u8>>) {
+    use sp_trie::{LayoutV1, MemoryDB, TrieDBMutBuilder, TrieMut};
+
+    let mut db = MemoryDB::<BlakeTwo256>::default();
+    let mut root = Default::default();
+    {
+        let mut trie = TrieDBMutBuilder::<LayoutV1<BlakeTwo256>>::new(&mut db, &mut root).build();
+        for (key, value) in entries {
+            trie.insert(key, value).unwrap();
+        }
+    }
+    let proof = db.drain().into_values().map(|(node, _)| node).collect();
+    (root, proof)
+}
+
+pub fn bounded_proof(proof: Vec<Vec<u8>>) -> BoundedStorageProof {
+    proof
+        .into_iter()
+        .map(|node| node.try_into().unwrap())
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
 pub fn get_mock_staking_ledger(derivative_index: u16) -> StakingLedger<AccountId, BalanceOf<Test>> {
     let mut staking_ledger = <StakingLedger<AccountId, BalanceOf<Test>>>::new(
         LiquidStaking::derivative_sovereign_account_id(derivative_index),
@@ -558,7 +654,7 @@ parameter_types! {
     pub const EraLength: BlockNumber = 10;
     pub SelfParaId: ParaId = para_a_id();
     pub const MinStake: Balance = 0;
-    pub const MinUnstake: Balance = 0;
+    pub static MinUnstake: Balance = 0;
     pub const StakingCurrency: CurrencyId = KSM;
     pub const LiquidCurrency: CurrencyId = SKSM;
     pub const CollateralCurrency: CurrencyId = KSM_U;
@@ -570,8 +666,17 @@ parameter_types! {
     pub const NumSlashingSpans: u32 = 0;
     pub static DerivativeIndexList: Vec<u16> = vec![0];
     pub static RelayChainValidationDataProvider: BlockNumber = 0;
+    pub static RelayParentStorageRoot: H256 =
+        H256::from_slice(&hex::decode(ROOT_HASH).unwrap());
     pub const ElectionSolutionStoredOffset: BlockNumber = 10;
     pub const DefaultProtocolFeeReceiver: AccountId32 = AccountId32::new([100u8; 32]);
+    pub const MaxValidators: u32 = 16;
+    pub const MaxNominations: u32 = 4;
+    pub const EraPointsDepth: u32 = 4;
+    pub const ExchangeRateHistoryDepth: u32 = 4;
+    pub const EraAdvanceBounty: Balance = 1_000_000_000;
+    pub const MaxPayoutsPerBlock: u32 = 1;
+    pub const MaxPendingPayouts: u32 = 3;
 }
 
 impl crate::Config for Test {
@@ -606,6 +711,68 @@ impl crate::Config for Test {
     type ElectionSolutionStoredOffset = ElectionSolutionStoredOffset;
     type ProtocolFeeReceiver = DefaultProtocolFeeReceiver;
     type Decimal = Decimal;
+    type NominationStrategy = EraPointsNomination;
+    type MaxValidators = MaxValidators;
+    type MaxNominations = MaxNominations;
+    type EraPointsDepth = EraPointsDepth;
+    type ExchangeRateHistoryDepth = ExchangeRateHistoryDepth;
+    type EraAdvanceBounty = EraAdvanceBounty;
+    type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+    type MaxPendingPayouts = MaxPendingPayouts;
+    type AMM = DefaultAMM;
+    type StableSwap = DefaultStableSwap;
+}
+
+parameter_types! {
+    pub const StakingPalletId2: PalletId = PalletId(*b"par/lqs2");
+    pub const StakingCurrency2: CurrencyId = DOT;
+    pub const LiquidCurrency2: CurrencyId = SDOT;
+    pub const CollateralCurrency2: CurrencyId = DOT_U;
+    pub static DerivativeIndexList2: Vec<u16> = vec![1];
+}
+
+impl crate::Config<crate::Instance2> for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type UpdateOrigin = UpdateOrigin;
+    type PalletId = StakingPalletId2;
+    type LoansPalletId = LoansPalletId;
+    type SelfParaId = SelfParaId;
+    type WeightInfo = ();
+    type StakingCurrency = StakingCurrency2;
+    type LiquidCurrency = LiquidCurrency2;
+    type CollateralCurrency = CollateralCurrency2;
+    type DerivativeIndexList = DerivativeIndexList2;
+    type XcmFees = XcmFees;
+    type LoansInstantUnstakeFee = LoansInstantUnstakeFee;
+    type MatchingPoolFastUnstakeFee = MatchingPoolFastUnstakeFee;
+    type Assets = CurrencyAdapter;
+    type RelayOrigin = RelayOrigin;
+    type EraLength = EraLength;
+    type MinStake = MinStake;
+    type MinUnstake = MinUnstake;
+    type XCM = XcmHelper2;
+    type BondingDuration = BondingDuration;
+    type MinNominatorBond = MinNominatorBond;
+    type RelayChainValidationDataProvider = RelayChainValidationDataProvider;
+    type Loans = Loans;
+    type Members = BobOrigin;
+    type NumSlashingSpans = NumSlashingSpans;
+    type DistributionStrategy = AverageDistribution;
+    type ElectionSolutionStoredOffset = ElectionSolutionStoredOffset;
+    type ProtocolFeeReceiver = DefaultProtocolFeeReceiver;
+    type Decimal = Decimal;
+    type NominationStrategy = EraPointsNomination;
+    type MaxValidators = MaxValidators;
+    type MaxNominations = MaxNominations;
+    type EraPointsDepth = EraPointsDepth;
+    type ExchangeRateHistoryDepth = ExchangeRateHistoryDepth;
+    type EraAdvanceBounty = EraAdvanceBounty;
+    type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+    type MaxPendingPayouts = MaxPendingPayouts;
+    type AMM = DefaultAMM;
+    type StableSwap = DefaultStableSwap;
 }
 
 pub struct Decimal;
@@ -658,6 +825,60 @@ impl pallet_assets::Config for Test {
     type BenchmarkHelper = ();
 }
 
+parameter_types! {
+    pub const AMMPalletId: PalletId = PalletId(*b"par/ammp");
+    pub DefaultLpFee: Ratio = Ratio::from_rational(25u32, 10000u32);
+    pub const MinimumLiquidity: u128 = 1_000u128;
+    pub const MaxObservations: u32 = 4;
+    pub const ObservationPeriod: BlockNumber = 10;
+    pub const MaxTicks: u32 = 8;
+    pub const MinPositionLiquidity: u128 = 1_000_000u128;
+    pub const LockAccountId: AccountId = ALICE;
+    pub const MaxLengthRoute: u8 = 10;
+}
+
+impl pallet_amm::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Assets = CurrencyAdapter;
+    type PalletId = AMMPalletId;
+    type LockAccountId = LockAccountId;
+    type AMMWeightInfo = ();
+    type CreatePoolOrigin = EnsureSignedBy<AliceOrigin, AccountId>;
+    type ProtocolFeeUpdateOrigin = EnsureSignedBy<AliceOrigin, AccountId>;
+    type LpFee = DefaultLpFee;
+    type MinimumLiquidity = MinimumLiquidity;
+    type MaxLengthRoute = MaxLengthRoute;
+    type MaxObservations = MaxObservations;
+    type ObservationPeriod = ObservationPeriod;
+    type MaxTicks = MaxTicks;
+    type MinPositionLiquidity = MinPositionLiquidity;
+    type GetNativeCurrencyId = NativeCurrencyId;
+}
+
+parameter_types! {
+    pub const StableSwapPalletId: PalletId = PalletId(*b"par/sswp");
+    pub DefaultProtocolFee: Ratio = Ratio::from_rational(5u32, 10000u32);
+    pub const NumTokens: u8 = 2;
+    pub const Precision: u32 = 100;
+    pub const AmplificationCoefficient: u8 = 85;
+}
+
+impl pallet_stableswap::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Assets = CurrencyAdapter;
+    type WeightInfo = ();
+    type PalletId = StableSwapPalletId;
+    type NumTokens = NumTokens;
+    type Precision = Precision;
+    type AmplificationCoefficient = AmplificationCoefficient;
+    type ProtocolFeeReceiver = DefaultProtocolFeeReceiver;
+    type LpFee = DefaultLpFee;
+    type LockAccountId = LockAccountId;
+    type ProtocolFee = DefaultProtocolFee;
+    type MinimumLiquidity = MinimumLiquidity;
+    type CreatePoolOrigin = EnsureSignedBy<AliceOrigin, AccountId>;
+}
+
 impl pallet_currency_adapter::Config for Test {
     type Assets = Assets;
     type Balances = Balances;
@@ -676,6 +897,7 @@ construct_runtime!(
         Utility: pallet_utility::{Pallet, Call, Event},
         Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
         LiquidStaking: crate::{Pallet, Storage, Call, Event<T>},
+        LiquidStaking2: crate::<Instance2>::{Pallet, Storage, Call, Event<T>},
         ParachainSystem: cumulus_pallet_parachain_system::{Pallet, Call, Config, Storage, Inherent, Event<T>},
         ParachainInfo: parachain_info::{Pallet, Storage, Config},
         XcmpQueue: cumulus_pallet_xcmp_queue::{Pallet, Call, Storage, Event<T>},
@@ -683,10 +905,13 @@ construct_runtime!(
         CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin},
         PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
         XcmHelper: pallet_xcm_helper::{Pallet, Storage, Call, Event<T>},
+        XcmHelper2: pallet_xcm_helper::<Instance2>::{Pallet, Storage, Call, Event<T>},
         XTokens: orml_xtokens::{Pallet, Storage, Call, Event<T>},
         Loans: pallet_loans::{Pallet, Storage, Call, Event<T>},
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
         CurrencyAdapter: pallet_currency_adapter::{Pallet, Call},
+        DefaultAMM: pallet_amm::{Pallet, Call, Storage, Event<T>},
+        DefaultStableSwap: pallet_stableswap::{Pallet, Call, Storage, Event<T>},
     }
 );
 
@@ -707,6 +932,14 @@ pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
         &mut t,
     )
     .unwrap();
+    GenesisBuild::<Test, crate::Instance2>::assimilate_storage(
+        &crate::GenesisConfig {
+            exchange_rate: Rate::one(),
+            reserve_factor: RESERVE_FACTOR,
+        },
+        &mut t,
+    )
+    .unwrap();
 
     let mut ext = sp_io::TestExternalities::new(t);
     ext.execute_with(|| {
@@ -773,6 +1006,26 @@ pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
         )
         .unwrap();
 
+        Assets::force_create(RuntimeOrigin::root(), DOT.into(), Id(ALICE), true, 1).unwrap();
+        Assets::force_create(RuntimeOrigin::root(), SDOT.into(), Id(ALICE), true, 1).unwrap();
+        Assets::force_create(RuntimeOrigin::root(), DOT_U.into(), Id(ALICE), true, 1).unwrap();
+        Assets::mint(
+            RuntimeOrigin::signed(ALICE),
+            DOT.into(),
+            Id(ALICE),
+            dot(100f64),
+        )
+        .unwrap();
+        Assets::mint(
+            RuntimeOrigin::signed(ALICE),
+            DOT.into(),
+            Id(XcmHelper2::account_id()),
+            dot(100f64),
+        )
+        .unwrap();
+        LiquidStaking2::update_staking_ledger_cap(RuntimeOrigin::signed(BOB), dot(10000f64))
+            .unwrap();
+
         Loans::add_market(RuntimeOrigin::root(), KSM, market_mock(PKSM)).unwrap();
         Loans::activate_market(RuntimeOrigin::root(), KSM).unwrap();
         Loans::add_market(RuntimeOrigin::root(), KSM_U, market_mock(PKSM_U)).unwrap();
@@ -949,6 +1202,10 @@ pub fn ksm(n: f64) -> Balance {
     ((n * 1000000f64) as u128) * KSM_DECIMAL / 1000000u128
 }
 
+pub fn dot(n: f64) -> Balance {
+    ((n * 1000000f64) as u128) * DOT_DECIMAL / 1000000u128
+}
+
 pub const fn market_mock(ptoken_id: u32) -> Market<Balance> {
     Market {
         close_factor: Ratio::from_percent(50),
@@ -967,5 +1224,10 @@ pub const fn market_mock(ptoken_id: u32) -> Market<Balance> {
         supply_cap: 1_000_000_000_000_000_000_000u128, // set to 1B
         borrow_cap: 1_000_000_000_000_000_000_000u128, // set to 1B
         ptoken_id,
+        isolated: false,
+        debt_ceiling: 0,
+        borrowable_in_isolation: false,
+        flash_loan_fee: Ratio::from_parts(900),
+        liquidation_mode: LiquidationMode::Instant,
     }
 }
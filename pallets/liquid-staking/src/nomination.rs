@@ -0,0 +1,64 @@
+use frame_support::traits::tokens::Balance as BalanceT;
+use pallet_traits::{NominationStrategy, ValidatorInfo};
+use primitives::DerivativeIndex;
+use sp_runtime::FixedPointOperand;
+use sp_std::vec::Vec;
+
+/// Nominates the validators with the most reward points on average, the lower
+/// commission and then the higher self stake first. Blocked validators are skipped.
+///
+/// Every derivative index gets its own slice of the ranking so that the stake is spread
+/// over as many validators as possible.
+pub struct EraPointsNomination;
+impl<AccountId: Ord + Clone, Balance: BalanceT + FixedPointOperand>
+    NominationStrategy<AccountId, Balance> for EraPointsNomination
+{
+    fn get_nominations(
+        validators: Vec<(AccountId, ValidatorInfo<Balance>)>,
+        derivative_indexes: Vec<DerivativeIndex>,
+        max_nominations: u32,
+    ) -> Vec<(DerivativeIndex, Vec<AccountId>)> {
+        let mut candidates: Vec<(AccountId, u32, ValidatorInfo<Balance>)> = validators
+            .into_iter()
+            .filter(|(_, info)| !info.blocked)
+            .map(|(validator, info)| (validator, average_era_points(&info), info))
+            .collect();
+        let count = candidates.len();
+        let per_index = (max_nominations as usize).min(count);
+        if per_index == 0 {
+            return Default::default();
+        }
+
+        // descending sequence, ties are broken by the account to keep it deterministic
+        candidates.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| a.2.commission.cmp(&b.2.commission))
+                .then_with(|| b.2.self_stake.cmp(&a.2.self_stake))
+                .then_with(|| a.0.cmp(&b.0))
+        });
+
+        derivative_indexes
+            .into_iter()
+            .enumerate()
+            .map(|(i, index)| {
+                let targets = (0..per_index)
+                    .map(|j| candidates[(i * per_index + j) % count].0.clone())
+                    .collect();
+                (index, targets)
+            })
+            .collect()
+    }
+}
+
+fn average_era_points<Balance>(info: &ValidatorInfo<Balance>) -> u32 {
+    let eras = info.era_points.len() as u64;
+    if eras == 0 {
+        return 0;
+    }
+    let total: u64 = info
+        .era_points
+        .iter()
+        .map(|(_, points)| *points as u64)
+        .sum();
+    (total / eras) as u32
+}
@@ -1,5 +1,5 @@
 use frame_support::{
-    assert_noop, assert_ok,
+    assert_noop, assert_ok, bounded_vec,
     dispatch::DispatchResult,
     error::BadOrigin,
     storage::with_transaction,
@@ -14,9 +14,12 @@ use sp_runtime::{
 use sp_trie::StorageProof;
 use xcm_simulator::TestExt;
 
-use pallet_traits::ump::RewardDestination;
+use pallet_traits::{
+    ump::{EraRewardPoints, RewardDestination},
+    PendingUnstake, StableSwap, ValidatorInfo, AMM,
+};
 use primitives::{
-    tokens::{KSM, SKSM},
+    tokens::{DOT, KSM, LP_KSM_SKSM, SDOT, SKSM},
     Balance, Rate, Ratio,
 };
 
@@ -163,11 +166,12 @@ fn unstake_should_work() {
         );
 
         assert_eq!(
-            Unlockings::<Test>::get(ALICE).unwrap(),
-            vec![UnlockChunk {
+            LiquidStaking::unstake_receipt(0).unwrap(),
+            UnstakeReceipt {
+                owner: ALICE,
                 value: ksm(6f64),
                 era: 4
-            }]
+            }
         );
 
         assert_ok!(with_transaction(
@@ -212,16 +216,27 @@ fn unstake_should_work() {
         ));
 
         assert_eq!(
-            Unlockings::<Test>::get(ALICE).unwrap(),
+            LiquidStaking::unstake_receipt(1).unwrap(),
+            UnstakeReceipt {
+                owner: ALICE,
+                value: ksm(3.95f64),
+                era: 5
+            }
+        );
+        assert_eq!(UnstakeReceiptsOf::<Test>::iter_key_prefix(ALICE).count(), 2);
+        assert_eq!(
+            LiquidStaking::pending_unstakes(&ALICE).unstakes,
             vec![
-                UnlockChunk {
+                PendingUnstake {
                     value: ksm(6f64),
-                    era: 4
+                    claimable_era: 4,
+                    receipt_id: Some(0)
                 },
-                UnlockChunk {
+                PendingUnstake {
                     value: ksm(3.95f64),
-                    era: 5
-                }
+                    claimable_era: 5,
+                    receipt_id: Some(1)
+                },
             ]
         );
 
@@ -722,13 +737,16 @@ fn claim_for_should_work() {
             ksm(3.95f64),
             Default::default()
         ));
+        // Unstakes of the same era top up the same receipt
         assert_eq!(
-            Unlockings::<Test>::get(ALICE).unwrap(),
-            vec![UnlockChunk {
+            LiquidStaking::unstake_receipt(0).unwrap(),
+            UnstakeReceipt {
+                owner: ALICE,
                 value: ksm(4.95f64),
                 era: 4
-            },]
+            }
         );
+        assert!(LiquidStaking::unstake_receipt(1).is_none());
 
         assert_noop!(
             LiquidStaking::claim_for(RuntimeOrigin::signed(BOB), Id(ALICE)),
@@ -768,7 +786,202 @@ fn claim_for_should_work() {
             ksm(90f64) + ksm(4.95f64)
         );
 
+        assert!(LiquidStaking::unstake_receipt(0).is_none());
+        assert_noop!(
+            LiquidStaking::claim_for(RuntimeOrigin::signed(BOB), Id(ALICE)),
+            Error::<Test>::NoUnlockings
+        );
+    })
+}
+
+#[test]
+fn transfer_unstake_receipt_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(LiquidStaking::stake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(10f64)
+        ));
+        assert_ok!(LiquidStaking::unstake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(4.95f64),
+            Default::default()
+        ));
+
+        assert_noop!(
+            LiquidStaking::transfer_unstake_receipt(RuntimeOrigin::signed(BOB), 0, Id(BOB)),
+            Error::<Test>::NotReceiptOwner
+        );
+        assert_noop!(
+            LiquidStaking::transfer_unstake_receipt(RuntimeOrigin::signed(ALICE), 1, Id(BOB)),
+            Error::<Test>::UnstakeReceiptNotFound
+        );
+        assert_ok!(LiquidStaking::transfer_unstake_receipt(
+            RuntimeOrigin::signed(ALICE),
+            0,
+            Id(BOB)
+        ));
+        // The receipt stays with the sender until the receiver accepts it
+        assert_eq!(LiquidStaking::pending_receipt_transfer(0), Some(BOB));
+        assert_eq!(LiquidStaking::unstake_receipt(0).unwrap().owner, ALICE);
+        assert_noop!(
+            LiquidStaking::accept_unstake_receipt(RuntimeOrigin::signed(ALICE), 0),
+            Error::<Test>::NoPendingReceiptTransfer
+        );
+        assert_ok!(LiquidStaking::accept_unstake_receipt(
+            RuntimeOrigin::signed(BOB),
+            0
+        ));
+        assert!(LiquidStaking::pending_receipt_transfer(0).is_none());
+        assert_eq!(LiquidStaking::unstake_receipt(0).unwrap().owner, BOB);
+        assert!(UnstakeReceiptsOf::<Test>::get(ALICE, 0).is_none());
+        assert!(UnstakeReceiptsOf::<Test>::get(BOB, 0).is_some());
+
+        let derivative_index = 0u16;
+        assert_ok!(with_transaction(
+            || -> TransactionOutcome<DispatchResult> {
+                assert_ok!(LiquidStaking::do_advance_era(4));
+                assert_ok!(LiquidStaking::do_matching());
+                TransactionOutcome::Commit(Ok(()))
+            }
+        ));
+        assert_ok!(LiquidStaking::notification_received(
+            pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+            0,
+            Response::ExecutionResult(None),
+        ));
+        assert_ok!(LiquidStaking::withdraw_unbonded(
+            RuntimeOrigin::root(),
+            derivative_index,
+            0
+        ));
+        assert_ok!(LiquidStaking::notification_received(
+            pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+            1,
+            Response::ExecutionResult(None),
+        ));
+
+        // The staking assets go to the holder of the receipt
+        assert_noop!(
+            LiquidStaking::claim_for(RuntimeOrigin::signed(BOB), Id(ALICE)),
+            Error::<Test>::NoUnlockings
+        );
+        let bob_balance = <Test as Config>::Assets::balance(KSM, &BOB);
+        assert_ok!(LiquidStaking::claim_for(
+            RuntimeOrigin::signed(BOB),
+            Id(BOB)
+        ));
+        assert_eq!(
+            <Test as Config>::Assets::balance(KSM, &BOB),
+            bob_balance + ksm(4.95f64)
+        );
+    })
+}
+
+#[test]
+fn transfer_unstake_receipt_should_not_work_below_min_unstake() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(LiquidStaking::stake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(10f64)
+        ));
+        assert_ok!(LiquidStaking::unstake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(1f64),
+            Default::default()
+        ));
+
+        MinUnstake::set(ksm(2f64));
+        assert_noop!(
+            LiquidStaking::transfer_unstake_receipt(RuntimeOrigin::signed(ALICE), 0, Id(BOB)),
+            Error::<Test>::UnstakeReceiptTooSmall
+        );
+    })
+}
+
+#[test]
+fn unstake_should_not_top_up_receipt_being_transferred() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(LiquidStaking::stake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(10f64)
+        ));
+        assert_ok!(LiquidStaking::unstake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(1f64),
+            Default::default()
+        ));
+        assert_ok!(LiquidStaking::transfer_unstake_receipt(
+            RuntimeOrigin::signed(ALICE),
+            0,
+            Id(BOB)
+        ));
+        assert_ok!(LiquidStaking::unstake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(2f64),
+            Default::default()
+        ));
+
+        assert_eq!(LiquidStaking::unstake_receipt(0).unwrap().value, ksm(1f64));
+        assert_eq!(
+            LiquidStaking::unstake_receipt(1).unwrap(),
+            UnstakeReceipt {
+                owner: ALICE,
+                value: ksm(2f64),
+                era: 4
+            }
+        );
+    })
+}
+
+#[test]
+fn migrate_unlockings_to_unstake_receipts_should_work() {
+    new_test_ext().execute_with(|| {
+        let loans_chunks = vec![UnlockChunk {
+            value: ksm(1f64),
+            era: 3,
+        }];
+        Unlockings::<Test>::insert(LiquidStaking::loans_account_id(), loans_chunks.clone());
+        Unlockings::<Test>::insert(
+            ALICE,
+            vec![
+                UnlockChunk {
+                    value: ksm(2f64),
+                    era: 3,
+                },
+                UnlockChunk {
+                    value: ksm(3f64),
+                    era: 4,
+                },
+            ],
+        );
+        StorageVersion::<Test>::put(Versions::V3);
+
+        migrations::v4::migrate::<Test, ()>();
+
+        assert_eq!(StorageVersion::<Test>::get(), Versions::V4);
         assert!(Unlockings::<Test>::get(ALICE).is_none());
+        assert_eq!(
+            Unlockings::<Test>::get(LiquidStaking::loans_account_id()),
+            Some(loans_chunks)
+        );
+        assert_eq!(
+            LiquidStaking::unstake_receipt(0).unwrap(),
+            UnstakeReceipt {
+                owner: ALICE,
+                value: ksm(2f64),
+                era: 3
+            }
+        );
+        assert_eq!(
+            LiquidStaking::unstake_receipt(1).unwrap(),
+            UnstakeReceipt {
+                owner: ALICE,
+                value: ksm(3f64),
+                era: 4
+            }
+        );
+        assert_eq!(UnstakeReceiptsOf::<Test>::iter_key_prefix(ALICE).count(), 2);
+        assert_eq!(LiquidStaking::next_unstake_receipt_id(), 2);
     })
 }
 
@@ -841,7 +1054,7 @@ fn test_set_staking_ledger_work() {
                 RuntimeOrigin::signed(ALICE),
                 derivative_index,
                 staking_ledger.clone(),
-                get_mock_proof_bytes()
+                bounded_proof(get_mock_proof_bytes())
             ),
             Error::<Test>::NotBonded
         );
@@ -856,7 +1069,7 @@ fn test_set_staking_ledger_work() {
                 RuntimeOrigin::signed(ALICE),
                 derivative_index,
                 staking_ledger.clone(),
-                get_mock_proof_bytes()
+                bounded_proof(get_mock_proof_bytes())
             ),
             Error::<Test>::InvalidProof
         );
@@ -865,7 +1078,7 @@ fn test_set_staking_ledger_work() {
             RuntimeOrigin::signed(ALICE),
             derivative_index,
             get_mock_staking_ledger(derivative_index),
-            get_mock_proof_bytes()
+            bounded_proof(get_mock_proof_bytes())
         ));
 
         assert_noop!(
@@ -873,7 +1086,7 @@ fn test_set_staking_ledger_work() {
                 RuntimeOrigin::signed(ALICE),
                 derivative_index,
                 staking_ledger.clone(),
-                get_mock_proof_bytes()
+                bounded_proof(get_mock_proof_bytes())
             ),
             Error::<Test>::StakingLedgerLocked
         );
@@ -1188,7 +1401,7 @@ fn test_charge_commission_work() {
             RuntimeOrigin::signed(ALICE),
             derivative_index,
             get_mock_staking_ledger(derivative_index),
-            get_mock_proof_bytes()
+            bounded_proof(get_mock_proof_bytes())
         ));
 
         assert_eq!(
@@ -1205,6 +1418,151 @@ fn test_charge_commission_work() {
     })
 }
 
+#[test]
+fn test_set_staking_ledger_with_slash_work() {
+    new_test_ext().execute_with(|| {
+        let derivative_index = 0u16;
+        let slashed_amount = ksm(2f64);
+        let staking_ledger = <StakingLedger<AccountId, BalanceOf<Test>>>::new(
+            LiquidStaking::derivative_sovereign_account_id(derivative_index),
+            MOCK_LEDGER_AMOUNT + slashed_amount,
+        );
+        StakingLedgers::<Test>::insert(derivative_index, staking_ledger);
+        TotalReserves::<Test>::put(ksm(1f64));
+        let exchange_rate = LiquidStaking::exchange_rate();
+        LiquidStaking::on_finalize(1);
+
+        assert_ok!(LiquidStaking::set_staking_ledger(
+            RuntimeOrigin::signed(ALICE),
+            derivative_index,
+            get_mock_staking_ledger(derivative_index),
+            bounded_proof(get_mock_proof_bytes())
+        ));
+
+        let current_era = LiquidStaking::current_era();
+        assert_eq!(
+            LiquidStaking::slashes(current_era, derivative_index),
+            SlashRecord {
+                amount: slashed_amount,
+                covered_by_reserves: ksm(1f64),
+            }
+        );
+        assert_eq!(LiquidStaking::total_reserves(), 0);
+        assert_eq!(
+            LiquidStaking::matching_pool().total_stake_amount.total,
+            ksm(1f64)
+        );
+        assert_eq!(
+            LiquidStaking::staking_ledger(derivative_index)
+                .unwrap()
+                .total,
+            MOCK_LEDGER_AMOUNT
+        );
+        assert!(LiquidStaking::exchange_rate() <= exchange_rate);
+        // No commission is charged for a slash
+        assert_eq!(
+            <Test as Config>::Assets::balance(SKSM, &DefaultProtocolFeeReceiver::get()),
+            0
+        );
+    })
+}
+
+#[test]
+fn test_set_staking_ledger_with_rewards_and_slash_work() {
+    new_test_ext().execute_with(|| {
+        let derivative_index = 0u16;
+        let mut staking_ledger = <StakingLedger<AccountId, BalanceOf<Test>>>::new(
+            LiquidStaking::derivative_sovereign_account_id(derivative_index),
+            ksm(10f64),
+        );
+        staking_ledger.unbond(ksm(4f64), 5);
+        StakingLedgers::<Test>::insert(derivative_index, staking_ledger.clone());
+        TotalReserves::<Test>::put(ksm(1f64));
+        assert_ok!(LiquidStaking::update_commission_rate(
+            RuntimeOrigin::root(),
+            Rate::from_rational(1, 10)
+        ));
+
+        // 3 KSM of rewards are bonded while 1 KSM is slashed from `active` and another
+        // from the unlocking chunk, only the net 1 KSM of rewards is known
+        staking_ledger.total = ksm(11f64);
+        staking_ledger.active = ksm(8f64);
+        staking_ledger.unlocking[0].value = ksm(3f64);
+        let (root, proof) = get_mock_staking_ledger_proof(derivative_index, &staking_ledger);
+        RelayParentStorageRoot::set(root);
+        LiquidStaking::on_finalize(1);
+        assert_ok!(LiquidStaking::set_staking_ledger(
+            RuntimeOrigin::signed(ALICE),
+            derivative_index,
+            staking_ledger.clone(),
+            bounded_proof(proof)
+        ));
+        assert_eq!(
+            LiquidStaking::staking_ledger(derivative_index),
+            Some(staking_ledger.clone())
+        );
+        let commission =
+            <Test as Config>::Assets::balance(SKSM, &DefaultProtocolFeeReceiver::get());
+        assert!(commission > 0);
+        assert_eq!(
+            LiquidStaking::slashes(LiquidStaking::current_era(), derivative_index),
+            Default::default()
+        );
+        assert_eq!(LiquidStaking::total_reserves(), ksm(1f64));
+
+        // 1 KSM of rewards is bonded while the 3 KSM chunk is slashed away, `active`
+        // grows but the net 2 KSM are slashed
+        staking_ledger.total = ksm(9f64);
+        staking_ledger.active = ksm(9f64);
+        staking_ledger.unlocking = vec![];
+        let (root, proof) = get_mock_staking_ledger_proof(derivative_index, &staking_ledger);
+        RelayParentStorageRoot::set(root);
+        LiquidStaking::on_finalize(1);
+        assert_ok!(LiquidStaking::set_staking_ledger(
+            RuntimeOrigin::signed(ALICE),
+            derivative_index,
+            staking_ledger.clone(),
+            bounded_proof(proof)
+        ));
+        assert_eq!(
+            LiquidStaking::staking_ledger(derivative_index),
+            Some(staking_ledger.clone())
+        );
+        assert_eq!(
+            LiquidStaking::slashes(LiquidStaking::current_era(), derivative_index),
+            SlashRecord {
+                amount: ksm(2f64),
+                covered_by_reserves: ksm(1f64),
+            }
+        );
+        assert_eq!(LiquidStaking::total_reserves(), 0);
+        // No commission is charged for a slash
+        assert_eq!(
+            <Test as Config>::Assets::balance(SKSM, &DefaultProtocolFeeReceiver::get()),
+            commission
+        );
+
+        // a chunk can't be added by a slash nor by rewards
+        staking_ledger.total = ksm(12f64);
+        staking_ledger.unlocking = vec![UnlockChunk {
+            value: ksm(3f64),
+            era: 6,
+        }];
+        let (root, proof) = get_mock_staking_ledger_proof(derivative_index, &staking_ledger);
+        RelayParentStorageRoot::set(root);
+        LiquidStaking::on_finalize(1);
+        assert_noop!(
+            LiquidStaking::set_staking_ledger(
+                RuntimeOrigin::signed(ALICE),
+                derivative_index,
+                staking_ledger,
+                bounded_proof(proof)
+            ),
+            Error::<Test>::InvalidStakingLedger
+        );
+    })
+}
+
 #[test]
 fn test_complete_fast_match_unstake_work() {
     new_test_ext().execute_with(|| {
@@ -1337,3 +1695,883 @@ fn test_partial_fast_match_unstake_work() {
         ));
     })
 }
+
+#[test]
+fn test_validator_registry_work() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            LiquidStaking::add_validator(RuntimeOrigin::signed(ALICE), BOB),
+            BadOrigin
+        );
+        assert_ok!(LiquidStaking::add_validator(RuntimeOrigin::root(), BOB));
+        assert_eq!(
+            LiquidStaking::validator_info(BOB),
+            Some(ValidatorInfo::default())
+        );
+        assert_noop!(
+            LiquidStaking::add_validator(RuntimeOrigin::root(), BOB),
+            Error::<Test>::ValidatorAlreadyAdded
+        );
+        assert_noop!(
+            LiquidStaking::set_validator_prefs(
+                RuntimeOrigin::signed(ALICE),
+                BOB,
+                Default::default(),
+                get_mock_proof_bytes()
+            ),
+            Error::<Test>::InvalidProof
+        );
+
+        assert_ok!(LiquidStaking::remove_validator(RuntimeOrigin::root(), BOB));
+        assert_eq!(LiquidStaking::validator_info(BOB), None);
+        assert_noop!(
+            LiquidStaking::remove_validator(RuntimeOrigin::root(), BOB),
+            Error::<Test>::ValidatorNotFound
+        );
+
+        for i in 0..MaxValidators::get() {
+            assert_ok!(LiquidStaking::add_validator(
+                RuntimeOrigin::root(),
+                AccountId::new([i as u8; 32])
+            ));
+        }
+        assert_noop!(
+            LiquidStaking::add_validator(RuntimeOrigin::root(), AccountId::new([255u8; 32])),
+            Error::<Test>::TooManyValidators
+        );
+    })
+}
+
+#[test]
+fn test_set_era_reward_points_should_not_work_if_with_invalid_era() {
+    new_test_ext().execute_with(|| {
+        let points = EraRewardPoints {
+            total: 0,
+            individual: Default::default(),
+        };
+        assert_noop!(
+            LiquidStaking::set_era_reward_points(
+                RuntimeOrigin::signed(ALICE),
+                0,
+                points.clone(),
+                get_mock_proof_bytes()
+            ),
+            Error::<Test>::InvalidEra
+        );
+        CurrentEra::<Test>::put(2);
+        LastRewardedEra::<Test>::put(1);
+        assert_noop!(
+            LiquidStaking::set_era_reward_points(
+                RuntimeOrigin::signed(ALICE),
+                1,
+                points,
+                get_mock_proof_bytes()
+            ),
+            Error::<Test>::InvalidEra
+        );
+    })
+}
+
+#[test]
+fn test_era_points_nomination_work() {
+    use crate::nomination::EraPointsNomination;
+    use pallet_traits::NominationStrategy;
+
+    let validator = |points: Vec<u32>, commission: u32, blocked: bool| ValidatorInfo::<Balance> {
+        commission: sp_runtime::Perbill::from_percent(commission),
+        blocked,
+        self_stake: 0,
+        total_stake: 0,
+        era_points: points
+            .into_iter()
+            .enumerate()
+            .map(|(era, p)| (era as u32, p))
+            .collect(),
+    };
+    let validators = vec![
+        (1u64, validator(vec![10, 20], 5, false)),
+        (2u64, validator(vec![30, 30], 5, false)),
+        (3u64, validator(vec![30, 30], 1, false)),
+        (4u64, validator(vec![50, 50], 1, true)),
+        (5u64, validator(vec![], 0, false)),
+    ];
+
+    assert_eq!(
+        <EraPointsNomination as NominationStrategy<u64, Balance>>::get_nominations(
+            validators.clone(),
+            vec![0, 1],
+            2
+        ),
+        vec![(0, vec![3, 2]), (1, vec![1, 5])]
+    );
+    // Derivative indexes share the validators if there are not enough of them
+    assert_eq!(
+        <EraPointsNomination as NominationStrategy<u64, Balance>>::get_nominations(
+            validators.clone(),
+            vec![0, 1],
+            3
+        ),
+        vec![(0, vec![3, 2, 1]), (1, vec![5, 3, 2])]
+    );
+    assert!(
+        <EraPointsNomination as NominationStrategy<u64, Balance>>::get_nominations(
+            validators,
+            vec![0, 1],
+            0
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn test_nominations_follow_era_points_on_era_advance() {
+    new_test_ext().execute_with(|| {
+        let derivative_index = 0u16;
+        assert_ok!(LiquidStaking::stake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(20f64),
+        ));
+        assert_ok!(LiquidStaking::bond(
+            RuntimeOrigin::signed(ALICE),
+            derivative_index,
+            ksm(10f64),
+            RewardDestination::Staked
+        ));
+        assert_ok!(LiquidStaking::notification_received(
+            RuntimeOrigin::root(),
+            0,
+            Response::ExecutionResult(None),
+        ));
+
+        let validators: Vec<AccountId> = (1..=5u8).map(|i| AccountId::new([i; 32])).collect();
+        for (i, validator) in validators.iter().enumerate() {
+            Validators::<Test>::insert(
+                validator,
+                ValidatorInfo {
+                    era_points: vec![(0, 10 * (i as u32 + 1))],
+                    ..Default::default()
+                },
+            );
+        }
+        assert_noop!(
+            LiquidStaking::nominate(
+                RuntimeOrigin::signed(ALICE),
+                derivative_index,
+                validators.clone()
+            ),
+            Error::<Test>::TooManyNominations
+        );
+
+        // at most `MaxNominations` validators with the most points are nominated
+        let targets: Vec<AccountId> = validators.iter().rev().take(4).cloned().collect();
+        assert_ok!(with_transaction(
+            || -> TransactionOutcome<DispatchResult> {
+                assert_ok!(LiquidStaking::do_advance_era(1));
+                TransactionOutcome::Commit(Ok(()))
+            }
+        ));
+        assert_eq!(
+            XcmRequests::<Test>::get(1),
+            Some(XcmRequest::Nominate {
+                index: derivative_index,
+                targets: targets.clone(),
+            })
+        );
+        assert_ok!(LiquidStaking::notification_received(
+            RuntimeOrigin::root(),
+            1,
+            Response::ExecutionResult(None),
+        ));
+        assert_eq!(
+            LiquidStaking::nominations(derivative_index).into_inner(),
+            targets
+        );
+
+        // unchanged nominations aren't sent again
+        assert_ok!(with_transaction(
+            || -> TransactionOutcome<DispatchResult> {
+                assert_ok!(LiquidStaking::do_advance_era(1));
+                TransactionOutcome::Commit(Ok(()))
+            }
+        ));
+        assert_eq!(XcmRequests::<Test>::iter().count(), 0);
+
+        // the nominations follow the new era points
+        Validators::<Test>::mutate(&validators[0], |info| {
+            info.as_mut().unwrap().era_points = vec![(0, 100)]
+        });
+        assert_ok!(with_transaction(
+            || -> TransactionOutcome<DispatchResult> {
+                assert_ok!(LiquidStaking::do_advance_era(1));
+                TransactionOutcome::Commit(Ok(()))
+            }
+        ));
+        assert_eq!(
+            XcmRequests::<Test>::get(2),
+            Some(XcmRequest::Nominate {
+                index: derivative_index,
+                targets: vec![
+                    validators[0].clone(),
+                    validators[4].clone(),
+                    validators[3].clone(),
+                    validators[2].clone()
+                ],
+            })
+        );
+    })
+}
+
+#[test]
+fn test_exchange_rate_history_and_apy_work() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(LiquidStaking::get_apy(), None);
+        for era in 1..=6u32 {
+            ExchangeRate::<Test>::put(Rate::saturating_from_rational(100_000 + era, 100_000));
+            assert_ok!(LiquidStaking::do_advance_era(1));
+        }
+
+        // only the last `ExchangeRateHistoryDepth` eras are kept
+        let history = LiquidStaking::exchange_rate_history();
+        assert_eq!(
+            history.iter().map(|s| s.era).collect::<Vec<_>>(),
+            vec![3, 4, 5, 6]
+        );
+        assert_eq!(
+            history[0].exchange_rate,
+            Rate::saturating_from_rational(100_003, 100_000)
+        );
+        assert_eq!(
+            history[3].total_liquid,
+            <Test as Config>::Assets::total_issuance(SKSM)
+        );
+
+        // (1.00006 / 1.00003 - 1) / 3 eras * 525_600 eras per year
+        let apy = LiquidStaking::get_apy().unwrap();
+        assert!(apy > Rate::saturating_from_rational(5255, 1000));
+        assert!(apy < Rate::saturating_from_rational(5256, 1000));
+    })
+}
+
+#[test]
+fn instant_unstake_should_pick_the_cheapest_provider() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(LiquidStaking::stake(RuntimeOrigin::signed(BOB), ksm(10f64)));
+        // a pending fast unstake request isn't matched by the instant unstake
+        assert_ok!(LiquidStaking::unstake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(1f64),
+            UnstakeProvider::MatchingPool
+        ));
+
+        // loans: 3 * (1 - 8/1000), matching pool: 3 * (1 - 1/1000)
+        let loans_amount = ksm(3f64) * 992 / 1000;
+        let matching_pool_amount = ksm(3f64) * 999 / 1000;
+        // the loans can't lend the staking asset without cash
+        assert_eq!(
+            LiquidStaking::instant_unstake_quotes(ksm(3f64)),
+            vec![(UnstakeProvider::MatchingPool, matching_pool_amount)]
+        );
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), KSM, ksm(10f64)));
+        assert_eq!(
+            LiquidStaking::instant_unstake_quotes(ksm(3f64)),
+            vec![
+                (UnstakeProvider::Loans, loans_amount),
+                (UnstakeProvider::MatchingPool, matching_pool_amount),
+            ]
+        );
+        assert_noop!(
+            LiquidStaking::instant_unstake(RuntimeOrigin::signed(ALICE), ksm(3f64), ksm(3f64)),
+            Error::<Test>::TooLittleReceived
+        );
+
+        assert_ok!(LiquidStaking::instant_unstake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(3f64),
+            ksm(2.99f64)
+        ));
+        assert_eq!(
+            <Test as Config>::Assets::balance(KSM, &ALICE),
+            ksm(100f64) + matching_pool_amount
+        );
+        assert_eq!(FastUnstakeRequests::<Test>::get(&ALICE), ksm(1f64));
+        System::assert_last_event(RuntimeEvent::LiquidStaking(crate::Event::InstantUnstaked(
+            ALICE,
+            UnstakeProvider::MatchingPool,
+            ksm(3f64),
+            matching_pool_amount,
+        )));
+
+        // sKSM is sold above the exchange rate in the AMM
+        Assets::force_create(RuntimeOrigin::root(), LP_KSM_SKSM, Id(ALICE), true, 1).unwrap();
+        assert_ok!(DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (SKSM, KSM),
+            (ksm(50f64), ksm(60f64)),
+            ALICE,
+            LP_KSM_SKSM,
+        ));
+        let amount_out = *DefaultAMM::get_amounts_out(ksm(3f64), vec![SKSM, KSM])
+            .unwrap()
+            .last()
+            .unwrap();
+        assert!(amount_out > matching_pool_amount);
+
+        let balance_before = <Test as Config>::Assets::balance(KSM, &ALICE);
+        assert_ok!(LiquidStaking::instant_unstake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(3f64),
+            amount_out
+        ));
+        assert_eq!(
+            <Test as Config>::Assets::balance(KSM, &ALICE),
+            balance_before + amount_out
+        );
+        System::assert_last_event(RuntimeEvent::LiquidStaking(crate::Event::InstantUnstaked(
+            ALICE,
+            UnstakeProvider::Amm,
+            ksm(3f64),
+            amount_out,
+        )));
+
+        assert_noop!(
+            LiquidStaking::unstake(
+                RuntimeOrigin::signed(ALICE),
+                ksm(3f64),
+                UnstakeProvider::Amm
+            ),
+            Error::<Test>::InvalidUnstakeProvider
+        );
+    })
+}
+
+#[test]
+fn instant_unstake_should_swap_through_the_stableswap_pool() {
+    new_test_ext().execute_with(|| {
+        // sKSM is sold above the exchange rate in the stableswap pool
+        Assets::force_create(RuntimeOrigin::root(), LP_KSM_SKSM, Id(ALICE), true, 1).unwrap();
+        assert_ok!(DefaultStableSwap::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (SKSM, KSM),
+            (ksm(50f64), ksm(60f64)),
+            ALICE,
+            LP_KSM_SKSM,
+        ));
+        let amount_out = *DefaultStableSwap::get_amounts_out(ksm(3f64), vec![SKSM, KSM])
+            .unwrap()
+            .last()
+            .unwrap();
+        assert_eq!(
+            LiquidStaking::instant_unstake_quotes(ksm(3f64)),
+            vec![(UnstakeProvider::StableSwap, amount_out)]
+        );
+
+        let balance_before = <Test as Config>::Assets::balance(KSM, &ALICE);
+        assert_ok!(LiquidStaking::instant_unstake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(3f64),
+            amount_out
+        ));
+        assert_eq!(
+            <Test as Config>::Assets::balance(KSM, &ALICE),
+            balance_before + amount_out
+        );
+        System::assert_last_event(RuntimeEvent::LiquidStaking(crate::Event::InstantUnstaked(
+            ALICE,
+            UnstakeProvider::StableSwap,
+            ksm(3f64),
+            amount_out,
+        )));
+
+        assert_noop!(
+            LiquidStaking::unstake(
+                RuntimeOrigin::signed(ALICE),
+                ksm(3f64),
+                UnstakeProvider::StableSwap
+            ),
+            Error::<Test>::InvalidUnstakeProvider
+        );
+    })
+}
+
+#[test]
+fn test_payout_stakers_work() {
+    new_test_ext().execute_with(|| {
+        let validator1: AccountId = AccountId::new([11u8; 32]);
+        let validator2: AccountId = AccountId::new([12u8; 32]);
+        let validator3: AccountId = AccountId::new([13u8; 32]);
+        Nominations::<Test>::insert(0, bounded_vec![validator2.clone(), validator1.clone()]);
+        Nominations::<Test>::insert(1, bounded_vec![validator1.clone()]);
+
+        assert_ok!(with_transaction(
+            || -> TransactionOutcome<DispatchResult> {
+                assert_ok!(LiquidStaking::do_advance_era(1));
+                TransactionOutcome::Commit(Ok(()))
+            }
+        ));
+        // nothing was nominated when the ended era started
+        assert!(LiquidStaking::pending_payouts().is_empty());
+        assert_eq!(
+            LiquidStaking::era_nominations().into_inner(),
+            vec![validator1.clone(), validator2.clone()]
+        );
+
+        // nominations changed during the era don't earn its rewards
+        Nominations::<Test>::insert(0, bounded_vec![validator3.clone()]);
+        Nominations::<Test>::remove(1);
+        assert_ok!(with_transaction(
+            || -> TransactionOutcome<DispatchResult> {
+                assert_ok!(LiquidStaking::do_advance_era(1));
+                TransactionOutcome::Commit(Ok(()))
+            }
+        ));
+        // every validator nominated in the ended era is paid out once
+        assert_eq!(
+            LiquidStaking::pending_payouts().into_inner(),
+            vec![(1, validator1.clone()), (1, validator2.clone())]
+        );
+        assert_eq!(
+            LiquidStaking::era_nominations().into_inner(),
+            vec![validator3.clone()]
+        );
+
+        // at most `MaxPayoutsPerBlock` payouts are sent in a block
+        LiquidStaking::on_initialize(System::block_number());
+        assert_eq!(
+            LiquidStaking::pending_payouts().into_inner(),
+            vec![(1, validator2.clone())]
+        );
+        assert_eq!(
+            XcmRequests::<Test>::iter_values()
+                .filter(|req| matches!(req, XcmRequest::PayoutStakers { .. }))
+                .count(),
+            1
+        );
+        System::assert_has_event(RuntimeEvent::LiquidStaking(crate::Event::PayingOutStakers(
+            validator1, 1,
+        )));
+
+        LiquidStaking::on_initialize(System::block_number());
+        assert!(LiquidStaking::pending_payouts().is_empty());
+    })
+}
+
+#[test]
+fn test_pending_payouts_drop_the_oldest_when_full() {
+    new_test_ext().execute_with(|| {
+        let validator1: AccountId = AccountId::new([11u8; 32]);
+        let validator2: AccountId = AccountId::new([12u8; 32]);
+        Nominations::<Test>::insert(0, bounded_vec![validator1.clone(), validator2.clone()]);
+
+        assert_ok!(with_transaction(
+            || -> TransactionOutcome<DispatchResult> {
+                assert_ok!(LiquidStaking::do_advance_era(1));
+                // two eras ended, 4 payouts for at most `MaxPendingPayouts`
+                assert_ok!(LiquidStaking::do_advance_era(2));
+                TransactionOutcome::Commit(Ok(()))
+            }
+        ));
+        assert_eq!(
+            LiquidStaking::pending_payouts().into_inner(),
+            vec![
+                (1, validator2.clone()),
+                (2, validator1.clone()),
+                (2, validator2.clone())
+            ]
+        );
+    })
+}
+
+#[test]
+fn test_stash_rewards_are_withdrawn_and_rebonded() {
+    new_test_ext().execute_with(|| {
+        let derivative_index = 0u16;
+        let stash = LiquidStaking::derivative_sovereign_account_id(derivative_index);
+        assert_ok!(LiquidStaking::stake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(10f64)
+        ));
+        let staking_ledger =
+            <StakingLedger<AccountId, BalanceOf<Test>>>::new(stash.clone(), ksm(10f64));
+        StakingLedgers::<Test>::insert(derivative_index, staking_ledger.clone());
+
+        // the ledger accounts for the bonded 10 KSM, the rest was paid out to the stash
+        let account = AccountInfo {
+            data: AccountData {
+                free: ksm(11f64),
+                misc_frozen: ksm(10f64),
+                fee_frozen: ksm(10f64),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (root, proof) = get_mock_stash_proof(derivative_index, &account, &staking_ledger);
+        RelayParentStorageRoot::set(root);
+        LiquidStaking::on_finalize(1);
+
+        assert_noop!(
+            LiquidStaking::set_stash_rewards(
+                RuntimeOrigin::signed(BOB),
+                1,
+                account.clone(),
+                staking_ledger.clone(),
+                bounded_proof(proof.clone())
+            ),
+            Error::<Test>::NotBonded
+        );
+        // the ledger must be proven at the same relaychain block as the account
+        let mut unbonding_ledger = staking_ledger.clone();
+        unbonding_ledger.total = ksm(11f64);
+        assert_noop!(
+            LiquidStaking::set_stash_rewards(
+                RuntimeOrigin::signed(ALICE),
+                derivative_index,
+                account.clone(),
+                unbonding_ledger,
+                bounded_proof(proof.clone())
+            ),
+            Error::<Test>::InvalidProof
+        );
+        assert_ok!(LiquidStaking::set_stash_rewards(
+            RuntimeOrigin::signed(ALICE),
+            derivative_index,
+            account.clone(),
+            staking_ledger.clone(),
+            bounded_proof(proof.clone())
+        ));
+        assert_eq!(
+            LiquidStaking::pending_rewards(derivative_index),
+            Some(ksm(1f64))
+        );
+
+        // the rewards are withdrawn when the next block is initialized
+        assert_eq!(LiquidStaking::do_withdraw_rewards(), 1);
+        assert_eq!(LiquidStaking::pending_rewards(derivative_index), None);
+        assert_eq!(
+            LiquidStaking::rewards_withdrawal(derivative_index),
+            Some(ksm(1f64))
+        );
+        assert_eq!(
+            XcmRequests::<Test>::get(0),
+            Some(XcmRequest::WithdrawRewards {
+                index: derivative_index,
+                amount: ksm(1f64)
+            })
+        );
+        // the rewards can't be set again before reaching the parachain
+        assert_noop!(
+            LiquidStaking::set_stash_rewards(
+                RuntimeOrigin::signed(ALICE),
+                derivative_index,
+                account,
+                staking_ledger,
+                bounded_proof(proof)
+            ),
+            Error::<Test>::RewardsLocked
+        );
+
+        let liquid_issuance = <Test as Config>::Assets::total_issuance(SKSM);
+        assert_ok!(LiquidStaking::notification_received(
+            pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+            0,
+            Response::ExecutionResult(None),
+        ));
+        System::assert_has_event(RuntimeEvent::LiquidStaking(crate::Event::RewardsRebonded(
+            derivative_index,
+            ksm(1f64),
+        )));
+        // the rewards are staked without issuing liquid currency and bonded again
+        assert_eq!(
+            <Test as Config>::Assets::total_issuance(SKSM),
+            liquid_issuance
+        );
+        assert_eq!(
+            MatchingPool::<Test>::get().total_stake_amount,
+            ReservableAmount {
+                total: ksm(10.95f64),
+                reserved: ksm(1f64)
+            }
+        );
+        assert_eq!(
+            <Test as Config>::Assets::balance(KSM, &LiquidStaking::account_id()),
+            ksm(11f64)
+        );
+        assert_eq!(
+            XcmRequests::<Test>::iter_values()
+                .filter_map(|req| match req {
+                    XcmRequest::Bond { amount, .. } | XcmRequest::BondExtra { amount, .. } =>
+                        Some(amount),
+                    _ => None,
+                })
+                .sum::<Balance>(),
+            ksm(1f64)
+        );
+    })
+}
+
+#[test]
+fn advance_era_should_not_work_if_with_invalid_param() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(LiquidStaking::force_set_current_era(
+            RuntimeOrigin::root(),
+            10
+        ));
+
+        assert_noop!(
+            LiquidStaking::advance_era(
+                RuntimeOrigin::signed(BOB),
+                ActiveEraInfo {
+                    index: 10,
+                    start: None
+                },
+                Default::default()
+            ),
+            Error::<Test>::InvalidEra
+        );
+        assert_noop!(
+            LiquidStaking::advance_era(
+                RuntimeOrigin::signed(BOB),
+                ActiveEraInfo {
+                    index: 11,
+                    start: None
+                },
+                bounded_proof(get_mock_proof_bytes())
+            ),
+            Error::<Test>::InvalidProof
+        );
+        assert_eq!(LiquidStaking::current_era(), 10);
+    })
+}
+
+#[test]
+fn advance_era_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(LiquidStaking::stake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(100f64)
+        ));
+        assert_eq!(LiquidStaking::total_reserves(), ksm(0.5f64));
+
+        let active_era = ActiveEraInfo {
+            index: 1,
+            start: Some(1_000),
+        };
+        let (root, proof) = get_mock_active_era_proof(&active_era);
+        RelayParentStorageRoot::set(root);
+        LiquidStaking::on_finalize(1);
+
+        let bob_balance = <Test as Config>::Assets::balance(KSM, &BOB);
+        assert_ok!(LiquidStaking::advance_era(
+            RuntimeOrigin::signed(BOB),
+            active_era,
+            bounded_proof(proof)
+        ));
+
+        let bounty = EraAdvanceBounty::get();
+        assert_eq!(LiquidStaking::current_era(), 1);
+        assert_eq!(
+            <Test as Config>::Assets::balance(KSM, &BOB),
+            bob_balance + bounty
+        );
+        assert_eq!(LiquidStaking::total_reserves(), ksm(0.5f64) - bounty);
+        System::assert_last_event(RuntimeEvent::LiquidStaking(crate::Event::EraAdvanced(
+            BOB, 1, bounty,
+        )));
+    })
+}
+
+#[test]
+fn test_weighted_distribution_work() {
+    new_test_ext().execute_with(|| {
+        DerivativeIndexList::set(vec![0, 1]);
+
+        assert_noop!(
+            LiquidStaking::set_distribution_strategy(
+                RuntimeOrigin::root(),
+                DistributionStrategyKind::Weighted
+            ),
+            Error::<Test>::DistributionWeightsRequired
+        );
+        assert_noop!(
+            LiquidStaking::set_distribution_weights(RuntimeOrigin::root(), vec![(0, 3), (5, 1)]),
+            Error::<Test>::InvalidDistributionWeights
+        );
+        assert_noop!(
+            LiquidStaking::set_distribution_weights(RuntimeOrigin::root(), vec![(0, 0)]),
+            Error::<Test>::InvalidDistributionWeights
+        );
+        assert_noop!(
+            LiquidStaking::set_distribution_weights(RuntimeOrigin::root(), vec![(0, 3), (0, 0)]),
+            Error::<Test>::InvalidDistributionWeights
+        );
+        // zero weights can't be used by the weighted distribution strategy
+        DistributionWeights::<Test>::insert(0, 0);
+        assert_noop!(
+            LiquidStaking::set_distribution_strategy(
+                RuntimeOrigin::root(),
+                DistributionStrategyKind::Weighted
+            ),
+            Error::<Test>::DistributionWeightsRequired
+        );
+        assert_ok!(LiquidStaking::set_distribution_weights(
+            RuntimeOrigin::root(),
+            vec![]
+        ));
+        assert_eq!(DistributionWeights::<Test>::iter().count(), 0);
+        assert_ok!(LiquidStaking::set_distribution_weights(
+            RuntimeOrigin::root(),
+            vec![(0, 3), (1, 1)]
+        ));
+
+        // `AverageDistribution` until the strategy is switched
+        assert_eq!(
+            LiquidStaking::get_bond_distributions(ksm(4f64)),
+            vec![(0, ksm(2f64)), (1, ksm(2f64))]
+        );
+
+        assert_ok!(LiquidStaking::set_distribution_strategy(
+            RuntimeOrigin::root(),
+            DistributionStrategyKind::Weighted
+        ));
+        assert_eq!(
+            LiquidStaking::get_bond_distributions(ksm(4f64)),
+            vec![(0, ksm(3f64)), (1, ksm(1f64))]
+        );
+        // the weights in use can't be cleared
+        assert_noop!(
+            LiquidStaking::set_distribution_weights(RuntimeOrigin::root(), vec![]),
+            Error::<Test>::DistributionWeightsRequired
+        );
+
+        for (index, amount) in [(0, ksm(30f64)), (1, ksm(10f64))] {
+            StakingLedgers::<Test>::insert(
+                index,
+                <StakingLedger<AccountId, BalanceOf<Test>>>::new(
+                    LiquidStaking::derivative_sovereign_account_id(index),
+                    amount,
+                ),
+            );
+        }
+        // the ledgers already follow the weights, the bond keeps them there
+        assert_eq!(
+            LiquidStaking::get_bond_distributions(ksm(4f64)),
+            vec![(0, ksm(3f64)), (1, ksm(1f64))]
+        );
+        // 32 KSM left, 24 KSM and 8 KSM targets
+        assert_eq!(
+            LiquidStaking::get_unbond_distributions(ksm(8f64)),
+            vec![(0, ksm(6f64)), (1, ksm(2f64))]
+        );
+    })
+}
+
+#[test]
+fn instances_should_be_isolated() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(LiquidStaking::stake(
+            RuntimeOrigin::signed(ALICE),
+            ksm(10f64)
+        ));
+        assert_ok!(LiquidStaking2::stake(
+            RuntimeOrigin::signed(ALICE),
+            dot(10f64)
+        ));
+        assert_eq!(
+            MatchingPool::<Test>::get().total_stake_amount.total,
+            ksm(9.95f64)
+        );
+        assert_eq!(
+            MatchingPool::<Test, Instance2>::get()
+                .total_stake_amount
+                .total,
+            dot(9.95f64)
+        );
+        assert_eq!(<Test as Config>::Assets::balance(DOT, &ALICE), dot(90f64));
+        assert_eq!(
+            <Test as Config>::Assets::balance(SDOT, &ALICE),
+            dot(9.95f64)
+        );
+
+        // bond
+        assert_ok!(with_transaction(
+            || -> TransactionOutcome<DispatchResult> {
+                LiquidStaking::do_advance_era(1).unwrap();
+                LiquidStaking::do_matching().unwrap();
+                LiquidStaking::notification_received(
+                    pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+                    0,
+                    Response::ExecutionResult(None),
+                )
+                .unwrap();
+                LiquidStaking2::do_advance_era(1).unwrap();
+                LiquidStaking2::do_matching().unwrap();
+                LiquidStaking2::notification_received(
+                    pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+                    1,
+                    Response::ExecutionResult(None),
+                )
+                .unwrap();
+                TransactionOutcome::Commit(Ok(()))
+            }
+        ));
+        assert_eq!(StakingLedgers::<Test>::get(0).unwrap().active, ksm(9.95f64));
+        assert_eq!(StakingLedgers::<Test>::get(1), None);
+        assert_eq!(
+            StakingLedgers::<Test, Instance2>::get(1).unwrap().active,
+            dot(9.95f64)
+        );
+        assert_eq!(StakingLedgers::<Test, Instance2>::get(0), None);
+        assert_ne!(
+            LiquidStaking::derivative_sovereign_account_id(0),
+            LiquidStaking2::derivative_sovereign_account_id(1)
+        );
+
+        // unbond
+        assert_ok!(LiquidStaking2::unstake(
+            RuntimeOrigin::signed(ALICE),
+            dot(5f64),
+            Default::default()
+        ));
+        assert_eq!(MatchingPool::<Test>::get(), Default::default());
+        assert_eq!(
+            MatchingPool::<Test, Instance2>::get()
+                .total_unstake_amount
+                .total,
+            dot(5f64)
+        );
+        assert_ok!(with_transaction(
+            || -> TransactionOutcome<DispatchResult> {
+                LiquidStaking2::do_advance_era(1).unwrap();
+                LiquidStaking2::do_matching().unwrap();
+                LiquidStaking2::notification_received(
+                    pallet_xcm::Origin::Response(MultiLocation::parent()).into(),
+                    2,
+                    Response::ExecutionResult(None),
+                )
+                .unwrap();
+                TransactionOutcome::Commit(Ok(()))
+            }
+        ));
+        let ledger = StakingLedgers::<Test, Instance2>::get(1).unwrap();
+        assert_eq!(ledger.active, dot(4.95f64));
+        assert_eq!(ledger.unlocking.len(), 1);
+        let ledger = StakingLedgers::<Test>::get(0).unwrap();
+        assert_eq!(ledger.active, ksm(9.95f64));
+        assert!(ledger.unlocking.is_empty());
+        assert_eq!(LiquidStaking::current_era(), 1);
+        assert_eq!(LiquidStaking2::current_era(), 2);
+
+        // exchange rate
+        StakingLedgers::<Test>::mutate(0, |ledger| {
+            ledger.as_mut().unwrap().bond_extra(ksm(200f64));
+        });
+        assert_ok!(with_transaction(
+            || -> TransactionOutcome<DispatchResult> {
+                LiquidStaking::do_advance_era(1).unwrap();
+                LiquidStaking2::do_advance_era(1).unwrap();
+                TransactionOutcome::Commit(Ok(()))
+            }
+        ));
+        assert!(ExchangeRate::<Test>::get() > Rate::one());
+        assert_eq!(ExchangeRate::<Test, Instance2>::get(), Rate::one());
+    })
+}
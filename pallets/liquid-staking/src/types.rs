@@ -1,6 +1,5 @@
 use codec::{Decode, Encode, HasCompact};
 
-use super::{BalanceOf, Config};
 use frame_support::{
     dispatch::DispatchResult,
     traits::{tokens::Balance as BalanceT, DefensiveSaturating},
@@ -195,23 +194,22 @@ impl<Balance: BalanceT + FixedPointOperand> MatchingLedger<Balance> {
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
-#[scale_info(skip_type_params(T))]
-pub enum XcmRequest<T: Config> {
+pub enum XcmRequest<AccountId, Balance> {
     Bond {
         index: DerivativeIndex,
-        amount: BalanceOf<T>,
+        amount: Balance,
     },
     BondExtra {
         index: DerivativeIndex,
-        amount: BalanceOf<T>,
+        amount: Balance,
     },
     Unbond {
         index: DerivativeIndex,
-        amount: BalanceOf<T>,
+        amount: Balance,
     },
     Rebond {
         index: DerivativeIndex,
-        amount: BalanceOf<T>,
+        amount: Balance,
     },
     WithdrawUnbonded {
         index: DerivativeIndex,
@@ -219,7 +217,15 @@ pub enum XcmRequest<T: Config> {
     },
     Nominate {
         index: DerivativeIndex,
-        targets: Vec<T::AccountId>,
+        targets: Vec<AccountId>,
+    },
+    PayoutStakers {
+        validator: AccountId,
+        era: EraIndex,
+    },
+    WithdrawRewards {
+        index: DerivativeIndex,
+        amount: Balance,
     },
 }
 
@@ -234,6 +240,45 @@ pub struct UnlockChunk<Balance: HasCompact> {
     pub era: EraIndex,
 }
 
+/// Information regarding the active era (era in use), the same as relaychain's.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ActiveEraInfo {
+    /// Index of era.
+    pub index: EraIndex,
+    /// Moment of start expressed as millisecond from `$UNIX_EPOCH`.
+    pub start: Option<u64>,
+}
+
+/// Balances of an account, the same as relaychain's `pallet_balances::AccountData`.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct AccountData<Balance> {
+    /// Non-reserved part of the balance.
+    pub free: Balance,
+    /// Balance which is reserved and may not be used at all.
+    pub reserved: Balance,
+    /// The amount that `free` may not drop below when withdrawing for anything except
+    /// transaction fee payment.
+    pub misc_frozen: Balance,
+    /// The amount that `free` may not drop below when withdrawing specifically for
+    /// transaction fee payment.
+    pub fee_frozen: Balance,
+}
+
+/// Information of an account, the same as relaychain's `frame_system::AccountInfo`.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct AccountInfo<Balance> {
+    /// The number of transactions this account has sent.
+    pub nonce: u32,
+    /// The number of other modules that currently depend on this account's existence.
+    pub consumers: u32,
+    /// The number of other modules that allow this account to exist.
+    pub providers: u32,
+    /// The number of modules that allow this account to exist for their own purposes only.
+    pub sufficients: u32,
+    /// The balances of the account.
+    pub data: AccountData<Balance>,
+}
+
 /// The ledger of a (bonded) stash.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct StakingLedger<AccountId, Balance: HasCompact> {
@@ -337,6 +382,70 @@ impl<AccountId, Balance: BalanceT + FixedPointOperand> StakingLedger<AccountId,
         // 2. No minimum balance check
         self.active -= value;
     }
+
+    /// Returns the rewards and the amount slashed on the relaychain since this ledger, given
+    /// `ledger`, the newer proven version of it. Slashes shrink `active` and the `unlocking`
+    /// chunks, which can be reduced or removed but never added, while rewards can only grow
+    /// `active`, so when both land in the same period only their net change of `total` is
+    /// known and at most one of the returned amounts is non-zero.
+    pub fn rewards_and_slash(&self, ledger: &Self) -> Option<(Balance, Balance)> {
+        let chunks_slashed = ledger.unlocking.iter().all(|chunk| {
+            self.unlocking
+                .iter()
+                .any(|c| c.era == chunk.era && c.value >= chunk.value)
+        });
+        if !chunks_slashed {
+            return None;
+        }
+        match ledger.total.cmp(&self.total) {
+            Ordering::Greater => Some((ledger.total - self.total, Zero::zero())),
+            Ordering::Less => Some((Zero::zero(), self.total - ledger.total)),
+            Ordering::Equal
+                if ledger.active != self.active || ledger.unlocking != self.unlocking =>
+            {
+                Some((Zero::zero(), Zero::zero()))
+            }
+            Ordering::Equal => None,
+        }
+    }
+}
+
+pub type UnstakeReceiptId = u64;
+
+/// Transferable claim on a chunk of unbonding staking assets, held instead of an
+/// `UnlockChunk` when unstaking via the relaychain
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct UnstakeReceipt<AccountId, Balance> {
+    /// The account which can claim the staking assets
+    pub owner: AccountId,
+    /// Amount of staking assets to claim
+    pub value: Balance,
+    /// Era number at which point the staking assets can be claimed
+    pub era: EraIndex,
+}
+
+/// The slash of one derivative index's stake in one era
+#[derive(Copy, Clone, Eq, PartialEq, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct SlashRecord<Balance> {
+    /// The amount slashed on the relaychain
+    pub amount: Balance,
+    /// The part of `amount` made up by the reserves
+    pub covered_by_reserves: Balance,
+}
+
+/// Strategy used to distribute the bonds, unbonds and rebonds over the derivative indexes
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum DistributionStrategyKind {
+    /// `Config::DistributionStrategy`
+    Default,
+    /// Move the ledgers toward the governance assigned weights, `DistributionWeights`
+    Weighted,
+}
+
+impl Default for DistributionStrategyKind {
+    fn default() -> Self {
+        DistributionStrategyKind::Default
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -344,6 +453,8 @@ pub enum UnstakeProvider {
     RelayChain = 0,
     Loans = 1,
     MatchingPool = 2,
+    Amm = 3,
+    StableSwap = 4,
 }
 
 impl Default for UnstakeProvider {
@@ -364,4 +475,12 @@ impl UnstakeProvider {
     pub fn is_matching_pool(&self) -> bool {
         self == &UnstakeProvider::MatchingPool
     }
+
+    pub fn is_amm(&self) -> bool {
+        self == &UnstakeProvider::Amm
+    }
+
+    pub fn is_stable_swap(&self) -> bool {
+        self == &UnstakeProvider::StableSwap
+    }
 }
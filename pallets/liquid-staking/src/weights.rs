@@ -67,6 +67,21 @@ pub trait WeightInfo {
 	fn cancel_unstake() -> Weight;
 	fn update_commission_rate() -> Weight;
 	fn fast_match_unstake(n: u32, ) -> Weight;
+	fn add_validator() -> Weight;
+	fn remove_validator() -> Weight;
+	fn set_validator_prefs() -> Weight;
+	fn set_validator_exposure() -> Weight;
+	fn set_era_reward_points(n: u32, ) -> Weight;
+	fn transfer_unstake_receipt() -> Weight;
+	fn instant_unstake() -> Weight;
+	fn payout_stakers() -> Weight;
+	fn set_distribution_strategy(n: u32, ) -> Weight;
+	fn set_distribution_weights(n: u32, ) -> Weight;
+	fn accept_unstake_receipt() -> Weight;
+	fn advance_era() -> Weight;
+	fn withdraw_rewards() -> Weight;
+	fn set_stash_rewards() -> Weight;
+	fn set_staking_ledger() -> Weight;
 }
 
 /// Weights for pallet_liquid_staking using the Substrate node and recommended hardware.
@@ -382,6 +397,188 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(4 as u64))
 			.saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(n as u64)))
 	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn add_validator() -> Weight {
+		Weight::from_ref_time(33_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn remove_validator() -> Weight {
+		Weight::from_ref_time(31_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn set_validator_prefs() -> Weight {
+		Weight::from_ref_time(49_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking CurrentEra (r:1 w:0)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn set_validator_exposure() -> Weight {
+		Weight::from_ref_time(62_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking CurrentEra (r:1 w:0)
+	// Storage: LiquidStaking LastRewardedEra (r:1 w:1)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn set_era_reward_points(n: u32, ) -> Weight {
+		Weight::from_ref_time(53_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(6_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking UnstakeReceipts (r:1 w:0)
+	// Storage: LiquidStaking ExchangeRate (r:1 w:0)
+	// Storage: LiquidStaking PendingReceiptTransfers (r:0 w:1)
+	fn transfer_unstake_receipt() -> Weight {
+		Weight::from_ref_time(29_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking ExchangeRate (r:1 w:0)
+	// Storage: LiquidStaking UnstakeReserveFactor (r:1 w:0)
+	// Storage: LiquidStaking FastUnstakeRequests (r:1 w:1)
+	// Storage: LiquidStaking MatchingPool (r:1 w:1)
+	// Storage: Loans Markets (r:2 w:0)
+	// Storage: AMM Pools (r:1 w:1)
+	// Storage: StableSwap Pools (r:1 w:0)
+	// Storage: Assets Asset (r:3 w:3)
+	// Storage: Assets Account (r:6 w:6)
+	fn instant_unstake() -> Weight {
+		Weight::from_ref_time(413_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(30 as u64))
+			.saturating_add(T::DbWeight::get().writes(17 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking PendingPayouts (r:1 w:1)
+	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: LiquidStaking XcmRequests (r:0 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	fn payout_stakers() -> Weight {
+		Weight::from_ref_time(62_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking DistributionWeights (r:1 w:0)
+	// Storage: LiquidStaking CurrentDistributionStrategy (r:0 w:1)
+	fn set_distribution_strategy(n: u32, ) -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(2_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking DistributionWeights (r:0 w:1)
+	fn set_distribution_weights(n: u32, ) -> Weight {
+		Weight::from_ref_time(23_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(4_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking PendingReceiptTransfers (r:1 w:1)
+	// Storage: LiquidStaking UnstakeReceipts (r:1 w:1)
+	// Storage: LiquidStaking UnstakeReceiptsOf (r:1 w:2)
+	fn accept_unstake_receipt() -> Weight {
+		Weight::from_ref_time(42_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking CurrentEra (r:1 w:1)
+	// Storage: ParachainSystem ValidationData (r:1 w:0)
+	// Storage: LiquidStaking StakingLedgers (r:3 w:0)
+	// Storage: LiquidStaking MatchingPool (r:1 w:0)
+	// Storage: Assets Asset (r:2 w:1)
+	// Storage: LiquidStaking ExchangeRate (r:1 w:1)
+	// Storage: LiquidStaking ExchangeRateHistory (r:1 w:1)
+	// Storage: LiquidStaking TotalReserves (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: LiquidStaking EraStartBlock (r:0 w:1)
+	// Storage: LiquidStaking IsMatched (r:0 w:1)
+	fn advance_era() -> Weight {
+		Weight::from_ref_time(188_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(15 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking PendingRewards (r:1 w:1)
+	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: LiquidStaking XcmRequests (r:0 w:1)
+	// Storage: LiquidStaking RewardsWithdrawals (r:0 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	fn withdraw_rewards() -> Weight {
+		Weight::from_ref_time(89_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking StakingLedgers (r:1 w:0)
+	// Storage: LiquidStaking PendingRewards (r:1 w:1)
+	// Storage: LiquidStaking RewardsWithdrawals (r:1 w:0)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	fn set_stash_rewards() -> Weight {
+		Weight::from_ref_time(52_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking StakingLedgers (r:1 w:1)
+	// Storage: LiquidStaking IsUpdated (r:1 w:1)
+	// Storage: LiquidStaking XcmRequests (r:1 w:0)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: LiquidStaking CommissionRate (r:1 w:0)
+	// Storage: LiquidStaking TotalReserves (r:1 w:1)
+	// Storage: LiquidStaking MatchingPool (r:1 w:1)
+	// Storage: LiquidStaking CurrentEra (r:1 w:0)
+	// Storage: LiquidStaking Slashes (r:1 w:1)
+	// Storage: LiquidStaking ExchangeRate (r:1 w:1)
+	fn set_staking_ledger() -> Weight {
+		Weight::from_ref_time(98_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -696,4 +893,186 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(4 as u64))
 			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(n as u64)))
 	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn add_validator() -> Weight {
+		Weight::from_ref_time(33_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn remove_validator() -> Weight {
+		Weight::from_ref_time(31_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn set_validator_prefs() -> Weight {
+		Weight::from_ref_time(49_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking CurrentEra (r:1 w:0)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn set_validator_exposure() -> Weight {
+		Weight::from_ref_time(62_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking CurrentEra (r:1 w:0)
+	// Storage: LiquidStaking LastRewardedEra (r:1 w:1)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn set_era_reward_points(n: u32, ) -> Weight {
+		Weight::from_ref_time(53_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(6_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking UnstakeReceipts (r:1 w:0)
+	// Storage: LiquidStaking ExchangeRate (r:1 w:0)
+	// Storage: LiquidStaking PendingReceiptTransfers (r:0 w:1)
+	fn transfer_unstake_receipt() -> Weight {
+		Weight::from_ref_time(29_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking ExchangeRate (r:1 w:0)
+	// Storage: LiquidStaking UnstakeReserveFactor (r:1 w:0)
+	// Storage: LiquidStaking FastUnstakeRequests (r:1 w:1)
+	// Storage: LiquidStaking MatchingPool (r:1 w:1)
+	// Storage: Loans Markets (r:2 w:0)
+	// Storage: AMM Pools (r:1 w:1)
+	// Storage: StableSwap Pools (r:1 w:0)
+	// Storage: Assets Asset (r:3 w:3)
+	// Storage: Assets Account (r:6 w:6)
+	fn instant_unstake() -> Weight {
+		Weight::from_ref_time(413_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(30 as u64))
+			.saturating_add(RocksDbWeight::get().writes(17 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking PendingPayouts (r:1 w:1)
+	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: LiquidStaking XcmRequests (r:0 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	fn payout_stakers() -> Weight {
+		Weight::from_ref_time(62_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking DistributionWeights (r:1 w:0)
+	// Storage: LiquidStaking CurrentDistributionStrategy (r:0 w:1)
+	fn set_distribution_strategy(n: u32, ) -> Weight {
+		Weight::from_ref_time(19_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(2_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking DistributionWeights (r:0 w:1)
+	fn set_distribution_weights(n: u32, ) -> Weight {
+		Weight::from_ref_time(23_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(4_000_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking PendingReceiptTransfers (r:1 w:1)
+	// Storage: LiquidStaking UnstakeReceipts (r:1 w:1)
+	// Storage: LiquidStaking UnstakeReceiptsOf (r:1 w:2)
+	fn accept_unstake_receipt() -> Weight {
+		Weight::from_ref_time(42_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking CurrentEra (r:1 w:1)
+	// Storage: ParachainSystem ValidationData (r:1 w:0)
+	// Storage: LiquidStaking StakingLedgers (r:3 w:0)
+	// Storage: LiquidStaking MatchingPool (r:1 w:0)
+	// Storage: Assets Asset (r:2 w:1)
+	// Storage: LiquidStaking ExchangeRate (r:1 w:1)
+	// Storage: LiquidStaking ExchangeRateHistory (r:1 w:1)
+	// Storage: LiquidStaking TotalReserves (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: LiquidStaking EraStartBlock (r:0 w:1)
+	// Storage: LiquidStaking IsMatched (r:0 w:1)
+	fn advance_era() -> Weight {
+		Weight::from_ref_time(188_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(15 as u64))
+			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking PendingRewards (r:1 w:1)
+	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: LiquidStaking XcmRequests (r:0 w:1)
+	// Storage: LiquidStaking RewardsWithdrawals (r:0 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	fn withdraw_rewards() -> Weight {
+		Weight::from_ref_time(89_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking StakingLedgers (r:1 w:0)
+	// Storage: LiquidStaking PendingRewards (r:1 w:1)
+	// Storage: LiquidStaking RewardsWithdrawals (r:1 w:0)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	fn set_stash_rewards() -> Weight {
+		Weight::from_ref_time(52_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: LiquidStaking StakingLedgers (r:1 w:1)
+	// Storage: LiquidStaking IsUpdated (r:1 w:1)
+	// Storage: LiquidStaking XcmRequests (r:1 w:0)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: LiquidStaking CommissionRate (r:1 w:0)
+	// Storage: LiquidStaking TotalReserves (r:1 w:1)
+	// Storage: LiquidStaking MatchingPool (r:1 w:1)
+	// Storage: LiquidStaking CurrentEra (r:1 w:0)
+	// Storage: LiquidStaking Slashes (r:1 w:1)
+	// Storage: LiquidStaking ExchangeRate (r:1 w:1)
+	fn set_staking_ledger() -> Weight {
+		Weight::from_ref_time(98_000_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(12 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+	}
 }
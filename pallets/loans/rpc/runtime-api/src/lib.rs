@@ -15,15 +15,20 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
+pub use pallet_traits::{AccountPositions, LiquidationCandidate, MarketPosition};
 use primitives::{CurrencyId, Liquidity, Rate, Ratio, Shortfall};
 use sp_runtime::{DispatchError, FixedU128};
+use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
+    #[api_version(2)]
     pub trait LoansApi<AccountId, Balance> where
         AccountId: Codec,
         Balance: Codec {
         fn get_account_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError>;
         fn get_market_status(asset_id: CurrencyId) -> Result<(Rate, Rate, Rate, Ratio, Balance, Balance, FixedU128), DispatchError>;
         fn get_liquidation_threshold_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError>;
+        fn get_account_positions(account: AccountId) -> Result<AccountPositions<Balance>, DispatchError>;
+        fn get_liquidation_candidates(start_key: Option<Vec<u8>>, limit: u32) -> Result<(Vec<LiquidationCandidate<AccountId, Balance>>, Option<Vec<u8>>), DispatchError>;
     }
 }
@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 pub use pallet_loans_rpc_runtime_api::LoansApi as LoansRuntimeApi;
+use pallet_loans_rpc_runtime_api::{AccountPositions, LiquidationCandidate, MarketPosition};
 
 use codec::Codec;
 use jsonrpsee::{
@@ -23,8 +24,9 @@ use jsonrpsee::{
     types::error::{CallError, ErrorCode, ErrorObject},
 };
 use primitives::{CurrencyId, Liquidity, Rate, Ratio, Shortfall};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT, FixedU128};
 
@@ -51,6 +53,22 @@ where
         account: AccountId,
         at: Option<BlockHash>,
     ) -> RpcResult<(Liquidity, Shortfall, Liquidity, Shortfall)>;
+    #[method(name = "loans_getAccountPositions")]
+    fn get_account_positions(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<AccountPositions<NumberOrHex>>;
+    #[method(name = "loans_getLiquidationCandidates")]
+    fn get_liquidation_candidates(
+        &self,
+        start_key: Option<Bytes>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<(
+        Vec<LiquidationCandidate<AccountId, NumberOrHex>>,
+        Option<Bytes>,
+    )>;
 }
 
 /// A struct that implements the [`LoansApi`].
@@ -73,6 +91,8 @@ pub enum Error {
     RuntimeError,
     AccountLiquidityError,
     MarketStatusError,
+    AccountPositionsError,
+    LiquidationCandidatesError,
 }
 
 impl From<Error> for i32 {
@@ -81,6 +101,8 @@ impl From<Error> for i32 {
             Error::RuntimeError => 1,
             Error::AccountLiquidityError => 2,
             Error::MarketStatusError => 3,
+            Error::AccountPositionsError => 4,
+            Error::LiquidationCandidatesError => 5,
         }
     }
 }
@@ -159,6 +181,110 @@ where
             .map_err(runtime_error_into_rpc_error)?
             .map_err(account_liquidity_error_into_rpc_error)
     }
+
+    fn get_account_positions(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<AccountPositions<NumberOrHex>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        ensure_api_version::<_, AccountId, Balance, _>(&*api, &at, "loans_getAccountPositions")?;
+        let account_positions = api
+            .get_account_positions(&at, account)
+            .map_err(runtime_error_into_rpc_error)?
+            .map_err(account_positions_error_into_rpc_error)?;
+        Ok(AccountPositions {
+            positions: account_positions
+                .positions
+                .into_iter()
+                .map(|position| {
+                    Ok(MarketPosition {
+                        asset_id: position.asset_id,
+                        supply_balance: try_into_rpc_balance(position.supply_balance)?,
+                        ptoken_balance: try_into_rpc_balance(position.ptoken_balance)?,
+                        borrow_balance: try_into_rpc_balance(position.borrow_balance)?,
+                        delegated_borrow_balance: try_into_rpc_balance(
+                            position.delegated_borrow_balance,
+                        )?,
+                        is_collateral: position.is_collateral,
+                        supply_value: position.supply_value,
+                        borrow_value: position.borrow_value,
+                        unclaimed_reward: try_into_rpc_balance(position.unclaimed_reward)?,
+                    })
+                })
+                .collect::<RpcResult<_>>()?,
+            unclaimed_reward: try_into_rpc_balance(account_positions.unclaimed_reward)?,
+            health_factor: account_positions.health_factor,
+        })
+    }
+
+    fn get_liquidation_candidates(
+        &self,
+        start_key: Option<Bytes>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(
+        Vec<LiquidationCandidate<AccountId, NumberOrHex>>,
+        Option<Bytes>,
+    )> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        ensure_api_version::<_, AccountId, Balance, _>(
+            &*api,
+            &at,
+            "loans_getLiquidationCandidates",
+        )?;
+        let (candidates, next_key) = api
+            .get_liquidation_candidates(&at, start_key.map(|key| key.0), limit)
+            .map_err(runtime_error_into_rpc_error)?
+            .map_err(liquidation_candidates_error_into_rpc_error)?;
+        let candidates = candidates
+            .into_iter()
+            .map(|candidate| {
+                Ok(LiquidationCandidate {
+                    borrower: candidate.borrower,
+                    shortfall: candidate.shortfall,
+                    liquidation_asset_id: candidate.liquidation_asset_id,
+                    collateral_asset_id: candidate.collateral_asset_id,
+                    max_repay_amount: try_into_rpc_balance(candidate.max_repay_amount)?,
+                })
+            })
+            .collect::<RpcResult<_>>()?;
+        Ok((candidates, next_key.map(Into::into)))
+    }
+}
+
+/// Ensures the runtime at `at` implements version 2 of `LoansApi`, which added
+/// the `method` RPC.
+fn ensure_api_version<Block, AccountId, Balance, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+    method: &str,
+) -> RpcResult<()>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    Balance: Codec,
+    Api: ApiExt<Block> + LoansRuntimeApi<Block, AccountId, Balance>,
+{
+    let version = api
+        .api_version::<dyn LoansRuntimeApi<Block, AccountId, Balance>>(at)
+        .map_err(runtime_error_into_rpc_error)?;
+    if version.map_or(true, |version| version < 2) {
+        return Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            Error::RuntimeError.into(),
+            format!("{} is not supported by the runtime", method),
+            None::<()>,
+        ))));
+    }
+    Ok(())
 }
 
 /// Converts a runtime trap into an RPC error.
@@ -188,6 +314,24 @@ fn market_status_error_into_rpc_error(err: impl std::fmt::Debug) -> JsonRpseeErr
     )))
 }
 
+/// Converts an account positions error into an RPC error.
+fn account_positions_error_into_rpc_error(err: impl std::fmt::Debug) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+        Error::AccountPositionsError.into(),
+        "Not able to get account positions",
+        Some(format!("{:?}", err)),
+    )))
+}
+
+/// Converts a liquidation candidates error into an RPC error.
+fn liquidation_candidates_error_into_rpc_error(err: impl std::fmt::Debug) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+        Error::LiquidationCandidatesError.into(),
+        "Not able to get liquidation candidates",
+        Some(format!("{:?}", err)),
+    )))
+}
+
 fn try_into_rpc_balance<T: std::fmt::Display + Copy + TryInto<NumberOrHex>>(
     value: T,
 ) -> RpcResult<NumberOrHex> {
@@ -0,0 +1,150 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use primitives::BlockNumber;
+use sp_runtime::{traits::Zero, DispatchResult};
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+    // Returns the liquidation incentive currently paid for the borrows of
+    // `borrower` in the market of `liquidation_asset_id`.
+    //
+    // In Dutch auction mode, the discount grows linearly with the number of
+    // blocks elapsed since the liquidation auction started. It is paid on top
+    // of the share of the seized collateral taken by the reserves, so that a
+    // liquidation breaks even for the liquidator when the auction starts.
+    pub(crate) fn current_liquidate_incentive(
+        borrower: &T::AccountId,
+        liquidation_asset_id: AssetIdOf<T>,
+        market: &Market<BalanceOf<T>>,
+    ) -> Result<Rate, DispatchError> {
+        match market.liquidation_mode {
+            LiquidationMode::Instant => Ok(market.liquidate_incentive),
+            LiquidationMode::DutchAuction {
+                max_discount,
+                duration,
+            } => {
+                let started_at = Self::liquidation_auctions(borrower, liquidation_asset_id)
+                    .ok_or(Error::<T>::LiquidationAuctionDoesNotExist)?;
+                let elapsed: BlockNumber = frame_system::Pallet::<T>::block_number()
+                    .saturating_sub(started_at)
+                    .saturated_into();
+                let discount = Ratio::from_rational(elapsed.min(duration), duration) * max_discount;
+                Ok(Rate::one()
+                    .saturating_add(Rate::from_inner(
+                        market
+                            .liquidate_incentive_reserved_factor
+                            .mul_floor(Rate::DIV),
+                    ))
+                    .saturating_add(Rate::from_inner(discount.mul_floor(Rate::DIV))))
+            }
+        }
+    }
+
+    // Ensures the parameters of a liquidation mode are sane.
+    pub(crate) fn ensure_valid_liquidation_mode(mode: &LiquidationMode) -> DispatchResult {
+        if let LiquidationMode::DutchAuction {
+            max_discount,
+            duration,
+        } = mode
+        {
+            ensure!(
+                !max_discount.is_zero() && !duration.is_zero(),
+                Error::<T>::InvalidLiquidationMode
+            );
+        }
+
+        Ok(())
+    }
+
+    // Starts the Dutch auction liquidating the borrows of an underwater `borrower`.
+    pub(crate) fn do_start_liquidation_auction(
+        borrower: &T::AccountId,
+        liquidation_asset_id: AssetIdOf<T>,
+    ) -> DispatchResult {
+        let market = Self::ensure_active_market(liquidation_asset_id)?;
+        ensure!(
+            matches!(
+                market.liquidation_mode,
+                LiquidationMode::DutchAuction { .. }
+            ),
+            Error::<T>::LiquidationAuctionNotEnabled
+        );
+        ensure!(
+            !LiquidationAuctions::<T>::contains_key(borrower, liquidation_asset_id),
+            Error::<T>::LiquidationAuctionAlreadyStarted
+        );
+        ensure!(
+            Self::is_liquidatable(borrower)?,
+            Error::<T>::InsufficientShortfall
+        );
+
+        let now = frame_system::Pallet::<T>::block_number();
+        LiquidationAuctions::<T>::insert(borrower, liquidation_asset_id, now);
+        Self::deposit_event(Event::<T>::LiquidationAuctionStarted(
+            borrower.clone(),
+            liquidation_asset_id,
+            now,
+        ));
+
+        Ok(())
+    }
+
+    // Closes the liquidation auction of `borrower`, which must not be
+    // liquidatable anymore.
+    pub(crate) fn do_close_liquidation_auction(
+        borrower: &T::AccountId,
+        liquidation_asset_id: AssetIdOf<T>,
+    ) -> DispatchResult {
+        ensure!(
+            LiquidationAuctions::<T>::contains_key(borrower, liquidation_asset_id),
+            Error::<T>::LiquidationAuctionDoesNotExist
+        );
+        ensure!(
+            !Self::is_liquidatable(borrower)?,
+            Error::<T>::BorrowerStillLiquidatable
+        );
+        Self::remove_liquidation_auction(borrower, liquidation_asset_id);
+
+        Ok(())
+    }
+
+    // Closes the liquidation auctions of `borrower` once it is no longer
+    // liquidatable, so that a later shortfall starts a new auction instead of
+    // inheriting the discount of a stale one.
+    pub(crate) fn close_recovered_liquidation_auctions(borrower: &T::AccountId) -> DispatchResult {
+        let auctions: Vec<AssetIdOf<T>> =
+            LiquidationAuctions::<T>::iter_key_prefix(borrower).collect();
+        if auctions.is_empty() || Self::is_liquidatable(borrower)? {
+            return Ok(());
+        }
+        for asset_id in auctions {
+            Self::remove_liquidation_auction(borrower, asset_id);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn remove_liquidation_auction(
+        borrower: &T::AccountId,
+        liquidation_asset_id: AssetIdOf<T>,
+    ) {
+        LiquidationAuctions::<T>::remove(borrower, liquidation_asset_id);
+        Self::deposit_event(Event::<T>::LiquidationAuctionClosed(
+            borrower.clone(),
+            liquidation_asset_id,
+        ));
+    }
+}
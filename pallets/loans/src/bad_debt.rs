@@ -0,0 +1,124 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sp_runtime::{traits::Zero, DispatchResult};
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+    // Returns whether `account` still has some collateral in any market. The dust
+    // a liquidation leaves behind because of rounding, worth less than
+    // `CollateralDustValue`, doesn't count.
+    pub(crate) fn has_collateral(account: &T::AccountId) -> bool {
+        Markets::<T>::iter_keys().any(|asset_id| {
+            let deposits = Self::account_deposits(asset_id, account);
+            if !deposits.is_collateral || deposits.voucher_balance.is_zero() {
+                return false;
+            }
+            // collateral which can't be valued is not known to be dust
+            Self::exchange_rate_stored(asset_id)
+                .and_then(|rate| Self::calc_underlying_amount(deposits.voucher_balance, rate))
+                .and_then(|amount| Self::get_asset_value(asset_id, amount))
+                .map_or(true, |value| {
+                    value.into_inner() >= T::CollateralDustValue::get()
+                })
+        })
+    }
+
+    // Writes off the borrows of `borrower` in `asset_id`, which are no longer
+    // backed by any collateral. This includes the borrows its delegatees drew
+    // against its collateral, but not the ones it drew against its delegators'.
+    //
+    // The debt is covered by the market reserves first, then by the insurance
+    // account if any, and the remainder is socialized across the suppliers by
+    // lowering the exchange rate.
+    #[require_transactional]
+    pub(crate) fn do_write_off_bad_debt(
+        borrower: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+    ) -> DispatchResult {
+        ensure!(
+            !Self::has_collateral(borrower),
+            Error::<T>::BorrowerHasCollateral
+        );
+        let bad_debt = Self::current_backed_borrow_balance(borrower, asset_id)?;
+        ensure!(!bad_debt.is_zero(), Error::<T>::NoBadDebt);
+
+        Self::update_reward_borrow_index(asset_id)?;
+        Self::distribute_borrower_reward(asset_id, borrower)?;
+
+        let total_reserves = Self::total_reserves(asset_id);
+        let covered_by_reserves = bad_debt.min(total_reserves);
+        TotalReserves::<T>::insert(asset_id, total_reserves - covered_by_reserves);
+        let mut remaining = bad_debt - covered_by_reserves;
+
+        let mut covered_by_insurance: BalanceOf<T> = Zero::zero();
+        if let Some(insurance_account) = T::InsuranceAccount::get() {
+            covered_by_insurance = remaining.min(T::Assets::reducible_balance(
+                asset_id,
+                &insurance_account,
+                false,
+            ));
+            if !covered_by_insurance.is_zero() {
+                T::Assets::transfer(
+                    asset_id,
+                    &insurance_account,
+                    &Self::account_id(),
+                    covered_by_insurance,
+                    false,
+                )?;
+            }
+            remaining -= covered_by_insurance;
+        }
+
+        Self::decrease_isolated_debt(borrower, asset_id, bad_debt)?;
+        let own_bad_debt = Self::current_own_borrow_balance(borrower, asset_id)?;
+        Self::reduce_delegated_borrows(borrower, asset_id, bad_debt - own_bad_debt)?;
+        let account_borrows = Self::current_borrow_balance(borrower, asset_id)?;
+        if account_borrows == own_bad_debt {
+            AccountBorrows::<T>::remove(asset_id, borrower);
+        } else {
+            AccountBorrows::<T>::insert(
+                asset_id,
+                borrower,
+                BorrowSnapshot {
+                    principal: account_borrows - own_bad_debt,
+                    borrow_index: Self::borrow_index(asset_id),
+                },
+            );
+        }
+        // total_borrows can be less than the borrow balance of the account, see
+        // `do_repay_borrow_with_amount`
+        TotalBorrows::<T>::mutate(asset_id, |total_borrows| {
+            *total_borrows = total_borrows.saturating_sub(bad_debt)
+        });
+        if LiquidationAuctions::<T>::contains_key(borrower, asset_id) {
+            Self::remove_liquidation_auction(borrower, asset_id);
+        }
+
+        // Fails if the suppliers can't absorb the remaining debt without the
+        // exchange rate going below its minimum.
+        ExchangeRate::<T>::insert(asset_id, Self::exchange_rate_stored(asset_id)?);
+
+        Self::deposit_event(Event::<T>::BadDebtWrittenOff(
+            borrower.clone(),
+            asset_id,
+            covered_by_reserves,
+            covered_by_insurance,
+            remaining,
+        ));
+
+        Ok(())
+    }
+}
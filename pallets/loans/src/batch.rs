@@ -0,0 +1,90 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sp_runtime::{traits::Zero, DispatchResult};
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+    pub(crate) fn batch_actions_weight(actions: &[LoanAction<BalanceOf<T>>]) -> Weight {
+        actions.iter().fold(Weight::zero(), |weight, action| {
+            weight.saturating_add(match action {
+                LoanAction::Mint { .. } => T::WeightInfo::mint(),
+                LoanAction::Redeem { .. } => T::WeightInfo::redeem(),
+                LoanAction::Borrow { .. } => T::WeightInfo::borrow(),
+                LoanAction::Repay { .. } => T::WeightInfo::repay_borrow(),
+                LoanAction::Collateral { .. } => T::WeightInfo::collateral_asset(),
+            })
+        })
+    }
+
+    // Executes `actions` in order for `who` without checking the liquidity in
+    // between, then ensures the final state is healthy.
+    #[require_transactional]
+    pub(crate) fn do_batch_actions(
+        who: &T::AccountId,
+        actions: &[LoanAction<BalanceOf<T>>],
+        min_health_factor: FixedU128,
+    ) -> DispatchResult {
+        for action in actions {
+            match *action {
+                LoanAction::Mint { asset_id, amount } => Self::do_mint(who, asset_id, amount)?,
+                LoanAction::Redeem { asset_id, amount } => {
+                    ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+                    Self::ensure_active_market(asset_id)?;
+                    Self::accrue_interest(asset_id)?;
+                    let exchange_rate = Self::exchange_rate_stored(asset_id)?;
+                    Self::update_earned_stored(who, asset_id, exchange_rate)?;
+                    let voucher_amount = Self::calc_collateral_amount(amount, exchange_rate)?;
+                    let redeem_amount =
+                        Self::do_redeem_voucher_internal(who, asset_id, voucher_amount, false)?;
+                    Self::deposit_event(Event::<T>::Redeemed(who.clone(), asset_id, redeem_amount));
+                }
+                LoanAction::Borrow { asset_id, amount } => {
+                    Self::do_borrow_internal(who, who, asset_id, amount, false)?
+                }
+                LoanAction::Repay { asset_id, amount } => {
+                    Self::do_repay_borrow(who, asset_id, amount)?
+                }
+                LoanAction::Collateral { asset_id, enable } => {
+                    if Self::account_deposits(asset_id, who).is_collateral == enable {
+                        return Err(Error::<T>::DuplicateOperation.into());
+                    }
+                    Self::do_collateral_asset_internal(who, asset_id, enable, false)?
+                }
+            }
+        }
+
+        Self::ensure_healthy(who, min_health_factor)
+    }
+
+    // Ensures the borrows of `account` are covered by its collaterals, the same way
+    // as `is_liquidatable` does with the collateral factors, and that its health
+    // factor is at least `min_health_factor`.
+    fn ensure_healthy(account: &T::AccountId, min_health_factor: FixedU128) -> DispatchResult {
+        let (liquidity, shortfall, lf_liquidity, _) = Self::get_account_liquidity(account)?;
+        ensure!(
+            shortfall.is_zero() && liquidity >= lf_liquidity,
+            Error::<T>::InsufficientLiquidity
+        );
+        if let Some(health_factor) = Self::health_factor(account)? {
+            ensure!(
+                health_factor >= min_health_factor,
+                Error::<T>::HealthFactorTooLow
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -23,6 +23,16 @@ const RATE_MODEL_MOCK: InterestRateModel = InterestRateModel::Jump(JumpModel {
     jump_utilization: Ratio::from_percent(80),
 });
 
+const DUTCH_AUCTION_MOCK: LiquidationMode = LiquidationMode::DutchAuction {
+    max_discount: Ratio::from_percent(10),
+    duration: 100,
+};
+
+const EMODE_CATEGORY_MOCK: EModeCategory = EModeCategory {
+    collateral_factor: Ratio::from_percent(90),
+    liquidation_threshold: Ratio::from_percent(93),
+};
+
 fn market_mock<T: Config>() -> Market<BalanceOf<T>> {
     Market {
         close_factor: Ratio::from_percent(50),
@@ -41,6 +51,11 @@ fn market_mock<T: Config>() -> Market<BalanceOf<T>> {
         supply_cap: 1_000_000_000_000_000_000_000u128, // set to 1B
         borrow_cap: 1_000_000_000_000_000_000_000u128, // set to 1B
         ptoken_id: 1200,
+        isolated: false,
+        debt_ceiling: 0,
+        borrowable_in_isolation: false,
+        flash_loan_fee: Ratio::from_parts(900),
+        liquidation_mode: LiquidationMode::Instant,
     }
 }
 
@@ -227,7 +242,9 @@ benchmarks! {
         Some(Ratio::from_percent(3)),
         Some(Rate::from_inner(Rate::DIV / 100 * 110)),
         Some(1_000_000_000_000_000_000_000u128),
-        Some(1_000_000_000_000_000_000_000u128)
+        Some(1_000_000_000_000_000_000_000u128),
+        Some(Ratio::from_parts(900)),
+        Some(LiquidationMode::Instant)
     )
     verify {
         let mut market = pending_market_mock::<T>(PKSM);
@@ -447,6 +464,152 @@ benchmarks! {
     verify {
         assert_last_event::<T>(Event::<T>::LiquidationFreeCollateralsUpdated(vec![CDOT_6_13]).into());
     }
+
+    update_market_isolation {
+        assert_ok!(Loans::<T>::add_market(SystemOrigin::Root.into(), SKSM, pending_market_mock::<T>(PSKSM)));
+    }: _(SystemOrigin::Root, SKSM, Some(true), Some(1_000_000_000_000_000_000_000u128), Some(false))
+    verify {
+        let mut market = pending_market_mock::<T>(PSKSM);
+        market.isolated = true;
+        market.debt_ceiling = 1_000_000_000_000_000_000_000u128;
+        assert_last_event::<T>(Event::<T>::UpdatedMarket(SKSM, market).into());
+    }
+
+    set_emode_category {
+    }: _(SystemOrigin::Root, 1, EMODE_CATEGORY_MOCK)
+    verify {
+        assert_last_event::<T>(Event::<T>::EModeCategorySet(1, EMODE_CATEGORY_MOCK).into());
+    }
+
+    set_asset_emode_category {
+        assert_ok!(Loans::<T>::add_market(SystemOrigin::Root.into(), SKSM, pending_market_mock::<T>(PSKSM)));
+        assert_ok!(Loans::<T>::set_emode_category(SystemOrigin::Root.into(), 1, EMODE_CATEGORY_MOCK));
+    }: _(SystemOrigin::Root, SKSM, Some(1))
+    verify {
+        assert_last_event::<T>(Event::<T>::AssetEModeCategoryUpdated(SKSM, Some(1)).into());
+    }
+
+    flash_loan {
+        let caller: T::AccountId = whitelisted_caller();
+        transfer_initial_balance::<T>(caller.clone());
+        let deposit_amount: u32 = 200_000_000;
+        let loan_amount: u32 = 100_000_000;
+        assert_ok!(Loans::<T>::add_market(SystemOrigin::Root.into(), USDT, pending_market_mock::<T>(PUSDT)));
+        assert_ok!(Loans::<T>::activate_market(SystemOrigin::Root.into(), USDT));
+        assert_ok!(Loans::<T>::mint(SystemOrigin::Signed(caller.clone()).into(), USDT, deposit_amount.into()));
+        let data = <T as frame_system::Config>::RuntimeCall::from(
+            frame_system::Call::<T>::remark { remark: vec![] }
+        ).encode();
+        let fee = pending_market_mock::<T>(PUSDT).flash_loan_fee.mul_ceil(loan_amount as u128);
+    }: _(SystemOrigin::Signed(caller.clone()), USDT, loan_amount.into(), data)
+    verify {
+        assert_last_event::<T>(Event::<T>::FlashLoaned(caller, USDT, loan_amount.into(), fee).into());
+    }
+
+    start_liquidation_auction {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        let bob: T::AccountId = account("Sample", 101, SEED);
+        transfer_initial_balance::<T>(alice.clone());
+        transfer_initial_balance::<T>(bob.clone());
+        let deposit_amount: u32 = 200_000_000;
+        let borrowed_amount: u32 = 200_000_000;
+        let mut market = pending_market_mock::<T>(PKSM);
+        market.liquidation_mode = DUTCH_AUCTION_MOCK;
+        assert_ok!(Loans::<T>::add_market(SystemOrigin::Root.into(), SKSM, pending_market_mock::<T>(PSKSM)));
+        assert_ok!(Loans::<T>::activate_market(SystemOrigin::Root.into(), SKSM));
+        assert_ok!(Loans::<T>::add_market(SystemOrigin::Root.into(), KSM, market));
+        assert_ok!(Loans::<T>::activate_market(SystemOrigin::Root.into(), KSM));
+        assert_ok!(Loans::<T>::mint(SystemOrigin::Signed(bob.clone()).into(), KSM, deposit_amount.into()));
+        assert_ok!(Loans::<T>::mint(SystemOrigin::Signed(alice.clone()).into(), SKSM, deposit_amount.into()));
+        assert_ok!(Loans::<T>::collateral_asset(SystemOrigin::Signed(alice.clone()).into(), SKSM, true));
+        set_account_borrows::<T>(alice.clone(), KSM, borrowed_amount.into());
+    }: _(SystemOrigin::Signed(bob.clone()), alice.clone(), KSM)
+    verify {
+        let now = frame_system::Pallet::<T>::block_number();
+        assert_last_event::<T>(Event::<T>::LiquidationAuctionStarted(alice, KSM, now).into());
+    }
+
+    close_liquidation_auction {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        let bob: T::AccountId = account("Sample", 101, SEED);
+        transfer_initial_balance::<T>(alice.clone());
+        let deposit_amount: u32 = 200_000_000;
+        let mut market = pending_market_mock::<T>(PKSM);
+        market.liquidation_mode = DUTCH_AUCTION_MOCK;
+        assert_ok!(Loans::<T>::add_market(SystemOrigin::Root.into(), KSM, market));
+        assert_ok!(Loans::<T>::activate_market(SystemOrigin::Root.into(), KSM));
+        assert_ok!(Loans::<T>::mint(SystemOrigin::Signed(alice.clone()).into(), KSM, deposit_amount.into()));
+        assert_ok!(Loans::<T>::collateral_asset(SystemOrigin::Signed(alice.clone()).into(), KSM, true));
+        LiquidationAuctions::<T>::insert(&alice, KSM, frame_system::Pallet::<T>::block_number());
+    }: _(SystemOrigin::Signed(bob), alice.clone(), KSM)
+    verify {
+        assert_last_event::<T>(Event::<T>::LiquidationAuctionClosed(alice, KSM).into());
+    }
+
+    write_off_bad_debt {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        let bob: T::AccountId = account("Sample", 101, SEED);
+        transfer_initial_balance::<T>(alice.clone());
+        transfer_initial_balance::<T>(bob.clone());
+        let deposit_amount: u32 = 200_000_000;
+        let reserves_amount: u32 = 50_000_000;
+        let borrowed_amount: u32 = 100_000_000;
+        assert_ok!(Loans::<T>::add_market(SystemOrigin::Root.into(), USDT, pending_market_mock::<T>(PUSDT)));
+        assert_ok!(Loans::<T>::activate_market(SystemOrigin::Root.into(), USDT));
+        assert_ok!(Loans::<T>::mint(SystemOrigin::Signed(bob.clone()).into(), USDT, deposit_amount.into()));
+        assert_ok!(Loans::<T>::add_reserves(SystemOrigin::Root.into(), T::Lookup::unlookup(bob), USDT, reserves_amount.into()));
+        set_account_borrows::<T>(alice.clone(), USDT, borrowed_amount.into());
+    }: _(SystemOrigin::Root, alice.clone(), USDT)
+    verify {
+        assert_eq!(AccountBorrows::<T>::get(USDT, &alice).principal, 0);
+    }
+
+    approve_delegation {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        let bob: T::AccountId = account("Sample", 101, SEED);
+        let allowance: u32 = 100_000_000;
+        assert_ok!(Loans::<T>::add_market(SystemOrigin::Root.into(), USDT, pending_market_mock::<T>(PUSDT)));
+        assert_ok!(Loans::<T>::activate_market(SystemOrigin::Root.into(), USDT));
+    }: _(SystemOrigin::Signed(alice.clone()), bob.clone(), USDT, allowance.into())
+    verify {
+        assert_last_event::<T>(Event::<T>::DelegationApproved(alice, bob, USDT, allowance.into()).into());
+    }
+
+    borrow_on_behalf {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        let bob: T::AccountId = account("Sample", 101, SEED);
+        transfer_initial_balance::<T>(alice.clone());
+        let deposit_amount: u32 = 200_000_000;
+        let borrowed_amount: u32 = 100_000_000;
+        assert_ok!(Loans::<T>::add_market(SystemOrigin::Root.into(), USDT, pending_market_mock::<T>(PUSDT)));
+        assert_ok!(Loans::<T>::activate_market(SystemOrigin::Root.into(), USDT));
+        assert_ok!(Loans::<T>::mint(SystemOrigin::Signed(alice.clone()).into(), USDT, deposit_amount.into()));
+        assert_ok!(Loans::<T>::collateral_asset(SystemOrigin::Signed(alice.clone()).into(), USDT, true));
+        assert_ok!(Loans::<T>::approve_delegation(SystemOrigin::Signed(alice.clone()).into(), bob.clone(), USDT, borrowed_amount.into()));
+    }: _(SystemOrigin::Signed(bob.clone()), alice.clone(), USDT, borrowed_amount.into())
+    verify {
+        assert_last_event::<T>(Event::<T>::BorrowedOnBehalf(bob, alice, USDT, borrowed_amount.into()).into());
+    }
+
+    repay_on_behalf {
+        let alice: T::AccountId = account("Sample", 100, SEED);
+        let bob: T::AccountId = account("Sample", 101, SEED);
+        transfer_initial_balance::<T>(alice.clone());
+        transfer_initial_balance::<T>(bob.clone());
+        let deposit_amount: u32 = 200_000_000;
+        let borrowed_amount: u32 = 100_000_000;
+        let repay_amount: u32 = 100;
+        assert_ok!(Loans::<T>::add_market(SystemOrigin::Root.into(), USDT, pending_market_mock::<T>(PUSDT)));
+        assert_ok!(Loans::<T>::activate_market(SystemOrigin::Root.into(), USDT));
+        assert_ok!(Loans::<T>::mint(SystemOrigin::Signed(alice.clone()).into(), USDT, deposit_amount.into()));
+        assert_ok!(Loans::<T>::collateral_asset(SystemOrigin::Signed(alice.clone()).into(), USDT, true));
+        assert_ok!(Loans::<T>::approve_delegation(SystemOrigin::Signed(alice.clone()).into(), bob.clone(), USDT, borrowed_amount.into()));
+        assert_ok!(Loans::<T>::borrow_on_behalf(SystemOrigin::Signed(bob.clone()).into(), alice.clone(), USDT, borrowed_amount.into()));
+    }: _(SystemOrigin::Signed(bob.clone()), alice.clone(), USDT, repay_amount.into())
+    verify {
+        assert_last_event::<T>(Event::<T>::RepaidOnBehalf(bob, alice, USDT, repay_amount.into()).into());
+    }
+
 }
 
 impl_benchmark_test_suite!(Loans, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,233 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sp_runtime::{traits::Zero, DispatchResult};
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+    /// Returns the borrows of `delegatee` in `asset_id` drawn against the collateral of
+    /// `delegator`, with the interest accrued up to the last accrual of the market.
+    pub fn current_delegated_borrow_balance(
+        asset_id: AssetIdOf<T>,
+        delegator: &T::AccountId,
+        delegatee: &T::AccountId,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        Self::snapshot_borrow_balance(
+            asset_id,
+            Self::delegated_borrows((asset_id, delegator, delegatee)),
+        )
+    }
+
+    // Returns the borrows of `account` in `asset_id` which are backed by its own
+    // collateral: its borrows, except the ones drawn against the collateral of its
+    // delegators, and the borrows its delegatees drew against its collateral.
+    pub(crate) fn current_backed_borrow_balance(
+        account: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let mut backed_borrows = Self::current_own_borrow_balance(account, asset_id)?;
+        for (_, snapshot) in DelegatedBorrows::<T>::iter_prefix((asset_id, account)) {
+            backed_borrows = backed_borrows
+                .checked_add(Self::snapshot_borrow_balance(asset_id, snapshot)?)
+                .ok_or(ArithmeticError::Overflow)?;
+        }
+
+        Ok(backed_borrows)
+    }
+
+    // Returns the borrows of `account` in `asset_id`, except the ones drawn against the
+    // collateral of its delegators.
+    pub(crate) fn current_own_borrow_balance(
+        account: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let mut own_borrows = Self::current_borrow_balance(account, asset_id)?;
+        for (delegator, _) in Delegators::<T>::iter_prefix((asset_id, account)) {
+            // Both snapshots accrue with the same index, up to rounding
+            own_borrows = own_borrows.saturating_sub(Self::current_delegated_borrow_balance(
+                asset_id, &delegator, account,
+            )?);
+        }
+
+        Ok(own_borrows)
+    }
+
+    // Borrows `amount` of `asset_id` for `delegatee`, spending its allowance.
+    //
+    // The debt is owed by `delegatee`, while the liquidity is checked against
+    // `delegator`, whose collateral backs the borrow.
+    #[require_transactional]
+    pub(crate) fn do_borrow_on_behalf(
+        delegatee: &T::AccountId,
+        delegator: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        BorrowAllowances::<T>::try_mutate_exists(
+            (asset_id, delegator, delegatee),
+            |allowance| -> DispatchResult {
+                let remaining = allowance
+                    .unwrap_or_default()
+                    .checked_sub(amount)
+                    .ok_or(Error::<T>::InsufficientBorrowAllowance)?;
+                *allowance = if remaining.is_zero() {
+                    None
+                } else {
+                    Some(remaining)
+                };
+                Ok(())
+            },
+        )?;
+        Self::ensure_active_market(asset_id)?;
+        Self::accrue_interest(asset_id)?;
+        Self::borrow_allowed(asset_id, delegator, amount)?;
+
+        // update borrow index after accrue interest.
+        Self::update_reward_borrow_index(asset_id)?;
+        Self::distribute_borrower_reward(asset_id, delegatee)?;
+
+        let account_borrows_new = Self::current_borrow_balance(delegatee, asset_id)?
+            .checked_add(amount)
+            .ok_or(ArithmeticError::Overflow)?;
+        let total_borrows_new = Self::total_borrows(asset_id)
+            .checked_add(amount)
+            .ok_or(ArithmeticError::Overflow)?;
+        AccountBorrows::<T>::insert(
+            asset_id,
+            delegatee,
+            BorrowSnapshot {
+                principal: account_borrows_new,
+                borrow_index: Self::borrow_index(asset_id),
+            },
+        );
+        TotalBorrows::<T>::insert(asset_id, total_borrows_new);
+        Self::increase_isolated_debt(delegator, asset_id, amount)?;
+
+        let delegated_borrows =
+            Self::current_delegated_borrow_balance(asset_id, delegator, delegatee)?
+                .checked_add(amount)
+                .ok_or(ArithmeticError::Overflow)?;
+        Self::update_delegated_borrows(asset_id, delegator, delegatee, delegated_borrows);
+        T::Assets::transfer(asset_id, &Self::account_id(), delegatee, amount, false)?;
+        Self::deposit_event(Event::<T>::BorrowedOnBehalf(
+            delegatee.clone(),
+            delegator.clone(),
+            asset_id,
+            amount,
+        ));
+
+        Ok(())
+    }
+
+    // Repays `amount` of the borrows of `delegatee` drawn against the collateral of
+    // `delegator`, paid by `delegatee`.
+    #[require_transactional]
+    pub(crate) fn do_repay_on_behalf(
+        delegatee: &T::AccountId,
+        delegator: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        Self::ensure_active_market(asset_id)?;
+        Self::accrue_interest(asset_id)?;
+        let delegated_borrows =
+            Self::current_delegated_borrow_balance(asset_id, delegator, delegatee)?;
+        ensure!(amount <= delegated_borrows, Error::<T>::TooMuchRepay);
+
+        // The delegated borrows may exceed the account borrows by a rounding error
+        let account_borrows = Self::current_borrow_balance(delegatee, asset_id)?;
+        Self::do_repay_borrow_with_amount(
+            delegatee,
+            delegatee,
+            asset_id,
+            account_borrows,
+            amount.min(account_borrows),
+        )?;
+        Self::decrease_isolated_debt(delegator, asset_id, amount)?;
+        Self::update_delegated_borrows(asset_id, delegator, delegatee, delegated_borrows - amount);
+        Self::deposit_event(Event::<T>::RepaidOnBehalf(
+            delegatee.clone(),
+            delegator.clone(),
+            asset_id,
+            amount,
+        ));
+
+        Ok(())
+    }
+
+    // Reduces by `amount` the borrows drawn by the delegatees of `delegator` in
+    // `asset_id`, which were repaid or written off on the behalf of `delegator`.
+    //
+    // The total borrows of the market are left to the caller.
+    #[require_transactional]
+    pub(crate) fn reduce_delegated_borrows(
+        delegator: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        let mut remaining = amount;
+        let delegated_borrows: Vec<_> =
+            DelegatedBorrows::<T>::iter_prefix((asset_id, delegator)).collect();
+        for (delegatee, snapshot) in delegated_borrows {
+            if remaining.is_zero() {
+                break;
+            }
+            let delegated_amount = Self::snapshot_borrow_balance(asset_id, snapshot)?;
+            let reduced_amount = remaining.min(delegated_amount);
+
+            Self::distribute_borrower_reward(asset_id, &delegatee)?;
+            let account_borrows = Self::current_borrow_balance(&delegatee, asset_id)?;
+            AccountBorrows::<T>::insert(
+                asset_id,
+                &delegatee,
+                BorrowSnapshot {
+                    principal: account_borrows.saturating_sub(reduced_amount),
+                    borrow_index: Self::borrow_index(asset_id),
+                },
+            );
+            Self::update_delegated_borrows(
+                asset_id,
+                delegator,
+                &delegatee,
+                delegated_amount - reduced_amount,
+            );
+            remaining -= reduced_amount;
+        }
+        ensure!(remaining.is_zero(), Error::<T>::TooMuchRepay);
+
+        Ok(())
+    }
+
+    fn update_delegated_borrows(
+        asset_id: AssetIdOf<T>,
+        delegator: &T::AccountId,
+        delegatee: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) {
+        if amount.is_zero() {
+            DelegatedBorrows::<T>::remove((asset_id, delegator, delegatee));
+            Delegators::<T>::remove((asset_id, delegatee, delegator));
+        } else {
+            DelegatedBorrows::<T>::insert(
+                (asset_id, delegator, delegatee),
+                BorrowSnapshot {
+                    principal: amount,
+                    borrow_index: Self::borrow_index(asset_id),
+                },
+            );
+            Delegators::<T>::insert((asset_id, delegatee, delegator), ());
+        }
+    }
+}
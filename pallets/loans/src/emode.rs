@@ -0,0 +1,83 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sp_runtime::traits::Zero;
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+    // Returns the efficiency category shared by all the collaterals and borrows of
+    // `account`, treating `extra_asset_id` as one more position if provided.
+    //
+    // Returns `None` if the account has no position, or if any of its positions is
+    // outside of the category.
+    pub(crate) fn account_emode_category(
+        account: &T::AccountId,
+        extra_asset_id: Option<AssetIdOf<T>>,
+    ) -> Option<EModeCategory> {
+        let mut category_id: Option<EModeCategoryId> = None;
+        for (asset_id, _) in Self::active_markets() {
+            let deposits = Self::account_deposits(asset_id, account);
+            let has_collateral = deposits.is_collateral && !deposits.voucher_balance.is_zero();
+            let has_borrow = Self::current_backed_borrow_balance(account, asset_id)
+                .map_or(false, |borrows| !borrows.is_zero());
+            if !has_collateral && !has_borrow && extra_asset_id != Some(asset_id) {
+                continue;
+            }
+
+            let asset_category_id = AssetEModeCategory::<T>::get(asset_id)?;
+            match category_id {
+                Some(id) if id != asset_category_id => return None,
+                _ => category_id = Some(asset_category_id),
+            }
+        }
+
+        category_id.and_then(EModeCategories::<T>::get)
+    }
+
+    // Returns the collateral factor of `market` for an account in `emode`.
+    pub(crate) fn effective_collateral_factor(
+        market: &Market<BalanceOf<T>>,
+        emode: Option<EModeCategory>,
+    ) -> Ratio {
+        emode
+            .map(|category| category.collateral_factor)
+            .unwrap_or(market.collateral_factor)
+    }
+
+    // Returns the liquidation threshold of `market` for an account in `emode`.
+    pub(crate) fn effective_liquidation_threshold(
+        market: &Market<BalanceOf<T>>,
+        emode: Option<EModeCategory>,
+    ) -> Ratio {
+        emode
+            .map(|category| category.liquidation_threshold)
+            .unwrap_or(market.liquidation_threshold)
+    }
+
+    // Ensures the parameters of an efficiency category are sane.
+    pub(crate) fn ensure_valid_emode_category(category: &EModeCategory) -> DispatchResult {
+        ensure!(
+            category.collateral_factor < Ratio::one(),
+            Error::<T>::InvalidFactor
+        );
+        ensure!(
+            category.liquidation_threshold >= category.collateral_factor
+                && category.liquidation_threshold < Ratio::one(),
+            Error::<T>::InvalidFactor
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::DecodeLimit;
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use pallet_traits::FlashLoanReceiver;
+use sp_runtime::{traits::Zero, DispatchResult};
+
+use crate::*;
+
+/// Maximum depth of nested calls accepted by [`FlashLoanCallDispatcher`].
+const MAX_CALL_DECODE_DEPTH: u32 = 256;
+
+/// Flash loan receiver which decodes the operation data as a runtime call and
+/// dispatches it with the signed origin of the receiver.
+pub struct FlashLoanCallDispatcher<Call>(PhantomData<Call>);
+
+impl<AccountId, Call> FlashLoanReceiver<AccountId, CurrencyId, Balance>
+    for FlashLoanCallDispatcher<Call>
+where
+    AccountId: Clone,
+    Call: Decode + GetDispatchInfo + Dispatchable<PostInfo = PostDispatchInfo>,
+    Call::RuntimeOrigin: From<frame_system::RawOrigin<AccountId>>,
+{
+    fn weight(data: &[u8]) -> Weight {
+        Call::decode_with_depth_limit(MAX_CALL_DECODE_DEPTH, &mut &data[..])
+            .map(|call| call.get_dispatch_info().weight)
+            .unwrap_or_default()
+    }
+
+    fn execute_operation(
+        receiver: &AccountId,
+        _asset_id: CurrencyId,
+        _amount: Balance,
+        _fee: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), DispatchError> {
+        let call = Call::decode_with_depth_limit(MAX_CALL_DECODE_DEPTH, &mut &data[..])
+            .map_err(|_| DispatchError::Other("invalid flash loan call"))?;
+        call.dispatch(frame_system::RawOrigin::Signed(receiver.clone()).into())
+            .map(|_| ())
+            .map_err(|e| e.error)
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    // Lends `amount` of the idle cash of `asset_id` to `receiver`, executes the
+    // operation described by `data` and takes back the loan plus the flash loan
+    // fee, which is added to the market reserves.
+    #[require_transactional]
+    pub(crate) fn do_flash_loan(
+        receiver: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+        amount: BalanceOf<T>,
+        data: Vec<u8>,
+    ) -> DispatchResult {
+        ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+        let market = Self::ensure_active_market(asset_id)?;
+        ensure!(
+            Self::outstanding_flash_loan(asset_id).is_zero(),
+            Error::<T>::FlashLoanInProgress
+        );
+        Self::accrue_interest(asset_id)?;
+        Self::ensure_enough_cash(asset_id, amount)?;
+
+        let fee = market.flash_loan_fee.mul_ceil(amount);
+        let repay_amount = amount.checked_add(fee).ok_or(ArithmeticError::Overflow)?;
+        let pallet_account = Self::account_id();
+
+        // The lent cash is still accounted as cash of the market, so that the
+        // exchange rate can't be manipulated while the loan is outstanding.
+        OutstandingFlashLoan::<T>::insert(asset_id, amount);
+        T::Assets::transfer(asset_id, &pallet_account, receiver, amount, false)?;
+        T::FlashLoanReceiver::execute_operation(receiver, asset_id, amount, fee, data)?;
+        T::Assets::transfer(asset_id, receiver, &pallet_account, repay_amount, false)?;
+        OutstandingFlashLoan::<T>::remove(asset_id);
+
+        TotalReserves::<T>::try_mutate(asset_id, |total_reserves| -> DispatchResult {
+            *total_reserves = total_reserves
+                .checked_add(fee)
+                .ok_or(ArithmeticError::Overflow)?;
+            Ok(())
+        })?;
+
+        Self::deposit_event(Event::<T>::FlashLoaned(
+            receiver.clone(),
+            asset_id,
+            amount,
+            fee,
+        ));
+
+        Ok(())
+    }
+}
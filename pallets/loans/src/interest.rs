@@ -41,6 +41,15 @@ impl<T: Config> Pallet<T> {
             borrow_index_new,
         ) = Self::get_market_status(asset_id)?;
 
+        let market = Self::market(asset_id)?;
+        if let Some(rate_at_target) = Self::next_rate_at_target(
+            asset_id,
+            &market.rate_model,
+            util,
+            now - last_accrued_interest_time,
+        )? {
+            RateAtTarget::<T>::insert(asset_id, rate_at_target);
+        }
         Self::update_last_accrued_interest_time(asset_id, now)?;
         TotalBorrows::<T>::insert(asset_id, total_borrows_new);
         TotalReserves::<T>::insert(asset_id, total_reserves_new);
@@ -76,18 +85,26 @@ impl<T: Config> Pallet<T> {
         let mut total_reserves = Self::total_reserves(asset_id);
         let mut borrow_index = Self::borrow_index(asset_id);
 
+        let now = T::UnixTime::now().as_secs();
+        let last_accrued_interest_time = Self::last_accrued_interest_time(asset_id);
+        let delta_time = now.saturating_sub(last_accrued_interest_time);
+
         let util = Self::calc_utilization_ratio(total_cash, total_borrows, total_reserves)?;
-        let borrow_rate = market
-            .rate_model
-            .get_borrow_rate(util)
-            .ok_or(ArithmeticError::Overflow)?;
+        let borrow_rate = match &market.rate_model {
+            InterestRateModel::Adaptive(adaptive) => {
+                let rate_at_target =
+                    Self::rate_at_target(asset_id).unwrap_or(adaptive.initial_rate_at_target);
+                adaptive
+                    .average_rate_at_target(rate_at_target, util, delta_time)
+                    .and_then(|rate_at_target| adaptive.get_borrow_rate(rate_at_target, util))
+            }
+            rate_model => rate_model.get_borrow_rate(util),
+        }
+        .ok_or(ArithmeticError::Overflow)?;
         let supply_rate =
             InterestRateModel::get_supply_rate(borrow_rate, util, market.reserve_factor);
 
-        let now = T::UnixTime::now().as_secs();
-        let last_accrued_interest_time = Self::last_accrued_interest_time(asset_id);
-        if now > last_accrued_interest_time {
-            let delta_time = now - last_accrued_interest_time;
+        if !delta_time.is_zero() {
             let interest_accumulated =
                 Self::accrued_interest(borrow_rate, total_borrows, delta_time)
                     .ok_or(ArithmeticError::Overflow)?;
@@ -119,6 +136,28 @@ impl<T: Config> Pallet<T> {
         ))
     }
 
+    /// Calculates the rate at target utilization of an adaptive rate model after
+    /// `delta_time` seconds at `util` since the last accrual. Returns `None` for
+    /// the other rate models.
+    pub(crate) fn next_rate_at_target(
+        asset_id: AssetIdOf<T>,
+        rate_model: &InterestRateModel,
+        util: Ratio,
+        delta_time: Timestamp,
+    ) -> Result<Option<Rate>, DispatchError> {
+        match rate_model {
+            InterestRateModel::Adaptive(adaptive) => {
+                let rate_at_target =
+                    Self::rate_at_target(asset_id).unwrap_or(adaptive.initial_rate_at_target);
+                let rate_at_target = adaptive
+                    .next_rate_at_target(rate_at_target, util, delta_time)
+                    .ok_or(ArithmeticError::Overflow)?;
+                Ok(Some(rate_at_target))
+            }
+            _ => Ok(None),
+        }
+    }
+
     /// Update the exchange rate according to the totalCash, totalBorrows and totalSupply.
     /// This function does not accrue interest before calculating the exchange rate.
     /// exchangeRate = (totalCash + totalBorrows - totalReserves) / totalSupply
@@ -0,0 +1,166 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sp_runtime::{traits::Zero, DispatchResult};
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+    // Returns the isolated collateral `account` is currently using, if any.
+    //
+    // An account can enable at most one isolated collateral, and when it does
+    // so no other collateral can be enabled at the same time.
+    pub(crate) fn isolated_collateral(
+        account: &T::AccountId,
+    ) -> Option<(AssetIdOf<T>, Market<BalanceOf<T>>)> {
+        let asset_id = Self::account_isolated_collateral(account)?;
+        Self::market(asset_id)
+            .ok()
+            .filter(|market| market.isolated)
+            .map(|market| (asset_id, market))
+    }
+
+    // Ensures `account` can enable `asset_id` as collateral without breaking
+    // the isolation mode rules.
+    pub(crate) fn ensure_collateral_isolation(
+        account: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+    ) -> DispatchResult {
+        let market = Self::market(asset_id)?;
+        let other_collateral_enabled = Markets::<T>::iter_keys().any(|id| {
+            id != asset_id
+                && AccountDeposits::<T>::contains_key(id, account)
+                && Self::account_deposits(id, account).is_collateral
+        });
+
+        if market.isolated {
+            ensure!(
+                !other_collateral_enabled,
+                Error::<T>::IsolatedCollateralConflict
+            );
+            // The debt ceiling only tracks borrows made while in isolation mode
+            ensure!(
+                Self::total_borrowed_value(account)?.is_zero(),
+                Error::<T>::IsolationWithOutstandingBorrows
+            );
+        } else {
+            ensure!(
+                Self::isolated_collateral(account).is_none(),
+                Error::<T>::IsolatedCollateralConflict
+            );
+        }
+
+        Ok(())
+    }
+
+    // Ensures an account in isolation mode is allowed to borrow `borrow_value`
+    // of `asset_id`.
+    pub(crate) fn ensure_borrow_isolation(
+        borrower: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+        borrow_value: FixedU128,
+    ) -> DispatchResult {
+        let (collateral_asset_id, collateral_market) = match Self::isolated_collateral(borrower) {
+            Some(isolated) => isolated,
+            None => return Ok(()),
+        };
+
+        let market = Self::market(asset_id)?;
+        ensure!(
+            market.borrowable_in_isolation,
+            Error::<T>::AssetNotBorrowableInIsolation
+        );
+
+        let isolated_debt = Self::isolated_debt(collateral_asset_id)
+            .checked_add(borrow_value.into_inner())
+            .ok_or(ArithmeticError::Overflow)?;
+        ensure!(
+            isolated_debt <= collateral_market.debt_ceiling,
+            Error::<T>::DebtCeilingExceeded
+        );
+
+        Ok(())
+    }
+
+    // Increases the debt backed by the isolated collateral of `borrower`, valued
+    // at the current price of `asset_id`.
+    pub(crate) fn increase_isolated_debt(
+        borrower: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+        borrow_amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        if let Some((collateral_asset_id, _)) = Self::isolated_collateral(borrower) {
+            let borrow_value = Self::get_asset_value(asset_id, borrow_amount)?.into_inner();
+            IsolatedBorrows::<T>::try_mutate(asset_id, borrower, |borrow| -> DispatchResult {
+                borrow.amount = borrow
+                    .amount
+                    .checked_add(borrow_amount)
+                    .ok_or(ArithmeticError::Overflow)?;
+                borrow.value = borrow
+                    .value
+                    .checked_add(borrow_value)
+                    .ok_or(ArithmeticError::Overflow)?;
+                Ok(())
+            })?;
+            IsolatedDebt::<T>::try_mutate(collateral_asset_id, |debt| -> DispatchResult {
+                *debt = debt
+                    .checked_add(borrow_value)
+                    .ok_or(ArithmeticError::Overflow)?;
+                Ok(())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    // Decreases the debt backed by the isolated collateral of `borrower` by the
+    // share of its borrows of `asset_id` which is repaid, at the value they were
+    // borrowed, so that price moves don't leave the debt ceiling drifting.
+    //
+    // Interest accrued on isolated borrows is not tracked by the debt ceiling,
+    // so repayments saturate at the borrowed amount.
+    pub(crate) fn decrease_isolated_debt(
+        borrower: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+        repay_amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        let (collateral_asset_id, _) = match Self::isolated_collateral(borrower) {
+            Some(isolated) => isolated,
+            None => return Ok(()),
+        };
+
+        let borrow = Self::isolated_borrows(asset_id, borrower);
+        let repay_value = if repay_amount >= borrow.amount {
+            IsolatedBorrows::<T>::remove(asset_id, borrower);
+            borrow.value
+        } else {
+            let repay_value = FixedU128::saturating_from_rational(repay_amount, borrow.amount)
+                .saturating_mul_int(borrow.value);
+            IsolatedBorrows::<T>::insert(
+                asset_id,
+                borrower,
+                IsolatedBorrow {
+                    amount: borrow.amount - repay_amount,
+                    value: borrow.value.saturating_sub(repay_value),
+                },
+            );
+            repay_value
+        };
+        IsolatedDebt::<T>::mutate(collateral_asset_id, |debt| {
+            *debt = debt.saturating_sub(repay_value);
+        });
+
+        Ok(())
+    }
+}
@@ -40,8 +40,8 @@ use frame_system::pallet_prelude::*;
 use num_traits::cast::ToPrimitive;
 pub use pallet::*;
 use pallet_traits::{
-    ConvertToBigUint, Loans as LoansTrait, LoansMarketDataProvider, LoansPositionDataProvider,
-    MarketInfo, MarketStatus, PriceFeeder,
+    ConvertToBigUint, FlashLoanReceiver, Loans as LoansTrait, LoansMarketDataProvider,
+    LoansPositionDataProvider, MarketInfo, MarketStatus, PriceFeeder,
 };
 use primitives::{
     is_auxiliary_token, Balance, CurrencyId, Liquidity, Price, Rate, Ratio, Shortfall, Timestamp,
@@ -55,8 +55,12 @@ use sp_runtime::{
 };
 use sp_std::{result::Result, vec::Vec};
 
+pub use flash_loan::FlashLoanCallDispatcher;
 use sp_io::hashing::blake2_256;
-pub use types::{BorrowSnapshot, Deposits, EarnedSnapshot, Market, MarketState, RewardMarketState};
+pub use types::{
+    BorrowSnapshot, Deposits, EModeCategory, EModeCategoryId, EarnedSnapshot, IsolatedBorrow,
+    LiquidationMode, LoanAction, Market, MarketState, RewardMarketState,
+};
 pub use weights::WeightInfo;
 
 mod benchmarking;
@@ -66,8 +70,17 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+mod auction;
+mod bad_debt;
+mod batch;
+mod delegation;
+mod emode;
 mod farming;
+mod flash_loan;
 mod interest;
+mod isolation;
+mod liquidation_candidates;
+mod positions;
 mod ptoken;
 mod rate_model;
 mod types;
@@ -96,6 +109,9 @@ pub enum Versions {
     V4,
     V5,
     V6,
+    V7,
+    V8,
+    V9,
 }
 
 #[frame_support::pallet]
@@ -138,6 +154,21 @@ pub mod pallet {
 
         #[pallet::constant]
         type LiquidationFreeAssetId: Get<AssetIdOf<Self>>;
+
+        /// Hook executed while a flash loan is outstanding
+        type FlashLoanReceiver: FlashLoanReceiver<Self::AccountId, AssetIdOf<Self>, BalanceOf<Self>>;
+
+        /// Account covering the bad debt the reserves can't absorb, if any
+        type InsuranceAccount: Get<Option<Self::AccountId>>;
+
+        /// Collateral worth less than this, valued in USD with 18 decimals, is dust which
+        /// doesn't keep the borrows of its owner from being written off as bad debt
+        #[pallet::constant]
+        type CollateralDustValue: Get<BalanceOf<Self>>;
+
+        /// The maximum number of actions in a batch
+        #[pallet::constant]
+        type MaxBatchActions: Get<u32>;
     }
 
     #[pallet::error]
@@ -200,6 +231,44 @@ pub mod pallet {
         CodecError,
         /// Collateral is reserved and cannot be liquidated
         CollateralReserved,
+        /// Isolated collateral cannot be enabled together with other collaterals
+        IsolatedCollateralConflict,
+        /// Isolated collateral cannot be enabled with outstanding borrows
+        IsolationWithOutstandingBorrows,
+        /// Asset cannot be borrowed against an isolated collateral
+        AssetNotBorrowableInIsolation,
+        /// Debt ceiling of the isolated collateral is exceeded
+        DebtCeilingExceeded,
+        /// The debt ceiling of an isolated market cannot be zero
+        InvalidDebtCeiling,
+        /// A market cannot become isolated while it has deposits
+        IsolationWithDeposits,
+        /// A market cannot leave isolation while debt is backed by its collateral
+        IsolationWithOutstandingDebt,
+        /// Efficiency mode category does not exist
+        EModeCategoryDoesNotExist,
+        /// A flash loan of the asset is already outstanding
+        FlashLoanInProgress,
+        /// Dutch auctions need a non-zero maximum discount and duration
+        InvalidLiquidationMode,
+        /// The market is not liquidated through Dutch auctions
+        LiquidationAuctionNotEnabled,
+        /// The liquidation auction has already started
+        LiquidationAuctionAlreadyStarted,
+        /// The liquidation auction does not exist
+        LiquidationAuctionDoesNotExist,
+        /// The borrower can still be liquidated
+        BorrowerStillLiquidatable,
+        /// Debt can't be written off while the borrower has collateral
+        BorrowerHasCollateral,
+        /// The borrower has no debt to write off
+        NoBadDebt,
+        /// The health factor is below the minimum required by the caller
+        HealthFactorTooLow,
+        /// The borrow exceeds the allowance approved by the delegator
+        InsufficientBorrowAllowance,
+        /// The delegatee can't be the delegator
+        DelegateeIsDelegator,
     }
 
     #[pallet::event]
@@ -265,6 +334,42 @@ pub mod pallet {
         IncentiveReservesReduced(T::AccountId, AssetIdOf<T>, BalanceOf<T>),
         /// Liquidation free collaterals has been updated
         LiquidationFreeCollateralsUpdated(Vec<AssetIdOf<T>>),
+        /// Efficiency mode category has been set
+        /// [category_id, category]
+        EModeCategorySet(EModeCategoryId, EModeCategory),
+        /// Efficiency mode category of an asset has been updated
+        /// [asset_id, category_id]
+        AssetEModeCategoryUpdated(AssetIdOf<T>, Option<EModeCategoryId>),
+        /// Event emitted when a flash loan is repaid
+        /// [receiver, asset_id, amount, fee]
+        FlashLoaned(T::AccountId, AssetIdOf<T>, BalanceOf<T>, BalanceOf<T>),
+        /// Event emitted when a liquidation auction starts
+        /// [borrower, liquidation_asset_id, block_number]
+        LiquidationAuctionStarted(T::AccountId, AssetIdOf<T>, T::BlockNumber),
+        /// Event emitted when a liquidation auction is closed
+        /// [borrower, liquidation_asset_id]
+        LiquidationAuctionClosed(T::AccountId, AssetIdOf<T>),
+        /// Event emitted when bad debt is written off
+        /// [borrower, asset_id, covered_by_reserves, covered_by_insurance, socialized]
+        BadDebtWrittenOff(
+            T::AccountId,
+            AssetIdOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+            BalanceOf<T>,
+        ),
+        /// Event emitted when a batch of actions is executed
+        /// [account, actions_count]
+        BatchActionsExecuted(T::AccountId, u32),
+        /// Event emitted when a delegator approves a borrow allowance
+        /// [delegator, delegatee, asset_id, amount]
+        DelegationApproved(T::AccountId, T::AccountId, AssetIdOf<T>, BalanceOf<T>),
+        /// Event emitted when a delegatee borrows against the collateral of a delegator
+        /// [delegatee, delegator, asset_id, amount]
+        BorrowedOnBehalf(T::AccountId, T::AccountId, AssetIdOf<T>, BalanceOf<T>),
+        /// Event emitted when a delegatee repays the borrows drawn on a delegator
+        /// [delegatee, delegator, asset_id, amount]
+        RepaidOnBehalf(T::AccountId, T::AccountId, AssetIdOf<T>, BalanceOf<T>),
     }
 
     /// The timestamp of the last calculation of accrued interest
@@ -366,12 +471,82 @@ pub mod pallet {
     pub type SupplyRate<T: Config> =
         StorageMap<_, Blake2_128Concat, AssetIdOf<T>, Rate, ValueQuery>;
 
+    /// Rate at target utilization of the markets using the adaptive rate model,
+    /// which drifts on every interest accrual
+    #[pallet::storage]
+    #[pallet::getter(fn rate_at_target)]
+    pub type RateAtTarget<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, Rate, OptionQuery>;
+
     /// Borrow utilization ratio
     #[pallet::storage]
     #[pallet::getter(fn utilization_ratio)]
     pub type UtilizationRatio<T: Config> =
         StorageMap<_, Blake2_128Concat, AssetIdOf<T>, Ratio, ValueQuery>;
 
+    /// Total debt, valued in USD, currently backed by each isolated collateral
+    /// CurrencyId -> Balance
+    #[pallet::storage]
+    #[pallet::getter(fn isolated_debt)]
+    pub type IsolatedDebt<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+    /// The isolated collateral enabled by each account
+    /// AccountId -> CurrencyId
+    #[pallet::storage]
+    #[pallet::getter(fn account_isolated_collateral)]
+    pub type AccountIsolatedCollateral<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, AssetIdOf<T>, OptionQuery>;
+
+    /// Borrows backed by an isolated collateral, valued when they were borrowed
+    /// CurrencyId -> Owner -> IsolatedBorrow
+    #[pallet::storage]
+    #[pallet::getter(fn isolated_borrows)]
+    pub type IsolatedBorrows<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AssetIdOf<T>,
+        Blake2_128Concat,
+        T::AccountId,
+        IsolatedBorrow<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    /// Efficiency mode categories
+    /// EModeCategoryId -> EModeCategory
+    #[pallet::storage]
+    #[pallet::getter(fn emode_categories)]
+    pub type EModeCategories<T: Config> =
+        StorageMap<_, Twox64Concat, EModeCategoryId, EModeCategory>;
+
+    /// Efficiency mode category of each asset
+    /// CurrencyId -> EModeCategoryId
+    #[pallet::storage]
+    #[pallet::getter(fn asset_emode_category)]
+    pub type AssetEModeCategory<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, EModeCategoryId>;
+
+    /// Amount lent by the flash loan currently outstanding for each asset
+    /// CurrencyId -> Balance
+    #[pallet::storage]
+    #[pallet::getter(fn outstanding_flash_loan)]
+    pub type OutstandingFlashLoan<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+    /// Block at which the Dutch auction liquidating the borrows of an account started,
+    /// keyed by the account first so that its open auctions can be found directly
+    /// Owner -> CurrencyId -> BlockNumber
+    #[pallet::storage]
+    #[pallet::getter(fn liquidation_auctions)]
+    pub type LiquidationAuctions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        AssetIdOf<T>,
+        T::BlockNumber,
+    >;
+
     /// Mapping of asset id to its market
     #[pallet::storage]
     pub type Markets<T: Config> =
@@ -451,6 +626,51 @@ pub mod pallet {
     pub type RewardAccrued<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
 
+    /// Amount a delegatee is still allowed to borrow against the collateral of a delegator
+    /// CurrencyId -> Delegator -> Delegatee -> Balance
+    #[pallet::storage]
+    #[pallet::getter(fn borrow_allowance)]
+    pub type BorrowAllowances<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, AssetIdOf<T>>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+        ),
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// Borrows of a delegatee drawn against the collateral of a delegator, part of
+    /// the `AccountBorrows` of the delegatee
+    /// CurrencyId -> Delegator -> Delegatee -> BorrowSnapshot
+    #[pallet::storage]
+    #[pallet::getter(fn delegated_borrows)]
+    pub type DelegatedBorrows<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, AssetIdOf<T>>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+        ),
+        BorrowSnapshot<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    /// Delegators whose collateral backs some borrows of a delegatee
+    /// CurrencyId -> Delegatee -> Delegator -> ()
+    #[pallet::storage]
+    pub type Delegators<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, AssetIdOf<T>>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+        ),
+        (),
+        OptionQuery,
+    >;
+
     /// DefaultVersion is using for initialize the StorageVersion
     #[pallet::type_value]
     pub(super) fn DefaultVersion<T: Config>() -> Versions {
@@ -525,6 +745,15 @@ pub mod pallet {
                 market.supply_cap > Zero::zero(),
                 Error::<T>::InvalidSupplyCap,
             );
+            ensure!(
+                !market.isolated || market.debt_ceiling > Zero::zero(),
+                Error::<T>::InvalidDebtCeiling,
+            );
+            ensure!(
+                market.flash_loan_fee < Ratio::one(),
+                Error::<T>::InvalidFactor,
+            );
+            Self::ensure_valid_liquidation_mode(&market.liquidation_mode)?;
 
             // Ensures a given `ptoken_id` not exists on the `Market` and `UnderlyingAssetId`.
             Self::ensure_ptoken(market.ptoken_id)?;
@@ -583,6 +812,8 @@ pub mod pallet {
                 stored_market.rate_model = rate_model;
                 stored_market.clone()
             })?;
+            // An adaptive rate model starts again from its initial rate at target
+            RateAtTarget::<T>::remove(asset_id);
             Self::deposit_event(Event::<T>::UpdatedMarket(asset_id, market));
 
             Ok(().into())
@@ -596,6 +827,8 @@ pub mod pallet {
         /// - `close_factor`: maximum liquidation ratio at one time
         /// - `liquidate_incentive`: liquidation incentive ratio
         /// - `cap`: market capacity
+        /// - `flash_loan_fee`: fee charged on flash loans
+        /// - `liquidation_mode`: instant or Dutch auction liquidations
         #[pallet::call_index(3)]
         #[pallet::weight(T::WeightInfo::update_market())]
         #[transactional]
@@ -610,6 +843,8 @@ pub mod pallet {
             liquidate_incentive: Option<Rate>,
             supply_cap: Option<BalanceOf<T>>,
             borrow_cap: Option<BalanceOf<T>>,
+            flash_loan_fee: Option<Ratio>,
+            liquidation_mode: Option<LiquidationMode>,
         ) -> DispatchResultWithPostInfo {
             T::UpdateOrigin::ensure_origin(origin)?;
 
@@ -625,6 +860,8 @@ pub mod pallet {
             let liquidate_incentive = liquidate_incentive.unwrap_or(market.liquidate_incentive);
             let supply_cap = supply_cap.unwrap_or(market.supply_cap);
             let borrow_cap = borrow_cap.unwrap_or(market.borrow_cap);
+            let flash_loan_fee = flash_loan_fee.unwrap_or(market.flash_loan_fee);
+            let liquidation_mode = liquidation_mode.unwrap_or(market.liquidation_mode);
 
             ensure!(
                 collateral_factor >= Ratio::zero() && collateral_factor < Ratio::one(),
@@ -639,12 +876,14 @@ pub mod pallet {
                 Error::<T>::InvalidFactor
             );
             ensure!(supply_cap > Zero::zero(), Error::<T>::InvalidSupplyCap);
+            ensure!(flash_loan_fee < Ratio::one(), Error::<T>::InvalidFactor);
+            Self::ensure_valid_liquidation_mode(&liquidation_mode)?;
 
             let market = Self::mutate_market(asset_id, |stored_market| {
                 *stored_market = Market {
                     state: stored_market.state,
                     ptoken_id: stored_market.ptoken_id,
-                    rate_model: stored_market.rate_model,
+                    rate_model: stored_market.rate_model.clone(),
                     collateral_factor,
                     liquidation_threshold,
                     reserve_factor,
@@ -653,6 +892,11 @@ pub mod pallet {
                     liquidate_incentive_reserved_factor,
                     supply_cap,
                     borrow_cap,
+                    isolated: stored_market.isolated,
+                    debt_ceiling: stored_market.debt_ceiling,
+                    borrowable_in_isolation: stored_market.borrowable_in_isolation,
+                    flash_loan_fee,
+                    liquidation_mode,
                 };
                 stored_market.clone()
             })?;
@@ -687,6 +931,9 @@ pub mod pallet {
             }
             UnderlyingAssetId::<T>::insert(market.ptoken_id, asset_id);
             let updated_market = Self::mutate_market(asset_id, |stored_market| {
+                if stored_market.rate_model != market.rate_model {
+                    RateAtTarget::<T>::remove(asset_id);
+                }
                 *stored_market = market;
                 stored_market.clone()
             })?;
@@ -938,7 +1185,7 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
             Self::ensure_active_market(asset_id)?;
             Self::accrue_interest(asset_id)?;
-            let account_borrows = Self::current_borrow_balance(&who, asset_id)?;
+            let account_borrows = Self::current_own_borrow_balance(&who, asset_id)?;
             Self::do_repay_borrow(&who, asset_id, account_borrows)?;
 
             Ok(().into())
@@ -1136,6 +1383,306 @@ pub mod pallet {
             Self::deposit_event(Event::<T>::LiquidationFreeCollateralsUpdated(collaterals));
             Ok(().into())
         }
+
+        /// Updates the isolation mode parameters of a stored market. A market can only
+        /// become isolated without deposits, and leave isolation once no debt is backed
+        /// by its collateral.
+        ///
+        /// The origin must conform to `UpdateOrigin`.
+        ///
+        /// - `asset_id`: market related currency
+        /// - `isolated`: whether the asset is an isolated collateral
+        /// - `debt_ceiling`: upper bound of the debt, valued in USD, backed by the isolated collateral
+        /// - `borrowable_in_isolation`: whether the asset can be borrowed against an isolated collateral
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::update_market_isolation())]
+        #[transactional]
+        pub fn update_market_isolation(
+            origin: OriginFor<T>,
+            asset_id: AssetIdOf<T>,
+            isolated: Option<bool>,
+            debt_ceiling: Option<BalanceOf<T>>,
+            borrowable_in_isolation: Option<bool>,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            let market = Self::market(asset_id)?;
+
+            let isolated = isolated.unwrap_or(market.isolated);
+            let debt_ceiling = debt_ceiling.unwrap_or(market.debt_ceiling);
+            let borrowable_in_isolation =
+                borrowable_in_isolation.unwrap_or(market.borrowable_in_isolation);
+
+            ensure!(
+                !isolated || debt_ceiling > Zero::zero(),
+                Error::<T>::InvalidDebtCeiling
+            );
+            // The accounts which enabled the collateral are only in isolation mode
+            // if it was isolated at that time
+            if isolated && !market.isolated {
+                ensure!(
+                    Self::total_supply(asset_id).is_zero(),
+                    Error::<T>::IsolationWithDeposits
+                );
+            }
+            if !isolated && market.isolated {
+                ensure!(
+                    Self::isolated_debt(asset_id).is_zero(),
+                    Error::<T>::IsolationWithOutstandingDebt
+                );
+            }
+
+            let market = Self::mutate_market(asset_id, |stored_market| {
+                stored_market.isolated = isolated;
+                stored_market.debt_ceiling = debt_ceiling;
+                stored_market.borrowable_in_isolation = borrowable_in_isolation;
+                stored_market.clone()
+            })?;
+            Self::deposit_event(Event::<T>::UpdatedMarket(asset_id, market));
+
+            Ok(().into())
+        }
+
+        /// Creates or updates an efficiency mode category.
+        ///
+        /// The origin must conform to `UpdateOrigin`.
+        ///
+        /// - `category_id`: the category to be set
+        /// - `category`: the collateral factor and liquidation threshold applied to accounts
+        ///   whose collaterals and borrows all belong to the category
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::set_emode_category())]
+        #[transactional]
+        pub fn set_emode_category(
+            origin: OriginFor<T>,
+            category_id: EModeCategoryId,
+            category: EModeCategory,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            Self::ensure_valid_emode_category(&category)?;
+
+            EModeCategories::<T>::insert(category_id, category);
+            Self::deposit_event(Event::<T>::EModeCategorySet(category_id, category));
+
+            Ok(().into())
+        }
+
+        /// Assigns an asset to an efficiency mode category, or removes it from its
+        /// category if `category_id` is `None`.
+        ///
+        /// The origin must conform to `UpdateOrigin`.
+        ///
+        /// - `asset_id`: market related currency
+        /// - `category_id`: the category the asset belongs to
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::set_asset_emode_category())]
+        #[transactional]
+        pub fn set_asset_emode_category(
+            origin: OriginFor<T>,
+            asset_id: AssetIdOf<T>,
+            category_id: Option<EModeCategoryId>,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            Self::market(asset_id)?;
+
+            match category_id {
+                Some(id) => {
+                    ensure!(
+                        EModeCategories::<T>::contains_key(id),
+                        Error::<T>::EModeCategoryDoesNotExist
+                    );
+                    AssetEModeCategory::<T>::insert(asset_id, id);
+                }
+                None => AssetEModeCategory::<T>::remove(asset_id),
+            }
+            Self::deposit_event(Event::<T>::AssetEModeCategoryUpdated(asset_id, category_id));
+
+            Ok(().into())
+        }
+
+        /// Lends `amount` of the idle cash of a market to the sender within the call.
+        ///
+        /// The operation described by `data` is executed through `T::FlashLoanReceiver`
+        /// once the sender holds the loan. The sender must then hold `amount` plus the
+        /// `flash_loan_fee` of the market, which is added to the reserves, otherwise the
+        /// whole call is reverted.
+        ///
+        /// - `asset_id`: the asset to be borrowed.
+        /// - `amount`: the amount to be borrowed.
+        /// - `data`: the operation executed while the loan is outstanding.
+        #[pallet::call_index(25)]
+        #[pallet::weight(
+            T::WeightInfo::flash_loan().saturating_add(T::FlashLoanReceiver::weight(data))
+        )]
+        #[transactional]
+        pub fn flash_loan(
+            origin: OriginFor<T>,
+            asset_id: AssetIdOf<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+            data: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_flash_loan(&who, asset_id, amount, data)?;
+            Ok(().into())
+        }
+
+        /// Starts the Dutch auction liquidating the borrows of an underwater account.
+        ///
+        /// The collateral discount offered to liquidators grows from zero up to the
+        /// `max_discount` of the market from the current block.
+        ///
+        /// - `borrower`: the borrower to be liquidated.
+        /// - `liquidation_asset_id`: the asset to be liquidated.
+        #[pallet::call_index(26)]
+        #[pallet::weight(T::WeightInfo::start_liquidation_auction())]
+        #[transactional]
+        pub fn start_liquidation_auction(
+            origin: OriginFor<T>,
+            borrower: T::AccountId,
+            liquidation_asset_id: AssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::accrue_interest(liquidation_asset_id)?;
+            Self::do_start_liquidation_auction(&borrower, liquidation_asset_id)?;
+            Ok(().into())
+        }
+
+        /// Closes the liquidation auction of an account which is no longer underwater.
+        ///
+        /// - `borrower`: the borrower of the auction.
+        /// - `liquidation_asset_id`: the asset of the auction.
+        #[pallet::call_index(27)]
+        #[pallet::weight(T::WeightInfo::close_liquidation_auction())]
+        #[transactional]
+        pub fn close_liquidation_auction(
+            origin: OriginFor<T>,
+            borrower: T::AccountId,
+            liquidation_asset_id: AssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::accrue_interest(liquidation_asset_id)?;
+            Self::do_close_liquidation_auction(&borrower, liquidation_asset_id)?;
+            Ok(().into())
+        }
+
+        /// Writes off the borrows of an account which has no collateral left.
+        ///
+        /// The debt is covered by the market reserves first, then by `T::InsuranceAccount`,
+        /// and the remainder is socialized across the suppliers through the exchange rate.
+        /// Fails if the exchange rate would go below its minimum, in which case reserves
+        /// need to be added first.
+        ///
+        /// The origin must conform to `UpdateOrigin`.
+        ///
+        /// - `borrower`: the borrower whose debt is written off.
+        /// - `asset_id`: the asset of the debt.
+        #[pallet::call_index(28)]
+        #[pallet::weight(T::WeightInfo::write_off_bad_debt())]
+        #[transactional]
+        pub fn write_off_bad_debt(
+            origin: OriginFor<T>,
+            borrower: T::AccountId,
+            asset_id: AssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            Self::market(asset_id)?;
+            Self::accrue_interest(asset_id)?;
+            Self::do_write_off_bad_debt(&borrower, asset_id)?;
+            Ok(().into())
+        }
+
+        /// Sender executes a batch of actions in order, with the liquidity checked
+        /// once at the end.
+        ///
+        /// The intermediate states may be unhealthy, only the final one must have
+        /// enough liquidity and a health factor of at least `min_health_factor`.
+        ///
+        /// - `actions`: the mints, redeems, borrows, repays and collateral toggles.
+        /// - `min_health_factor`: the minimum health factor after the last action.
+        #[pallet::call_index(29)]
+        #[pallet::weight(Pallet::<T>::batch_actions_weight(actions))]
+        #[transactional]
+        pub fn batch_actions(
+            origin: OriginFor<T>,
+            actions: BoundedVec<LoanAction<BalanceOf<T>>, T::MaxBatchActions>,
+            min_health_factor: FixedU128,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_batch_actions(&who, &actions, min_health_factor)?;
+            Self::deposit_event(Event::<T>::BatchActionsExecuted(who, actions.len() as u32));
+            Ok(().into())
+        }
+
+        /// Sender approves `delegatee` to borrow against the sender's collateral.
+        ///
+        /// The borrows are recorded on the sender, so they count against the sender's
+        /// liquidity and are liquidated from the sender's collateral, while the
+        /// delegatee owes them in `DelegatedBorrows`.
+        ///
+        /// - `delegatee`: the account allowed to borrow.
+        /// - `asset_id`: the asset to be borrowed.
+        /// - `amount`: the allowance, replacing the current one.
+        #[pallet::call_index(30)]
+        #[pallet::weight(T::WeightInfo::approve_delegation())]
+        #[transactional]
+        pub fn approve_delegation(
+            origin: OriginFor<T>,
+            delegatee: T::AccountId,
+            asset_id: AssetIdOf<T>,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(who != delegatee, Error::<T>::DelegateeIsDelegator);
+            Self::ensure_active_market(asset_id)?;
+            if amount.is_zero() {
+                BorrowAllowances::<T>::remove((asset_id, &who, &delegatee));
+            } else {
+                BorrowAllowances::<T>::insert((asset_id, &who, &delegatee), amount);
+            }
+            Self::deposit_event(Event::<T>::DelegationApproved(
+                who, delegatee, asset_id, amount,
+            ));
+            Ok(().into())
+        }
+
+        /// Sender borrows assets against the collateral of `delegator`, up to the
+        /// allowance approved by `delegator`.
+        ///
+        /// - `delegator`: the account whose collateral backs the borrow.
+        /// - `asset_id`: the asset to be borrowed.
+        /// - `borrow_amount`: the amount to be borrowed.
+        #[pallet::call_index(31)]
+        #[pallet::weight(T::WeightInfo::borrow_on_behalf())]
+        #[transactional]
+        pub fn borrow_on_behalf(
+            origin: OriginFor<T>,
+            delegator: T::AccountId,
+            asset_id: AssetIdOf<T>,
+            #[pallet::compact] borrow_amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_borrow_on_behalf(&who, &delegator, asset_id, borrow_amount)?;
+            Ok(().into())
+        }
+
+        /// Sender repays the borrows drawn against the collateral of `delegator`.
+        ///
+        /// - `delegator`: the account whose collateral backs the borrow.
+        /// - `asset_id`: the asset to be repaid.
+        /// - `repay_amount`: the amount to be repaid.
+        #[pallet::call_index(32)]
+        #[pallet::weight(T::WeightInfo::repay_on_behalf())]
+        #[transactional]
+        pub fn repay_on_behalf(
+            origin: OriginFor<T>,
+            delegator: T::AccountId,
+            asset_id: AssetIdOf<T>,
+            #[pallet::compact] repay_amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::do_repay_on_behalf(&who, &delegator, asset_id, repay_amount)?;
+            Ok(().into())
+        }
     }
 }
 
@@ -1146,17 +1693,20 @@ impl<T: Config> Pallet<T> {
 
     fn get_lf_borrowed_value(account: &T::AccountId) -> Result<FixedU128, DispatchError> {
         let lf_borrowed_amount =
-            Self::current_borrow_balance(account, T::LiquidationFreeAssetId::get())?;
+            Self::current_backed_borrow_balance(account, T::LiquidationFreeAssetId::get())?;
         Self::get_asset_value(T::LiquidationFreeAssetId::get(), lf_borrowed_amount)
     }
 
-    fn get_lf_base_position(account: &T::AccountId) -> Result<FixedU128, DispatchError> {
+    fn get_lf_base_position(
+        account: &T::AccountId,
+        emode: Option<EModeCategory>,
+    ) -> Result<FixedU128, DispatchError> {
         let mut total_asset_value: FixedU128 = FixedU128::zero();
         for (asset_id, _market) in Self::active_markets()
             .filter(|(asset_id, _)| Self::liquidation_free_collaterals().contains(asset_id))
         {
             total_asset_value = total_asset_value
-                .checked_add(&Self::collateral_asset_value(account, asset_id)?)
+                .checked_add(&Self::collateral_asset_value(account, asset_id, emode)?)
                 .ok_or(ArithmeticError::Overflow)?;
         }
         Ok(total_asset_value)
@@ -1164,13 +1714,16 @@ impl<T: Config> Pallet<T> {
 
     fn get_lf_liquidation_base_position(
         account: &T::AccountId,
+        emode: Option<EModeCategory>,
     ) -> Result<FixedU128, DispatchError> {
         let mut total_asset_value: FixedU128 = FixedU128::zero();
         for (asset_id, _market) in Self::active_markets()
             .filter(|(asset_id, _)| Self::liquidation_free_collaterals().contains(asset_id))
         {
             total_asset_value = total_asset_value
-                .checked_add(&Self::liquidation_threshold_asset_value(account, asset_id)?)
+                .checked_add(&Self::liquidation_threshold_asset_value(
+                    account, asset_id, emode,
+                )?)
                 .ok_or(ArithmeticError::Overflow)?;
         }
         Ok(total_asset_value)
@@ -1178,11 +1731,19 @@ impl<T: Config> Pallet<T> {
 
     pub fn get_account_liquidity(
         account: &T::AccountId,
+    ) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
+        Self::get_account_liquidity_in_emode(account, Self::account_emode_category(account, None))
+    }
+
+    // Calculates the liquidity of `account` with the collateral factors of `emode`.
+    pub(crate) fn get_account_liquidity_in_emode(
+        account: &T::AccountId,
+        emode: Option<EModeCategory>,
     ) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
         let total_borrow_value = Self::total_borrowed_value(account)?;
-        let total_collateral_value = Self::total_collateral_value(account)?;
+        let total_collateral_value = Self::total_collateral_value_in_emode(account, emode)?;
         let lf_borrowed_value = Self::get_lf_borrowed_value(account)?;
-        let lf_base_position = Self::get_lf_base_position(account)?;
+        let lf_base_position = Self::get_lf_base_position(account, emode)?;
 
         log::trace!(
             target: "loans::get_account_liquidity",
@@ -1227,11 +1788,12 @@ impl<T: Config> Pallet<T> {
     pub fn get_account_liquidation_threshold_liquidity(
         account: &T::AccountId,
     ) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
+        let emode = Self::account_emode_category(account, None);
         let total_borrow_value = Self::total_borrowed_value(account)?;
-        let total_collateral_value = Self::total_liquidation_threshold_value(account)?;
+        let total_collateral_value = Self::total_liquidation_threshold_value(account, emode)?;
 
         let lf_borrowed_value = Self::get_lf_borrowed_value(account)?;
-        let lf_base_position = Self::get_lf_liquidation_base_position(account)?;
+        let lf_base_position = Self::get_lf_liquidation_base_position(account, emode)?;
 
         log::trace!(
             target: "loans::get_account_liquidation_threshold_liquidity",
@@ -1277,7 +1839,7 @@ impl<T: Config> Pallet<T> {
     fn total_borrowed_value(borrower: &T::AccountId) -> Result<FixedU128, DispatchError> {
         let mut total_borrow_value: FixedU128 = FixedU128::zero();
         for (asset_id, _) in Self::active_markets() {
-            let currency_borrow_amount = Self::current_borrow_balance(borrower, asset_id)?;
+            let currency_borrow_amount = Self::current_backed_borrow_balance(borrower, asset_id)?;
             if currency_borrow_amount.is_zero() {
                 continue;
             }
@@ -1292,6 +1854,7 @@ impl<T: Config> Pallet<T> {
     fn current_collateral_balance(
         supplier: &T::AccountId,
         asset_id: AssetIdOf<T>,
+        emode: Option<EModeCategory>,
     ) -> Result<BalanceOf<T>, DispatchError> {
         if !AccountDeposits::<T>::contains_key(asset_id, supplier) {
             return Ok(BalanceOf::<T>::zero());
@@ -1307,7 +1870,8 @@ impl<T: Config> Pallet<T> {
         let underlying_amount =
             Self::calc_underlying_amount(deposits.voucher_balance, exchange_rate)?;
         let market = Self::market(asset_id)?;
-        let effects_amount = market.collateral_factor.mul_ceil(underlying_amount);
+        let effects_amount =
+            Self::effective_collateral_factor(&market, emode).mul_ceil(underlying_amount);
 
         Ok(BalanceOf::<T>::saturated_from(effects_amount))
     }
@@ -1315,8 +1879,9 @@ impl<T: Config> Pallet<T> {
     fn collateral_asset_value(
         supplier: &T::AccountId,
         asset_id: AssetIdOf<T>,
+        emode: Option<EModeCategory>,
     ) -> Result<FixedU128, DispatchError> {
-        let effects_amount = Self::current_collateral_balance(supplier, asset_id)?;
+        let effects_amount = Self::current_collateral_balance(supplier, asset_id, emode)?;
 
         Self::get_asset_value(asset_id, effects_amount)
     }
@@ -1324,6 +1889,7 @@ impl<T: Config> Pallet<T> {
     fn liquidation_threshold_asset_value(
         borrower: &T::AccountId,
         asset_id: AssetIdOf<T>,
+        emode: Option<EModeCategory>,
     ) -> Result<FixedU128, DispatchError> {
         if !AccountDeposits::<T>::contains_key(asset_id, borrower) {
             return Ok(FixedU128::zero());
@@ -1339,16 +1905,27 @@ impl<T: Config> Pallet<T> {
         let underlying_amount =
             Self::calc_underlying_amount(deposits.voucher_balance, exchange_rate)?;
         let market = Self::market(asset_id)?;
-        let effects_amount = market.liquidation_threshold.mul_ceil(underlying_amount);
+        let effects_amount =
+            Self::effective_liquidation_threshold(&market, emode).mul_ceil(underlying_amount);
 
         Self::get_asset_value(asset_id, effects_amount)
     }
 
     fn total_collateral_value(supplier: &T::AccountId) -> Result<FixedU128, DispatchError> {
+        Self::total_collateral_value_in_emode(
+            supplier,
+            Self::account_emode_category(supplier, None),
+        )
+    }
+
+    fn total_collateral_value_in_emode(
+        supplier: &T::AccountId,
+        emode: Option<EModeCategory>,
+    ) -> Result<FixedU128, DispatchError> {
         let mut total_asset_value: FixedU128 = FixedU128::zero();
         for (asset_id, _market) in Self::active_markets() {
             total_asset_value = total_asset_value
-                .checked_add(&Self::collateral_asset_value(supplier, asset_id)?)
+                .checked_add(&Self::collateral_asset_value(supplier, asset_id, emode)?)
                 .ok_or(ArithmeticError::Overflow)?;
         }
 
@@ -1357,12 +1934,13 @@ impl<T: Config> Pallet<T> {
 
     fn total_liquidation_threshold_value(
         borrower: &T::AccountId,
+        emode: Option<EModeCategory>,
     ) -> Result<FixedU128, DispatchError> {
         let mut total_asset_value: FixedU128 = FixedU128::zero();
         for (asset_id, _market) in Self::active_markets() {
             total_asset_value = total_asset_value
                 .checked_add(&Self::liquidation_threshold_asset_value(
-                    borrower, asset_id,
+                    borrower, asset_id, emode,
                 )?)
                 .ok_or(ArithmeticError::Overflow)?;
         }
@@ -1383,21 +1961,15 @@ impl<T: Config> Pallet<T> {
             redeemer,
             voucher_amount,
         );
-        let deposit = Self::account_deposits(asset_id, redeemer);
-        if deposit.voucher_balance < voucher_amount {
-            return Err(Error::<T>::InsufficientDeposit.into());
-        }
-
-        let exchange_rate = Self::exchange_rate_stored(asset_id)?;
-        let redeem_amount = Self::calc_underlying_amount(voucher_amount, exchange_rate)?;
-        Self::ensure_enough_cash(asset_id, redeem_amount)?;
-
-        if !deposit.is_collateral {
+        let redeem_amount = Self::ensure_redeemable(asset_id, redeemer, voucher_amount)?;
+        if !Self::account_deposits(asset_id, redeemer).is_collateral {
             return Ok(());
         }
 
         let market = Self::market(asset_id)?;
-        let effects_amount = market.collateral_factor.mul_ceil(redeem_amount);
+        let emode = Self::account_emode_category(redeemer, None);
+        let effects_amount =
+            Self::effective_collateral_factor(&market, emode).mul_ceil(redeem_amount);
         let redeem_effects_value = Self::get_asset_value(asset_id, effects_amount)?;
         log::trace!(
             target: "loans::redeem_allowed",
@@ -1410,18 +1982,54 @@ impl<T: Config> Pallet<T> {
             redeemer,
             redeem_effects_value,
             Self::liquidation_free_collaterals().contains(&asset_id),
+            emode,
         )?;
 
         Ok(())
     }
 
+    // Checks the deposit of `redeemer` and the cash of the market, leaving the liquidity
+    // out. Returns the underlying amount of `voucher_amount`.
+    fn ensure_redeemable(
+        asset_id: AssetIdOf<T>,
+        redeemer: &T::AccountId,
+        voucher_amount: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let deposit = Self::account_deposits(asset_id, redeemer);
+        if deposit.voucher_balance < voucher_amount {
+            return Err(Error::<T>::InsufficientDeposit.into());
+        }
+
+        let exchange_rate = Self::exchange_rate_stored(asset_id)?;
+        let redeem_amount = Self::calc_underlying_amount(voucher_amount, exchange_rate)?;
+        Self::ensure_enough_cash(asset_id, redeem_amount)?;
+
+        Ok(redeem_amount)
+    }
+
     #[require_transactional]
     pub fn do_redeem_voucher(
         who: &T::AccountId,
         asset_id: AssetIdOf<T>,
         voucher_amount: BalanceOf<T>,
     ) -> Result<BalanceOf<T>, DispatchError> {
-        Self::redeem_allowed(asset_id, who, voucher_amount)?;
+        Self::do_redeem_voucher_internal(who, asset_id, voucher_amount, true)
+    }
+
+    // Redeems `voucher_amount` of the deposits of `who`. The liquidity of `who` is left
+    // unchecked if `check_liquidity` is false, which is up to the caller then.
+    #[require_transactional]
+    pub(crate) fn do_redeem_voucher_internal(
+        who: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+        voucher_amount: BalanceOf<T>,
+        check_liquidity: bool,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        if check_liquidity {
+            Self::redeem_allowed(asset_id, who, voucher_amount)?;
+        } else {
+            Self::ensure_redeemable(asset_id, who, voucher_amount)?;
+        }
         Self::update_reward_supply_index(asset_id)?;
         Self::distribute_supplier_reward(asset_id, who)?;
 
@@ -1461,20 +2069,36 @@ impl<T: Config> Pallet<T> {
         borrower: &T::AccountId,
         borrow_amount: BalanceOf<T>,
     ) -> DispatchResult {
-        Self::ensure_under_borrow_cap(asset_id, borrow_amount)?;
-        Self::ensure_enough_cash(asset_id, borrow_amount)?;
-        let borrow_value = Self::get_asset_value(asset_id, borrow_amount)?;
+        let borrow_value = Self::ensure_borrowable(asset_id, borrower, borrow_amount)?;
+        // The borrowed asset may move the account out of its efficiency category
         Self::ensure_liquidity(
             borrower,
             borrow_value,
             asset_id == T::LiquidationFreeAssetId::get(),
+            Self::account_emode_category(borrower, Some(asset_id)),
         )?;
 
         Ok(())
     }
 
+    // Checks the caps, the cash and the isolation mode of the borrow, leaving the
+    // liquidity out. Returns the value of `borrow_amount`.
+    fn ensure_borrowable(
+        asset_id: AssetIdOf<T>,
+        borrower: &T::AccountId,
+        borrow_amount: BalanceOf<T>,
+    ) -> Result<FixedU128, DispatchError> {
+        Self::ensure_under_borrow_cap(asset_id, borrow_amount)?;
+        Self::ensure_enough_cash(asset_id, borrow_amount)?;
+        let borrow_value = Self::get_asset_value(asset_id, borrow_amount)?;
+        Self::ensure_borrow_isolation(borrower, asset_id, borrow_value)?;
+
+        Ok(borrow_value)
+    }
+
     #[require_transactional]
-    fn do_repay_borrow_with_amount(
+    pub(crate) fn do_repay_borrow_with_amount(
+        payer: &T::AccountId,
         borrower: &T::AccountId,
         asset_id: AssetIdOf<T>,
         account_borrows: BalanceOf<T>,
@@ -1486,7 +2110,7 @@ impl<T: Config> Pallet<T> {
         Self::update_reward_borrow_index(asset_id)?;
         Self::distribute_borrower_reward(asset_id, borrower)?;
 
-        T::Assets::transfer(asset_id, borrower, &Self::account_id(), repay_amount, false)?;
+        T::Assets::transfer(asset_id, payer, &Self::account_id(), repay_amount, false)?;
         let account_borrows_new = account_borrows
             .checked_sub(repay_amount)
             .ok_or(ArithmeticError::Underflow)?;
@@ -1516,7 +2140,15 @@ impl<T: Config> Pallet<T> {
         who: &T::AccountId,
         asset_id: AssetIdOf<T>,
     ) -> Result<BalanceOf<T>, DispatchError> {
-        let snapshot: BorrowSnapshot<BalanceOf<T>> = Self::account_borrows(asset_id, who);
+        Self::snapshot_borrow_balance(asset_id, Self::account_borrows(asset_id, who))
+    }
+
+    // Calculates the amount owed on `snapshot` with the current borrow index of
+    // `asset_id`.
+    pub(crate) fn snapshot_borrow_balance(
+        asset_id: AssetIdOf<T>,
+        snapshot: BorrowSnapshot<BalanceOf<T>>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
         if snapshot.principal.is_zero() || snapshot.borrow_index.is_zero() {
             return Ok(Zero::zero());
         }
@@ -1571,25 +2203,17 @@ impl<T: Config> Pallet<T> {
             repay_amount,
             market
         );
-        let (liquidity, shortfall, lf_liquidity, _) =
-            Self::get_account_liquidation_threshold_liquidity(borrower)?;
-
-        // C_other >= B_other + B_dot_over
-        // C_other >= B_other + max(B_dot - C_lf, 0)
-        // C_other + C_lf >= B_other + B_dot - B_dot + C_lf + max(B_dot - C_lf, 0)
-        // C_all - B_all >= max(0, C_lf - B_dot)
-        // C_all - B_all >= 0 && C_all - B_all >= max(0, C_lf - B_dot)
-        // shortfall == 0 && liquidity > lf_liquidity
-        if shortfall.is_zero() && liquidity >= lf_liquidity {
+        if !Self::is_liquidatable(borrower)? {
             return Err(Error::<T>::InsufficientShortfall.into());
         }
 
         // The liquidator may not repay more than 50%(close_factor) of the borrower's borrow balance.
-        let account_borrows = Self::current_borrow_balance(borrower, liquidation_asset_id)?;
+        let account_borrows = Self::current_backed_borrow_balance(borrower, liquidation_asset_id)?;
         let account_borrows_value = Self::get_asset_value(liquidation_asset_id, account_borrows)?;
         let repay_value = Self::get_asset_value(liquidation_asset_id, repay_amount)?;
         let effects_borrows_value = if liquidation_asset_id == T::LiquidationFreeAssetId::get() {
-            let base_position = Self::get_lf_base_position(borrower)?;
+            let base_position =
+                Self::get_lf_base_position(borrower, Self::account_emode_category(borrower, None))?;
             if account_borrows_value > base_position {
                 account_borrows_value - base_position
             } else {
@@ -1610,6 +2234,20 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    // Returns whether the borrows of `borrower` can be liquidated.
+    fn is_liquidatable(borrower: &T::AccountId) -> Result<bool, DispatchError> {
+        let (liquidity, shortfall, lf_liquidity, _) =
+            Self::get_account_liquidation_threshold_liquidity(borrower)?;
+
+        // C_other >= B_other + B_dot_over
+        // C_other >= B_other + max(B_dot - C_lf, 0)
+        // C_other + C_lf >= B_other + B_dot - B_dot + C_lf + max(B_dot - C_lf, 0)
+        // C_all - B_all >= max(0, C_lf - B_dot)
+        // C_all - B_all >= 0 && C_all - B_all >= max(0, C_lf - B_dot)
+        // shortfall == 0 && liquidity > lf_liquidity
+        Ok(!shortfall.is_zero() || liquidity < lf_liquidity)
+    }
+
     /// Note:
     /// - liquidation_asset_id is borrower's debt asset.
     /// - collateral_asset_id is borrower's collateral asset.
@@ -1647,9 +2285,11 @@ impl<T: Config> Pallet<T> {
             .ok_or(ArithmeticError::Overflow)?;
 
         let collateral_value = Self::get_asset_value(collateral_asset_id, borrower_deposit_amount)?;
+        let liquidate_incentive =
+            Self::current_liquidate_incentive(&borrower, liquidation_asset_id, &market)?;
         // liquidate_value contains the incentive of liquidator and the punishment of the borrower
         let liquidate_value = Self::get_asset_value(liquidation_asset_id, repay_amount)?
-            .checked_mul(&market.liquidate_incentive)
+            .checked_mul(&liquidate_incentive)
             .ok_or(ArithmeticError::Overflow)?;
 
         if collateral_value < liquidate_value {
@@ -1679,9 +2319,20 @@ impl<T: Config> Pallet<T> {
             collateral_asset_id,
             repay_amount,
             real_collateral_underlying_amount,
+            liquidate_incentive,
             &market,
         )?;
 
+        // The auction is over once the debt or the collateral is fully liquidated,
+        // or the position is healthy again
+        if LiquidationAuctions::<T>::contains_key(&borrower, liquidation_asset_id)
+            && (Self::current_borrow_balance(&borrower, liquidation_asset_id)?.is_zero()
+                || !Self::has_collateral(&borrower))
+        {
+            Self::remove_liquidation_auction(&borrower, liquidation_asset_id);
+        }
+        Self::close_recovered_liquidation_auctions(&borrower)?;
+
         Ok(())
     }
 
@@ -1693,6 +2344,7 @@ impl<T: Config> Pallet<T> {
         collateral_asset_id: AssetIdOf<T>,
         repay_amount: BalanceOf<T>,
         collateral_underlying_amount: BalanceOf<T>,
+        liquidate_incentive: Rate,
         market: &Market<BalanceOf<T>>,
     ) -> DispatchResult {
         log::trace!(
@@ -1721,10 +2373,21 @@ impl<T: Config> Pallet<T> {
             false,
         )?;
 
-        // 2.the system reduce borrower's debt
+        // 2.the system reduce borrower's debt, then the debt its delegatees drew
+        // against its collateral
+        Self::decrease_isolated_debt(borrower, liquidation_asset_id, repay_amount)?;
+        let own_repay_amount = repay_amount.min(Self::current_own_borrow_balance(
+            borrower,
+            liquidation_asset_id,
+        )?);
+        Self::reduce_delegated_borrows(
+            borrower,
+            liquidation_asset_id,
+            repay_amount - own_repay_amount,
+        )?;
         let account_borrows = Self::current_borrow_balance(borrower, liquidation_asset_id)?;
         let account_borrows_new = account_borrows
-            .checked_sub(repay_amount)
+            .checked_sub(own_repay_amount)
             .ok_or(ArithmeticError::Underflow)?;
         let total_borrows = Self::total_borrows(liquidation_asset_id);
         let total_borrows_new = total_borrows
@@ -1766,7 +2429,7 @@ impl<T: Config> Pallet<T> {
         )?;
         let incentive_reserved_amount = market.liquidate_incentive_reserved_factor.mul_floor(
             FixedU128::from_inner(collateral_amount)
-                .checked_div(&market.liquidate_incentive)
+                .checked_div(&liquidate_incentive)
                 .map(|r| r.into_inner())
                 .ok_or(ArithmeticError::Underflow)?,
         );
@@ -1819,7 +2482,8 @@ impl<T: Config> Pallet<T> {
     fn ensure_under_supply_cap(asset_id: AssetIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
         let market = Self::market(asset_id)?;
         // Assets holded by market currently.
-        let current_cash = T::Assets::balance(asset_id, &Self::account_id());
+        let current_cash = T::Assets::balance(asset_id, &Self::account_id())
+            .saturating_add(Self::outstanding_flash_loan(asset_id));
         let total_cash = current_cash
             .checked_add(amount)
             .ok_or(ArithmeticError::Overflow)?;
@@ -1881,12 +2545,15 @@ impl<T: Config> Pallet<T> {
     // `reduce_amount`: values that will have an impact on liquidity
     // `lf_enable`: check in liquidation free mode which means borrowing dot or redeeming assets in
     // `LiquidationFreeCollaterals`.
+    // `emode`: the efficiency category the account is in after the action
     fn ensure_liquidity(
         account: &T::AccountId,
         reduce_amount: FixedU128,
         lf_enable: bool,
+        emode: Option<EModeCategory>,
     ) -> DispatchResult {
-        let (total_liquidity, _, lf_liquidity, _) = Self::get_account_liquidity(account)?;
+        let (total_liquidity, _, lf_liquidity, _) =
+            Self::get_account_liquidity_in_emode(account, emode)?;
 
         if lf_enable && max(total_liquidity, lf_liquidity) >= reduce_amount {
             return Ok(());
@@ -1919,6 +2586,7 @@ impl<T: Config> Pallet<T> {
 
     fn get_total_cash(asset_id: AssetIdOf<T>) -> BalanceOf<T> {
         T::Assets::reducible_balance(asset_id, &Self::account_id(), false)
+            .saturating_add(Self::outstanding_flash_loan(asset_id))
     }
 
     // Returns the uniform format price.
@@ -2016,57 +2684,25 @@ impl<T: Config> Pallet<T> {
         let entropy = (b"loans/incentive", &[account_id]).using_encoded(blake2_256);
         Ok(T::AccountId::decode(&mut &entropy[..]).map_err(|_| Error::<T>::CodecError)?)
     }
-}
-
-impl<T: Config> LoansTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for Pallet<T> {
-    fn do_mint(
-        supplier: &AccountIdOf<T>,
-        asset_id: AssetIdOf<T>,
-        amount: BalanceOf<T>,
-    ) -> Result<(), DispatchError> {
-        ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
-        Self::ensure_active_market(asset_id)?;
-        Self::ensure_under_supply_cap(asset_id, amount)?;
-
-        Self::accrue_interest(asset_id)?;
-
-        // update supply index before modify supply balance.
-        Self::update_reward_supply_index(asset_id)?;
-        Self::distribute_supplier_reward(asset_id, supplier)?;
 
-        let exchange_rate = Self::exchange_rate_stored(asset_id)?;
-        Self::update_earned_stored(supplier, asset_id, exchange_rate)?;
-        let voucher_amount = Self::calc_collateral_amount(amount, exchange_rate)?;
-        ensure!(!voucher_amount.is_zero(), Error::<T>::InvalidExchangeRate);
-
-        T::Assets::transfer(asset_id, supplier, &Self::account_id(), amount, false)?;
-        AccountDeposits::<T>::try_mutate(asset_id, supplier, |deposits| -> DispatchResult {
-            deposits.voucher_balance = deposits
-                .voucher_balance
-                .checked_add(voucher_amount)
-                .ok_or(ArithmeticError::Overflow)?;
-            Ok(())
-        })?;
-        TotalSupply::<T>::try_mutate(asset_id, |total_balance| -> DispatchResult {
-            let new_balance = total_balance
-                .checked_add(voucher_amount)
-                .ok_or(ArithmeticError::Overflow)?;
-            *total_balance = new_balance;
-            Ok(())
-        })?;
-        Self::deposit_event(Event::<T>::Deposited(supplier.clone(), asset_id, amount));
-        Ok(())
-    }
-
-    fn do_borrow(
-        borrower: &AccountIdOf<T>,
+    // Borrows `amount` of `asset_id` for `borrower` and transfers it to `receiver`. The
+    // liquidity of `borrower` is left unchecked if `check_liquidity` is false, which is
+    // up to the caller then.
+    pub(crate) fn do_borrow_internal(
+        borrower: &T::AccountId,
+        receiver: &T::AccountId,
         asset_id: AssetIdOf<T>,
         amount: BalanceOf<T>,
-    ) -> Result<(), DispatchError> {
+        check_liquidity: bool,
+    ) -> DispatchResult {
         Self::ensure_active_market(asset_id)?;
 
         Self::accrue_interest(asset_id)?;
-        Self::borrow_allowed(asset_id, borrower, amount)?;
+        if check_liquidity {
+            Self::borrow_allowed(asset_id, borrower, amount)?;
+        } else {
+            Self::ensure_borrowable(asset_id, borrower, amount)?;
+        }
 
         // update borrow index after accrue interest.
         Self::update_reward_borrow_index(asset_id)?;
@@ -2089,16 +2725,21 @@ impl<T: Config> LoansTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for Palle
             },
         );
         TotalBorrows::<T>::insert(asset_id, total_borrows_new);
-        T::Assets::transfer(asset_id, &Self::account_id(), borrower, amount, false)?;
+        Self::increase_isolated_debt(borrower, asset_id, amount)?;
+        T::Assets::transfer(asset_id, &Self::account_id(), receiver, amount, false)?;
         Self::deposit_event(Event::<T>::Borrowed(borrower.clone(), asset_id, amount));
         Ok(())
     }
 
-    fn do_collateral_asset(
-        supplier: &AccountIdOf<T>,
+    // Turns the collateral option of `asset_id` on or off for `supplier`. The liquidity
+    // of `supplier` is left unchecked if `check_liquidity` is false, which is up to the
+    // caller then.
+    pub(crate) fn do_collateral_asset_internal(
+        supplier: &T::AccountId,
         asset_id: AssetIdOf<T>,
         enable: bool,
-    ) -> Result<(), DispatchError> {
+        check_liquidity: bool,
+    ) -> DispatchResult {
         Self::ensure_active_market(asset_id)?;
         ensure!(
             AccountDeposits::<T>::contains_key(asset_id, supplier),
@@ -2107,14 +2748,49 @@ impl<T: Config> LoansTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for Palle
         let mut deposits = Self::account_deposits(asset_id, supplier);
         // turn on the collateral button
         if enable {
+            Self::ensure_collateral_isolation(supplier, asset_id)?;
+            let emode = Self::account_emode_category(supplier, None);
             deposits.is_collateral = true;
             AccountDeposits::<T>::insert(asset_id, supplier, deposits);
+            if Self::market(asset_id)?.isolated {
+                AccountIsolatedCollateral::<T>::insert(supplier, asset_id);
+            }
+            // Leaving an efficiency category lowers the collateral factors
+            if check_liquidity && Self::account_emode_category(supplier, None) != emode {
+                let (_, shortfall, _, _) = Self::get_account_liquidity(supplier)?;
+                ensure!(shortfall.is_zero(), Error::<T>::InsufficientLiquidity);
+            }
+            Self::close_recovered_liquidation_auctions(supplier)?;
             Self::deposit_event(Event::<T>::CollateralAssetAdded(supplier.clone(), asset_id));
             return Ok(());
         }
         // turn off the collateral button after checking the liquidity
-        let total_collateral_value = Self::total_collateral_value(supplier)?;
-        let collateral_asset_value = Self::collateral_asset_value(supplier, asset_id)?;
+        if check_liquidity {
+            Self::ensure_collateral_removable(supplier, asset_id)?;
+        }
+        deposits.is_collateral = false;
+        AccountDeposits::<T>::insert(asset_id, supplier, deposits);
+        if Self::account_isolated_collateral(supplier) == Some(asset_id) {
+            AccountIsolatedCollateral::<T>::remove(supplier);
+        }
+
+        Self::deposit_event(Event::<T>::CollateralAssetRemoved(
+            supplier.clone(),
+            asset_id,
+        ));
+
+        Ok(())
+    }
+
+    // Ensures the collateral value of `supplier` still covers the borrows once
+    // `asset_id` is no longer a collateral.
+    fn ensure_collateral_removable(
+        supplier: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+    ) -> DispatchResult {
+        let emode = Self::account_emode_category(supplier, None);
+        let total_collateral_value = Self::total_collateral_value_in_emode(supplier, emode)?;
+        let collateral_asset_value = Self::collateral_asset_value(supplier, asset_id, emode)?;
         let total_borrowed_value = Self::total_borrowed_value(supplier)?;
         log::trace!(
             target: "loans::collateral_asset",
@@ -2130,17 +2806,70 @@ impl<T: Config> LoansTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for Palle
         {
             return Err(Error::<T>::InsufficientLiquidity.into());
         }
-        deposits.is_collateral = false;
-        AccountDeposits::<T>::insert(asset_id, supplier, deposits);
 
-        Self::deposit_event(Event::<T>::CollateralAssetRemoved(
-            supplier.clone(),
-            asset_id,
-        ));
+        Ok(())
+    }
+}
 
+impl<T: Config> LoansTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for Pallet<T> {
+    fn do_mint(
+        supplier: &AccountIdOf<T>,
+        asset_id: AssetIdOf<T>,
+        amount: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+        Self::ensure_active_market(asset_id)?;
+        Self::ensure_under_supply_cap(asset_id, amount)?;
+
+        Self::accrue_interest(asset_id)?;
+
+        // update supply index before modify supply balance.
+        Self::update_reward_supply_index(asset_id)?;
+        Self::distribute_supplier_reward(asset_id, supplier)?;
+
+        let exchange_rate = Self::exchange_rate_stored(asset_id)?;
+        Self::update_earned_stored(supplier, asset_id, exchange_rate)?;
+        let voucher_amount = Self::calc_collateral_amount(amount, exchange_rate)?;
+        ensure!(!voucher_amount.is_zero(), Error::<T>::InvalidExchangeRate);
+
+        T::Assets::transfer(asset_id, supplier, &Self::account_id(), amount, false)?;
+        AccountDeposits::<T>::try_mutate(asset_id, supplier, |deposits| -> DispatchResult {
+            deposits.voucher_balance = deposits
+                .voucher_balance
+                .checked_add(voucher_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            Ok(())
+        })?;
+        TotalSupply::<T>::try_mutate(asset_id, |total_balance| -> DispatchResult {
+            let new_balance = total_balance
+                .checked_add(voucher_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            *total_balance = new_balance;
+            Ok(())
+        })?;
+        if Self::account_deposits(asset_id, supplier).is_collateral {
+            Self::close_recovered_liquidation_auctions(supplier)?;
+        }
+        Self::deposit_event(Event::<T>::Deposited(supplier.clone(), asset_id, amount));
         Ok(())
     }
 
+    fn do_borrow(
+        borrower: &AccountIdOf<T>,
+        asset_id: AssetIdOf<T>,
+        amount: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        Self::do_borrow_internal(borrower, borrower, asset_id, amount, true)
+    }
+
+    fn do_collateral_asset(
+        supplier: &AccountIdOf<T>,
+        asset_id: AssetIdOf<T>,
+        enable: bool,
+    ) -> Result<(), DispatchError> {
+        Self::do_collateral_asset_internal(supplier, asset_id, enable, true)
+    }
+
     fn do_repay_borrow(
         borrower: &AccountIdOf<T>,
         asset_id: AssetIdOf<T>,
@@ -2148,8 +2877,15 @@ impl<T: Config> LoansTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for Palle
     ) -> Result<(), DispatchError> {
         Self::ensure_active_market(asset_id)?;
         Self::accrue_interest(asset_id)?;
+        // The borrows drawn against the collateral of delegators are repaid on their behalf
+        ensure!(
+            amount <= Self::current_own_borrow_balance(borrower, asset_id)?,
+            Error::<T>::TooMuchRepay
+        );
         let account_borrows = Self::current_borrow_balance(borrower, asset_id)?;
-        Self::do_repay_borrow_with_amount(borrower, asset_id, account_borrows, amount)?;
+        Self::do_repay_borrow_with_amount(borrower, borrower, asset_id, account_borrows, amount)?;
+        Self::decrease_isolated_debt(borrower, asset_id, amount)?;
+        Self::close_recovered_liquidation_auctions(borrower)?;
         Self::deposit_event(Event::<T>::RepaidBorrow(borrower.clone(), asset_id, amount));
         Ok(())
     }
@@ -2172,6 +2908,16 @@ impl<T: Config> LoansTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for Palle
         ));
         Ok(())
     }
+
+    fn ensure_can_borrow(
+        borrower: &AccountIdOf<T>,
+        asset_id: AssetIdOf<T>,
+        amount: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        Self::ensure_active_market(asset_id)?;
+        Self::ensure_borrowable(asset_id, borrower, amount)?;
+        Ok(())
+    }
 }
 
 impl<T: Config> LoansMarketDataProvider<AssetIdOf<T>, BalanceOf<T>> for Pallet<T> {
@@ -2210,14 +2956,9 @@ impl<T: Config> LoansMarketDataProvider<AssetIdOf<T>, BalanceOf<T>> for Pallet<T
     }
 
     fn get_full_interest_rate(asset_id: AssetIdOf<T>) -> Option<Rate> {
-        if let Ok(market) = Self::market(asset_id) {
-            let rate = match market.rate_model {
-                InterestRateModel::Jump(jump) => Some(jump.full_rate),
-                _ => None,
-            };
-            return rate;
-        }
-        None
+        Self::market(asset_id)
+            .ok()
+            .and_then(|market| market.rate_model.get_full_rate())
     }
 }
 
@@ -2236,6 +2977,10 @@ impl<T: Config> LoansPositionDataProvider<AssetIdOf<T>, AccountIdOf<T>, BalanceO
         supplier: &AccountIdOf<T>,
         asset_id: AssetIdOf<T>,
     ) -> Result<BalanceOf<T>, DispatchError> {
-        Self::current_collateral_balance(supplier, asset_id)
+        Self::current_collateral_balance(
+            supplier,
+            asset_id,
+            Self::account_emode_category(supplier, None),
+        )
     }
 }
@@ -0,0 +1,178 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use pallet_traits::LiquidationCandidate;
+use sp_runtime::traits::Zero;
+
+use crate::*;
+
+type LiquidationCandidateOf<T> = LiquidationCandidate<AccountIdOf<T>, BalanceOf<T>>;
+
+impl<T: Config> Pallet<T> {
+    /// Returns the liquidatable accounts found in at most `limit` borrows,
+    /// starting after the raw `AccountBorrows` storage key `start_key`, and the
+    /// key to resume from if there may be more borrows.
+    ///
+    /// An account is returned once per page, from the borrow of its liquidation
+    /// asset, or from the borrows its delegatees drew against its collateral.
+    /// The interest is accrued beforehand and rolled back, so the state is left
+    /// untouched.
+    pub fn get_liquidation_candidates(
+        start_key: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Result<(Vec<LiquidationCandidateOf<T>>, Option<Vec<u8>>), DispatchError> {
+        with_transaction(|| {
+            TransactionOutcome::Rollback(Self::scan_liquidation_candidates(start_key, limit))
+        })
+    }
+
+    fn scan_liquidation_candidates(
+        start_key: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Result<(Vec<LiquidationCandidateOf<T>>, Option<Vec<u8>>), DispatchError> {
+        for (asset_id, _) in Self::active_markets() {
+            Self::accrue_interest(asset_id)?;
+        }
+
+        let mut borrows = match start_key {
+            Some(start_key) => AccountBorrows::<T>::iter_from(start_key),
+            None => AccountBorrows::<T>::iter(),
+        };
+        let mut candidates = Vec::new();
+        for _ in 0..limit {
+            let (asset_id, borrower, snapshot) = match borrows.next() {
+                Some(borrow) => borrow,
+                None => return Ok((candidates, None)),
+            };
+            if snapshot.principal.is_zero() {
+                continue;
+            }
+            // The borrows drawn on the behalf of delegators are liquidated from them
+            let delegators = Delegators::<T>::iter_key_prefix((asset_id, &borrower));
+            for account in sp_std::iter::once(borrower.clone()).chain(delegators) {
+                if candidates
+                    .iter()
+                    .any(|candidate: &LiquidationCandidateOf<T>| candidate.borrower == account)
+                    || !matches!(Self::is_liquidatable(&account), Ok(true))
+                {
+                    continue;
+                }
+                // Accounts whose price or market is broken are skipped
+                if let Ok(Some(candidate)) = Self::best_liquidation(&account) {
+                    if candidate.liquidation_asset_id == asset_id {
+                        candidates.push(candidate);
+                    }
+                }
+            }
+        }
+
+        Ok((candidates, Some(borrows.last_raw_key().to_vec())))
+    }
+
+    // Returns the liquidation of `borrower` seizing its most valuable collateral
+    // and repaying the borrow allowing the largest repay value.
+    //
+    // Returns `None` if the account has no collateral left.
+    fn best_liquidation(
+        borrower: &T::AccountId,
+    ) -> Result<Option<LiquidationCandidateOf<T>>, DispatchError> {
+        let mut collateral: Option<(AssetIdOf<T>, FixedU128)> = None;
+        for (asset_id, _) in Self::active_markets() {
+            let deposits = Self::account_deposits(asset_id, borrower);
+            if !deposits.is_collateral || deposits.voucher_balance.is_zero() {
+                continue;
+            }
+            let amount = Self::calc_underlying_amount(
+                deposits.voucher_balance,
+                Self::exchange_rate_stored(asset_id)?,
+            )?;
+            let value = Self::get_asset_value(asset_id, amount)?;
+            if collateral.map_or(true, |(_, best_value)| value > best_value) {
+                collateral = Some((asset_id, value));
+            }
+        }
+        let (collateral_asset_id, collateral_value) = match collateral {
+            Some(collateral) => collateral,
+            None => return Ok(None),
+        };
+
+        let mut liquidation: Option<(AssetIdOf<T>, BalanceOf<T>, FixedU128)> = None;
+        for (asset_id, market) in Self::active_markets() {
+            let borrow_amount = Self::current_backed_borrow_balance(borrower, asset_id)?;
+            if borrow_amount.is_zero() {
+                continue;
+            }
+            let (repay_amount, repay_value) =
+                Self::max_repay(borrower, asset_id, &market, borrow_amount, collateral_value)?;
+            if liquidation.map_or(true, |(_, _, best_value)| repay_value > best_value) {
+                liquidation = Some((asset_id, repay_amount, repay_value));
+            }
+        }
+
+        let (_, shortfall, _, _) = Self::get_account_liquidation_threshold_liquidity(borrower)?;
+        Ok(liquidation.map(
+            |(liquidation_asset_id, max_repay_amount, _)| LiquidationCandidate {
+                borrower: borrower.clone(),
+                shortfall,
+                liquidation_asset_id,
+                collateral_asset_id,
+                max_repay_amount,
+            },
+        ))
+    }
+
+    // Returns the maximum amount of `liquidation_asset_id` repayable in a single
+    // liquidation of `borrower`, and its value. See `liquidate_borrow_allowed`
+    // and `do_liquidate_borrow`.
+    //
+    // In Dutch auction mode, the market incentive is used until the auction starts.
+    fn max_repay(
+        borrower: &T::AccountId,
+        liquidation_asset_id: AssetIdOf<T>,
+        market: &Market<BalanceOf<T>>,
+        borrow_amount: BalanceOf<T>,
+        collateral_value: FixedU128,
+    ) -> Result<(BalanceOf<T>, FixedU128), DispatchError> {
+        let borrow_value = Self::get_asset_value(liquidation_asset_id, borrow_amount)?;
+        let effects_borrows_value = if liquidation_asset_id == T::LiquidationFreeAssetId::get() {
+            let base_position =
+                Self::get_lf_base_position(borrower, Self::account_emode_category(borrower, None))?;
+            borrow_value.saturating_sub(base_position)
+        } else {
+            borrow_value
+        };
+        let close_factor_value = FixedU128::from_inner(
+            market
+                .close_factor
+                .mul_floor(effects_borrows_value.into_inner()),
+        );
+
+        let liquidate_incentive =
+            Self::current_liquidate_incentive(borrower, liquidation_asset_id, market)
+                .unwrap_or(market.liquidate_incentive);
+        let collateral_cover_value = collateral_value
+            .checked_div(&liquidate_incentive)
+            .ok_or(ArithmeticError::Underflow)?;
+
+        let repay_value = close_factor_value.min(collateral_cover_value);
+        let repay_amount = repay_value
+            .checked_div(&Self::get_price(liquidation_asset_id)?)
+            .ok_or(ArithmeticError::Underflow)?
+            .into_inner()
+            .min(borrow_amount);
+
+        Ok((repay_amount, repay_value))
+    }
+}
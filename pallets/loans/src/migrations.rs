@@ -45,6 +45,35 @@ pub mod v3 {
         /// Ptoken asset id
         pub ptoken_id: CurrencyId,
     }
+
+    #[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+    #[derive(Clone, PartialEq, Eq, codec::Decode, codec::Encode, RuntimeDebug, TypeInfo)]
+    pub struct V3Market<Balance> {
+        /// The collateral utilization ratio
+        pub collateral_factor: Ratio,
+        /// A liquidation_threshold ratio more than collateral_factor to avoid liquidate_borrow too casual
+        pub liquidation_threshold: Ratio,
+        /// Fraction of interest currently set aside for reserves
+        pub reserve_factor: Ratio,
+        /// The percent, ranging from 0% to 100%, of a liquidatable account's
+        /// borrow that can be repaid in a single liquidate transaction.
+        pub close_factor: Ratio,
+        /// Liquidation incentive ratio
+        pub liquidate_incentive: Rate,
+        /// Liquidation incentive reserved ratio
+        pub liquidate_incentive_reserved_factor: Ratio,
+        /// Current interest rate model being used
+        pub rate_model: InterestRateModel,
+        /// Current market state
+        pub state: MarketState,
+        /// Upper bound of supplying
+        pub supply_cap: Balance,
+        /// Upper bound of borrowing
+        pub borrow_cap: Balance,
+        /// Ptoken asset id
+        pub ptoken_id: CurrencyId,
+    }
+
     #[frame_support::storage_alias]
     type MarketRewardSpeed<T: Config> =
         StorageMap<crate::Pallet<T>, Blake2_128Concat, AssetIdOf<T>, BalanceOf<T>>;
@@ -60,6 +89,10 @@ pub mod v3 {
     #[frame_support::storage_alias]
     type LastAccruedTimestamp<T: Config> = StorageValue<crate::Pallet<T>, Timestamp, ValueQuery>;
 
+    #[frame_support::storage_alias]
+    type Markets<T: Config> =
+        StorageMap<crate::Pallet<T>, Blake2_128Concat, AssetIdOf<T>, V3Market<BalanceOf<T>>>;
+
     #[cfg(feature = "try-runtime")]
     pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
         #[frame_support::storage_alias]
@@ -103,7 +136,7 @@ pub mod v3 {
             log::info!("migrating loans to Versions::V3",);
 
             Markets::<T>::translate::<V2Market<BalanceOf<T>>, _>(|_key, market| {
-                Some(Market {
+                Some(V3Market {
                     borrow_cap: market.borrow_cap,
                     supply_cap: market.supply_cap,
                     collateral_factor: market.collateral_factor,
@@ -188,3 +221,417 @@ pub mod v3 {
         Ok(())
     }
 }
+
+pub mod v7 {
+    use super::*;
+    use crate::{pallet::StorageVersion, Config, Weight};
+    use frame_support::{log, traits::OnRuntimeUpgrade};
+
+    #[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+    #[derive(Clone, PartialEq, Eq, codec::Decode, codec::Encode, RuntimeDebug, TypeInfo)]
+    pub struct V6Market<Balance> {
+        /// The collateral utilization ratio
+        pub collateral_factor: Ratio,
+        /// A liquidation_threshold ratio more than collateral_factor to avoid liquidate_borrow too casual
+        pub liquidation_threshold: Ratio,
+        /// Fraction of interest currently set aside for reserves
+        pub reserve_factor: Ratio,
+        /// The percent, ranging from 0% to 100%, of a liquidatable account's
+        /// borrow that can be repaid in a single liquidate transaction.
+        pub close_factor: Ratio,
+        /// Liquidation incentive ratio
+        pub liquidate_incentive: Rate,
+        /// Liquidation incentive reserved ratio
+        pub liquidate_incentive_reserved_factor: Ratio,
+        /// Current interest rate model being used
+        pub rate_model: InterestRateModel,
+        /// Current market state
+        pub state: MarketState,
+        /// Upper bound of supplying
+        pub supply_cap: Balance,
+        /// Upper bound of borrowing
+        pub borrow_cap: Balance,
+        /// Ptoken asset id
+        pub ptoken_id: CurrencyId,
+    }
+
+    #[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+    #[derive(Clone, PartialEq, Eq, codec::Decode, codec::Encode, RuntimeDebug, TypeInfo)]
+    pub struct V7Market<Balance> {
+        /// The collateral utilization ratio
+        pub collateral_factor: Ratio,
+        /// A liquidation_threshold ratio more than collateral_factor to avoid liquidate_borrow too casual
+        pub liquidation_threshold: Ratio,
+        /// Fraction of interest currently set aside for reserves
+        pub reserve_factor: Ratio,
+        /// The percent, ranging from 0% to 100%, of a liquidatable account's
+        /// borrow that can be repaid in a single liquidate transaction.
+        pub close_factor: Ratio,
+        /// Liquidation incentive ratio
+        pub liquidate_incentive: Rate,
+        /// Liquidation incentive reserved ratio
+        pub liquidate_incentive_reserved_factor: Ratio,
+        /// Current interest rate model being used
+        pub rate_model: InterestRateModel,
+        /// Current market state
+        pub state: MarketState,
+        /// Upper bound of supplying
+        pub supply_cap: Balance,
+        /// Upper bound of borrowing
+        pub borrow_cap: Balance,
+        /// Ptoken asset id
+        pub ptoken_id: CurrencyId,
+        /// Whether this asset is an isolated collateral. An account using it as collateral
+        /// can't enable any other collateral and can only borrow `borrowable_in_isolation` assets
+        pub isolated: bool,
+        /// Upper bound of the total debt backed by this isolated collateral, valued in USD
+        /// with 18 decimals
+        pub debt_ceiling: Balance,
+        /// Whether this asset can be borrowed against an isolated collateral
+        pub borrowable_in_isolation: bool,
+    }
+
+    #[frame_support::storage_alias]
+    type Markets<T: Config> =
+        StorageMap<crate::Pallet<T>, Blake2_128Concat, AssetIdOf<T>, V7Market<BalanceOf<T>>>;
+
+    #[cfg(feature = "try-runtime")]
+    pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+        #[frame_support::storage_alias]
+        type Markets<T: Config> =
+            StorageMap<crate::Pallet<T>, Blake2_128Concat, AssetIdOf<T>, V6Market<BalanceOf<T>>>;
+        frame_support::ensure!(
+            StorageVersion::<T>::get() == crate::Versions::V6,
+            "must upgrade linearly"
+        );
+        Markets::<T>::iter().for_each(|(asset_id, _)| {
+            log::info!("market {:#?} need to migrate", asset_id,);
+        });
+        log::info!("👜 loans v7 migration passes PRE migrate checks ✅",);
+
+        Ok(())
+    }
+
+    /// Migration to markets with isolation mode parameters.
+    ///
+    /// Existing markets are cross-collateral markets, i.e. not isolated and
+    /// not borrowable in isolation.
+    pub fn migrate<T: Config>() -> Weight {
+        if StorageVersion::<T>::get() == crate::Versions::V6 {
+            log::info!("migrating loans to Versions::V7",);
+
+            let mut count = 0;
+            Markets::<T>::translate::<V6Market<BalanceOf<T>>, _>(|_key, market| {
+                count += 1;
+                Some(V7Market {
+                    borrow_cap: market.borrow_cap,
+                    supply_cap: market.supply_cap,
+                    collateral_factor: market.collateral_factor,
+                    liquidation_threshold: market.liquidation_threshold,
+                    reserve_factor: market.reserve_factor,
+                    close_factor: market.close_factor,
+                    liquidate_incentive_reserved_factor: market.liquidate_incentive_reserved_factor,
+                    liquidate_incentive: market.liquidate_incentive,
+                    rate_model: market.rate_model,
+                    state: market.state,
+                    ptoken_id: market.ptoken_id,
+                    isolated: false,
+                    debt_ceiling: Zero::zero(),
+                    borrowable_in_isolation: false,
+                })
+            });
+
+            StorageVersion::<T>::put(crate::Versions::V7);
+            log::info!("👜 completed loans migration to Versions::V7",);
+
+            T::DbWeight::get().reads_writes(count + 1, count + 1)
+        } else {
+            T::DbWeight::get().reads(1)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+        frame_support::ensure!(
+            StorageVersion::<T>::get() == crate::Versions::V7,
+            "must upgrade to V7"
+        );
+        Markets::<T>::iter().for_each(|(asset_id, market)| {
+            log::info!(
+                "market {:#?}, isolated {:?}, debt_ceiling {:?}, borrowable_in_isolation {:?}",
+                asset_id,
+                market.isolated,
+                market.debt_ceiling,
+                market.borrowable_in_isolation
+            );
+        });
+        log::info!("👜 loans v7 migration passes POST migrate checks ✅",);
+
+        Ok(())
+    }
+
+    pub struct MigrateToV7<T>(PhantomData<T>);
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            pre_migrate::<T>()?;
+            Ok(Default::default())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrate::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            post_migrate::<T>()
+        }
+    }
+}
+
+pub mod v8 {
+    use super::v7::V7Market;
+    use super::*;
+    use crate::{pallet::StorageVersion, Config, Weight};
+    use frame_support::{log, traits::OnRuntimeUpgrade};
+
+    pub const DEFAULT_FLASH_LOAN_FEE: Ratio = Ratio::from_parts(900);
+
+    #[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+    #[derive(Clone, PartialEq, Eq, codec::Decode, codec::Encode, RuntimeDebug, TypeInfo)]
+    pub struct V8Market<Balance> {
+        /// The collateral utilization ratio
+        pub collateral_factor: Ratio,
+        /// A liquidation_threshold ratio more than collateral_factor to avoid liquidate_borrow too casual
+        pub liquidation_threshold: Ratio,
+        /// Fraction of interest currently set aside for reserves
+        pub reserve_factor: Ratio,
+        /// The percent, ranging from 0% to 100%, of a liquidatable account's
+        /// borrow that can be repaid in a single liquidate transaction.
+        pub close_factor: Ratio,
+        /// Liquidation incentive ratio
+        pub liquidate_incentive: Rate,
+        /// Liquidation incentive reserved ratio
+        pub liquidate_incentive_reserved_factor: Ratio,
+        /// Current interest rate model being used
+        pub rate_model: InterestRateModel,
+        /// Current market state
+        pub state: MarketState,
+        /// Upper bound of supplying
+        pub supply_cap: Balance,
+        /// Upper bound of borrowing
+        pub borrow_cap: Balance,
+        /// Ptoken asset id
+        pub ptoken_id: CurrencyId,
+        /// Whether this asset is an isolated collateral. An account using it as collateral
+        /// can't enable any other collateral and can only borrow `borrowable_in_isolation` assets
+        pub isolated: bool,
+        /// Upper bound of the total debt backed by this isolated collateral, valued in USD
+        /// with 18 decimals
+        pub debt_ceiling: Balance,
+        /// Whether this asset can be borrowed against an isolated collateral
+        pub borrowable_in_isolation: bool,
+        /// Fee charged on flash loans, added to the market reserves
+        pub flash_loan_fee: Ratio,
+    }
+
+    #[frame_support::storage_alias]
+    type Markets<T: Config> =
+        StorageMap<crate::Pallet<T>, Blake2_128Concat, AssetIdOf<T>, V8Market<BalanceOf<T>>>;
+
+    #[cfg(feature = "try-runtime")]
+    pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+        #[frame_support::storage_alias]
+        type Markets<T: Config> =
+            StorageMap<crate::Pallet<T>, Blake2_128Concat, AssetIdOf<T>, V7Market<BalanceOf<T>>>;
+        frame_support::ensure!(
+            StorageVersion::<T>::get() == crate::Versions::V7,
+            "must upgrade linearly"
+        );
+        Markets::<T>::iter().for_each(|(asset_id, _)| {
+            log::info!("market {:#?} need to migrate", asset_id,);
+        });
+        log::info!("👜 loans v8 migration passes PRE migrate checks ✅",);
+
+        Ok(())
+    }
+
+    /// Migration to markets with a flash loan fee.
+    ///
+    /// Existing markets are charged the default fee of 0.09%.
+    pub fn migrate<T: Config>() -> Weight {
+        if StorageVersion::<T>::get() == crate::Versions::V7 {
+            log::info!("migrating loans to Versions::V8",);
+
+            let mut count = 0;
+            Markets::<T>::translate::<V7Market<BalanceOf<T>>, _>(|_key, market| {
+                count += 1;
+                Some(V8Market {
+                    borrow_cap: market.borrow_cap,
+                    supply_cap: market.supply_cap,
+                    collateral_factor: market.collateral_factor,
+                    liquidation_threshold: market.liquidation_threshold,
+                    reserve_factor: market.reserve_factor,
+                    close_factor: market.close_factor,
+                    liquidate_incentive_reserved_factor: market.liquidate_incentive_reserved_factor,
+                    liquidate_incentive: market.liquidate_incentive,
+                    rate_model: market.rate_model,
+                    state: market.state,
+                    ptoken_id: market.ptoken_id,
+                    isolated: market.isolated,
+                    debt_ceiling: market.debt_ceiling,
+                    borrowable_in_isolation: market.borrowable_in_isolation,
+                    flash_loan_fee: DEFAULT_FLASH_LOAN_FEE,
+                })
+            });
+
+            StorageVersion::<T>::put(crate::Versions::V8);
+            log::info!("👜 completed loans migration to Versions::V8",);
+
+            T::DbWeight::get().reads_writes(count + 1, count + 1)
+        } else {
+            T::DbWeight::get().reads(1)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+        frame_support::ensure!(
+            StorageVersion::<T>::get() == crate::Versions::V8,
+            "must upgrade to V8"
+        );
+        Markets::<T>::iter().for_each(|(asset_id, market)| {
+            log::info!(
+                "market {:#?}, flash_loan_fee {:?}",
+                asset_id,
+                market.flash_loan_fee
+            );
+        });
+        log::info!("👜 loans v8 migration passes POST migrate checks ✅",);
+
+        Ok(())
+    }
+
+    pub struct MigrateToV8<T>(PhantomData<T>);
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV8<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            pre_migrate::<T>()?;
+            Ok(Default::default())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrate::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            post_migrate::<T>()
+        }
+    }
+}
+
+pub mod v9 {
+    use super::v8::V8Market;
+    use super::*;
+    use crate::{pallet::StorageVersion, Config, Weight};
+    use frame_support::{log, traits::OnRuntimeUpgrade};
+
+    #[cfg(feature = "try-runtime")]
+    pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+        #[frame_support::storage_alias]
+        type Markets<T: Config> =
+            StorageMap<crate::Pallet<T>, Blake2_128Concat, AssetIdOf<T>, V8Market<BalanceOf<T>>>;
+        frame_support::ensure!(
+            StorageVersion::<T>::get() == crate::Versions::V8,
+            "must upgrade linearly"
+        );
+        Markets::<T>::iter().for_each(|(asset_id, _)| {
+            log::info!("market {:#?} need to migrate", asset_id,);
+        });
+        log::info!("👜 loans v9 migration passes PRE migrate checks ✅",);
+
+        Ok(())
+    }
+
+    /// Migration to markets with a liquidation mode.
+    ///
+    /// Existing markets keep being liquidated instantly.
+    pub fn migrate<T: Config>() -> Weight {
+        if StorageVersion::<T>::get() == crate::Versions::V8 {
+            log::info!("migrating loans to Versions::V9",);
+
+            let mut count = 0;
+            Markets::<T>::translate::<V8Market<BalanceOf<T>>, _>(|_key, market| {
+                count += 1;
+                Some(Market {
+                    borrow_cap: market.borrow_cap,
+                    supply_cap: market.supply_cap,
+                    collateral_factor: market.collateral_factor,
+                    liquidation_threshold: market.liquidation_threshold,
+                    reserve_factor: market.reserve_factor,
+                    close_factor: market.close_factor,
+                    liquidate_incentive_reserved_factor: market.liquidate_incentive_reserved_factor,
+                    liquidate_incentive: market.liquidate_incentive,
+                    rate_model: market.rate_model,
+                    state: market.state,
+                    ptoken_id: market.ptoken_id,
+                    isolated: market.isolated,
+                    debt_ceiling: market.debt_ceiling,
+                    borrowable_in_isolation: market.borrowable_in_isolation,
+                    flash_loan_fee: market.flash_loan_fee,
+                    liquidation_mode: LiquidationMode::Instant,
+                })
+            });
+
+            StorageVersion::<T>::put(crate::Versions::V9);
+            log::info!("👜 completed loans migration to Versions::V9",);
+
+            T::DbWeight::get().reads_writes(count + 1, count + 1)
+        } else {
+            T::DbWeight::get().reads(1)
+        }
+    }
+
+    #[cfg(feature = "try-runtime")]
+    pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+        frame_support::ensure!(
+            StorageVersion::<T>::get() == crate::Versions::V9,
+            "must upgrade to V9"
+        );
+        Markets::<T>::iter().for_each(|(asset_id, market)| {
+            log::info!(
+                "market {:#?}, liquidation_mode {:?}",
+                asset_id,
+                market.liquidation_mode
+            );
+        });
+        // the deposits are untouched by the market migrations and must still decode
+        let deposits = AccountDeposits::<T>::iter_values().count();
+        frame_support::ensure!(
+            AccountDeposits::<T>::iter_keys().count() == deposits,
+            "account deposits must decode"
+        );
+        log::info!("👜 loans v9 migration passes POST migrate checks ✅",);
+
+        Ok(())
+    }
+
+    pub struct MigrateToV9<T>(PhantomData<T>);
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV9<T> {
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            pre_migrate::<T>()?;
+            Ok(Default::default())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrate::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            post_migrate::<T>()
+        }
+    }
+}
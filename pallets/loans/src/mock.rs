@@ -231,6 +231,10 @@ parameter_types! {
     // pub const DefaultProtocolFee: Ratio = Ratio::from_rational(5u32, 10000u32);
     pub  DefaultLpFee: Ratio = Ratio::from_rational(25u32, 10000u32);         // 0.3%
     pub const MinimumLiquidity: u128 = 1_000u128;
+    pub const MaxObservations: u32 = 4;
+    pub const ObservationPeriod: BlockNumber = 10;
+    pub const MaxTicks: u32 = 8;
+    pub const MinPositionLiquidity: u128 = 1_000_000u128;
     pub const LockAccountId: AccountId = ALICE;
     pub const MaxLengthRoute: u8 = 10;
 }
@@ -253,6 +257,10 @@ impl pallet_amm::Config for Test {
     type LpFee = DefaultLpFee;
     type MinimumLiquidity = MinimumLiquidity;
     type MaxLengthRoute = MaxLengthRoute;
+    type MaxObservations = MaxObservations;
+    type ObservationPeriod = ObservationPeriod;
+    type MaxTicks = MaxTicks;
+    type MinPositionLiquidity = MinPositionLiquidity;
     type GetNativeCurrencyId = NativeCurrencyId;
 }
 
@@ -344,6 +352,9 @@ parameter_types! {
     pub const LoansPalletId: PalletId = PalletId(*b"par/loan");
     pub const RewardAssetId: CurrencyId = HKO;
     pub const LiquidationFreeAssetId: CurrencyId = DOT;
+    pub const InsuranceAccount: Option<AccountId> = Some(EVE);
+    pub const MaxBatchActions: u32 = 8;
+    pub const CollateralDustValue: Balance = 100_000_000_000; // 0.1 unit at the price of 1
 }
 
 impl Config for Test {
@@ -357,6 +368,10 @@ impl Config for Test {
     type Assets = CurrencyAdapter;
     type RewardAssetId = RewardAssetId;
     type LiquidationFreeAssetId = LiquidationFreeAssetId;
+    type FlashLoanReceiver = FlashLoanCallDispatcher<RuntimeCall>;
+    type InsuranceAccount = InsuranceAccount;
+    type MaxBatchActions = MaxBatchActions;
+    type CollateralDustValue = CollateralDustValue;
 }
 
 parameter_types! {
@@ -481,6 +496,11 @@ pub const fn market_mock(ptoken_id: u32) -> Market<Balance> {
         supply_cap: 1_000_000_000_000_000_000_000u128, // set to 1B
         borrow_cap: 1_000_000_000_000_000_000_000u128, // set to 1B
         ptoken_id,
+        isolated: false,
+        debt_ceiling: 0,
+        borrowable_in_isolation: false,
+        flash_loan_fee: Ratio::from_parts(900),
+        liquidation_mode: LiquidationMode::Instant,
     }
 }
 
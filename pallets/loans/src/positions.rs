@@ -0,0 +1,104 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use pallet_traits::{AccountPositions, MarketPosition};
+use sp_runtime::traits::Zero;
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+    /// Returns the positions of `account` in all the active markets, with the
+    /// interest and the rewards accrued up to the current block.
+    ///
+    /// The accruals are rolled back, so the state is left untouched.
+    pub fn get_account_positions(
+        account: &T::AccountId,
+    ) -> Result<AccountPositions<BalanceOf<T>>, DispatchError> {
+        with_transaction(|| TransactionOutcome::Rollback(Self::accrued_account_positions(account)))
+    }
+
+    fn accrued_account_positions(
+        account: &T::AccountId,
+    ) -> Result<AccountPositions<BalanceOf<T>>, DispatchError> {
+        let mut positions = Vec::new();
+        for (asset_id, _) in Self::active_markets() {
+            Self::accrue_interest(asset_id)?;
+            let reward_accrued = Self::reward_accrued(account);
+            Self::collect_market_reward(asset_id, account)?;
+            let unclaimed_reward = Self::reward_accrued(account).saturating_sub(reward_accrued);
+
+            let deposits = Self::account_deposits(asset_id, account);
+            // Same split as `total_borrowed_value`, the delegated borrows are backed
+            // by the collateral of the delegators
+            let borrow_balance = Self::current_backed_borrow_balance(account, asset_id)?;
+            let delegated_borrow_balance = Self::current_borrow_balance(account, asset_id)?
+                .saturating_sub(Self::current_own_borrow_balance(account, asset_id)?);
+            if deposits.voucher_balance.is_zero()
+                && borrow_balance.is_zero()
+                && delegated_borrow_balance.is_zero()
+                && unclaimed_reward.is_zero()
+            {
+                continue;
+            }
+            let supply_balance = Self::calc_underlying_amount(
+                deposits.voucher_balance,
+                Self::exchange_rate_stored(asset_id)?,
+            )?;
+
+            positions.push(MarketPosition {
+                asset_id,
+                supply_balance,
+                ptoken_balance: deposits.voucher_balance,
+                borrow_balance,
+                delegated_borrow_balance,
+                is_collateral: deposits.is_collateral,
+                supply_value: Self::get_asset_value(asset_id, supply_balance)?,
+                borrow_value: Self::get_asset_value(asset_id, borrow_balance)?,
+                unclaimed_reward,
+            });
+        }
+
+        Ok(AccountPositions {
+            positions,
+            unclaimed_reward: Self::reward_accrued(account),
+            health_factor: Self::health_factor(account)?,
+        })
+    }
+
+    // Returns the liquidation threshold value of the collaterals of `account` over the
+    // value of its borrows, or `None` if it has no borrows.
+    //
+    // As in `is_liquidatable`, the liquidation free collaterals only count up to the
+    // borrow of `LiquidationFreeAssetId`, so the account can be liquidated once
+    // its health factor is below one.
+    pub(crate) fn health_factor(
+        account: &T::AccountId,
+    ) -> Result<Option<FixedU128>, DispatchError> {
+        let total_borrow_value = Self::total_borrowed_value(account)?;
+        if total_borrow_value.is_zero() {
+            return Ok(None);
+        }
+        let (liquidity, shortfall, lf_liquidity, _) =
+            Self::get_account_liquidation_threshold_liquidity(account)?;
+        let collateral_value = total_borrow_value
+            .saturating_add(liquidity)
+            .saturating_sub(shortfall)
+            .saturating_sub(lf_liquidity);
+        let health_factor = collateral_value
+            .checked_div(&total_borrow_value)
+            .ok_or(ArithmeticError::Overflow)?;
+        Ok(Some(health_factor))
+    }
+}
@@ -207,10 +207,11 @@ impl<T: Config> Pallet<T> {
         }
 
         let market = Self::ensure_active_market(underlying_id)?;
-        let collateral_value = Self::collateral_asset_value(who, underlying_id)?;
+        let emode = Self::account_emode_category(who, None);
+        let collateral_value = Self::collateral_asset_value(who, underlying_id, emode)?;
 
         // liquidity of all assets
-        let (liquidity, _, _, _) = Self::get_account_liquidity(who)?;
+        let (liquidity, _, _, _) = Self::get_account_liquidity_in_emode(who, emode)?;
 
         if liquidity >= collateral_value {
             return Ok(voucher_balance);
@@ -221,7 +222,7 @@ impl<T: Config> Pallet<T> {
         let price = Self::get_price(underlying_id)?;
 
         let reducible_supply_value = liquidity
-            .checked_div(&market.collateral_factor.into())
+            .checked_div(&Self::effective_collateral_factor(&market, emode).into())
             .ok_or(ArithmeticError::Overflow)?;
 
         let reducible_underlying_amount = reducible_supply_value
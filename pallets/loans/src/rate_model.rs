@@ -12,18 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use primitives::{Rate, Ratio};
+use primitives::{Rate, Ratio, Timestamp, SECONDS_PER_YEAR};
 use scale_info::TypeInfo;
-use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedSub, Saturating};
+use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Saturating};
+use sp_std::iter;
 
 use crate::*;
 
 /// Parallel interest rate model
 #[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
 pub enum InterestRateModel {
     Jump(JumpModel),
     Curve(CurveModel),
+    Kinked(KinkedModel),
+    Adaptive(AdaptiveModel),
 }
 
 impl Default for InterestRateModel {
@@ -56,18 +59,65 @@ impl InterestRateModel {
         Self::Curve(CurveModel::new_model(base_rate))
     }
 
+    pub fn new_kinked_model(
+        base_rate: Rate,
+        kinks: BoundedVec<(Ratio, Rate), MaxKinks>,
+        full_rate: Rate,
+    ) -> Self {
+        Self::Kinked(KinkedModel::new_model(base_rate, kinks, full_rate))
+    }
+
+    pub fn new_adaptive_model(
+        target_utilization: Ratio,
+        initial_rate_at_target: Rate,
+        min_rate_at_target: Rate,
+        max_rate_at_target: Rate,
+        adjustment_speed: Rate,
+        curve_steepness: Rate,
+    ) -> Self {
+        Self::Adaptive(AdaptiveModel::new_model(
+            target_utilization,
+            initial_rate_at_target,
+            min_rate_at_target,
+            max_rate_at_target,
+            adjustment_speed,
+            curve_steepness,
+        ))
+    }
+
     pub fn check_model(&self) -> bool {
         match self {
             Self::Jump(jump) => jump.check_model(),
             Self::Curve(curve) => curve.check_model(),
+            Self::Kinked(kinked) => kinked.check_model(),
+            Self::Adaptive(adaptive) => adaptive.check_model(),
         }
     }
 
     /// Calculates the current borrow interest rate
+    ///
+    /// The adaptive model is evaluated at its initial rate at target, use
+    /// [`AdaptiveModel::get_borrow_rate`] to evaluate it at its current one.
     pub fn get_borrow_rate(&self, utilization: Ratio) -> Option<Rate> {
         match self {
             Self::Jump(jump) => jump.get_borrow_rate(utilization),
             Self::Curve(curve) => curve.get_borrow_rate(utilization),
+            Self::Kinked(kinked) => kinked.get_borrow_rate(utilization),
+            Self::Adaptive(adaptive) => {
+                adaptive.get_borrow_rate(adaptive.initial_rate_at_target, utilization)
+            }
+        }
+    }
+
+    /// Returns the highest borrow interest rate of the model
+    pub fn get_full_rate(&self) -> Option<Rate> {
+        match self {
+            Self::Jump(jump) => Some(jump.full_rate),
+            Self::Curve(_) => None,
+            Self::Kinked(kinked) => Some(kinked.full_rate),
+            Self::Adaptive(adaptive) => adaptive
+                .max_rate_at_target
+                .checked_mul(&adaptive.curve_steepness),
         }
     }
 
@@ -187,6 +237,249 @@ impl CurveModel {
     }
 }
 
+/// Maximum number of kinks of the kinked interest rate model
+pub type MaxKinks = ConstU32<8>;
+
+/// The kinked interest rate model
+///
+/// A piecewise-linear model going from `base_rate` at 0% utilization to
+/// `full_rate` at 100% utilization through each of the kinks.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, Default, TypeInfo)]
+pub struct KinkedModel {
+    /// The base interest rate when utilization rate is 0
+    pub base_rate: Rate,
+    /// The utilization points, sorted in increasing order, and the interest
+    /// rates applied on them
+    pub kinks: BoundedVec<(Ratio, Rate), MaxKinks>,
+    /// The max interest rate when utilization rate is 100%
+    pub full_rate: Rate,
+}
+
+impl KinkedModel {
+    pub const MAX_BASE_RATE: Rate = Rate::from_inner(100_000_000_000_000_000); // 10%
+    pub const MAX_FULL_RATE: Rate = Rate::from_inner(1_000_000_000_000_000_000); // 100%
+
+    /// Create a new kinked model
+    pub fn new_model(
+        base_rate: Rate,
+        kinks: BoundedVec<(Ratio, Rate), MaxKinks>,
+        full_rate: Rate,
+    ) -> KinkedModel {
+        Self {
+            base_rate,
+            kinks,
+            full_rate,
+        }
+    }
+
+    /// Check the kinked model for sanity
+    pub fn check_model(&self) -> bool {
+        if self.base_rate > Self::MAX_BASE_RATE || self.full_rate > Self::MAX_FULL_RATE {
+            return false;
+        }
+
+        // Utilization must be strictly increasing and the rates must not decrease
+        let mut previous = (Ratio::zero(), self.base_rate);
+        for (utilization, rate) in self.points() {
+            if utilization <= previous.0 || rate < previous.1 {
+                return false;
+            }
+            previous = (utilization, rate);
+        }
+
+        true
+    }
+
+    /// Calculates the borrow interest rate of kinked model
+    pub fn get_borrow_rate(&self, utilization: Ratio) -> Option<Rate> {
+        let mut lower = (Ratio::zero(), self.base_rate);
+        for upper in self.points() {
+            if utilization <= upper.0 {
+                // (utilization - lower_utilization) * (upper_rate - lower_rate) /
+                // (upper_utilization - lower_utilization) + lower_rate
+                let result = upper
+                    .1
+                    .checked_sub(&lower.1)?
+                    .saturating_mul(utilization.saturating_sub(lower.0).into())
+                    .checked_div(&upper.0.saturating_sub(lower.0).into())?
+                    .checked_add(&lower.1)?;
+
+                return Some(result);
+            }
+            lower = upper;
+        }
+
+        None
+    }
+
+    // The kinks followed by the 100% utilization point
+    fn points(&self) -> impl Iterator<Item = (Ratio, Rate)> + '_ {
+        self.kinks
+            .iter()
+            .copied()
+            .chain(iter::once((Ratio::one(), self.full_rate)))
+    }
+}
+
+/// The adaptive interest rate model
+///
+/// The borrow rate follows a curve around the rate at target utilization,
+/// which itself drifts on every accrual, up when the utilization is above the
+/// target and down when it's below, at a speed proportional to the distance to
+/// the target.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, Default, TypeInfo)]
+pub struct AdaptiveModel {
+    /// The utilization the model steers the market towards
+    pub target_utilization: Ratio,
+    /// The rate at target utilization when the model is set
+    pub initial_rate_at_target: Rate,
+    /// The lower bound of the rate at target utilization
+    pub min_rate_at_target: Rate,
+    /// The upper bound of the rate at target utilization
+    pub max_rate_at_target: Rate,
+    /// The yearly relative change of the rate at target utilization when the
+    /// utilization is 0% or 100%
+    pub adjustment_speed: Rate,
+    /// The ratio between the borrow rate at 100% utilization and the rate at
+    /// target utilization, and between the rate at target utilization and the
+    /// borrow rate at 0% utilization
+    pub curve_steepness: Rate,
+}
+
+impl AdaptiveModel {
+    pub const MAX_RATE_AT_TARGET: Rate = Rate::from_inner(1_000_000_000_000_000_000); // 100%
+    pub const MAX_ADJUSTMENT_SPEED: Rate = Rate::from_inner(100_000_000_000_000_000_000); // 100
+    pub const MAX_CURVE_STEEPNESS: Rate = Rate::from_inner(10_000_000_000_000_000_000); // 10
+
+    /// Create a new adaptive model
+    pub fn new_model(
+        target_utilization: Ratio,
+        initial_rate_at_target: Rate,
+        min_rate_at_target: Rate,
+        max_rate_at_target: Rate,
+        adjustment_speed: Rate,
+        curve_steepness: Rate,
+    ) -> AdaptiveModel {
+        Self {
+            target_utilization,
+            initial_rate_at_target,
+            min_rate_at_target,
+            max_rate_at_target,
+            adjustment_speed,
+            curve_steepness,
+        }
+    }
+
+    /// Check the adaptive model for sanity
+    pub fn check_model(&self) -> bool {
+        if self.target_utilization.is_zero() || self.target_utilization == Ratio::one() {
+            return false;
+        }
+        if self.min_rate_at_target.is_zero()
+            || self.min_rate_at_target > self.initial_rate_at_target
+            || self.initial_rate_at_target > self.max_rate_at_target
+            || self.max_rate_at_target > Self::MAX_RATE_AT_TARGET
+        {
+            return false;
+        }
+        if self.adjustment_speed > Self::MAX_ADJUSTMENT_SPEED
+            || self.curve_steepness < Rate::one()
+            || self.curve_steepness > Self::MAX_CURVE_STEEPNESS
+        {
+            return false;
+        }
+
+        true
+    }
+
+    /// Calculates the rate at target utilization after `delta_time` seconds
+    /// spent at `utilization`
+    ///
+    /// The exponential drift is approximated linearly and bounded by
+    /// `min_rate_at_target` and `max_rate_at_target`.
+    pub fn next_rate_at_target(
+        &self,
+        rate_at_target: Rate,
+        utilization: Ratio,
+        delta_time: Timestamp,
+    ) -> Option<Rate> {
+        let (error, above_target) = self.normalized_error(utilization)?;
+        // adjustment_speed * error * delta_time / SECONDS_PER_YEAR
+        let adjustment = self
+            .adjustment_speed
+            .saturating_mul(error)
+            .saturating_mul(Rate::saturating_from_rational(delta_time, SECONDS_PER_YEAR));
+        let rate_at_target = if above_target {
+            rate_at_target.saturating_mul(Rate::one().saturating_add(adjustment))
+        } else {
+            rate_at_target.saturating_mul(Rate::one().saturating_sub(adjustment))
+        };
+
+        Some(
+            rate_at_target
+                .max(self.min_rate_at_target)
+                .min(self.max_rate_at_target),
+        )
+    }
+
+    /// Calculates the average rate at target utilization over `delta_time`
+    /// seconds spent at `utilization`
+    ///
+    /// The drift is averaged from its start, middle and end values with the
+    /// trapezoidal rule: (start + 2 * middle + end) / 4, so that a long gap
+    /// between two accruals isn't charged at the rate reached at its end.
+    pub fn average_rate_at_target(
+        &self,
+        rate_at_target: Rate,
+        utilization: Ratio,
+        delta_time: Timestamp,
+    ) -> Option<Rate> {
+        let middle = self.next_rate_at_target(rate_at_target, utilization, delta_time / 2)?;
+        let end = self.next_rate_at_target(rate_at_target, utilization, delta_time)?;
+        rate_at_target
+            .checked_add(&middle.checked_mul(&Rate::saturating_from_integer(2))?)?
+            .checked_add(&end)?
+            .checked_div(&Rate::saturating_from_integer(4))
+    }
+
+    /// Calculates the borrow interest rate of adaptive model
+    pub fn get_borrow_rate(&self, rate_at_target: Rate, utilization: Ratio) -> Option<Rate> {
+        let (error, above_target) = self.normalized_error(utilization)?;
+        let coefficient = if above_target {
+            // (curve_steepness - 1) * error + 1
+            self.curve_steepness
+                .checked_sub(&Rate::one())?
+                .saturating_mul(error)
+                .checked_add(&Rate::one())?
+        } else {
+            // 1 - (1 - 1 / curve_steepness) * error
+            let slope =
+                Rate::one().checked_sub(&Rate::one().checked_div(&self.curve_steepness)?)?;
+            Rate::one().checked_sub(&slope.saturating_mul(error))?
+        };
+
+        rate_at_target.checked_mul(&coefficient)
+    }
+
+    // The distance of `utilization` to the target, normalized to the range
+    // [0, 1] on each side of the target, and whether it's above the target
+    fn normalized_error(&self, utilization: Ratio) -> Option<(Rate, bool)> {
+        if utilization > self.target_utilization {
+            // (utilization - target_utilization) / (1 - target_utilization)
+            let error = Rate::from(utilization.saturating_sub(self.target_utilization))
+                .checked_div(&Ratio::one().saturating_sub(self.target_utilization).into())?;
+            Some((error, true))
+        } else {
+            // (target_utilization - utilization) / target_utilization
+            let error = Rate::from(self.target_utilization.saturating_sub(utilization))
+                .checked_div(&self.target_utilization.into())?;
+            Some((error, false))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,4 +563,237 @@ mod tests {
             Rate::from_inner(154217728000000000)
         );
     }
+
+    fn kinked_model(kinks: Vec<(Ratio, Rate)>) -> KinkedModel {
+        KinkedModel::new_model(
+            Rate::saturating_from_rational(2, 100),
+            kinks.try_into().unwrap(),
+            Rate::saturating_from_rational(96, 100),
+        )
+    }
+
+    #[test]
+    fn kinked_model_correctly_calculates_borrow_rate() {
+        let model = kinked_model(vec![
+            (
+                Ratio::from_percent(50),
+                Rate::saturating_from_rational(6, 100),
+            ),
+            (
+                Ratio::from_percent(90),
+                Rate::saturating_from_rational(16, 100),
+            ),
+        ]);
+        assert!(model.check_model());
+
+        assert_eq!(
+            model.get_borrow_rate(Ratio::zero()).unwrap(),
+            Rate::saturating_from_rational(2, 100)
+        );
+        // First slope: 2% + 25 / 50 * 4%
+        assert_eq!(
+            model.get_borrow_rate(Ratio::from_percent(25)).unwrap(),
+            Rate::saturating_from_rational(4, 100)
+        );
+        assert_eq!(
+            model.get_borrow_rate(Ratio::from_percent(50)).unwrap(),
+            Rate::saturating_from_rational(6, 100)
+        );
+        // Second slope: 6% + 20 / 40 * 10%
+        assert_eq!(
+            model.get_borrow_rate(Ratio::from_percent(70)).unwrap(),
+            Rate::saturating_from_rational(11, 100)
+        );
+        // Third slope: 16% + 5 / 10 * 80%
+        assert_eq!(
+            model.get_borrow_rate(Ratio::from_percent(95)).unwrap(),
+            Rate::saturating_from_rational(56, 100)
+        );
+        assert_eq!(
+            model.get_borrow_rate(Ratio::one()).unwrap(),
+            Rate::saturating_from_rational(96, 100)
+        );
+    }
+
+    #[test]
+    fn kinked_model_sanity_checks() {
+        // Unsorted kinks
+        let model = kinked_model(vec![
+            (
+                Ratio::from_percent(90),
+                Rate::saturating_from_rational(16, 100),
+            ),
+            (
+                Ratio::from_percent(50),
+                Rate::saturating_from_rational(6, 100),
+            ),
+        ]);
+        assert!(!model.check_model());
+
+        // Decreasing rates
+        let model = kinked_model(vec![
+            (
+                Ratio::from_percent(50),
+                Rate::saturating_from_rational(6, 100),
+            ),
+            (
+                Ratio::from_percent(90),
+                Rate::saturating_from_rational(5, 100),
+            ),
+        ]);
+        assert!(!model.check_model());
+
+        // Kink at 100% utilization
+        let model = kinked_model(vec![(
+            Ratio::one(),
+            Rate::saturating_from_rational(16, 100),
+        )]);
+        assert!(!model.check_model());
+
+        // Too high full rate
+        let mut model = kinked_model(vec![]);
+        assert!(model.check_model());
+        model.full_rate = Rate::saturating_from_rational(101, 100);
+        assert!(!model.check_model());
+    }
+
+    fn adaptive_model() -> AdaptiveModel {
+        AdaptiveModel::new_model(
+            Ratio::from_percent(90),
+            Rate::saturating_from_rational(4, 100),
+            Rate::saturating_from_rational(1, 1000),
+            Rate::saturating_from_rational(2, 10),
+            Rate::saturating_from_integer(50),
+            Rate::saturating_from_integer(4),
+        )
+    }
+
+    #[test]
+    fn adaptive_model_correctly_calculates_borrow_rate() {
+        let model = adaptive_model();
+        assert!(model.check_model());
+        let rate_at_target = model.initial_rate_at_target;
+
+        assert_eq!(
+            model
+                .get_borrow_rate(rate_at_target, Ratio::from_percent(90))
+                .unwrap(),
+            rate_at_target
+        );
+        assert_eq!(
+            model.get_borrow_rate(rate_at_target, Ratio::one()).unwrap(),
+            Rate::saturating_from_rational(16, 100)
+        );
+        assert_eq!(
+            model
+                .get_borrow_rate(rate_at_target, Ratio::zero())
+                .unwrap(),
+            Rate::saturating_from_rational(1, 100)
+        );
+        // 4% * (1 + 3 * 0.5)
+        assert_eq!(
+            model
+                .get_borrow_rate(rate_at_target, Ratio::from_percent(95))
+                .unwrap(),
+            Rate::saturating_from_rational(10, 100)
+        );
+    }
+
+    #[test]
+    fn adaptive_model_rate_at_target_drifts_towards_target() {
+        let model = adaptive_model();
+        let rate_at_target = model.initial_rate_at_target;
+        let one_day = SECONDS_PER_YEAR / 365;
+
+        // Unchanged at target utilization
+        assert_eq!(
+            model
+                .next_rate_at_target(rate_at_target, Ratio::from_percent(90), one_day)
+                .unwrap(),
+            rate_at_target
+        );
+        // 4% * (1 + 50 * 1 * 1 / 365)
+        assert_eq!(
+            model
+                .next_rate_at_target(rate_at_target, Ratio::one(), one_day)
+                .unwrap(),
+            rate_at_target * (Rate::one() + Rate::saturating_from_rational(50, 365))
+        );
+        // 4% * (1 - 50 * 0.5 * 1 / 365)
+        assert_eq!(
+            model
+                .next_rate_at_target(rate_at_target, Ratio::from_percent(45), one_day)
+                .unwrap(),
+            rate_at_target * (Rate::one() - Rate::saturating_from_rational(25, 365))
+        );
+        // Bounded by the min and max rates at target
+        assert_eq!(
+            model
+                .next_rate_at_target(rate_at_target, Ratio::one(), SECONDS_PER_YEAR)
+                .unwrap(),
+            model.max_rate_at_target
+        );
+        assert_eq!(
+            model
+                .next_rate_at_target(rate_at_target, Ratio::zero(), SECONDS_PER_YEAR)
+                .unwrap(),
+            model.min_rate_at_target
+        );
+    }
+
+    #[test]
+    fn adaptive_model_averages_the_rate_at_target_over_the_drift() {
+        let model = adaptive_model();
+        let rate_at_target = model.initial_rate_at_target;
+        let one_day = SECONDS_PER_YEAR / 365;
+
+        // Unchanged at target utilization
+        assert_eq!(
+            model
+                .average_rate_at_target(rate_at_target, Ratio::from_percent(90), one_day)
+                .unwrap(),
+            rate_at_target
+        );
+        // Between the start and the end of the drift
+        let average = model
+            .average_rate_at_target(rate_at_target, Ratio::one(), one_day)
+            .unwrap();
+        assert!(average > rate_at_target);
+        assert!(
+            average
+                < model
+                    .next_rate_at_target(rate_at_target, Ratio::one(), one_day)
+                    .unwrap()
+        );
+        // (4% + 2 * 20% + 20%) / 4, the middle and the end are bounded by the max
+        assert_eq!(
+            model
+                .average_rate_at_target(rate_at_target, Ratio::one(), SECONDS_PER_YEAR)
+                .unwrap(),
+            Rate::saturating_from_rational(16, 100)
+        );
+    }
+
+    #[test]
+    fn adaptive_model_sanity_checks() {
+        let mut model = adaptive_model();
+        model.target_utilization = Ratio::one();
+        assert!(!model.check_model());
+
+        let mut model = adaptive_model();
+        model.initial_rate_at_target = Rate::saturating_from_rational(3, 10);
+        assert!(!model.check_model());
+
+        let mut model = adaptive_model();
+        model.min_rate_at_target = Rate::zero();
+        assert!(!model.check_model());
+
+        let mut model = adaptive_model();
+        model.curve_steepness = Rate::saturating_from_rational(1, 2);
+        assert!(!model.check_model());
+
+        let mut model = adaptive_model();
+        model.adjustment_speed = Rate::saturating_from_integer(101);
+        assert!(!model.check_model());
+    }
 }
@@ -12,10 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod bad_debt;
+mod batch_actions;
+mod delegation;
 mod edge_cases;
+mod emode;
+mod flash_loan;
 mod interest_rate;
+mod isolation;
 mod liquidate_borrow;
+mod liquidation_candidates;
 mod market;
+mod positions;
 mod ptokens;
 
 use frame_support::{assert_err, assert_noop, assert_ok};
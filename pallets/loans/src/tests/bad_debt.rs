@@ -0,0 +1,122 @@
+use crate::{
+    mock::{
+        new_test_ext, unit, Assets, Loans, MockPriceFeeder, RuntimeEvent, RuntimeOrigin, System,
+        Test, ALICE, BOB, DAVE, DOT, EVE, USDT,
+    },
+    Error, Event,
+};
+use frame_support::{assert_noop, assert_ok};
+use primitives::{Price, Rate};
+use sp_runtime::{traits::Zero, DispatchError, FixedPointNumber};
+
+fn alice_borrows_100_dot() {
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), USDT, unit(200)));
+    assert_ok!(Loans::collateral_asset(
+        RuntimeOrigin::signed(ALICE),
+        USDT,
+        true
+    ));
+    assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(100)));
+}
+
+fn liquidate_alice_collateral() {
+    // USDT crashes and repaying 50 DOT seizes 50 * 1.1 / 0.2751 = 199.927 USDT,
+    // leaving Alice the dust of her collateral
+    MockPriceFeeder::set_price(USDT, Price::saturating_from_rational(2751, 10000));
+    assert!(Loans::has_collateral(&ALICE));
+    assert_ok!(Loans::liquidate_borrow(
+        RuntimeOrigin::signed(BOB),
+        ALICE,
+        DOT,
+        unit(50),
+        USDT
+    ));
+    assert!(!Loans::account_deposits(USDT, ALICE)
+        .voucher_balance
+        .is_zero());
+    assert!(!Loans::has_collateral(&ALICE));
+}
+
+#[test]
+fn write_off_bad_debt_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        alice_borrows_100_dot();
+        assert_ok!(Loans::add_reserves(
+            RuntimeOrigin::root(),
+            DAVE,
+            DOT,
+            unit(20)
+        ));
+        // Suppliers earned 60 DOT and the insurance holds 10 DOT
+        Assets::mint(
+            RuntimeOrigin::signed(ALICE),
+            DOT.into(),
+            Loans::account_id(),
+            unit(60),
+        )
+        .unwrap();
+        Assets::mint(RuntimeOrigin::signed(ALICE), DOT.into(), EVE, unit(10)).unwrap();
+        liquidate_alice_collateral();
+
+        // exchange_rate = (530 + 50 - 20) / 25000
+        assert_eq!(
+            Loans::exchange_rate_stored(DOT).unwrap(),
+            Rate::saturating_from_rational(224, 10000)
+        );
+        assert_ok!(Loans::write_off_bad_debt(RuntimeOrigin::root(), ALICE, DOT));
+
+        // 20 DOT from the reserves, 10 DOT from the insurance and 20 DOT socialized
+        System::assert_last_event(RuntimeEvent::Loans(Event::BadDebtWrittenOff(
+            ALICE,
+            DOT,
+            unit(20),
+            unit(10),
+            unit(20),
+        )));
+        assert_eq!(Loans::account_borrows(DOT, ALICE).principal, 0);
+        assert_eq!(Loans::total_borrows(DOT), 0);
+        assert_eq!(Loans::total_reserves(DOT), 0);
+        assert_eq!(Assets::balance(DOT, EVE), 0);
+        // exchange_rate = 540 / 25000
+        assert_eq!(
+            Loans::exchange_rate(DOT),
+            Rate::saturating_from_rational(216, 10000)
+        );
+    })
+}
+
+#[test]
+fn write_off_bad_debt_cannot_break_the_exchange_rate() {
+    new_test_ext().execute_with(|| {
+        alice_borrows_100_dot();
+        liquidate_alice_collateral();
+
+        // Suppliers can't absorb 50 DOT without going below the minimum exchange rate
+        assert_noop!(
+            Loans::write_off_bad_debt(RuntimeOrigin::root(), ALICE, DOT),
+            Error::<Test>::InvalidExchangeRate
+        );
+    })
+}
+
+#[test]
+fn write_off_bad_debt_should_not_work_with_invalid_params() {
+    new_test_ext().execute_with(|| {
+        alice_borrows_100_dot();
+
+        assert_noop!(
+            Loans::write_off_bad_debt(RuntimeOrigin::signed(BOB), ALICE, DOT),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Loans::write_off_bad_debt(RuntimeOrigin::root(), ALICE, DOT),
+            Error::<Test>::BorrowerHasCollateral
+        );
+        assert_noop!(
+            Loans::write_off_bad_debt(RuntimeOrigin::root(), BOB, DOT),
+            Error::<Test>::NoBadDebt
+        );
+    })
+}
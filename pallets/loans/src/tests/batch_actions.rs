@@ -0,0 +1,159 @@
+use crate::{
+    mock::{
+        new_test_ext, unit, Assets, Loans, MaxBatchActions, RuntimeEvent, RuntimeOrigin, System,
+        Test, ALICE, BOB, DOT, KSM, USDT,
+    },
+    Error, Event, LoanAction,
+};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_runtime::{traits::One, FixedPointNumber, FixedU128};
+
+fn actions(actions: Vec<LoanAction<u128>>) -> BoundedVec<LoanAction<u128>, MaxBatchActions> {
+    actions.try_into().unwrap()
+}
+
+fn alice_borrows_100_ksm() {
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), KSM, unit(500)));
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), USDT, unit(200)));
+    assert_ok!(Loans::collateral_asset(
+        RuntimeOrigin::signed(ALICE),
+        USDT,
+        true
+    ));
+    assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(100)));
+}
+
+#[test]
+fn batch_actions_allows_unhealthy_intermediate_states() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        alice_borrows_100_ksm();
+        // Redeeming the USDT collateral on its own is not allowed
+        assert_noop!(
+            Loans::redeem(RuntimeOrigin::signed(ALICE), USDT, unit(200)),
+            Error::<Test>::InsufficientLiquidity
+        );
+
+        // Replace the USDT collateral with DOT
+        assert_ok!(Loans::batch_actions(
+            RuntimeOrigin::signed(ALICE),
+            actions(vec![
+                LoanAction::Redeem {
+                    asset_id: USDT,
+                    amount: unit(200)
+                },
+                LoanAction::Mint {
+                    asset_id: DOT,
+                    amount: unit(200)
+                },
+                LoanAction::Collateral {
+                    asset_id: DOT,
+                    enable: true
+                },
+            ]),
+            FixedU128::saturating_from_rational(11, 10),
+        ));
+
+        System::assert_last_event(RuntimeEvent::Loans(Event::BatchActionsExecuted(ALICE, 3)));
+        assert_eq!(Assets::balance(USDT, ALICE), unit(1000));
+        assert_eq!(Assets::balance(DOT, ALICE), unit(800));
+        assert!(Loans::account_deposits(DOT, ALICE).is_collateral);
+        // health_factor = 200 * 0.55 / 100
+        assert_eq!(
+            Loans::health_factor(&ALICE).unwrap(),
+            Some(FixedU128::saturating_from_rational(11, 10))
+        );
+    })
+}
+
+#[test]
+fn batch_actions_can_borrow_over_the_limit_in_between() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), KSM, unit(500)));
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), USDT, unit(200)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            USDT,
+            true
+        ));
+
+        assert_ok!(Loans::batch_actions(
+            RuntimeOrigin::signed(ALICE),
+            actions(vec![
+                LoanAction::Borrow {
+                    asset_id: KSM,
+                    amount: unit(150)
+                },
+                LoanAction::Repay {
+                    asset_id: KSM,
+                    amount: unit(60)
+                },
+            ]),
+            FixedU128::one(),
+        ));
+        assert_eq!(Loans::account_borrows(KSM, ALICE).principal, unit(90));
+    })
+}
+
+#[test]
+fn batch_actions_checks_the_final_state() {
+    new_test_ext().execute_with(|| {
+        alice_borrows_100_ksm();
+
+        assert_noop!(
+            Loans::batch_actions(
+                RuntimeOrigin::signed(ALICE),
+                actions(vec![LoanAction::Redeem {
+                    asset_id: USDT,
+                    amount: unit(100)
+                }]),
+                FixedU128::one(),
+            ),
+            Error::<Test>::InsufficientLiquidity
+        );
+        // health_factor = 200 * 0.55 / 100 is below 1.2
+        assert_noop!(
+            Loans::batch_actions(
+                RuntimeOrigin::signed(ALICE),
+                actions(vec![
+                    LoanAction::Redeem {
+                        asset_id: USDT,
+                        amount: unit(200)
+                    },
+                    LoanAction::Mint {
+                        asset_id: DOT,
+                        amount: unit(200)
+                    },
+                    LoanAction::Collateral {
+                        asset_id: DOT,
+                        enable: true
+                    },
+                ]),
+                FixedU128::saturating_from_rational(12, 10),
+            ),
+            Error::<Test>::HealthFactorTooLow
+        );
+        assert_noop!(
+            Loans::batch_actions(
+                RuntimeOrigin::signed(ALICE),
+                actions(vec![LoanAction::Collateral {
+                    asset_id: USDT,
+                    enable: true
+                }]),
+                FixedU128::one(),
+            ),
+            Error::<Test>::DuplicateOperation
+        );
+        assert_noop!(
+            Loans::batch_actions(
+                RuntimeOrigin::signed(ALICE),
+                actions(vec![LoanAction::Redeem {
+                    asset_id: USDT,
+                    amount: 0
+                }]),
+                FixedU128::one(),
+            ),
+            Error::<Test>::InvalidAmount
+        );
+    })
+}
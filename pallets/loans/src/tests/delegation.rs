@@ -0,0 +1,286 @@
+use crate::{
+    mock::{
+        new_test_ext, unit, Assets, Loans, MockPriceFeeder, RuntimeEvent, RuntimeOrigin, System,
+        Test, BOB, CHARLIE, DAVE, KSM, USDT,
+    },
+    Error, Event,
+};
+use frame_support::{assert_noop, assert_ok};
+use primitives::Price;
+use sp_runtime::FixedPointNumber;
+
+fn dave_supplies_200_usdt() {
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), KSM, unit(500)));
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(DAVE), USDT, unit(200)));
+    assert_ok!(Loans::collateral_asset(
+        RuntimeOrigin::signed(DAVE),
+        USDT,
+        true
+    ));
+}
+
+#[test]
+fn borrow_on_behalf_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        dave_supplies_200_usdt();
+        assert_ok!(Loans::approve_delegation(
+            RuntimeOrigin::signed(DAVE),
+            CHARLIE,
+            KSM,
+            unit(60)
+        ));
+        assert_eq!(Loans::borrow_allowance((KSM, DAVE, CHARLIE)), unit(60));
+
+        assert_ok!(Loans::borrow_on_behalf(
+            RuntimeOrigin::signed(CHARLIE),
+            DAVE,
+            KSM,
+            unit(50)
+        ));
+        System::assert_last_event(RuntimeEvent::Loans(Event::BorrowedOnBehalf(
+            CHARLIE,
+            DAVE,
+            KSM,
+            unit(50),
+        )));
+        // The borrow is backed by Dave and owed by Charlie
+        assert_eq!(Assets::balance(KSM, CHARLIE), unit(50));
+        assert_eq!(Loans::account_borrows(KSM, DAVE).principal, 0);
+        assert_eq!(Loans::account_borrows(KSM, CHARLIE).principal, unit(50));
+        assert!(crate::Delegators::<Test>::contains_key((
+            KSM, CHARLIE, DAVE
+        )));
+        assert_eq!(
+            Loans::current_delegated_borrow_balance(KSM, &DAVE, &CHARLIE),
+            Ok(unit(50))
+        );
+        assert_eq!(Loans::borrow_allowance((KSM, DAVE, CHARLIE)), unit(10));
+        assert_noop!(
+            Loans::borrow_on_behalf(RuntimeOrigin::signed(CHARLIE), DAVE, KSM, unit(20)),
+            Error::<Test>::InsufficientBorrowAllowance
+        );
+
+        assert_ok!(Loans::repay_on_behalf(
+            RuntimeOrigin::signed(CHARLIE),
+            DAVE,
+            KSM,
+            unit(30)
+        ));
+        System::assert_last_event(RuntimeEvent::Loans(Event::RepaidOnBehalf(
+            CHARLIE,
+            DAVE,
+            KSM,
+            unit(30),
+        )));
+        assert_eq!(Assets::balance(KSM, CHARLIE), unit(20));
+        assert_eq!(Loans::account_borrows(KSM, CHARLIE).principal, unit(20));
+        assert_eq!(
+            Loans::current_delegated_borrow_balance(KSM, &DAVE, &CHARLIE),
+            Ok(unit(20))
+        );
+        assert_noop!(
+            Loans::repay_on_behalf(RuntimeOrigin::signed(CHARLIE), DAVE, KSM, unit(30)),
+            Error::<Test>::TooMuchRepay
+        );
+        assert_noop!(
+            Loans::repay_on_behalf(RuntimeOrigin::signed(BOB), DAVE, KSM, unit(10)),
+            Error::<Test>::TooMuchRepay
+        );
+        // The delegated borrows are repaid on the behalf of the delegator only
+        assert_noop!(
+            Loans::repay_borrow(RuntimeOrigin::signed(CHARLIE), KSM, unit(10)),
+            Error::<Test>::TooMuchRepay
+        );
+
+        assert_ok!(Loans::repay_on_behalf(
+            RuntimeOrigin::signed(CHARLIE),
+            DAVE,
+            KSM,
+            unit(20)
+        ));
+        assert_eq!(Loans::account_borrows(KSM, CHARLIE).principal, 0);
+        assert!(!crate::DelegatedBorrows::<Test>::contains_key((
+            KSM, DAVE, CHARLIE
+        )));
+        assert!(!crate::Delegators::<Test>::contains_key((
+            KSM, CHARLIE, DAVE
+        )));
+    })
+}
+
+#[test]
+fn borrow_on_behalf_checks_the_delegator_liquidity() {
+    new_test_ext().execute_with(|| {
+        dave_supplies_200_usdt();
+        assert_noop!(
+            Loans::borrow_on_behalf(RuntimeOrigin::signed(CHARLIE), DAVE, KSM, unit(10)),
+            Error::<Test>::InsufficientBorrowAllowance
+        );
+        assert_noop!(
+            Loans::approve_delegation(RuntimeOrigin::signed(DAVE), DAVE, KSM, unit(150)),
+            Error::<Test>::DelegateeIsDelegator
+        );
+
+        assert_ok!(Loans::approve_delegation(
+            RuntimeOrigin::signed(DAVE),
+            CHARLIE,
+            KSM,
+            unit(150)
+        ));
+        // Dave's collateral covers 100 KSM at most
+        assert_noop!(
+            Loans::borrow_on_behalf(RuntimeOrigin::signed(CHARLIE), DAVE, KSM, unit(101)),
+            Error::<Test>::InsufficientLiquidity
+        );
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(DAVE), KSM, unit(60)));
+        assert_noop!(
+            Loans::borrow_on_behalf(RuntimeOrigin::signed(CHARLIE), DAVE, KSM, unit(41)),
+            Error::<Test>::InsufficientLiquidity
+        );
+        assert_ok!(Loans::borrow_on_behalf(
+            RuntimeOrigin::signed(CHARLIE),
+            DAVE,
+            KSM,
+            unit(40)
+        ));
+
+        // Revoking the allowance doesn't touch the borrows
+        assert_ok!(Loans::approve_delegation(
+            RuntimeOrigin::signed(DAVE),
+            CHARLIE,
+            KSM,
+            0
+        ));
+        assert!(!crate::BorrowAllowances::<Test>::contains_key((
+            KSM, DAVE, CHARLIE
+        )));
+        assert_eq!(Loans::account_borrows(KSM, DAVE).principal, unit(60));
+        assert_eq!(Loans::account_borrows(KSM, CHARLIE).principal, unit(40));
+
+        // Only the collateral of Dave is charged for the borrows of Charlie
+        let (liquidity, shortfall, _, _) = Loans::get_account_liquidity(&DAVE).unwrap();
+        assert_eq!((liquidity, shortfall), (0.into(), 0.into()));
+        let (liquidity, shortfall, _, _) = Loans::get_account_liquidity(&CHARLIE).unwrap();
+        assert_eq!((liquidity, shortfall), (0.into(), 0.into()));
+    })
+}
+
+#[test]
+fn delegated_borrows_are_liquidated_from_the_delegator() {
+    new_test_ext().execute_with(|| {
+        dave_supplies_200_usdt();
+        assert_ok!(Loans::approve_delegation(
+            RuntimeOrigin::signed(DAVE),
+            CHARLIE,
+            KSM,
+            unit(100)
+        ));
+        assert_ok!(Loans::borrow_on_behalf(
+            RuntimeOrigin::signed(CHARLIE),
+            DAVE,
+            KSM,
+            unit(100)
+        ));
+        MockPriceFeeder::set_price(KSM, 2.into());
+
+        let collateral = Loans::account_deposits(USDT, DAVE).voucher_balance;
+        assert_ok!(Loans::liquidate_borrow(
+            RuntimeOrigin::signed(BOB),
+            DAVE,
+            KSM,
+            unit(50),
+            USDT
+        ));
+        assert!(Loans::account_deposits(USDT, DAVE).voucher_balance < collateral);
+        // The liquidator repaid half of the borrow of Charlie
+        assert_eq!(Loans::account_borrows(KSM, DAVE).principal, 0);
+        assert_eq!(Loans::account_borrows(KSM, CHARLIE).principal, unit(50));
+        assert_eq!(
+            Loans::current_delegated_borrow_balance(KSM, &DAVE, &CHARLIE),
+            Ok(unit(50))
+        );
+        assert_eq!(Loans::total_borrows(KSM), unit(50));
+    })
+}
+
+#[test]
+fn delegatee_is_not_liquidated_for_delegated_borrows() {
+    new_test_ext().execute_with(|| {
+        dave_supplies_200_usdt();
+        assert_ok!(Loans::approve_delegation(
+            RuntimeOrigin::signed(DAVE),
+            CHARLIE,
+            KSM,
+            unit(100)
+        ));
+        assert_ok!(Loans::borrow_on_behalf(
+            RuntimeOrigin::signed(CHARLIE),
+            DAVE,
+            KSM,
+            unit(100)
+        ));
+        MockPriceFeeder::set_price(KSM, 2.into());
+
+        assert_noop!(
+            Loans::liquidate_borrow(RuntimeOrigin::signed(BOB), CHARLIE, KSM, unit(50), USDT),
+            Error::<Test>::InsufficientShortfall
+        );
+        let (candidates, _) = Loans::get_liquidation_candidates(None, 10).unwrap();
+        assert_eq!(
+            candidates
+                .iter()
+                .map(|candidate| candidate.borrower.clone())
+                .collect::<Vec<_>>(),
+            vec![DAVE]
+        );
+    })
+}
+
+#[test]
+fn delegated_bad_debt_is_written_off_from_the_delegator() {
+    new_test_ext().execute_with(|| {
+        dave_supplies_200_usdt();
+        assert_ok!(Loans::approve_delegation(
+            RuntimeOrigin::signed(DAVE),
+            CHARLIE,
+            KSM,
+            unit(100)
+        ));
+        assert_ok!(Loans::borrow_on_behalf(
+            RuntimeOrigin::signed(CHARLIE),
+            DAVE,
+            KSM,
+            unit(100)
+        ));
+        assert_noop!(
+            Loans::write_off_bad_debt(RuntimeOrigin::root(), CHARLIE, KSM),
+            Error::<Test>::NoBadDebt
+        );
+        // The reserves absorb the 50 KSM left once the collateral of Dave is liquidated
+        assert_ok!(Loans::add_reserves(
+            RuntimeOrigin::root(),
+            BOB,
+            KSM,
+            unit(50)
+        ));
+        MockPriceFeeder::set_price(USDT, Price::saturating_from_rational(2751, 10000));
+        assert_ok!(Loans::liquidate_borrow(
+            RuntimeOrigin::signed(BOB),
+            DAVE,
+            KSM,
+            unit(50),
+            USDT
+        ));
+        assert!(!Loans::has_collateral(&DAVE));
+
+        assert_ok!(Loans::write_off_bad_debt(RuntimeOrigin::root(), DAVE, KSM));
+        assert_eq!(Loans::account_borrows(KSM, CHARLIE).principal, 0);
+        assert!(!crate::DelegatedBorrows::<Test>::contains_key((
+            KSM, DAVE, CHARLIE
+        )));
+        assert!(!crate::Delegators::<Test>::contains_key((
+            KSM, CHARLIE, DAVE
+        )));
+    })
+}
@@ -0,0 +1,139 @@
+use crate::{
+    mock::{new_test_ext, unit, Loans, RuntimeOrigin, Test, ALICE, BOB, DAVE, DOT, KSM, USDT},
+    EModeCategory, Error,
+};
+use frame_support::{assert_noop, assert_ok};
+use primitives::Ratio;
+use sp_runtime::FixedU128;
+
+const CATEGORY: EModeCategory = EModeCategory {
+    collateral_factor: Ratio::from_percent(90),
+    liquidation_threshold: Ratio::from_percent(93),
+};
+
+fn setup_category() {
+    assert_ok!(Loans::set_emode_category(
+        RuntimeOrigin::root(),
+        1,
+        CATEGORY
+    ));
+    assert_ok!(Loans::set_asset_emode_category(
+        RuntimeOrigin::root(),
+        DOT,
+        Some(1)
+    ));
+    assert_ok!(Loans::set_asset_emode_category(
+        RuntimeOrigin::root(),
+        KSM,
+        Some(1)
+    ));
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), KSM, unit(500)));
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(DAVE), USDT, unit(500)));
+}
+
+#[test]
+fn set_emode_category_works() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Loans::set_emode_category(
+                RuntimeOrigin::root(),
+                1,
+                EModeCategory {
+                    collateral_factor: Ratio::from_percent(95),
+                    liquidation_threshold: Ratio::from_percent(90),
+                }
+            ),
+            Error::<Test>::InvalidFactor
+        );
+        assert_noop!(
+            Loans::set_asset_emode_category(RuntimeOrigin::root(), DOT, Some(1)),
+            Error::<Test>::EModeCategoryDoesNotExist
+        );
+
+        assert_ok!(Loans::set_emode_category(
+            RuntimeOrigin::root(),
+            1,
+            CATEGORY
+        ));
+        assert_eq!(Loans::emode_categories(1), Some(CATEGORY));
+        assert_ok!(Loans::set_asset_emode_category(
+            RuntimeOrigin::root(),
+            DOT,
+            Some(1)
+        ));
+        assert_eq!(Loans::asset_emode_category(DOT), Some(1));
+        assert_ok!(Loans::set_asset_emode_category(
+            RuntimeOrigin::root(),
+            DOT,
+            None
+        ));
+        assert_eq!(Loans::asset_emode_category(DOT), None);
+    })
+}
+
+#[test]
+fn emode_category_applies_to_correlated_positions() {
+    new_test_ext().execute_with(|| {
+        setup_category();
+
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), DOT, unit(200)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            DOT,
+            true
+        ));
+
+        // 200 DOT * 90% instead of 200 DOT * 50%
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(170)));
+        let (liquidity, shortfall, _, _) = Loans::get_account_liquidity(&ALICE).unwrap();
+        assert_eq!(liquidity, FixedU128::from_inner(unit(10)));
+        assert_eq!(shortfall, FixedU128::from_inner(0));
+
+        // 200 DOT * 93% - 170 KSM
+        let (liquidity, shortfall, _, _) =
+            Loans::get_account_liquidation_threshold_liquidity(&ALICE).unwrap();
+        assert_eq!(liquidity, FixedU128::from_inner(unit(16)));
+        assert_eq!(shortfall, FixedU128::from_inner(0));
+    })
+}
+
+#[test]
+fn leaving_emode_category_requires_liquidity() {
+    new_test_ext().execute_with(|| {
+        setup_category();
+
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), DOT, unit(200)));
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), USDT, unit(100)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            DOT,
+            true
+        ));
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(170)));
+
+        // Borrowing USDT moves the account out of the category: 200 DOT * 50% < 170 KSM
+        assert_noop!(
+            Loans::borrow(RuntimeOrigin::signed(ALICE), USDT, unit(1)),
+            Error::<Test>::InsufficientLiquidity
+        );
+
+        // So does enabling USDT as collateral: (200 DOT + 100 USDT) * 50% < 170 KSM
+        assert_noop!(
+            Loans::collateral_asset(RuntimeOrigin::signed(ALICE), USDT, true),
+            Error::<Test>::InsufficientLiquidity
+        );
+
+        assert_ok!(Loans::repay_borrow(
+            RuntimeOrigin::signed(ALICE),
+            KSM,
+            unit(40)
+        ));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            USDT,
+            true
+        ));
+        let (liquidity, _, _, _) = Loans::get_account_liquidity(&ALICE).unwrap();
+        assert_eq!(liquidity, FixedU128::from_inner(unit(20)));
+    })
+}
@@ -0,0 +1,133 @@
+use crate::{
+    mock::{
+        new_test_ext, unit, Assets, Loans, RuntimeCall, RuntimeOrigin, Test, ALICE, BOB, CHARLIE,
+        DOT, SDOT,
+    },
+    Error,
+};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{Permill, TokenError};
+
+fn remark() -> Vec<u8> {
+    RuntimeCall::System(frame_system::Call::remark { remark: vec![] }).encode()
+}
+
+#[test]
+fn flash_loan_fee_goes_to_reserves() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+        let exchange_rate = Loans::exchange_rate_stored(DOT).unwrap();
+
+        assert_ok!(Loans::flash_loan(
+            RuntimeOrigin::signed(ALICE),
+            DOT,
+            unit(100),
+            remark()
+        ));
+
+        // 0.09% of 100 DOT
+        let fee = 90_000_000_000;
+        assert_eq!(Assets::balance(DOT, ALICE), unit(1000) - fee);
+        assert_eq!(Assets::balance(DOT, Loans::account_id()), unit(500) + fee);
+        assert_eq!(Loans::total_reserves(DOT), fee);
+        assert_eq!(Loans::outstanding_flash_loan(DOT), 0);
+        assert_eq!(Loans::exchange_rate_stored(DOT).unwrap(), exchange_rate);
+    })
+}
+
+#[test]
+fn flash_loan_must_be_repaid() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+
+        // Charlie holds the principal but can't pay the fee
+        assert_noop!(
+            Loans::flash_loan(RuntimeOrigin::signed(CHARLIE), DOT, unit(100), remark()),
+            TokenError::NoFunds
+        );
+    })
+}
+
+#[test]
+fn flash_loan_does_not_change_the_exchange_rate() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+
+        let mint = RuntimeCall::Loans(crate::Call::mint {
+            asset_id: DOT,
+            mint_amount: unit(100),
+        });
+        assert_ok!(Loans::flash_loan(
+            RuntimeOrigin::signed(ALICE),
+            DOT,
+            unit(400),
+            mint.encode()
+        ));
+
+        // Suppliers get the same amount of vouchers while the loan is outstanding
+        assert_eq!(
+            Loans::account_deposits(DOT, ALICE).voucher_balance,
+            unit(100) * 50
+        );
+    })
+}
+
+#[test]
+fn flash_loan_cannot_be_nested() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+
+        let flash_loan = RuntimeCall::Loans(crate::Call::flash_loan {
+            asset_id: DOT,
+            amount: unit(10),
+            data: remark(),
+        });
+        assert_noop!(
+            Loans::flash_loan(
+                RuntimeOrigin::signed(ALICE),
+                DOT,
+                unit(100),
+                flash_loan.encode()
+            ),
+            Error::<Test>::FlashLoanInProgress
+        );
+    })
+}
+
+#[test]
+fn flash_loan_should_not_work_with_invalid_params() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+
+        assert_noop!(
+            Loans::flash_loan(RuntimeOrigin::signed(ALICE), DOT, 0, remark()),
+            Error::<Test>::InvalidAmount
+        );
+        assert_noop!(
+            Loans::flash_loan(RuntimeOrigin::signed(ALICE), SDOT, unit(100), remark()),
+            Error::<Test>::MarketNotActivated
+        );
+        assert_noop!(
+            Loans::flash_loan(RuntimeOrigin::signed(ALICE), DOT, unit(501), remark()),
+            Error::<Test>::InsufficientCash
+        );
+        assert_noop!(
+            Loans::update_market(
+                RuntimeOrigin::root(),
+                DOT,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(Permill::from_percent(100)),
+                None,
+            ),
+            Error::<Test>::InvalidFactor
+        );
+    })
+}
@@ -1,5 +1,5 @@
 use crate::tests::Loans;
-use crate::{mock::*, Markets};
+use crate::{mock::*, AdaptiveModel, InterestRateModel, Markets};
 use frame_support::assert_ok;
 use primitives::{Rate, Ratio, SECONDS_PER_YEAR};
 use sp_runtime::{
@@ -406,3 +406,115 @@ fn a_market_can_only_accrue_interest_once_in_a_block() {
         );
     })
 }
+
+#[test]
+fn adaptive_rate_model_drifts_on_accrual() {
+    new_test_ext().execute_with(|| {
+        let model = AdaptiveModel::new_model(
+            Ratio::from_percent(40),
+            Rate::saturating_from_rational(4, 100),
+            Rate::saturating_from_rational(1, 1000),
+            Rate::saturating_from_rational(2, 10),
+            Rate::saturating_from_integer(50),
+            Rate::saturating_from_integer(4),
+        );
+        assert_ok!(Loans::update_rate_model(
+            RuntimeOrigin::root(),
+            DOT,
+            InterestRateModel::Adaptive(model)
+        ));
+        // Deposit 200 DOT and borrow 100 DOT
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), DOT, unit(200)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            DOT,
+            true
+        ));
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(100)));
+        assert_eq!(Loans::rate_at_target(DOT), None);
+
+        // One day at 50% utilization, above the 40% target
+        let one_day = SECONDS_PER_YEAR / 365;
+        TimestampPallet::set_timestamp(6000 + one_day * 1000);
+        let (borrow_rate, ..) = Loans::get_market_status(DOT).unwrap();
+        assert_ok!(Loans::accrue_interest(DOT));
+
+        let util = Ratio::from_percent(50);
+        let rate_at_target = model
+            .next_rate_at_target(model.initial_rate_at_target, util, one_day)
+            .unwrap();
+        assert!(rate_at_target > model.initial_rate_at_target);
+        assert_eq!(Loans::rate_at_target(DOT), Some(rate_at_target));
+        // Interest accrues at the average rate at target over the day
+        let average_rate_at_target = model
+            .average_rate_at_target(model.initial_rate_at_target, util, one_day)
+            .unwrap();
+        assert_eq!(
+            Loans::borrow_rate(DOT),
+            model.get_borrow_rate(average_rate_at_target, util).unwrap()
+        );
+        assert_eq!(Loans::borrow_rate(DOT), borrow_rate);
+
+        // A new rate model starts from scratch
+        assert_ok!(Loans::update_rate_model(
+            RuntimeOrigin::root(),
+            DOT,
+            InterestRateModel::Adaptive(model)
+        ));
+        assert_eq!(Loans::rate_at_target(DOT), None);
+    })
+}
+
+#[test]
+fn adaptive_rate_model_accrues_a_long_gap_at_the_average_rate() {
+    new_test_ext().execute_with(|| {
+        let model = AdaptiveModel::new_model(
+            Ratio::from_percent(40),
+            Rate::saturating_from_rational(4, 100),
+            Rate::saturating_from_rational(1, 1000),
+            Rate::saturating_from_rational(2, 10),
+            Rate::saturating_from_integer(50),
+            Rate::saturating_from_integer(4),
+        );
+        assert_ok!(Loans::update_rate_model(
+            RuntimeOrigin::root(),
+            DOT,
+            InterestRateModel::Adaptive(model)
+        ));
+        // Deposit 200 DOT and borrow 100 DOT
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), DOT, unit(200)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            DOT,
+            true
+        ));
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(100)));
+
+        // No accrual for a year at 50% utilization, the rate at target
+        // reaches its max halfway through
+        TimestampPallet::set_timestamp(6000 + SECONDS_PER_YEAR * 1000);
+        assert_ok!(Loans::accrue_interest(DOT));
+
+        let util = Ratio::from_percent(50);
+        assert_eq!(Loans::rate_at_target(DOT), Some(model.max_rate_at_target));
+        // (4% + 2 * 20% + 20%) / 4
+        let borrow_rate = model
+            .get_borrow_rate(Rate::saturating_from_rational(16, 100), util)
+            .unwrap();
+        assert_eq!(Loans::borrow_rate(DOT), borrow_rate);
+        assert!(
+            borrow_rate
+                > model
+                    .get_borrow_rate(model.initial_rate_at_target, util)
+                    .unwrap()
+        );
+        assert!(
+            borrow_rate
+                < model
+                    .get_borrow_rate(model.max_rate_at_target, util)
+                    .unwrap()
+        );
+        // A year of interest at that rate
+        assert_eq!(Loans::borrow_index(DOT), Rate::one() + borrow_rate);
+    })
+}
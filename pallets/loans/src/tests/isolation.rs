@@ -0,0 +1,205 @@
+use crate::{
+    mock::{
+        new_test_ext, unit, Loans, MockPriceFeeder, RuntimeOrigin, Test, ALICE, BOB, DAVE, DOT,
+        KSM, USDT,
+    },
+    Error, IsolatedBorrow,
+};
+use frame_support::{assert_noop, assert_ok};
+
+fn isolate_ksm() {
+    // KSM can only back DOT borrows, up to 100 USD
+    assert_ok!(Loans::update_market_isolation(
+        RuntimeOrigin::root(),
+        KSM,
+        Some(true),
+        Some(unit(100)),
+        None,
+    ));
+    assert_ok!(Loans::update_market_isolation(
+        RuntimeOrigin::root(),
+        DOT,
+        None,
+        None,
+        Some(true),
+    ));
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(DAVE), USDT, unit(500)));
+}
+
+#[test]
+fn update_market_isolation_works() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Loans::update_market_isolation(RuntimeOrigin::root(), KSM, Some(true), None, None),
+            Error::<Test>::InvalidDebtCeiling
+        );
+        assert_ok!(Loans::update_market_isolation(
+            RuntimeOrigin::root(),
+            KSM,
+            Some(true),
+            Some(unit(100)),
+            Some(false),
+        ));
+        let market = Loans::market(KSM).unwrap();
+        assert!(market.isolated);
+        assert_eq!(market.debt_ceiling, unit(100));
+        assert!(!market.borrowable_in_isolation);
+    })
+}
+
+#[test]
+fn isolated_collateral_must_be_exclusive() {
+    new_test_ext().execute_with(|| {
+        isolate_ksm();
+
+        // Alice enters isolation mode with KSM
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), KSM, unit(200)));
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), USDT, unit(100)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            KSM,
+            true
+        ));
+        assert_noop!(
+            Loans::collateral_asset(RuntimeOrigin::signed(ALICE), USDT, true),
+            Error::<Test>::IsolatedCollateralConflict
+        );
+
+        // Bob cannot enter isolation mode while using other collaterals
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(BOB),
+            DOT,
+            true
+        ));
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), KSM, unit(100)));
+        assert_noop!(
+            Loans::collateral_asset(RuntimeOrigin::signed(BOB), KSM, true),
+            Error::<Test>::IsolatedCollateralConflict
+        );
+    })
+}
+
+#[test]
+fn isolated_borrow_respects_whitelist_and_debt_ceiling() {
+    new_test_ext().execute_with(|| {
+        isolate_ksm();
+
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), KSM, unit(400)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            KSM,
+            true
+        ));
+
+        // USDT is not borrowable in isolation
+        assert_noop!(
+            Loans::borrow(RuntimeOrigin::signed(ALICE), USDT, unit(10)),
+            Error::<Test>::AssetNotBorrowableInIsolation
+        );
+
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(60)));
+        assert_eq!(Loans::isolated_debt(KSM), unit(60));
+
+        // 60 + 50 is above the 100 USD debt ceiling even though liquidity is enough
+        assert_noop!(
+            Loans::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(50)),
+            Error::<Test>::DebtCeilingExceeded
+        );
+
+        assert_ok!(Loans::repay_borrow(
+            RuntimeOrigin::signed(ALICE),
+            DOT,
+            unit(30)
+        ));
+        assert_eq!(Loans::isolated_debt(KSM), unit(30));
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(50)));
+        assert_eq!(Loans::isolated_debt(KSM), unit(80));
+    })
+}
+
+#[test]
+fn isolation_cannot_be_toggled_while_in_use() {
+    new_test_ext().execute_with(|| {
+        // USDT has deposits already
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), USDT, unit(100)));
+        assert_noop!(
+            Loans::update_market_isolation(
+                RuntimeOrigin::root(),
+                USDT,
+                Some(true),
+                Some(unit(100)),
+                None
+            ),
+            Error::<Test>::IsolationWithDeposits
+        );
+
+        isolate_ksm();
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), KSM, unit(400)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            KSM,
+            true
+        ));
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(60)));
+        assert_noop!(
+            Loans::update_market_isolation(RuntimeOrigin::root(), KSM, Some(false), None, None),
+            Error::<Test>::IsolationWithOutstandingDebt
+        );
+
+        assert_ok!(Loans::repay_borrow_all(RuntimeOrigin::signed(ALICE), DOT));
+        assert_eq!(Loans::isolated_debt(KSM), 0);
+        assert_ok!(Loans::update_market_isolation(
+            RuntimeOrigin::root(),
+            KSM,
+            Some(false),
+            None,
+            None
+        ));
+        // Alice is no longer in isolation mode
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), USDT, unit(10)));
+        assert_eq!(Loans::isolated_debt(KSM), 0);
+    })
+}
+
+#[test]
+fn isolated_debt_is_released_at_the_borrowed_value() {
+    new_test_ext().execute_with(|| {
+        isolate_ksm();
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), KSM, unit(400)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            KSM,
+            true
+        ));
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(60)));
+        assert_eq!(
+            Loans::isolated_borrows(DOT, ALICE),
+            IsolatedBorrow {
+                amount: unit(60),
+                value: unit(60),
+            }
+        );
+
+        // Half of the borrows are repaid after DOT doubled, which releases
+        // half of the debt valued at the borrow time
+        MockPriceFeeder::set_price(DOT, 2.into());
+        assert_ok!(Loans::repay_borrow(
+            RuntimeOrigin::signed(ALICE),
+            DOT,
+            unit(30)
+        ));
+        assert_eq!(Loans::isolated_debt(KSM), unit(30));
+        assert_eq!(
+            Loans::isolated_borrows(DOT, ALICE),
+            IsolatedBorrow {
+                amount: unit(30),
+                value: unit(30),
+            }
+        );
+
+        assert_ok!(Loans::repay_borrow_all(RuntimeOrigin::signed(ALICE), DOT));
+        assert_eq!(Loans::isolated_debt(KSM), 0);
+        assert_eq!(Loans::isolated_borrows(DOT, ALICE), Default::default());
+    })
+}
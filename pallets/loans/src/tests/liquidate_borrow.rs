@@ -1,13 +1,13 @@
 use crate::{
     mock::{
-        new_test_ext, Assets, Loans, MockPriceFeeder, RuntimeOrigin, Test, ALICE, BOB, DOT, KSM,
-        USDT,
+        new_test_ext, Assets, Loans, MockPriceFeeder, RuntimeEvent, RuntimeOrigin, System, Test,
+        ALICE, BOB, DOT, KSM, USDT,
     },
     tests::unit,
-    Error, MarketState,
+    Error, Event, LiquidationMode, MarketState,
 };
 use frame_support::{assert_err, assert_noop, assert_ok};
-use primitives::{tokens::CDOT_6_13, Rate, DOT_U};
+use primitives::{tokens::CDOT_6_13, Rate, Ratio, DOT_U};
 use sp_runtime::FixedPointNumber;
 
 #[test]
@@ -257,6 +257,226 @@ fn liquidator_must_not_be_borrower() {
     })
 }
 
+#[test]
+fn dutch_auction_liquidation_works() {
+    new_test_ext().execute_with(|| {
+        initial_setup();
+        alice_borrows_100_ksm();
+        enable_ksm_dutch_auction();
+        MockPriceFeeder::set_price(KSM, 2.into());
+        System::set_block_number(1);
+
+        assert_noop!(
+            Loans::liquidate_borrow(RuntimeOrigin::signed(BOB), ALICE, KSM, unit(50), USDT),
+            Error::<Test>::LiquidationAuctionDoesNotExist
+        );
+        assert_ok!(Loans::start_liquidation_auction(
+            RuntimeOrigin::signed(BOB),
+            ALICE,
+            KSM
+        ));
+        assert_eq!(Loans::liquidation_auctions(ALICE, KSM), Some(1));
+
+        // Half of the auction has elapsed, the discount is 5% on top of the 3%
+        // reserved incentive
+        System::set_block_number(51);
+        assert_ok!(Loans::liquidate_borrow(
+            RuntimeOrigin::signed(BOB),
+            ALICE,
+            KSM,
+            unit(50),
+            USDT
+        ));
+
+        // incentive = repay KSM value * 1.08 = (50 * 2) * 1.08 = 108
+        // Alice USDT collateral: deposit - incentive = 200 - 108 = 92
+        // Bob USDT collateral: incentive - reserved = 108 - (108 / 1.08 * 0.03) = 105
+        assert_eq!(
+            Loans::exchange_rate(USDT)
+                .saturating_mul_int(Loans::account_deposits(USDT, ALICE).voucher_balance),
+            unit(92),
+        );
+        assert_eq!(
+            Loans::exchange_rate(USDT)
+                .saturating_mul_int(Loans::account_deposits(USDT, BOB).voucher_balance),
+            unit(105),
+        );
+        let incentive_reward_account = Loans::incentive_reward_account_id().unwrap();
+        assert_eq!(
+            Loans::exchange_rate(USDT).saturating_mul_int(
+                Loans::account_deposits(USDT, incentive_reward_account).voucher_balance
+            ),
+            unit(3),
+        );
+        // Alice is still underwater, so the auction goes on
+        assert_eq!(Loans::liquidation_auctions(ALICE, KSM), Some(1));
+    })
+}
+
+#[test]
+fn dutch_auction_discount_is_capped() {
+    new_test_ext().execute_with(|| {
+        initial_setup();
+        alice_borrows_100_ksm();
+        enable_ksm_dutch_auction();
+        MockPriceFeeder::set_price(KSM, 2.into());
+        System::set_block_number(1);
+        assert_ok!(Loans::start_liquidation_auction(
+            RuntimeOrigin::signed(BOB),
+            ALICE,
+            KSM
+        ));
+
+        // The auction starts where liquidating breaks even, the reserves taking 3%
+        let market = Loans::market(KSM).unwrap();
+        assert_eq!(
+            Loans::current_liquidate_incentive(&ALICE, KSM, &market).unwrap(),
+            Rate::saturating_from_rational(103, 100)
+        );
+        System::set_block_number(1000);
+        assert_eq!(
+            Loans::current_liquidate_incentive(&ALICE, KSM, &market).unwrap(),
+            Rate::saturating_from_rational(113, 100)
+        );
+    })
+}
+
+#[test]
+fn start_liquidation_auction_should_not_work_with_invalid_params() {
+    new_test_ext().execute_with(|| {
+        initial_setup();
+        alice_borrows_100_ksm();
+        MockPriceFeeder::set_price(KSM, 2.into());
+        assert_noop!(
+            Loans::start_liquidation_auction(RuntimeOrigin::signed(BOB), ALICE, KSM),
+            Error::<Test>::LiquidationAuctionNotEnabled
+        );
+
+        enable_ksm_dutch_auction();
+        assert_noop!(
+            Loans::start_liquidation_auction(RuntimeOrigin::signed(BOB), BOB, KSM),
+            Error::<Test>::InsufficientShortfall
+        );
+        assert_ok!(Loans::start_liquidation_auction(
+            RuntimeOrigin::signed(BOB),
+            ALICE,
+            KSM
+        ));
+        assert_noop!(
+            Loans::start_liquidation_auction(RuntimeOrigin::signed(BOB), ALICE, KSM),
+            Error::<Test>::LiquidationAuctionAlreadyStarted
+        );
+    })
+}
+
+#[test]
+fn close_liquidation_auction_works() {
+    new_test_ext().execute_with(|| {
+        initial_setup();
+        alice_borrows_100_ksm();
+        enable_ksm_dutch_auction();
+        MockPriceFeeder::set_price(KSM, 2.into());
+        assert_ok!(Loans::start_liquidation_auction(
+            RuntimeOrigin::signed(BOB),
+            ALICE,
+            KSM
+        ));
+        assert_noop!(
+            Loans::close_liquidation_auction(RuntimeOrigin::signed(ALICE), ALICE, KSM),
+            Error::<Test>::BorrowerStillLiquidatable
+        );
+
+        MockPriceFeeder::set_price(KSM, 1.into());
+        assert_ok!(Loans::close_liquidation_auction(
+            RuntimeOrigin::signed(ALICE),
+            ALICE,
+            KSM
+        ));
+        assert_eq!(Loans::liquidation_auctions(ALICE, KSM), None);
+        assert_noop!(
+            Loans::close_liquidation_auction(RuntimeOrigin::signed(ALICE), ALICE, KSM),
+            Error::<Test>::LiquidationAuctionDoesNotExist
+        );
+    })
+}
+
+#[test]
+fn liquidation_auction_is_closed_when_borrower_recovers() {
+    new_test_ext().execute_with(|| {
+        initial_setup();
+        alice_borrows_100_ksm();
+        enable_ksm_dutch_auction();
+        MockPriceFeeder::set_price(KSM, 2.into());
+        System::set_block_number(1);
+        assert_ok!(Loans::start_liquidation_auction(
+            RuntimeOrigin::signed(BOB),
+            ALICE,
+            KSM
+        ));
+
+        // Still underwater after a partial repay
+        assert_ok!(Loans::repay_borrow(
+            RuntimeOrigin::signed(ALICE),
+            KSM,
+            unit(10)
+        ));
+        assert_eq!(Loans::liquidation_auctions(ALICE, KSM), Some(1));
+
+        // Healthy again after supplying more collateral
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), USDT, unit(200)));
+        assert_eq!(Loans::liquidation_auctions(ALICE, KSM), None);
+        System::assert_has_event(RuntimeEvent::Loans(Event::LiquidationAuctionClosed(
+            ALICE, KSM,
+        )));
+    })
+}
+
+#[test]
+fn update_market_rejects_invalid_dutch_auction() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Loans::update_market(
+                RuntimeOrigin::root(),
+                KSM,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(LiquidationMode::DutchAuction {
+                    max_discount: Ratio::from_percent(10),
+                    duration: 0,
+                }),
+            ),
+            Error::<Test>::InvalidLiquidationMode
+        );
+    })
+}
+
+fn enable_ksm_dutch_auction() {
+    assert_ok!(Loans::update_market(
+        RuntimeOrigin::root(),
+        KSM,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(LiquidationMode::DutchAuction {
+            max_discount: Ratio::from_percent(10),
+            duration: 100,
+        }),
+    ));
+}
+
 fn alice_borrows_100_ksm() {
     assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(100)));
 }
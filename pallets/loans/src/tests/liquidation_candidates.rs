@@ -0,0 +1,88 @@
+use crate::mock::{
+    new_test_ext, unit, Loans, MockPriceFeeder, RuntimeOrigin, ALICE, BOB, DAVE, DOT, KSM, USDT,
+};
+use frame_support::assert_ok;
+use pallet_traits::LiquidationCandidate;
+use sp_runtime::{FixedPointNumber, FixedU128};
+
+fn initial_setup() {
+    // Bob deposits 200 KSM and borrows 10 KSM against 200 DOT
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), DOT, unit(200)));
+    assert_ok!(Loans::collateral_asset(
+        RuntimeOrigin::signed(BOB),
+        DOT,
+        true
+    ));
+    assert_ok!(Loans::borrow(RuntimeOrigin::signed(BOB), KSM, unit(10)));
+    // Dave deposits 200 USDT
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(DAVE), USDT, unit(200)));
+    // Alice borrows 70 KSM and 10 USDT against 200 USDT
+    assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), USDT, unit(200)));
+    assert_ok!(Loans::collateral_asset(
+        RuntimeOrigin::signed(ALICE),
+        USDT,
+        true
+    ));
+    assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(70)));
+    assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), USDT, unit(10)));
+    // Alice's borrows are now worth 150 USD
+    MockPriceFeeder::set_price(KSM, 2.into());
+}
+
+fn alice_candidate() -> LiquidationCandidate<crate::mock::AccountId, u128> {
+    LiquidationCandidate {
+        borrower: ALICE,
+        // 150 - 200 * 55%
+        shortfall: FixedU128::from_inner(unit(40)),
+        liquidation_asset_id: KSM,
+        collateral_asset_id: USDT,
+        // 140 * 50% / 2
+        max_repay_amount: unit(35),
+    }
+}
+
+#[test]
+fn get_liquidation_candidates_works() {
+    new_test_ext().execute_with(|| {
+        initial_setup();
+
+        assert_eq!(
+            Loans::get_liquidation_candidates(None, 10).unwrap(),
+            (vec![alice_candidate()], None)
+        );
+
+        // Liquidating the maximum repay amount works
+        assert_ok!(Loans::liquidate_borrow(
+            RuntimeOrigin::signed(BOB),
+            ALICE,
+            KSM,
+            unit(35),
+            USDT
+        ));
+    })
+}
+
+#[test]
+fn get_liquidation_candidates_can_be_paginated() {
+    new_test_ext().execute_with(|| {
+        initial_setup();
+
+        let mut candidates = vec![];
+        let mut start_key = None;
+        let mut pages = 0;
+        loop {
+            let (page, next_key) = Loans::get_liquidation_candidates(start_key, 1).unwrap();
+            candidates.extend(page);
+            pages += 1;
+            match next_key {
+                Some(key) => start_key = Some(key),
+                None => break,
+            }
+        }
+
+        // Three borrows and one last empty page, Alice is only returned once
+        assert_eq!(pages, 4);
+        assert_eq!(candidates, vec![alice_candidate()]);
+    })
+}
@@ -213,6 +213,8 @@ fn update_market_ensures_that_it_is_not_possible_to_modify_unknown_market_curren
                 None,
                 None,
                 None,
+                None,
+                None,
             ),
             Error::<Test>::MarketDoesNotExist
         );
@@ -239,6 +241,8 @@ fn update_market_works() {
             None,
             None,
             None,
+            None,
+            None,
         ));
 
         assert_eq!(Loans::market(DOT).unwrap().close_factor, Default::default());
@@ -266,6 +270,8 @@ fn update_market_should_not_work_if_with_invalid_params() {
             None,
             None,
             None,
+            None,
+            None,
         ));
         assert_noop!(
             Loans::update_market(
@@ -279,6 +285,8 @@ fn update_market_should_not_work_if_with_invalid_params() {
                 None,
                 None,
                 None,
+                None,
+                None,
             ),
             Error::<Test>::InvalidFactor
         );
@@ -295,6 +303,8 @@ fn update_market_should_not_work_if_with_invalid_params() {
                 None,
                 None,
                 None,
+                None,
+                None,
             ),
             Error::<Test>::InvalidFactor
         );
@@ -310,6 +320,8 @@ fn update_market_should_not_work_if_with_invalid_params() {
                 None,
                 None,
                 None,
+                None,
+                None,
             ),
             Error::<Test>::InvalidFactor
         );
@@ -326,6 +338,8 @@ fn update_market_should_not_work_if_with_invalid_params() {
                 Some(Rate::from_inner(Rate::DIV / 100 * 90)),
                 Some(Zero::zero()),
                 None,
+                None,
+                None,
             ),
             Error::<Test>::InvalidSupplyCap
         );
@@ -344,7 +358,7 @@ fn update_rate_model_works() {
         assert_ok!(Loans::update_rate_model(
             RuntimeOrigin::root(),
             DOT,
-            new_rate_model,
+            new_rate_model.clone(),
         ));
         assert_eq!(Loans::market(DOT).unwrap().rate_model, new_rate_model);
 
@@ -0,0 +1,162 @@
+use crate::mock::{
+    new_test_ext, unit, Loans, RuntimeOrigin, TimestampPallet, ALICE, BOB, CHARLIE, DAVE, DOT, KSM,
+    USDT,
+};
+use frame_support::assert_ok;
+use pallet_traits::{LoansPositionDataProvider, MarketPosition};
+use primitives::{tokens::CDOT_6_13, Rate};
+use sp_runtime::{
+    traits::{CheckedDiv, One, Zero},
+    FixedPointNumber, FixedU128,
+};
+
+#[test]
+fn get_account_positions_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), DOT, unit(200)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            DOT,
+            true
+        ));
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), KSM, unit(50)));
+        TimestampPallet::set_timestamp(12000);
+
+        let account_positions = Loans::get_account_positions(&ALICE).unwrap();
+        // The interest accrual is not stored
+        assert_eq!(Loans::borrow_index(KSM), Rate::one());
+
+        let borrow_balance = Loans::get_current_borrow_balance(&ALICE, KSM).unwrap();
+        assert!(borrow_balance > unit(50));
+        assert_eq!(account_positions.positions.len(), 2);
+        assert!(account_positions.positions.contains(&MarketPosition {
+            asset_id: DOT,
+            supply_balance: unit(200),
+            ptoken_balance: unit(200) * 50,
+            borrow_balance: 0,
+            delegated_borrow_balance: 0,
+            is_collateral: true,
+            supply_value: FixedU128::from_inner(unit(200)),
+            borrow_value: FixedU128::zero(),
+            unclaimed_reward: 0,
+        }));
+        assert!(account_positions.positions.contains(&MarketPosition {
+            asset_id: KSM,
+            supply_balance: 0,
+            ptoken_balance: 0,
+            borrow_balance,
+            delegated_borrow_balance: 0,
+            is_collateral: false,
+            supply_value: FixedU128::zero(),
+            borrow_value: FixedU128::from_inner(borrow_balance),
+            unclaimed_reward: 0,
+        }));
+        assert_eq!(account_positions.unclaimed_reward, 0);
+        // 200 DOT with a liquidation threshold of 55% over the KSM borrow
+        assert_eq!(
+            account_positions.health_factor,
+            FixedU128::from_inner(unit(110)).checked_div(&FixedU128::from_inner(borrow_balance))
+        );
+    })
+}
+
+#[test]
+fn get_account_positions_reports_the_delegated_borrows_apart() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(BOB), KSM, unit(500)));
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(DAVE), USDT, unit(200)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(DAVE),
+            USDT,
+            true
+        ));
+        assert_ok!(Loans::approve_delegation(
+            RuntimeOrigin::signed(DAVE),
+            CHARLIE,
+            KSM,
+            unit(50)
+        ));
+        assert_ok!(Loans::borrow_on_behalf(
+            RuntimeOrigin::signed(CHARLIE),
+            DAVE,
+            KSM,
+            unit(50)
+        ));
+
+        // Charlie owes the borrow, which is backed by the collateral of Dave
+        let charlie_positions = Loans::get_account_positions(&CHARLIE).unwrap();
+        assert_eq!(
+            charlie_positions.positions,
+            vec![MarketPosition {
+                asset_id: KSM,
+                supply_balance: 0,
+                ptoken_balance: 0,
+                borrow_balance: 0,
+                delegated_borrow_balance: unit(50),
+                is_collateral: false,
+                supply_value: FixedU128::zero(),
+                borrow_value: FixedU128::zero(),
+                unclaimed_reward: 0,
+            }]
+        );
+        assert_eq!(charlie_positions.health_factor, None);
+
+        let dave_positions = Loans::get_account_positions(&DAVE).unwrap();
+        assert!(dave_positions.positions.contains(&MarketPosition {
+            asset_id: KSM,
+            supply_balance: 0,
+            ptoken_balance: 0,
+            borrow_balance: unit(50),
+            delegated_borrow_balance: 0,
+            is_collateral: false,
+            supply_value: FixedU128::zero(),
+            borrow_value: FixedU128::from_inner(unit(50)),
+            unclaimed_reward: 0,
+        }));
+        assert!(dave_positions.health_factor.is_some());
+    })
+}
+
+#[test]
+fn get_account_positions_of_an_account_without_position() {
+    new_test_ext().execute_with(|| {
+        let account_positions = Loans::get_account_positions(&CHARLIE).unwrap();
+        assert!(account_positions.positions.is_empty());
+        assert_eq!(account_positions.health_factor, None);
+    })
+}
+
+#[test]
+fn health_factor_counts_liquidation_free_collaterals_up_to_their_borrow() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(DAVE), USDT, unit(200)));
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(DAVE), DOT, unit(200)));
+        // CDOT is a liquidation free collateral backing the DOT borrow
+        assert_ok!(Loans::mint(
+            RuntimeOrigin::signed(ALICE),
+            CDOT_6_13,
+            unit(200)
+        ));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            CDOT_6_13,
+            true
+        ));
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(50)));
+        assert_ok!(Loans::mint(RuntimeOrigin::signed(ALICE), KSM, unit(200)));
+        assert_ok!(Loans::collateral_asset(
+            RuntimeOrigin::signed(ALICE),
+            KSM,
+            true
+        ));
+        assert_ok!(Loans::borrow(RuntimeOrigin::signed(ALICE), USDT, unit(100)));
+
+        // (200 * 0.55 + min(200 * 0.55, 50)) / (50 + 100)
+        assert_eq!(
+            Loans::get_account_positions(&ALICE).unwrap().health_factor,
+            Some(FixedU128::saturating_from_rational(16, 15))
+        );
+        assert!(!Loans::is_liquidatable(&ALICE).unwrap());
+    })
+}
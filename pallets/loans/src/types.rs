@@ -1,6 +1,6 @@
 use crate::InterestRateModel;
 use frame_support::pallet_prelude::*;
-use primitives::{CurrencyId, Rate, Ratio};
+use primitives::{BlockNumber, CurrencyId, Rate, Ratio};
 use scale_info::TypeInfo;
 
 /// Container for borrow balance information
@@ -30,6 +30,15 @@ pub struct Deposits<Balance> {
     pub is_collateral: bool,
 }
 
+/// Borrows drawn against an isolated collateral
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, Default, TypeInfo)]
+pub struct IsolatedBorrow<Balance> {
+    /// Borrowed amount, the accrued interest excluded
+    pub amount: Balance,
+    /// Value of the borrowed amount, in USD with 18 decimals, at the time it was borrowed
+    pub value: Balance,
+}
+
 /// The current state of a market. For more information, see [Market].
 #[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
 #[derive(Clone, Copy, PartialEq, Eq, codec::Decode, codec::Encode, RuntimeDebug, TypeInfo)]
@@ -39,6 +48,28 @@ pub enum MarketState {
     Supervision,
 }
 
+/// How the collateral of an underwater position is sold to liquidators.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, PartialEq, Eq, codec::Decode, codec::Encode, RuntimeDebug, TypeInfo)]
+pub enum LiquidationMode {
+    /// The collateral is sold at the fixed `liquidate_incentive` discount
+    Instant,
+    /// The collateral discount grows linearly from zero up to `max_discount`
+    /// over `duration` blocks since the liquidation auction started. It comes on
+    /// top of the `liquidate_incentive_reserved_factor` taken by the reserves,
+    /// where liquidating breaks even
+    DutchAuction {
+        max_discount: Ratio,
+        duration: BlockNumber,
+    },
+}
+
+impl Default for LiquidationMode {
+    fn default() -> Self {
+        Self::Instant
+    }
+}
+
 /// Market.
 ///
 /// A large pool of liquidity where accounts can lend and borrow.
@@ -68,6 +99,34 @@ pub struct Market<Balance> {
     pub borrow_cap: Balance,
     /// Ptoken asset id
     pub ptoken_id: CurrencyId,
+    /// Whether this asset is an isolated collateral. An account using it as collateral
+    /// can't enable any other collateral and can only borrow `borrowable_in_isolation` assets
+    pub isolated: bool,
+    /// Upper bound of the total debt backed by this isolated collateral, valued in USD
+    /// with 18 decimals
+    pub debt_ceiling: Balance,
+    /// Whether this asset can be borrowed against an isolated collateral
+    pub borrowable_in_isolation: bool,
+    /// Fee charged on flash loans, added to the market reserves
+    pub flash_loan_fee: Ratio,
+    /// How the borrows of this market are liquidated
+    pub liquidation_mode: LiquidationMode,
+}
+
+/// Identifier of an efficiency mode category
+pub type EModeCategoryId = u8;
+
+/// Efficiency mode category.
+///
+/// Parameters applied instead of the market ones when all of an account's
+/// collaterals and borrows are correlated assets of the same category.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, Default, TypeInfo)]
+pub struct EModeCategory {
+    /// The collateral utilization ratio inside the category
+    pub collateral_factor: Ratio,
+    /// The liquidation threshold inside the category
+    pub liquidation_threshold: Ratio,
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, Default)]
@@ -76,3 +135,30 @@ pub struct RewardMarketState<BlockNumber, Balance> {
     /// total amount of staking asset user deposited
     pub block: BlockNumber,
 }
+
+/// A step of a batch of actions, see `batch_actions`.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+pub enum LoanAction<Balance> {
+    /// Supplies `amount` of `asset_id`
+    Mint {
+        asset_id: CurrencyId,
+        amount: Balance,
+    },
+    /// Redeems `amount` of the underlying `asset_id`
+    Redeem {
+        asset_id: CurrencyId,
+        amount: Balance,
+    },
+    /// Borrows `amount` of `asset_id`
+    Borrow {
+        asset_id: CurrencyId,
+        amount: Balance,
+    },
+    /// Repays `amount` of the borrows of `asset_id`
+    Repay {
+        asset_id: CurrencyId,
+        amount: Balance,
+    },
+    /// Turns the collateral option of `asset_id` on or off
+    Collateral { asset_id: CurrencyId, enable: bool },
+}
@@ -19,6 +19,9 @@
 //! This pallet provides the price from Oracle Module by implementing the
 //! `PriceFeeder` trait. In case of emergency, the price can be set directly
 //! by Oracle Collective.
+//!
+//! Long-tail assets that the oracle doesn't cover can be priced from the TWAP
+//! of their AMM pool against an asset the oracle does cover.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -29,7 +32,7 @@ use frame_system::pallet_prelude::*;
 use orml_traits::{DataFeeder, DataProvider, DataProviderExtended};
 use primitives::*;
 use sp_runtime::{
-    traits::{CheckedDiv, CheckedMul},
+    traits::{CheckedDiv, CheckedMul, Zero},
     FixedPointNumber, FixedU128,
 };
 use sp_std::vec::Vec;
@@ -117,6 +120,16 @@ pub mod pallet {
         SetPrice(CurrencyId, Price),
         /// Reset emergency price. \[asset_id\]
         ResetPrice(CurrencyId),
+        /// Set TWAP price source. \[asset_id, quote_asset_id, window\]
+        TwapSourceSet(CurrencyId, CurrencyId, T::BlockNumber),
+        /// Remove TWAP price source. \[asset_id\]
+        TwapSourceRemoved(CurrencyId),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// TWAP source is quoted in the asset itself or has an empty window
+        InvalidTwapSource,
     }
 
     /// Mapping from currency id to it's emergency price
@@ -131,6 +144,13 @@ pub mod pallet {
     pub type ForeignToNativeAsset<T: Config> =
         StorageMap<_, Twox64Concat, CurrencyId, CurrencyId, OptionQuery>;
 
+    /// Mapping from currency id to the quote asset and window in blocks of
+    /// the AMM TWAP used as its price when the oracle has none
+    #[pallet::storage]
+    #[pallet::getter(fn twap_source)]
+    pub type TwapSources<T: Config> =
+        StorageMap<_, Twox64Concat, CurrencyId, (CurrencyId, T::BlockNumber), OptionQuery>;
+
     #[pallet::pallet]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(PhantomData<T>);
@@ -179,6 +199,41 @@ pub mod pallet {
             ForeignToNativeAsset::<T>::insert(foreign_asset_id, asset_id);
             Ok(().into())
         }
+
+        /// Price `asset_id` from the AMM TWAP against `quote_asset_id` over
+        /// `window` blocks when the oracle has no price for it
+        #[pallet::call_index(3)]
+        #[pallet::weight((<T as Config>::WeightInfo::set_twap_source(), DispatchClass::Operational))]
+        #[transactional]
+        pub fn set_twap_source(
+            origin: OriginFor<T>,
+            asset_id: CurrencyId,
+            quote_asset_id: CurrencyId,
+            window: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            ensure!(
+                asset_id != quote_asset_id && !window.is_zero(),
+                Error::<T>::InvalidTwapSource
+            );
+            TwapSources::<T>::insert(asset_id, (quote_asset_id, window));
+            Self::deposit_event(Event::TwapSourceSet(asset_id, quote_asset_id, window));
+            Ok(().into())
+        }
+
+        /// Remove the TWAP price source of `asset_id`
+        #[pallet::call_index(4)]
+        #[pallet::weight((<T as Config>::WeightInfo::remove_twap_source(), DispatchClass::Operational))]
+        #[transactional]
+        pub fn remove_twap_source(
+            origin: OriginFor<T>,
+            asset_id: CurrencyId,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            TwapSources::<T>::remove(asset_id);
+            Self::deposit_event(Event::TwapSourceRemoved(asset_id));
+            Ok(().into())
+        }
     }
 }
 
@@ -206,7 +261,9 @@ impl<T: Config> Pallet<T> {
         asset_id: CurrencyId,
         base_price: TimeStampedPrice,
     ) -> Option<TimeStampedPrice> {
-        if is_ls_token(asset_id) {
+        if is_ls_token(asset_id)
+            || T::LiquidStakingCurrenciesProvider::get_staking_currency_of(&asset_id).is_some()
+        {
             Self::get_staking_asset_price(asset_id, base_price)
         } else if is_vault_token(asset_id) {
             Self::get_vault_asset_price(asset_id, base_price)
@@ -221,6 +278,14 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    // Liquid staking tokens are priced against their own staking currency, everything
+    // else against the relay currency.
+    fn get_base_currency(asset_id: CurrencyId) -> CurrencyId {
+        let asset_id = Self::foreign_to_native_asset(asset_id).unwrap_or(asset_id);
+        T::LiquidStakingCurrenciesProvider::get_staking_currency_of(&asset_id)
+            .unwrap_or_else(T::RelayCurrency::get)
+    }
+
     fn normalize_detail_price(price: TimeStampedPrice, mantissa: u128) -> Option<PriceDetail> {
         price
             .value
@@ -245,9 +310,8 @@ impl<T: Config> Pallet<T> {
         asset_id: CurrencyId,
         base_price: TimeStampedPrice,
     ) -> Option<TimeStampedPrice> {
-        let liquid_currency = T::LiquidStakingCurrenciesProvider::get_liquid_currency()
-            .filter(|liquid_currency| liquid_currency == &asset_id)?;
-        T::LiquidStakingExchangeRateProvider::get_exchange_rate(&liquid_currency)
+        T::LiquidStakingCurrenciesProvider::get_staking_currency_of(&asset_id)?;
+        T::LiquidStakingExchangeRateProvider::get_exchange_rate(&asset_id)
             .and_then(|rate| Self::scale_timestamped_price(base_price, rate))
     }
 
@@ -293,6 +357,38 @@ impl<T: Config> Pallet<T> {
             timestamp: vault_asset_price.timestamp,
         })
     }
+
+    // price of a long-tail asset from the AMM TWAP against its quote asset, the
+    // TWAP is between the smallest units of both assets so it's scaled by their
+    // mantissas to get the price of a whole unit
+    fn get_twap_asset_price(
+        asset_id: CurrencyId,
+        quote_price_of: impl Fn(&CurrencyId) -> Option<TimeStampedPrice>,
+    ) -> Option<TimeStampedPrice> {
+        let (quote_asset_id, window) = Self::twap_source(asset_id)?;
+        let quote_price = quote_price_of(&quote_asset_id)?;
+        let twap = T::AMM::get_twap((asset_id, quote_asset_id), window).ok()?;
+        let mantissa_rate = Price::checked_from_rational(
+            Self::get_asset_mantissa(&asset_id)?,
+            Self::get_asset_mantissa(&quote_asset_id)?,
+        )?;
+        log::trace!(
+            target: "prices::get_twap_asset_price",
+            "asset_id: {:?}, quote_asset_id: {:?}, window: {:?}, twap: {:?}",
+            asset_id,
+            quote_asset_id,
+            window,
+            twap
+        );
+        let value = quote_price
+            .value
+            .checked_mul(&twap)?
+            .checked_mul(&mantissa_rate)?;
+        Some(TimeStampedPrice {
+            value,
+            timestamp: quote_price.timestamp,
+        })
+    }
 }
 
 impl<T: Config> PriceFeeder for Pallet<T> {
@@ -307,9 +403,10 @@ impl<T: Config> PriceFeeder for Pallet<T> {
         // if emergency price exists, return it
         Self::get_emergency_price(asset_id).or_else(|| {
             let mantissa = Self::get_asset_mantissa(asset_id)?;
-            T::Source::get(&T::RelayCurrency::get())
+            T::Source::get(&Self::get_base_currency(*asset_id))
                 .and_then(|base_price| Self::get_special_asset_price(*asset_id, base_price))
                 .or_else(|| T::Source::get(asset_id))
+                .or_else(|| Self::get_twap_asset_price(*asset_id, T::Source::get))
                 .and_then(|price| Self::normalize_detail_price(price, mantissa))
         })
     }
@@ -333,9 +430,10 @@ impl<T: Config> EmergencyPriceFeeder<CurrencyId, Price> for Pallet<T> {
 impl<T: Config> DataProviderExtended<CurrencyId, TimeStampedPrice> for Pallet<T> {
     fn get_no_op(asset_id: &CurrencyId) -> Option<TimeStampedPrice> {
         let _mantissa = Self::get_asset_mantissa(asset_id)?;
-        T::Source::get_no_op(&T::RelayCurrency::get())
+        T::Source::get_no_op(&Self::get_base_currency(*asset_id))
             .and_then(|base_price| Self::get_special_asset_price(*asset_id, base_price))
             .or_else(|| T::Source::get_no_op(asset_id))
+            .or_else(|| Self::get_twap_asset_price(*asset_id, T::Source::get_no_op))
     }
 
     fn get_all_values() -> Vec<(CurrencyId, Option<TimeStampedPrice>)> {
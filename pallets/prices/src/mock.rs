@@ -25,7 +25,7 @@ use frame_system::{EnsureRoot, EnsureSigned, EnsureSignedBy};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, FixedPointNumber};
 
-pub use primitives::tokens::{CDOT_7_14, CKSM_20_27, DOT, KSM, LP_DOT_CDOT_7_14, SDOT, SKSM};
+pub use primitives::tokens::{CDOT_7_14, CKSM_20_27, DOT, KSM, LKSM, LP_DOT_CDOT_7_14, SDOT, SKSM};
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -34,6 +34,10 @@ pub const CHARLIE: AccountId = 2;
 
 pub const PRICE_ONE: u128 = 1_000_000_000_000_000_000;
 
+// an asset the oracle doesn't cover and its AMM pool's lp token
+pub const LONG_TAIL: CurrencyId = 4000;
+pub const LP_DOT_LONG_TAIL: CurrencyId = 4001;
+
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
 }
@@ -133,11 +137,12 @@ impl DecimalProvider<CurrencyId> for Decimal {
     fn get_decimal(asset_id: &CurrencyId) -> Option<u8> {
         match *asset_id {
             DOT | SDOT => Some(10),
-            KSM | SKSM => Some(12),
+            KSM | SKSM | LKSM => Some(12),
             CKSM_20_27 => Some(12),
             CDOT_7_14 => Some(10),
             LP_DOT_CDOT_7_14 => Some(12),
             LC_DOT => Some(10),
+            LONG_TAIL => Some(12),
             _ => None,
         }
     }
@@ -153,6 +158,16 @@ impl LiquidStakingCurrenciesProvider<CurrencyId> for LiquidStaking {
     }
 }
 
+pub struct KsmLiquidStaking;
+impl LiquidStakingCurrenciesProvider<CurrencyId> for KsmLiquidStaking {
+    fn get_staking_currency() -> Option<CurrencyId> {
+        Some(KSM)
+    }
+    fn get_liquid_currency() -> Option<CurrencyId> {
+        Some(LKSM)
+    }
+}
+
 pub struct TokenCurrenciesFilter;
 impl VaultTokenCurrenciesFilter<CurrencyId> for TokenCurrenciesFilter {
     fn contains(asset_id: &CurrencyId) -> bool {
@@ -246,6 +261,10 @@ parameter_types! {
     // pub const DefaultProtocolFee: Ratio = Ratio::from_rational(5u32, 10000u32);
     pub  DefaultLpFee: Ratio = Ratio::from_rational(25u32, 10000u32);         // 0.3%
     pub const MinimumLiquidity: u128 = 1_000u128;
+    pub const MaxObservations: u32 = 4;
+    pub const ObservationPeriod: BlockNumber = 10;
+    pub const MaxTicks: u32 = 8;
+    pub const MinPositionLiquidity: u128 = 1_000_000u128;
     pub const LockAccountId: AccountId = ALICE;
     pub const MaxLengthRoute: u8 = 10;
 }
@@ -268,6 +287,10 @@ impl pallet_amm::Config for Test {
     type LpFee = DefaultLpFee;
     type MinimumLiquidity = MinimumLiquidity;
     type MaxLengthRoute = MaxLengthRoute;
+    type MaxObservations = MaxObservations;
+    type ObservationPeriod = ObservationPeriod;
+    type MaxTicks = MaxTicks;
+    type MinPositionLiquidity = MinPositionLiquidity;
     type GetNativeCurrencyId = NativeCurrencyId;
 }
 
@@ -276,7 +299,7 @@ impl crate::Config for Test {
     type Source = MockDataProvider;
     type FeederOrigin = EnsureSignedBy<One, AccountId>;
     type UpdateOrigin = EnsureSignedBy<One, AccountId>;
-    type LiquidStakingCurrenciesProvider = LiquidStaking;
+    type LiquidStakingCurrenciesProvider = (LiquidStaking, KsmLiquidStaking);
     type LiquidStakingExchangeRateProvider = LiquidStakingExchangeRateProvider;
     type VaultTokenCurrenciesFilter = TokenCurrenciesFilter;
     type VaultTokenExchangeRateProvider = TokenExchangeRateProvider;
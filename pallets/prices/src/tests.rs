@@ -199,6 +199,38 @@ fn get_liquid_price_work() {
     });
 }
 
+#[test]
+fn get_liquid_price_of_several_pools_work() {
+    new_test_ext().execute_with(|| {
+        // LKSM is priced against KSM instead of the relay currency
+        assert_eq!(
+            Prices::get_price(&LKSM),
+            LiquidStakingExchangeRateProvider::get_exchange_rate(&LKSM)
+                .unwrap()
+                .checked_mul_int(500_000_000 * PRICE_ONE)
+                .map(|i| (Price::from_inner(i), 0))
+        );
+        assert_eq!(
+            Prices::get_price(&SDOT),
+            LiquidStakingExchangeRateProvider::get_exchange_rate(&SDOT)
+                .unwrap()
+                .checked_mul_int(10_000_000_000 * PRICE_ONE)
+                .map(|i| (Price::from_inner(i), 0))
+        );
+
+        // the combined pools don't have a single pair of currencies
+        type Pools = <Test as Config>::LiquidStakingCurrenciesProvider;
+        assert_eq!(Pools::get_staking_currency(), None);
+        assert_eq!(Pools::get_liquid_currency(), None);
+        assert_eq!(Pools::get_staking_currency_of(&SDOT), Some(DOT));
+        assert_eq!(Pools::get_staking_currency_of(&LKSM), Some(KSM));
+        assert_eq!(
+            <(LiquidStaking, LiquidStaking)>::get_liquid_currency(),
+            Some(SDOT)
+        );
+    });
+}
+
 #[test]
 fn get_ctoken_price_work() {
     new_test_ext().execute_with(|| {
@@ -303,3 +335,82 @@ fn get_foreign_ctoken_price_work() {
         assert_eq!(Prices::get_price(&CDOT_7_14), Prices::get_price(&LC_DOT));
     });
 }
+
+#[test]
+fn get_twap_source_price_work() {
+    new_test_ext().execute_with(|| {
+        Assets::force_create(RuntimeOrigin::root(), LONG_TAIL.into(), ALICE, true, 1).unwrap();
+        Assets::force_create(
+            RuntimeOrigin::root(),
+            LP_DOT_LONG_TAIL.into(),
+            ALICE,
+            true,
+            1,
+        )
+        .unwrap();
+        Assets::mint(
+            RuntimeOrigin::signed(ALICE),
+            LONG_TAIL.into(),
+            ALICE,
+            1000 * PRICE_ONE,
+        )
+        .unwrap();
+        DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (LONG_TAIL, DOT),
+            (100 * PRICE_ONE, 200 * PRICE_ONE), //1:2
+            ALICE,
+            LP_DOT_LONG_TAIL,
+        )
+        .unwrap();
+
+        // the first trade observes the pool
+        System::set_block_number(1);
+        assert_ok!(<DefaultAMM as AMM<_, _, _, _>>::swap(
+            &ALICE,
+            (DOT, LONG_TAIL),
+            1_000
+        ));
+
+        assert_noop!(
+            Prices::set_twap_source(RuntimeOrigin::signed(ALICE), LONG_TAIL, LONG_TAIL, 10),
+            Error::<Test>::InvalidTwapSource
+        );
+        assert_noop!(
+            Prices::set_twap_source(RuntimeOrigin::signed(ALICE), LONG_TAIL, DOT, 0),
+            Error::<Test>::InvalidTwapSource
+        );
+        assert_ok!(Prices::set_twap_source(
+            RuntimeOrigin::signed(ALICE),
+            LONG_TAIL,
+            DOT,
+            10
+        ));
+        System::assert_last_event(RuntimeEvent::Prices(crate::Event::TwapSourceSet(
+            LONG_TAIL, DOT, 10,
+        )));
+
+        // no observation covers the window yet
+        assert_eq!(Prices::get_price(&LONG_TAIL), None);
+
+        System::set_block_number(11);
+
+        let twap = DefaultAMM::get_twap((LONG_TAIL, DOT), 10).unwrap();
+        assert!(twap > Price::saturating_from_rational(199, 100));
+        assert!(twap < Price::saturating_from_rational(201, 100));
+
+        // DOT price * TWAP * 10^12 / 10^10, normalized by the LONG_TAIL mantissa
+        let price = Price::saturating_from_integer(100)
+            .checked_mul(&twap)
+            .and_then(|price| price.checked_mul(&Price::saturating_from_integer(100)))
+            .and_then(|price| price.checked_div(&Price::from_inner(1_000_000_000_000)))
+            .unwrap();
+        assert_eq!(Prices::get_price(&LONG_TAIL), Some((price, 0)));
+
+        assert_ok!(Prices::remove_twap_source(
+            RuntimeOrigin::signed(ALICE),
+            LONG_TAIL
+        ));
+        assert_eq!(Prices::get_price(&LONG_TAIL), None);
+    });
+}
@@ -26,6 +26,8 @@ pub trait WeightInfo {
     fn set_price() -> Weight;
     fn reset_price() -> Weight;
     fn set_foreign_asset() -> Weight;
+    fn set_twap_source() -> Weight;
+    fn remove_twap_source() -> Weight;
 }
 
 /// Weights for pallet_prices using the Substrate node and recommended hardware.
@@ -46,6 +48,18 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1 as u64))
             .saturating_add(T::DbWeight::get().writes(1 as u64))
     }
+    // Hand estimate, not benchmarked.
+    fn set_twap_source() -> Weight {
+        Weight::from_ref_time(25_000_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
+    // Hand estimate, not benchmarked.
+    fn remove_twap_source() -> Weight {
+        Weight::from_ref_time(22_000_000 as u64)
+            .saturating_add(T::DbWeight::get().reads(1 as u64))
+            .saturating_add(T::DbWeight::get().writes(1 as u64))
+    }
 }
 
 // For backwards compatibility and tests
@@ -65,4 +79,16 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1 as u64))
             .saturating_add(RocksDbWeight::get().writes(1 as u64))
     }
+    // Hand estimate, not benchmarked.
+    fn set_twap_source() -> Weight {
+        Weight::from_ref_time(25_000_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
+    // Hand estimate, not benchmarked.
+    fn remove_twap_source() -> Weight {
+        Weight::from_ref_time(22_000_000 as u64)
+            .saturating_add(RocksDbWeight::get().reads(1 as u64))
+            .saturating_add(RocksDbWeight::get().writes(1 as u64))
+    }
 }
@@ -15,18 +15,36 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use primitives::CurrencyId;
+use primitives::{CurrencyId, SwapVenue};
 use sp_runtime::DispatchError;
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
+    #[api_version(2)]
     pub trait RouterApi<Balance> where
         Balance: Codec, {
+        #[changed_in(2)]
         fn get_best_route(
             amount: Balance,
             token_in: CurrencyId,
             token_out: CurrencyId,
             reversed: bool,
         ) -> Result<(Vec<CurrencyId>, Balance), DispatchError>;
+
+        fn get_best_route(
+            amount: Balance,
+            token_in: CurrencyId,
+            token_out: CurrencyId,
+            reversed: bool,
+        ) -> Result<(Vec<CurrencyId>, Vec<SwapVenue>, Balance), DispatchError>;
+
+        /// Splits `amount` across the best routes from `token_in` to `token_out`
+        /// which don't share a pool, returning the amount in of each route and the
+        /// total amount out.
+        fn get_split_routes(
+            amount: Balance,
+            token_in: CurrencyId,
+            token_out: CurrencyId,
+        ) -> Result<(Vec<(Vec<CurrencyId>, Balance)>, Balance), DispatchError>;
     }
 }
@@ -22,8 +22,8 @@ use jsonrpsee::{
     proc_macros::rpc,
     types::error::{CallError, ErrorCode, ErrorObject},
 };
-use primitives::CurrencyId;
-use sp_api::ProvideRuntimeApi;
+use primitives::{CurrencyId, SwapVenue};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
@@ -42,7 +42,16 @@ where
         token_out: CurrencyId,
         reversed: bool,
         at: Option<BlockHash>,
-    ) -> RpcResult<(Vec<CurrencyId>, NumberOrHex)>;
+    ) -> RpcResult<(Vec<CurrencyId>, Vec<SwapVenue>, NumberOrHex)>;
+
+    #[method(name = "router_getSplitRoutes")]
+    fn get_split_routes(
+        &self,
+        amount: NumberOrHex,
+        token_in: CurrencyId,
+        token_out: CurrencyId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<(Vec<(Vec<CurrencyId>, NumberOrHex)>, NumberOrHex)>;
 }
 
 /// A struct that implements the [`RouteApi`].
@@ -92,21 +101,71 @@ where
         token_out: CurrencyId,
         reversed: bool,
         at: Option<<Block as BlockT>::Hash>,
-    ) -> RpcResult<(Vec<CurrencyId>, NumberOrHex)> {
+    ) -> RpcResult<(Vec<CurrencyId>, Vec<SwapVenue>, NumberOrHex)> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or(self.client.info().best_hash));
-        let (route, amt) = api
-            .get_best_route(
-                &at,
-                decode_hex(amount, "balance")?,
-                token_in,
-                token_out,
-                reversed,
-            )
+        let amount = decode_hex(amount, "balance")?;
+
+        // runtimes before version 2 only route through pallet-amm pools
+        if api_version::<_, Balance, _>(&*api, &at)? < 2 {
+            #[allow(deprecated)]
+            let (route, amt) = api
+                .get_best_route_before_version_2(&at, amount, token_in, token_out, reversed)
+                .map_err(runtime_error_into_rpc_error)?
+                .map_err(smart_route_rpc_error)?;
+            let venues = vec![SwapVenue::Amm; route.len().saturating_sub(1)];
+            return Ok((route, venues, try_into_rpc_balance(amt)?));
+        }
+
+        let (route, venues, amt) = api
+            .get_best_route(&at, amount, token_in, token_out, reversed)
             .map_err(runtime_error_into_rpc_error)?
             .map_err(smart_route_rpc_error)?;
-        Ok((route, try_into_rpc_balance(amt)?))
+        Ok((route, venues, try_into_rpc_balance(amt)?))
     }
+
+    fn get_split_routes(
+        &self,
+        amount: NumberOrHex,
+        token_in: CurrencyId,
+        token_out: CurrencyId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<(Vec<(Vec<CurrencyId>, NumberOrHex)>, NumberOrHex)> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(self.client.info().best_hash));
+        if api_version::<_, Balance, _>(&*api, &at)? < 2 {
+            return Err(unsupported_rpc_error("router_getSplitRoutes"));
+        }
+        let (split_routes, amt) = api
+            .get_split_routes(&at, decode_hex(amount, "balance")?, token_in, token_out)
+            .map_err(runtime_error_into_rpc_error)?
+            .map_err(smart_route_rpc_error)?;
+        let split_routes = split_routes
+            .into_iter()
+            .map(|(route, amount_in)| Ok((route, try_into_rpc_balance(amount_in)?)))
+            .collect::<Result<Vec<_>, JsonRpseeError>>()?;
+        Ok((split_routes, try_into_rpc_balance(amt)?))
+    }
+}
+
+/// Returns the version of `RouterApi` implemented by the runtime at `at`.
+fn api_version<Block, Balance, Api>(api: &Api, at: &BlockId<Block>) -> Result<u32, JsonRpseeError>
+where
+    Block: BlockT,
+    Balance: Codec,
+    Api: ApiExt<Block> + RouterRuntimeApi<Block, Balance>,
+{
+    api.api_version::<dyn RouterRuntimeApi<Block, Balance>>(at)
+        .map_err(runtime_error_into_rpc_error)?
+        .ok_or_else(|| unsupported_rpc_error("RouterApi"))
+}
+
+fn unsupported_rpc_error(name: &str) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+        Error::RuntimeError.into(),
+        format!("{} is not supported by the runtime", name),
+        None::<()>,
+    )))
 }
 
 /// Converts a runtime trap into an RPC error.
@@ -20,7 +20,7 @@ use frame_support::{
     },
 };
 use frame_system::{self, RawOrigin as SystemOrigin};
-use primitives::{tokens, Balance, CurrencyId};
+use primitives::{tokens, Balance, CurrencyId, Price};
 use sp_runtime::traits::{One, StaticLookup};
 use sp_std::{vec, vec::Vec};
 
@@ -28,6 +28,10 @@ const DOT: CurrencyId = tokens::DOT;
 const SDOT: CurrencyId = tokens::SDOT;
 const INITIAL_AMOUNT: u128 = 1_000_000_000_000_000;
 const ASSET_ID: u32 = 11;
+const INTERMEDIATE_ASSET_ID: u32 = 1000;
+const CONCENTRATED_ASSET_ID: u32 = 2000;
+const TICK_SPACING: i32 = 60;
+const POSITION_AMOUNT: u128 = 10_000_000_000;
 
 fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::RuntimeEvent) {
     frame_system::Pallet::<T>::assert_last_event(generic_event.into());
@@ -92,6 +96,98 @@ fn initial_set_up<
     ));
 }
 
+// Creates a route from DOT to SDOT through a new intermediate token
+fn create_route_through<
+    T: Config<I> + pallet_assets::Config<AssetId = CurrencyId, Balance = Balance> + pallet_amm::Config,
+    I: 'static,
+>(
+    token: CurrencyId,
+) {
+    let pool_creator: T::AccountId = account("pool_creator", 1, 0);
+    for asset_id in [token, token + 1, token + 2] {
+        pallet_assets::Pallet::<T>::force_create(
+            SystemOrigin::Root.into(),
+            asset_id.into(),
+            T::Lookup::unlookup(pool_creator.clone()),
+            true,
+            One::one(),
+        )
+        .ok();
+    }
+    <T as crate::Config<I>>::Assets::mint_into(token, &pool_creator, INITIAL_AMOUNT).ok();
+
+    assert_ok!(pallet_amm::Pallet::<T>::create_pool(
+        T::CreatePoolOrigin::try_successful_origin()
+            .expect("No origin exists which can satisfy the guard"),
+        (DOT, token),
+        (100_000_000u128, 100_000_000u128),
+        pool_creator.clone(),
+        token + 1
+    ));
+    assert_ok!(pallet_amm::Pallet::<T>::create_pool(
+        T::CreatePoolOrigin::try_successful_origin()
+            .expect("No origin exists which can satisfy the guard"),
+        (token, SDOT),
+        (100_000_000u128, 100_000_000u128),
+        pool_creator,
+        token + 2
+    ));
+}
+
+// Creates a concentrated pool of DOT and a new token whose `MaxTicks` ticks are
+// all initialized above the current price, so that buying the token crosses them
+fn create_concentrated_pool_with_every_tick<
+    T: Config<I> + pallet_assets::Config<AssetId = CurrencyId, Balance = Balance> + pallet_amm::Config,
+    I: 'static,
+>() {
+    let pool_creator: T::AccountId = account("pool_creator", 1, 0);
+    pallet_assets::Pallet::<T>::force_create(
+        SystemOrigin::Root.into(),
+        CONCENTRATED_ASSET_ID.into(),
+        T::Lookup::unlookup(pool_creator.clone()),
+        true,
+        One::one(),
+    )
+    .ok();
+    <T as crate::Config<I>>::Assets::mint_into(
+        CONCENTRATED_ASSET_ID,
+        &pool_creator,
+        INITIAL_AMOUNT,
+    )
+    .ok();
+
+    assert_ok!(pallet_amm::Pallet::<T>::create_concentrated_pool(
+        T::CreatePoolOrigin::try_successful_origin()
+            .expect("No origin exists which can satisfy the guard"),
+        (CONCENTRATED_ASSET_ID, DOT),
+        TICK_SPACING as u32,
+        Price::one()
+    ));
+
+    // adjacent narrow positions, then a wide one so that the price never runs out
+    // of liquidity after the last crossing
+    let last = <T as pallet_amm::Config>::MaxTicks::get() as i32 - 2;
+    for i in 0..last {
+        assert_ok!(pallet_amm::Pallet::<T>::add_concentrated_liquidity(
+            SystemOrigin::Signed(pool_creator.clone()).into(),
+            (CONCENTRATED_ASSET_ID, DOT),
+            (i * TICK_SPACING, (i + 1) * TICK_SPACING),
+            (POSITION_AMOUNT, POSITION_AMOUNT),
+            (0u128, 0u128)
+        ));
+    }
+    assert_ok!(pallet_amm::Pallet::<T>::add_concentrated_liquidity(
+        SystemOrigin::Signed(pool_creator).into(),
+        (CONCENTRATED_ASSET_ID, DOT),
+        (
+            last * TICK_SPACING,
+            pallet_amm::MAX_TICK / TICK_SPACING * TICK_SPACING
+        ),
+        (POSITION_AMOUNT * 10_000, POSITION_AMOUNT * 10_000),
+        (0u128, 0u128)
+    ));
+}
+
 benchmarks_instance_pallet! {
     where_clause {
         where
@@ -134,6 +230,59 @@ benchmarks_instance_pallet! {
         assert_eq!(amount_in, expected);
         assert_last_event::<T, I>(Event::Traded(caller, expected, routes, amount_out).into());
     }
+
+    swap_through_concentrated_pool {
+        let caller: T::AccountId = whitelisted_caller();
+        initial_set_up::<T, I>(caller.clone());
+        create_concentrated_pool_with_every_tick::<T, I>();
+        let max_ticks = <T as pallet_amm::Config>::MaxTicks::get();
+        let amount_in = 3 * POSITION_AMOUNT * max_ticks as u128;
+        let routes: Vec<_> = vec![DOT, CONCENTRATED_ASSET_ID];
+    }: swap_exact_tokens_for_tokens(SystemOrigin::Signed(caller.clone()), routes, amount_in, 0u128)
+
+    verify {
+        let pool = pallet_amm::Pallet::<T>::concentrated_pools(CONCENTRATED_ASSET_ID, DOT).unwrap();
+        assert!(pool.current_tick >= (max_ticks as i32 - 2) * TICK_SPACING);
+        assert_eq!(
+            pallet_amm::Pallet::<T>::ticks(CONCENTRATED_ASSET_ID, DOT).len(),
+            max_ticks as usize
+        );
+    }
+
+    search_routes {
+        let p in 1 .. T::MaxPools::get();
+        let caller: T::AccountId = whitelisted_caller();
+        initial_set_up::<T, I>(caller);
+        // each route adds two pools to the one created by the set up
+        for i in 1..(p + 1) / 2 {
+            create_route_through::<T, I>(INTERMEDIATE_ASSET_ID + 3 * i);
+        }
+    }: {
+        let (pools, _) = Pallet::<T, I>::do_get_liquid_pools(DOT)?;
+        let routes = Pallet::<T, I>::get_routes(&pools, DOT, SDOT)?;
+        Pallet::<T, I>::cache_routes(&pools, DOT, SDOT, &routes);
+    }
+
+    verify {
+        assert!(RouteCache::<T, I>::contains_key(DOT, SDOT));
+    }
+
+    swap_exact_tokens_for_tokens_split {
+        let h in 1 .. 2 * T::MaxSplitRoutes::get() - 1;
+        let caller: T::AccountId = whitelisted_caller();
+        initial_set_up::<T, I>(caller.clone());
+        // the set up creates a route of one hop and each other route has two hops
+        for i in 1..=(h - 1) / 2 {
+            create_route_through::<T, I>(INTERMEDIATE_ASSET_ID + 3 * i);
+        }
+        let amount_in = 10_000_000u128;
+        let min_amount_out = 0u128;
+    }: _(SystemOrigin::Signed(caller.clone()), DOT, SDOT, amount_in, min_amount_out)
+
+    verify {
+        let balance: BalanceOf<T, I> = <T as crate::Config<I>>::Assets::balance(DOT, &caller);
+        assert_eq!(balance, INITIAL_AMOUNT - amount_in);
+    }
 }
 
 impl_benchmark_test_suite!(AMMRoute, crate::mock::new_test_ext(), crate::mock::Runtime,);
@@ -35,19 +35,29 @@ pub mod pallet {
     use super::*;
     use frame_support::{
         ensure, log,
-        pallet_prelude::{DispatchResult, DispatchResultWithPostInfo},
+        pallet_prelude::{DispatchResult, DispatchResultWithPostInfo, OptionQuery},
         require_transactional,
         traits::{
             fungibles::{Inspect, Mutate, Transfer},
             Get, IsType,
         },
-        transactional, BoundedVec, PalletId,
+        transactional,
+        weights::Weight,
+        Blake2_128Concat, BoundedVec, PalletId,
     };
     use frame_system::{ensure_signed, pallet_prelude::OriginFor};
-    use pallet_traits::AMM;
-    use primitives::{Balance, CurrencyId};
-    use sp_runtime::{traits::Zero, DispatchError};
-    use sp_std::{cmp::Reverse, collections::btree_map::BTreeMap, vec::Vec};
+    use pallet_traits::{StableSwap, AMM};
+    use primitives::{Balance, CurrencyId, SwapVenue};
+    use sp_runtime::{
+        traits::{Hash, Zero},
+        ArithmeticError, DispatchError,
+    };
+    use sp_std::{
+        cmp::Reverse,
+        collections::{btree_map::BTreeMap, btree_set::BTreeSet, vec_deque::VecDeque},
+        vec,
+        vec::Vec,
+    };
 
     pub type Route<T, I> = BoundedVec<
         (
@@ -59,6 +69,22 @@ pub mod pallet {
         <T as Config<I>>::MaxLengthRoute,
     >;
 
+    /// A route, the venue each of its hops trades on and its output amount
+    /// (or input amount when reversed)
+    pub type RouteOutput<T, I> = (Vec<AssetIdOf<T, I>>, Vec<SwapVenue>, BalanceOf<T, I>);
+
+    /// The routes an order is split across along with the amount in of each route
+    pub type SplitRoutes<T, I> = Vec<(Vec<AssetIdOf<T, I>>, BalanceOf<T, I>)>;
+
+    /// Routes found between two tokens by the path search
+    pub type CachedRoutes<T, I> = BoundedVec<
+        BoundedVec<AssetIdOf<T, I>, <T as Config<I>>::MaxLengthRoute>,
+        <T as Config<I>>::MaxRoutes,
+    >;
+
+    /// How many parts an order is cut into when splitting it across routes
+    pub const SPLIT_PARTS: u128 = 20;
+
     pub(crate) type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
     pub(crate) type AssetIdOf<T, I = ()> =
         <<T as Config<I>>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
@@ -77,6 +103,9 @@ pub mod pallet {
         /// Specify all the AMMs we are routing between
         type AMM: AMM<AccountIdOf<Self>, AssetIdOf<Self, I>, BalanceOf<Self, I>, Self::BlockNumber>;
 
+        /// Specify the stableswap pools we are routing between along with the AMM
+        type StableSwap: StableSwap<AccountIdOf<Self>, AssetIdOf<Self, I>, BalanceOf<Self, I>>;
+
         /// Weight information for extrinsics in this pallet.
         type AMMRouterWeightInfo: WeightInfo;
 
@@ -84,6 +113,23 @@ pub mod pallet {
         #[pallet::constant]
         type MaxLengthRoute: Get<u32>;
 
+        /// How many routes an order can be split across at most
+        #[pallet::constant]
+        type MaxSplitRoutes: Get<u32>;
+
+        /// How many routes the path search keeps at most
+        #[pallet::constant]
+        type MaxRoutes: Get<u32>;
+
+        /// How many pools the path search visits at most, the routing weights
+        /// are charged for this many pools
+        #[pallet::constant]
+        type MaxPools: Get<u32>;
+
+        /// Pools with a reserve below this amount are skipped by the path search
+        #[pallet::constant]
+        type MinRouteLiquidity: Get<BalanceOf<Self, I>>;
+
         /// The asset id for native currency.
         #[pallet::constant]
         type GetNativeCurrencyId: Get<AssetIdOf<Self, I>>;
@@ -120,6 +166,21 @@ pub mod pallet {
         NoPossibleRoute,
     }
 
+    /// Routes between two tokens along with the hash of the liquid pools reachable
+    /// from the first token they were searched over. The routes are searched again
+    /// once these pools change.
+    #[pallet::storage]
+    #[pallet::getter(fn route_cache)]
+    pub type RouteCache<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        (T::Hash, CachedRoutes<T, I>),
+        OptionQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub (crate) fn deposit_event)]
     pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -163,70 +224,232 @@ pub mod pallet {
             token_in: AssetIdOf<T, I>,
             token_out: AssetIdOf<T, I>,
             reversed: bool,
-        ) -> Result<Vec<(Vec<AssetIdOf<T, I>>, BalanceOf<T, I>)>, DispatchError> {
-            // get all the pool asset pairs from the AMM
-            let pools = T::AMM::get_pools()?;
+        ) -> Result<Vec<RouteOutput<T, I>>, DispatchError> {
+            let pools = Self::get_liquid_pools(token_in)?;
+            let routes = Self::get_routes(&pools, token_in, token_out)?;
+            Ok(Self::sorted_output_routes(amount, routes, reversed))
+        }
+
+        fn sorted_output_routes(
+            amount: BalanceOf<T, I>,
+            routes: Vec<Vec<AssetIdOf<T, I>>>,
+            reversed: bool,
+        ) -> Vec<RouteOutput<T, I>> {
+            // get output amounts for all routes
+            let mut output_routes = Self::get_output_routes(amount, routes, reversed);
 
-            let mut graph: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+            // sort values greatest to least
+            output_routes.sort_by_key(|k| Reverse(k.2));
+
+            output_routes
+        }
+
+        /// Returns the pool asset pairs reachable from `token_in` in less than
+        /// `MaxLengthRoute` hops whose reserves are both above `MinRouteLiquidity`,
+        /// sorted and without duplicates.
+        ///
+        /// Pools are visited breadth first from `token_in` and the search stops after
+        /// `MaxPools` pools, so pools far from `token_in` may be left out.
+        pub fn get_liquid_pools(
+            token_in: AssetIdOf<T, I>,
+        ) -> Result<Vec<(AssetIdOf<T, I>, AssetIdOf<T, I>)>, DispatchError> {
+            Self::do_get_liquid_pools(token_in).map(|(pools, _)| pools)
+        }
+
+        /// How many hops an order split across routes trades through at most
+        pub fn max_split_hops() -> u32 {
+            T::MaxSplitRoutes::get().saturating_mul(T::MaxLengthRoute::get().saturating_sub(1))
+        }
+
+        /// Weight of a trade through `hops` pools, any of which may be a concentrated
+        /// pool crossing all its ticks
+        pub fn swap_weight(base: Weight, hops: usize) -> Weight {
+            let hops = hops
+                .min(T::MaxLengthRoute::get().saturating_sub(1).into())
+                .max(1);
+            base.max(T::AMMRouterWeightInfo::swap_through_concentrated_pool())
+                .saturating_mul(hops as u64)
+        }
+
+        /// Weight of a split trade searching `pools` pools and trading through `hops`
+        /// pools
+        pub fn split_weight(pools: u32, hops: u32) -> Weight {
+            T::AMMRouterWeightInfo::search_routes(pools).saturating_add(
+                T::AMMRouterWeightInfo::swap_exact_tokens_for_tokens_split(hops).max(
+                    T::AMMRouterWeightInfo::swap_through_concentrated_pool()
+                        .saturating_mul(hops.into()),
+                ),
+            )
+        }
+
+        /// Returns the liquid pools reachable from `token_in` along with the number
+        /// of pools visited to find them.
+        pub(crate) fn do_get_liquid_pools(
+            token_in: AssetIdOf<T, I>,
+        ) -> Result<(Vec<(AssetIdOf<T, I>, AssetIdOf<T, I>)>, u32), DispatchError> {
+            let min_liquidity = T::MinRouteLiquidity::get();
+            let is_liquid = |(reserve_a, reserve_b): (BalanceOf<T, I>, BalanceOf<T, I>)| {
+                reserve_a >= min_liquidity && reserve_b >= min_liquidity
+            };
+            let max_pools = T::MaxPools::get();
+
+            let mut visited = 0u32;
+            let mut seen = BTreeSet::new();
+            // a pair listed on both venues is a single edge of the graph
+            let mut pools = BTreeSet::new();
+            let mut reached = BTreeSet::from([token_in]);
+            let mut frontier = vec![token_in];
+
+            for _ in 1..T::MaxLengthRoute::get() {
+                let mut next = Vec::new();
+                for asset in frontier {
+                    let amm_pools = T::AMM::get_pools_of(asset)?
+                        .into_iter()
+                        .map(|pool| (pool, false));
+                    let stable_pools = T::StableSwap::get_pools_of(asset)?
+                        .into_iter()
+                        .map(|pool| (pool, true));
+
+                    for ((a, b), stable) in amm_pools.chain(stable_pools) {
+                        let pair = if a < b { (a, b) } else { (b, a) };
+                        // pools are listed under both of their assets
+                        if !seen.insert((pair, stable)) {
+                            continue;
+                        }
+                        if visited >= max_pools {
+                            return Ok((pools.into_iter().collect(), visited));
+                        }
+                        visited += 1;
+
+                        let reserves = if stable {
+                            T::StableSwap::get_reserves(a, b)
+                        } else {
+                            T::AMM::get_reserves(a, b)
+                        };
+                        if !reserves.map_or(false, is_liquid) {
+                            continue;
+                        }
+
+                        pools.insert(pair);
+                        let other = if a == asset { b } else { a };
+                        if reached.insert(other) {
+                            next.push(other);
+                        }
+                    }
+                }
+                if next.is_empty() {
+                    break;
+                }
+                frontier = next;
+            }
+
+            Ok((pools.into_iter().collect(), visited))
+        }
+
+        /// Returns the routes from `token_in` to `token_out` over `pools`. The
+        /// routes are read from the cache unless `pools` changed since they were
+        /// searched.
+        pub fn get_routes(
+            pools: &[(AssetIdOf<T, I>, AssetIdOf<T, I>)],
+            token_in: AssetIdOf<T, I>,
+            token_out: AssetIdOf<T, I>,
+        ) -> Result<Vec<Vec<AssetIdOf<T, I>>>, DispatchError> {
+            if let Some((hash, routes)) = RouteCache::<T, I>::get(token_in, token_out) {
+                if hash == T::Hashing::hash_of(&pools) {
+                    return Ok(routes.into_iter().map(|route| route.into_inner()).collect());
+                }
+            }
+
+            Self::search_routes(pools, token_in, token_out)
+        }
+
+        /// Caches the routes from `token_in` to `token_out` found over `pools`.
+        pub(crate) fn cache_routes(
+            pools: &[(AssetIdOf<T, I>, AssetIdOf<T, I>)],
+            token_in: AssetIdOf<T, I>,
+            token_out: AssetIdOf<T, I>,
+            routes: &[Vec<AssetIdOf<T, I>>],
+        ) {
+            // the search already bounds the routes so this always succeeds
+            let cached: Option<CachedRoutes<T, I>> = routes
+                .iter()
+                .map(|route| route.clone().try_into().ok())
+                .collect::<Option<Vec<_>>>()
+                .and_then(|routes| routes.try_into().ok());
+            if let Some(cached) = cached {
+                RouteCache::<T, I>::insert(
+                    token_in,
+                    token_out,
+                    (T::Hashing::hash_of(&pools), cached),
+                );
+            }
+        }
+
+        /// Breadth first search of the routes from `token_in` to `token_out` over `pools`.
+        ///
+        /// Routes are at most `MaxLengthRoute` tokens long and the `MaxRoutes` shortest
+        /// ones are kept. At most `MaxLengthRoute` partial routes are expanded per
+        /// pool, so the cost of the search grows linearly with the number of pools.
+        fn search_routes(
+            pools: &[(AssetIdOf<T, I>, AssetIdOf<T, I>)],
+            token_in: AssetIdOf<T, I>,
+            token_out: AssetIdOf<T, I>,
+        ) -> Result<Vec<Vec<AssetIdOf<T, I>>>, DispatchError> {
+            let mut graph: BTreeMap<AssetIdOf<T, I>, Vec<AssetIdOf<T, I>>> = BTreeMap::new();
 
             // build a non directed graph from pool asset pairs
-            pools.into_iter().for_each(|(a, b)| {
+            pools.iter().for_each(|&(a, b)| {
                 graph.entry(a).or_insert_with(Vec::new).push(b);
                 graph.entry(b).or_insert_with(Vec::new).push(a);
             });
 
-            // init mutable variables
-            let mut path = Vec::new();
-            let mut paths = Vec::new();
-
-            let mut start = token_in;
-            let mut end = token_out;
-
-            let mut queue: Vec<(u32, u32, Vec<u32>)> = Vec::from([(start, end, path)]);
-
             // check that both tokens exist in graph
             ensure!(
-                graph.contains_key(&start),
+                graph.contains_key(&token_in),
+                Error::<T, I>::TokenDoesNotExists
+            );
+            ensure!(
+                graph.contains_key(&token_out),
                 Error::<T, I>::TokenDoesNotExists
             );
-            ensure!(graph.contains_key(&end), Error::<T, I>::TokenDoesNotExists);
 
-            // iterate until we build all routes
-            while !queue.is_empty() {
-                // desugared RFC 2909-destructuring-assignment
-                let (_start, _end, _path) = queue.swap_remove(0);
-                start = _start;
-                end = _end;
-                path = _path;
+            let max_length = T::MaxLengthRoute::get() as usize;
+            let max_routes = T::MaxRoutes::get() as usize;
+            let mut expansions = pools.len().saturating_mul(max_length).max(1);
 
-                path.push(start);
+            let mut routes = Vec::new();
+            let mut queue = VecDeque::from([vec![token_in]]);
 
-                // exit if we reached our target
-                if start == end {
-                    paths.push(path.clone());
+            while let Some(path) = queue.pop_front() {
+                if routes.len() >= max_routes || expansions.is_zero() {
+                    break;
                 }
+                expansions -= 1;
 
-                // cant error because we fetch pools above
-                let adjacents = graph.get(&start).unwrap();
+                let last = path[path.len() - 1];
 
-                // items that are adjacent but not already in path
-                let difference: Vec<_> = adjacents
-                    .iter()
-                    .filter(|item| !path.contains(item))
-                    .collect();
-
-                for node in difference {
-                    queue.push((*node, end, path.clone()));
+                // stop at our target
+                if last == token_out {
+                    routes.push(path);
+                    continue;
                 }
-            }
 
-            // get output amounts for all routes
-            let mut output_routes = Self::get_output_routes(amount, paths, reversed);
+                // prune routes which can't get any longer
+                if path.len() >= max_length {
+                    continue;
+                }
 
-            // sort values greatest to least
-            output_routes.sort_by_key(|k| Reverse(k.1));
+                // items that are adjacent but not already in path
+                for node in graph.get(&last).into_iter().flatten() {
+                    if !path.contains(node) {
+                        let mut next = path.clone();
+                        next.push(*node);
+                        queue.push_back(next);
+                    }
+                }
+            }
 
-            Ok(output_routes)
+            Ok(routes)
         }
 
         /// Returns the route that results in the largest amount out for amount in
@@ -235,7 +458,7 @@ pub mod pallet {
             token_in: AssetIdOf<T, I>,
             token_out: AssetIdOf<T, I>,
             reversed: bool,
-        ) -> Result<(Vec<AssetIdOf<T, I>>, BalanceOf<T, I>), DispatchError> {
+        ) -> Result<RouteOutput<T, I>, DispatchError> {
             let mut all_routes = Self::get_all_routes(amount, token_in, token_out, reversed)?;
             ensure!(!all_routes.is_empty(), Error::<T, I>::NoPossibleRoute);
             let best_route = if reversed {
@@ -262,26 +485,209 @@ pub mod pallet {
             amount: BalanceOf<T, I>,
             routes: Vec<Vec<AssetIdOf<T, I>>>,
             reversed: bool,
-        ) -> Vec<(Vec<AssetIdOf<T, I>>, BalanceOf<T, I>)> {
+        ) -> Vec<RouteOutput<T, I>> {
             let mut output_routes = Vec::new();
 
-            if reversed {
-                for route in routes {
-                    let amounts = T::AMM::get_amounts_in(amount, route.clone());
-                    if let Ok(amounts) = amounts {
-                        output_routes.push((route, amounts[0]));
+            for route in routes {
+                if let Ok((amounts, venues)) = Self::get_amounts(amount, &route, reversed) {
+                    let amount = if reversed {
+                        amounts[0]
+                    } else {
+                        amounts[amounts.len() - 1]
+                    };
+                    output_routes.push((route, venues, amount));
+                }
+            }
+
+            output_routes
+        }
+
+        /// Splits `amount` across the best `MaxSplitRoutes` routes from `token_in`
+        /// to `token_out`. Returns the amount in of each route and the total amount out.
+        ///
+        /// Only routes which don't share a pool are used, so that the quote of each
+        /// route doesn't depend on the trades of the others. The order is cut into
+        /// `SPLIT_PARTS` parts and each part goes to the route with the largest
+        /// marginal output. As the output of a route is concave in its input, this
+        /// maximizes the total output over these routes, up to the size of a part.
+        pub fn get_split_routes(
+            amount: BalanceOf<T, I>,
+            token_in: AssetIdOf<T, I>,
+            token_out: AssetIdOf<T, I>,
+        ) -> Result<(SplitRoutes<T, I>, BalanceOf<T, I>), DispatchError> {
+            let pools = Self::get_liquid_pools(token_in)?;
+            let routes = Self::get_routes(&pools, token_in, token_out)?;
+            Self::do_get_split_routes(amount, routes)
+        }
+
+        fn do_get_split_routes(
+            amount: BalanceOf<T, I>,
+            routes: Vec<Vec<AssetIdOf<T, I>>>,
+        ) -> Result<(SplitRoutes<T, I>, BalanceOf<T, I>), DispatchError> {
+            ensure!(!amount.is_zero(), Error::<T, I>::ZeroBalance);
+
+            // the best routes which don't trade through the pools of better ones
+            let mut used_pools = BTreeSet::new();
+            let routes: Vec<Vec<AssetIdOf<T, I>>> =
+                Self::sorted_output_routes(amount, routes, false)
+                    .into_iter()
+                    .map(|(route, _, _)| route)
+                    .filter(|route| route.len() <= T::MaxLengthRoute::get() as usize)
+                    .filter(|route| {
+                        let pools: Vec<_> = route
+                            .windows(2)
+                            .map(|pair| (pair[0].max(pair[1]), pair[0].min(pair[1])))
+                            .collect();
+                        if pools.iter().any(|pool| used_pools.contains(pool)) {
+                            return false;
+                        }
+                        used_pools.extend(pools);
+                        true
+                    })
+                    .take(T::MaxSplitRoutes::get() as usize)
+                    .collect();
+            ensure!(!routes.is_empty(), Error::<T, I>::NoPossibleRoute);
+
+            let part = (amount / SPLIT_PARTS).max(1);
+            let mut amounts_in: Vec<BalanceOf<T, I>> = vec![Zero::zero(); routes.len()];
+            let mut amounts_out: Vec<BalanceOf<T, I>> = vec![Zero::zero(); routes.len()];
+            let mut remaining = amount;
+
+            while !remaining.is_zero() {
+                let step = part.min(remaining);
+
+                // (index, marginal output, output) of the best route for this part
+                let mut best: Option<(usize, BalanceOf<T, I>, BalanceOf<T, I>)> = None;
+                for (i, route) in routes.iter().enumerate() {
+                    let amount_out =
+                        match Self::get_amounts(amounts_in[i].saturating_add(step), route, false) {
+                            Ok((amounts, _)) => amounts[amounts.len() - 1],
+                            Err(_) => continue,
+                        };
+                    let gain = amount_out.saturating_sub(amounts_out[i]);
+                    if best.map_or(true, |(_, best_gain, _)| gain > best_gain) {
+                        best = Some((i, gain, amount_out));
                     }
                 }
+
+                let (i, _, amount_out) = best.ok_or(Error::<T, I>::NoPossibleRoute)?;
+                amounts_in[i] = amounts_in[i].saturating_add(step);
+                amounts_out[i] = amount_out;
+                remaining = remaining.saturating_sub(step);
+            }
+
+            let total_out = amounts_out
+                .iter()
+                .fold(Zero::zero(), |acc: BalanceOf<T, I>, x| {
+                    acc.saturating_add(*x)
+                });
+            let split_routes: SplitRoutes<T, I> = routes
+                .into_iter()
+                .zip(amounts_in)
+                .filter(|(_, amount_in)| !amount_in.is_zero())
+                .collect();
+
+            log::trace!(
+                target: "router::get_split_routes",
+                "amount: {:?}, token_in: {:?}, token_out: {:?}, split_routes: {:?}, total_out: {:?}",
+                amount,
+                token_in,
+                token_out,
+                split_routes,
+                total_out
+            );
+
+            Ok((split_routes, total_out))
+        }
+
+        /// Returns the amounts traded along `route` and the venue of each hop.
+        ///
+        /// Every hop trades on the venue giving the largest amount out, or
+        /// the smallest amount in when `reversed`. Since a route never visits
+        /// a pool twice this is also the best choice for the whole route.
+        pub fn get_amounts(
+            amount: BalanceOf<T, I>,
+            route: &[AssetIdOf<T, I>],
+            reversed: bool,
+        ) -> Result<(Vec<BalanceOf<T, I>>, Vec<SwapVenue>), DispatchError> {
+            ensure!(!route.is_empty(), Error::<T, I>::EmptyRoute);
+
+            let hops = route.len() - 1;
+            let mut amounts: Vec<BalanceOf<T, I>> = vec![Zero::zero(); route.len()];
+            let mut venues = vec![SwapVenue::Amm; hops];
+
+            if reversed {
+                amounts[hops] = amount;
+                for i in (0..hops).rev() {
+                    let (venue, amount_in) =
+                        Self::get_hop_amount_in(amounts[i + 1], (route[i], route[i + 1]))?;
+                    amounts[i] = amount_in;
+                    venues[i] = venue;
+                }
             } else {
-                for route in routes {
-                    let amounts = T::AMM::get_amounts_out(amount, route.clone());
-                    if let Ok(amounts) = amounts {
-                        output_routes.push((route, amounts[amounts.len() - 1]));
-                    }
+                amounts[0] = amount;
+                for i in 0..hops {
+                    let (venue, amount_out) =
+                        Self::get_hop_amount_out(amounts[i], (route[i], route[i + 1]))?;
+                    amounts[i + 1] = amount_out;
+                    venues[i] = venue;
                 }
             }
 
-            output_routes
+            Ok((amounts, venues))
+        }
+
+        /// Returns the venue giving the largest amount out for a single hop
+        fn get_hop_amount_out(
+            amount_in: BalanceOf<T, I>,
+            (asset_in, asset_out): (AssetIdOf<T, I>, AssetIdOf<T, I>),
+        ) -> Result<(SwapVenue, BalanceOf<T, I>), DispatchError> {
+            let amm = T::AMM::get_amounts_out(amount_in, vec![asset_in, asset_out]);
+            let stable = T::StableSwap::get_amounts_out(amount_in, vec![asset_in, asset_out]);
+            match (amm, stable) {
+                (Ok(amm), Ok(stable)) if stable[1] > amm[1] => {
+                    Ok((SwapVenue::StableSwap, stable[1]))
+                }
+                (Ok(amm), _) => Ok((SwapVenue::Amm, amm[1])),
+                (Err(_), Ok(stable)) => Ok((SwapVenue::StableSwap, stable[1])),
+                (Err(e), Err(_)) => Err(e),
+            }
+        }
+
+        /// Returns the venue requiring the smallest amount in for a single hop
+        fn get_hop_amount_in(
+            amount_out: BalanceOf<T, I>,
+            (asset_in, asset_out): (AssetIdOf<T, I>, AssetIdOf<T, I>),
+        ) -> Result<(SwapVenue, BalanceOf<T, I>), DispatchError> {
+            let amm = T::AMM::get_amounts_in(amount_out, vec![asset_in, asset_out]);
+            let stable = T::StableSwap::get_amounts_in(amount_out, vec![asset_in, asset_out]);
+            match (amm, stable) {
+                (Ok(amm), Ok(stable)) if stable[0] < amm[0] => {
+                    Ok((SwapVenue::StableSwap, stable[0]))
+                }
+                (Ok(amm), _) => Ok((SwapVenue::Amm, amm[0])),
+                (Err(_), Ok(stable)) => Ok((SwapVenue::StableSwap, stable[0])),
+                (Err(e), Err(_)) => Err(e),
+            }
+        }
+
+        /// Executes the trades of `route` on the venue of each hop
+        #[require_transactional]
+        fn do_swap_route(
+            trader: &AccountIdOf<T>,
+            route: &[AssetIdOf<T, I>],
+            amounts: &[BalanceOf<T, I>],
+            venues: &[SwapVenue],
+        ) -> DispatchResult {
+            for (i, venue) in venues.iter().enumerate() {
+                let pair = (route[i], route[i + 1]);
+                match venue {
+                    SwapVenue::Amm => T::AMM::swap(trader, pair, amounts[i])?,
+                    SwapVenue::StableSwap => T::StableSwap::swap(trader, pair, amounts[i])?,
+                }
+            }
+
+            Ok(())
         }
     }
 
@@ -294,7 +700,10 @@ pub mod pallet {
         /// - `amount_in`: the amount of trading assets
         /// - `min_amount_out`: the minimum a trader is willing to receive
         #[pallet::call_index(0)]
-        #[pallet::weight(T::AMMRouterWeightInfo::swap_exact_tokens_for_tokens())]
+        #[pallet::weight(Pallet::<T, I>::swap_weight(
+            T::AMMRouterWeightInfo::swap_exact_tokens_for_tokens(),
+            route.len().saturating_sub(1)
+        ))]
         #[transactional]
         pub fn swap_exact_tokens_for_tokens(
             origin: OriginFor<T>,
@@ -324,7 +733,7 @@ pub mod pallet {
                 Error::<T, I>::InsufficientBalance
             );
 
-            let amounts = T::AMM::get_amounts_out(amount_in, route.clone())?;
+            let (amounts, venues) = Self::get_amounts(amount_in, &route, false)?;
 
             // make sure the required amount in does not violate our input
             ensure!(
@@ -332,10 +741,7 @@ pub mod pallet {
                 Error::<T, I>::MinimumAmountOutViolated
             );
 
-            for i in 0..(route.len() - 1) {
-                let next_index = i + 1;
-                T::AMM::swap(&trader, (route[i], route[next_index]), amounts[i])?;
-            }
+            Self::do_swap_route(&trader, &route, &amounts, &venues)?;
 
             Self::deposit_event(Event::Traded(
                 trader,
@@ -354,7 +760,10 @@ pub mod pallet {
         /// - `amount_out`: the amount of trading assets
         /// - `max_amount_in`: the maximum a trader is willing to input
         #[pallet::call_index(1)]
-        #[pallet::weight(T::AMMRouterWeightInfo::swap_tokens_for_exact_tokens())]
+        #[pallet::weight(Pallet::<T, I>::swap_weight(
+            T::AMMRouterWeightInfo::swap_tokens_for_exact_tokens(),
+            route.len().saturating_sub(1)
+        ))]
         #[transactional]
         pub fn swap_tokens_for_exact_tokens(
             origin: OriginFor<T>,
@@ -374,7 +783,7 @@ pub mod pallet {
             );
 
             // calculate trading amounts
-            let amounts = T::AMM::get_amounts_in(amount_out, route.clone())?;
+            let (amounts, venues) = Self::get_amounts(amount_out, &route, true)?;
 
             // we need to check after calc so we know how much is expected to be input
             // Ensure the trader has enough tokens for transaction.
@@ -394,10 +803,7 @@ pub mod pallet {
                 Error::<T, I>::MaximumAmountInViolated
             );
 
-            for i in 0..(route.len() - 1) {
-                let next_index = i + 1;
-                T::AMM::swap(&trader, (route[i], route[next_index]), amounts[i])?;
-            }
+            Self::do_swap_route(&trader, &route, &amounts, &venues)?;
 
             Self::deposit_event(Event::Traded(
                 trader,
@@ -408,5 +814,81 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Given input amount is fixed, splits the trade across the best routes
+        /// between two tokens to reduce the slippage of large trades.
+        ///
+        /// - `origin`: the trader.
+        /// - `token_in`: the asset to trade
+        /// - `token_out`: the asset to receive
+        /// - `amount_in`: the amount of trading assets
+        /// - `min_amount_out`: the minimum a trader is willing to receive in total
+        #[pallet::call_index(2)]
+        #[pallet::weight(Pallet::<T, I>::split_weight(
+            T::MaxPools::get(),
+            Pallet::<T, I>::max_split_hops()
+        ))]
+        #[transactional]
+        pub fn swap_exact_tokens_for_tokens_split(
+            origin: OriginFor<T>,
+            token_in: AssetIdOf<T, I>,
+            token_out: AssetIdOf<T, I>,
+            #[pallet::compact] amount_in: BalanceOf<T, I>,
+            #[pallet::compact] min_amount_out: BalanceOf<T, I>,
+        ) -> DispatchResultWithPostInfo {
+            let trader = ensure_signed(origin)?;
+
+            ensure!(token_in != token_out, Error::<T, I>::DuplicatedRoute);
+
+            // Ensure balances user input is bigger than zero.
+            ensure!(amount_in > Zero::zero(), Error::<T, I>::ZeroBalance);
+
+            // Ensure the trader has enough tokens for transaction.
+            ensure!(
+                T::Assets::reducible_balance(
+                    token_in,
+                    &trader,
+                    token_in == T::GetNativeCurrencyId::get()
+                ) >= amount_in,
+                Error::<T, I>::InsufficientBalance
+            );
+
+            // the path search visits at most `MaxPools` pools
+            let (pools, visited) = Self::do_get_liquid_pools(token_in)?;
+
+            let routes = Self::get_routes(&pools, token_in, token_out)?;
+            Self::cache_routes(&pools, token_in, token_out, &routes);
+
+            let (split_routes, _) = Self::do_get_split_routes(amount_in, routes)?;
+
+            // the routes don't share pools, so the trade of a route doesn't move
+            // the amounts quoted for the next ones
+            let mut amount_out: BalanceOf<T, I> = Zero::zero();
+            let mut hops = 0u32;
+            for (route, route_amount_in) in split_routes {
+                hops = hops.saturating_add(route.len().saturating_sub(1) as u32);
+                let (amounts, venues) = Self::get_amounts(route_amount_in, &route, false)?;
+                Self::do_swap_route(&trader, &route, &amounts, &venues)?;
+
+                let route_amount_out = amounts[amounts.len() - 1];
+                amount_out = amount_out
+                    .checked_add(route_amount_out)
+                    .ok_or(ArithmeticError::Overflow)?;
+
+                Self::deposit_event(Event::Traded(
+                    trader.clone(),
+                    route_amount_in,
+                    route,
+                    route_amount_out,
+                ));
+            }
+
+            ensure!(
+                amount_out >= min_amount_out,
+                Error::<T, I>::MinimumAmountOutViolated
+            );
+
+            Ok(Some(Self::split_weight(visited, hops)).into())
+        }
     }
 }
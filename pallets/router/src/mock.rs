@@ -42,6 +42,8 @@ pub const KSM: CurrencyId = tokens::KSM;
 pub const SAMPLE_LP_TOKEN: CurrencyId = 42;
 pub const SAMPLE_LP_TOKEN_2: CurrencyId = 43;
 pub const SAMPLE_LP_TOKEN_3: CurrencyId = 44;
+pub const SAMPLE_LP_TOKEN_4: CurrencyId = 45;
+pub const PROTOCOL_FEE_RECEIVER: AccountId = 99;
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
 }
@@ -131,6 +133,10 @@ parameter_types! {
     // pub const DefaultProtocolFee: Ratio = Ratio::from_rational(5u32, 10000u32);
     pub  DefaultLpFee: Ratio = Ratio::from_rational(25u32, 10000u32);         // 0.3%
     pub const MinimumLiquidity: u128 = 1_000u128;
+    pub const MaxObservations: u32 = 4;
+    pub const ObservationPeriod: BlockNumber = 10;
+    pub const MaxTicks: u32 = 8;
+    pub const MinPositionLiquidity: u128 = 1_000_000u128;
     pub const LockAccountId: AccountId = ALICE;
 
 }
@@ -153,9 +159,39 @@ impl pallet_amm::Config for Runtime {
     type LpFee = DefaultLpFee;
     type MinimumLiquidity = MinimumLiquidity;
     type MaxLengthRoute = MaxLengthRoute;
+    type MaxObservations = MaxObservations;
+    type ObservationPeriod = ObservationPeriod;
+    type MaxTicks = MaxTicks;
+    type MinPositionLiquidity = MinPositionLiquidity;
     type GetNativeCurrencyId = NativeCurrencyId;
 }
 
+// StableSwap instance initialization
+parameter_types! {
+    pub const StableSwapPalletId: PalletId = PalletId(*b"par/sswp");
+    pub DefaultProtocolFee: Ratio = Ratio::from_rational(5u32, 10000u32);   // 0.05%
+    pub const DefaultProtocolFeeReceiver: AccountId = PROTOCOL_FEE_RECEIVER;
+    pub const NumTokens: u8 = 2;
+    pub const Precision: u32 = 100;
+    pub const AmplificationCoefficient: u8 = 85;
+}
+
+impl pallet_stableswap::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Assets = CurrencyAdapter;
+    type WeightInfo = ();
+    type PalletId = StableSwapPalletId;
+    type NumTokens = NumTokens;
+    type Precision = Precision;
+    type AmplificationCoefficient = AmplificationCoefficient;
+    type ProtocolFeeReceiver = DefaultProtocolFeeReceiver;
+    type LpFee = DefaultLpFee;
+    type LockAccountId = LockAccountId;
+    type ProtocolFee = DefaultProtocolFee;
+    type MinimumLiquidity = MinimumLiquidity;
+    type CreatePoolOrigin = EnsureSignedBy<AliceCreatePoolOrigin, AccountId>;
+}
+
 parameter_types! {
     pub const NativeCurrencyId: CurrencyId = 0;
 }
@@ -169,6 +205,10 @@ impl pallet_currency_adapter::Config for Runtime {
 
 parameter_types! {
     pub const MaxLengthRoute: u8 = 10;
+    pub const MaxSplitRoutes: u8 = 3;
+    pub const MaxRoutes: u8 = 10;
+    pub const MaxPools: u32 = 20;
+    pub const MinRouteLiquidity: Balance = 10_000;
     pub const RouterPalletId: PalletId = PalletId(*b"ammroute");
 }
 
@@ -176,8 +216,13 @@ impl Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type PalletId = RouterPalletId;
     type AMM = DefaultAMM;
+    type StableSwap = DefaultStableSwap;
     type AMMRouterWeightInfo = ();
     type MaxLengthRoute = MaxLengthRoute;
+    type MaxSplitRoutes = MaxSplitRoutes;
+    type MaxRoutes = MaxRoutes;
+    type MaxPools = MaxPools;
+    type MinRouteLiquidity = MinRouteLiquidity;
     type Assets = CurrencyAdapter;
     type GetNativeCurrencyId = NativeCurrencyId;
 }
@@ -196,6 +241,7 @@ construct_runtime!(
         Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
         // AMM instances
         DefaultAMM: pallet_amm::{Pallet, Call, Storage, Event<T>},
+        DefaultStableSwap: pallet_stableswap::{Pallet, Call, Storage, Event<T>},
         // AMM Route
         AMMRoute: pallet_route::{Pallet, Call, Event<T>},
         CurrencyAdapter: pallet_currency_adapter::{Pallet, Call},
@@ -245,6 +291,14 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
             1,
         )
         .unwrap();
+        Assets::force_create(
+            RuntimeOrigin::root(),
+            SAMPLE_LP_TOKEN_4.into(),
+            ALICE,
+            true,
+            1,
+        )
+        .unwrap();
 
         Assets::mint(
             RuntimeOrigin::signed(ALICE),
@@ -17,6 +17,8 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::*;
+use primitives::{Price, SwapVenue};
+use sp_runtime::FixedPointNumber;
 
 #[test]
 fn too_many_routes_should_not_work() {
@@ -448,7 +450,14 @@ fn get_all_routes_should_work() {
         // Returns descending order `highest` value first.
         assert_eq!(
             routes,
-            vec![(vec![101, 1001, 100], 893), (vec![101, 100], 697)]
+            vec![
+                (
+                    vec![101, 1001, 100],
+                    vec![SwapVenue::Amm, SwapVenue::Amm],
+                    893
+                ),
+                (vec![101, 100], vec![SwapVenue::Amm], 697)
+            ]
         );
     })
 }
@@ -493,7 +502,14 @@ fn get_best_route_should_work() {
         .unwrap();
 
         // Returns descending order `highest` value first.
-        assert_eq!(best_route, (vec![101, 1001, 100], 893));
+        assert_eq!(
+            best_route,
+            (
+                vec![101, 1001, 100],
+                vec![SwapVenue::Amm, SwapVenue::Amm],
+                893
+            )
+        );
     })
 }
 
@@ -635,7 +651,7 @@ fn get_best_route_same_tokens_should_work() {
         )
         .unwrap();
 
-        assert_eq!(best_route, (vec![101], 1000));
+        assert_eq!(best_route, (vec![101], vec![], 1000));
     })
 }
 
@@ -678,7 +694,7 @@ fn get_best_route_same_tokens_reversed_should_work() {
         )
         .unwrap();
 
-        assert_eq!(best_route, (vec![101], 1000));
+        assert_eq!(best_route, (vec![101], vec![], 1000));
     })
 }
 
@@ -724,7 +740,14 @@ fn get_all_routes_reversed_should_work() {
         // Returns descending order `highest` value first.
         assert_eq!(
             routes,
-            vec![(vec![101, 100], 1433), (vec![101, 1001, 100], 1119)]
+            vec![
+                (vec![101, 100], vec![SwapVenue::Amm], 1433),
+                (
+                    vec![101, 1001, 100],
+                    vec![SwapVenue::Amm, SwapVenue::Amm],
+                    1119
+                )
+            ]
         );
     })
 }
@@ -769,6 +792,394 @@ fn get_best_route_reversed_should_work() {
         .unwrap();
 
         // Returns descending order `highest` value first.
-        assert_eq!(best_route, (vec![101, 1001, 100], 1119));
+        assert_eq!(
+            best_route,
+            (
+                vec![101, 1001, 100],
+                vec![SwapVenue::Amm, SwapVenue::Amm],
+                1119
+            )
+        );
+    })
+}
+
+#[test]
+fn get_best_route_across_venues_should_work() {
+    new_test_ext().execute_with(|| {
+        let trader = ALICE;
+
+        // the stable pair is much better priced on the stableswap
+        assert_ok!(DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (DOT, SDOT),
+            (1_000_000, 500_000),
+            DAVE,
+            SAMPLE_LP_TOKEN
+        ));
+        assert_ok!(DefaultStableSwap::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (DOT, SDOT),
+            (100_000_000, 100_000_000),
+            DAVE,
+            SAMPLE_LP_TOKEN_4
+        ));
+        assert_ok!(DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (SDOT, KSM),
+            (100_000_000, 100_000_000),
+            DAVE,
+            SAMPLE_LP_TOKEN_2
+        ));
+
+        let (route, venues, amount_out) = AMMRoute::get_best_route(1_000, DOT, KSM, false).unwrap();
+        assert_eq!(route, vec![DOT, SDOT, KSM]);
+        assert_eq!(venues, vec![SwapVenue::StableSwap, SwapVenue::Amm]);
+
+        // the AMM alone gives less for the same route
+        let amm_amounts = <DefaultAMM as pallet_traits::AMM<_, _, _, _>>::get_amounts_out(
+            1_000,
+            vec![DOT, SDOT, KSM],
+        )
+        .unwrap();
+        assert!(amount_out > amm_amounts[2]);
+
+        let ksm_before = Assets::balance(KSM, trader);
+        assert_ok!(AMMRoute::swap_exact_tokens_for_tokens(
+            RuntimeOrigin::signed(trader),
+            route,
+            1_000,
+            amount_out,
+        ));
+        assert_eq!(Assets::balance(DOT, trader), 10_000 - 1_000);
+        assert_eq!(Assets::balance(KSM, trader), ksm_before + amount_out);
+    })
+}
+
+fn create_split_pools() {
+    // create pool and add liquidity
+    assert_ok!(DefaultAMM::create_pool(
+        RuntimeOrigin::signed(ALICE),
+        (DOT, SDOT),
+        (1_000_000, 1_000_000),
+        DAVE,
+        SAMPLE_LP_TOKEN
+    ));
+
+    // create pool and add liquidity
+    assert_ok!(DefaultAMM::create_pool(
+        RuntimeOrigin::signed(ALICE),
+        (DOT, KSM),
+        (1_000_000, 1_000_000),
+        DAVE,
+        SAMPLE_LP_TOKEN_2
+    ));
+
+    // create pool and add liquidity
+    assert_ok!(DefaultAMM::create_pool(
+        RuntimeOrigin::signed(ALICE),
+        (KSM, SDOT),
+        (1_000_000, 1_000_000),
+        DAVE,
+        SAMPLE_LP_TOKEN_3
+    ));
+}
+
+#[test]
+fn get_split_routes_should_work() {
+    new_test_ext().execute_with(|| {
+        let input_amount = 200_000;
+        create_split_pools();
+
+        let (split_routes, amount_out) =
+            AMMRoute::get_split_routes(input_amount, DOT, SDOT).unwrap();
+
+        // the order is split across both routes
+        assert_eq!(split_routes.len(), 2);
+        assert_eq!(split_routes[0].0, vec![DOT, SDOT]);
+        assert_eq!(split_routes[1].0, vec![DOT, KSM, SDOT]);
+        assert_eq!(
+            split_routes
+                .iter()
+                .map(|(_, amount)| amount)
+                .sum::<Balance>(),
+            input_amount
+        );
+
+        // and gives more than sending everything down the best route
+        let (_, _, best_amount_out) =
+            AMMRoute::get_best_route(input_amount, DOT, SDOT, false).unwrap();
+        assert!(amount_out > best_amount_out);
+    })
+}
+
+#[test]
+fn swap_exact_tokens_for_tokens_split_should_work() {
+    new_test_ext().execute_with(|| {
+        let trader = DAVE;
+        let input_amount = 200_000;
+        create_split_pools();
+
+        let dot_before = Assets::balance(DOT, trader);
+        let sdot_before = Assets::balance(SDOT, trader);
+        let (_, expected) = AMMRoute::get_split_routes(input_amount, DOT, SDOT).unwrap();
+
+        assert_noop!(
+            AMMRoute::swap_exact_tokens_for_tokens_split(
+                RuntimeOrigin::signed(trader),
+                DOT,
+                SDOT,
+                input_amount,
+                expected + 1,
+            ),
+            Error::<Runtime>::MinimumAmountOutViolated
+        );
+
+        assert_ok!(AMMRoute::swap_exact_tokens_for_tokens_split(
+            RuntimeOrigin::signed(trader),
+            DOT,
+            SDOT,
+            input_amount,
+            expected,
+        ));
+
+        // the routes don't share pools so the quote is exact
+        assert_eq!(Assets::balance(DOT, trader), dot_before - input_amount);
+        assert_eq!(Assets::balance(SDOT, trader), sdot_before + expected);
+    })
+}
+
+#[test]
+fn split_routes_should_not_share_pools() {
+    new_test_ext().execute_with(|| {
+        create_split_pools();
+        // a third route trading through the DOT/KSM pool
+        assert_ok!(DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (KSM, USDT),
+            (1_000_000, 1_000_000),
+            DAVE,
+            SAMPLE_LP_TOKEN_4
+        ));
+        let lp_token: CurrencyId = 46;
+        assert_ok!(Assets::force_create(
+            RuntimeOrigin::root(),
+            lp_token.into(),
+            ALICE,
+            true,
+            1
+        ));
+        assert_ok!(DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (USDT, SDOT),
+            (1_000_000, 1_000_000),
+            DAVE,
+            lp_token
+        ));
+
+        let (split_routes, expected) = AMMRoute::get_split_routes(200_000, DOT, SDOT).unwrap();
+        assert_eq!(
+            split_routes
+                .iter()
+                .map(|(route, _)| route.clone())
+                .collect::<Vec<_>>(),
+            vec![vec![DOT, SDOT], vec![DOT, KSM, SDOT]]
+        );
+
+        // so the quote is the amount received
+        let sdot_before = Assets::balance(SDOT, DAVE);
+        assert_ok!(AMMRoute::swap_exact_tokens_for_tokens_split(
+            RuntimeOrigin::signed(DAVE),
+            DOT,
+            SDOT,
+            200_000,
+            expected,
+        ));
+        assert_eq!(Assets::balance(SDOT, DAVE), sdot_before + expected);
+    })
+}
+
+#[test]
+fn get_all_routes_should_skip_illiquid_pools() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (DOT, SDOT),
+            (1_000_000, 1_000_000),
+            DAVE,
+            SAMPLE_LP_TOKEN
+        ));
+        assert_ok!(DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (DOT, KSM),
+            (1_000_000, 1_000_000),
+            DAVE,
+            SAMPLE_LP_TOKEN_2
+        ));
+
+        // below `MinRouteLiquidity`
+        assert_ok!(DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (KSM, SDOT),
+            (5_000, 5_000),
+            DAVE,
+            SAMPLE_LP_TOKEN_3
+        ));
+
+        let routes: Vec<_> = AMMRoute::get_all_routes(1_000, DOT, SDOT, false)
+            .unwrap()
+            .into_iter()
+            .map(|(route, _, _)| route)
+            .collect();
+        assert_eq!(routes, vec![vec![DOT, SDOT]]);
     })
 }
+
+#[test]
+fn routing_should_ignore_pools_beyond_max_pools() {
+    new_test_ext().execute_with(|| {
+        create_split_pools();
+
+        // more pools than `MaxPools` which can't be reached from DOT
+        for i in 0..MaxPools::get() {
+            let (asset, lp_token) = (1_000 + i, 2_000 + i);
+            for id in [asset, lp_token] {
+                assert_ok!(Assets::force_create(
+                    RuntimeOrigin::root(),
+                    id.into(),
+                    ALICE,
+                    true,
+                    1
+                ));
+            }
+            assert_ok!(Assets::mint(
+                RuntimeOrigin::signed(ALICE),
+                asset.into(),
+                DAVE,
+                1_000_000
+            ));
+            assert_ok!(DefaultAMM::create_pool(
+                RuntimeOrigin::signed(ALICE),
+                (asset, USDT),
+                (1_000_000, 1_000_000),
+                DAVE,
+                lp_token
+            ));
+        }
+
+        let pools = AMMRoute::get_liquid_pools(DOT).unwrap();
+        assert_eq!(pools.len(), 3);
+
+        let (_, expected) = AMMRoute::get_split_routes(200_000, DOT, SDOT).unwrap();
+        assert_ok!(AMMRoute::swap_exact_tokens_for_tokens_split(
+            RuntimeOrigin::signed(DAVE),
+            DOT,
+            SDOT,
+            200_000,
+            expected,
+        ));
+
+        // the search from USDT stops after `MaxPools` pools
+        assert_eq!(
+            AMMRoute::get_liquid_pools(USDT).unwrap().len(),
+            MaxPools::get() as usize
+        );
+    })
+}
+
+#[test]
+fn route_cache_should_be_invalidated_by_new_pools() {
+    new_test_ext().execute_with(|| {
+        let cached_routes = || {
+            AMMRoute::route_cache(DOT, SDOT).map(|(_, routes)| {
+                routes
+                    .into_iter()
+                    .map(|route| route.into_inner())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert_ok!(DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (DOT, SDOT),
+            (1_000_000, 1_000_000),
+            DAVE,
+            SAMPLE_LP_TOKEN
+        ));
+        assert_ok!(DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (DOT, KSM),
+            (1_000_000, 1_000_000),
+            DAVE,
+            SAMPLE_LP_TOKEN_2
+        ));
+
+        // quotes don't write the cache
+        assert_ok!(AMMRoute::get_split_routes(1_000, DOT, SDOT));
+        assert_eq!(cached_routes(), None);
+
+        assert_ok!(AMMRoute::swap_exact_tokens_for_tokens_split(
+            RuntimeOrigin::signed(DAVE),
+            DOT,
+            SDOT,
+            1_000,
+            0,
+        ));
+        assert_eq!(cached_routes(), Some(vec![vec![DOT, SDOT]]));
+
+        // a new pool changes the liquid pools so the routes are searched again
+        assert_ok!(DefaultAMM::create_pool(
+            RuntimeOrigin::signed(ALICE),
+            (KSM, SDOT),
+            (1_000_000, 1_000_000),
+            DAVE,
+            SAMPLE_LP_TOKEN_3
+        ));
+        let (split_routes, _) = AMMRoute::get_split_routes(200_000, DOT, SDOT).unwrap();
+        assert_eq!(split_routes.len(), 2);
+
+        assert_ok!(AMMRoute::swap_exact_tokens_for_tokens_split(
+            RuntimeOrigin::signed(DAVE),
+            DOT,
+            SDOT,
+            1_000,
+            0,
+        ));
+        assert_eq!(
+            cached_routes(),
+            Some(vec![vec![DOT, SDOT], vec![DOT, KSM, SDOT]])
+        );
+    })
+}
+
+#[test]
+fn swap_through_concentrated_pool_should_work() {
+    new_test_ext().execute_with(|| {
+        let trader = ALICE;
+
+        // create a concentrated pool and add liquidity around the current price
+        assert_ok!(DefaultAMM::create_concentrated_pool(
+            RuntimeOrigin::signed(ALICE),
+            (DOT, SDOT),
+            60,
+            Price::saturating_from_integer(1),
+        ));
+        assert_ok!(DefaultAMM::add_concentrated_liquidity(
+            RuntimeOrigin::signed(DAVE),
+            (DOT, SDOT),
+            (-60, 60),
+            (1_000_000, 1_000_000),
+            (0, 0),
+        ));
+
+        AMMRoute::swap_exact_tokens_for_tokens(
+            RuntimeOrigin::signed(ALICE),
+            vec![DOT, SDOT],
+            1_000, // amount_in
+            900,   // min_amount_out
+        )
+        .unwrap();
+
+        assert_eq!(Assets::balance(DOT, trader), 10_000 - 1_000);
+        assert_eq!(Assets::balance(SDOT, trader), 10_000 + 996);
+    });
+}
@@ -203,6 +203,18 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// The pools trading each asset, keyed by the asset and the other asset of the pool
+    #[pallet::storage]
+    pub type AssetPools<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        Blake2_128Concat,
+        AssetIdOf<T, I>,
+        (),
+        OptionQuery,
+    >;
+
     // No Extrinsic Calls
     #[pallet::call]
     impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -389,6 +401,8 @@ pub mod pallet {
             )?;
 
             Pools::<T, I>::insert(base_asset, quote_asset, pool);
+            AssetPools::<T, I>::insert(base_asset, quote_asset, ());
+            AssetPools::<T, I>::insert(quote_asset, base_asset, ());
 
             log::trace!(
                 target: "stableswap::create_pool",
@@ -1370,6 +1384,20 @@ impl<T: Config<I>, I: 'static>
         Ok(Pools::<T, I>::iter_keys().collect())
     }
 
+    fn get_pools_of(
+        asset: AssetIdOf<T, I>,
+    ) -> Result<Vec<(AssetIdOf<T, I>, AssetIdOf<T, I>)>, DispatchError> {
+        Ok(AssetPools::<T, I>::iter_key_prefix(asset)
+            .map(|other| {
+                if asset > other {
+                    (asset, other)
+                } else {
+                    (other, asset)
+                }
+            })
+            .collect())
+    }
+
     fn get_reserves(
         asset_in: AssetIdOf<T, I>,
         asset_out: AssetIdOf<T, I>,
@@ -4,16 +4,19 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{dispatch::DispatchError, traits::tokens::Balance as BalanceT};
 use num_bigint::{BigUint, ToBigUint};
 use scale_info::TypeInfo;
-use sp_runtime::{traits::Zero, RuntimeDebug};
+use sp_runtime::{traits::Zero, Perbill, RuntimeDebug};
 use sp_std::prelude::*;
 
 use primitives::{
-    CurrencyId, DerivativeIndex, PersistedValidationData, PriceDetail, Rate, Timestamp,
+    CurrencyId, DerivativeIndex, EraIndex, PersistedValidationData, Price, PriceDetail, Rate,
+    Timestamp,
 };
 
+pub mod liquid_staking;
 pub mod loans;
 pub mod ump;
 pub mod xcm;
+pub use liquid_staking::*;
 pub use loans::*;
 
 pub trait EmergencyCallFilter<Call> {
@@ -37,14 +40,74 @@ pub trait ExchangeRateProvider<CurrencyId> {
     fn get_exchange_rate(asset_id: &CurrencyId) -> Option<Rate>;
 }
 
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<CurrencyId> ExchangeRateProvider<CurrencyId> for Tuple {
+    fn get_exchange_rate(asset_id: &CurrencyId) -> Option<Rate> {
+        for_tuples!( #(
+            if let Some(rate) = Tuple::get_exchange_rate(asset_id) {
+                return Some(rate);
+            }
+        )* );
+        None
+    }
+}
+
 pub trait LiquidStakingConvert<Balance> {
     fn staking_to_liquid(amount: Balance) -> Option<Balance>;
     fn liquid_to_staking(liquid_amount: Balance) -> Option<Balance>;
 }
 
-pub trait LiquidStakingCurrenciesProvider<CurrencyId> {
+pub trait LiquidStakingCurrenciesProvider<CurrencyId: PartialEq> {
     fn get_staking_currency() -> Option<CurrencyId>;
     fn get_liquid_currency() -> Option<CurrencyId>;
+
+    /// Returns the staking currency backing `liquid_currency`.
+    fn get_staking_currency_of(liquid_currency: &CurrencyId) -> Option<CurrencyId> {
+        Self::get_liquid_currency()
+            .filter(|asset_id| asset_id == liquid_currency)
+            .and_then(|_| Self::get_staking_currency())
+    }
+}
+
+/// Several liquid staking pools are combined as a tuple. A pool is looked up by
+/// its liquid currency with `get_staking_currency_of`, the currencies of the
+/// combined pools are only returned when every answering pool agrees on them.
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<CurrencyId: PartialEq> LiquidStakingCurrenciesProvider<CurrencyId> for Tuple {
+    fn get_staking_currency() -> Option<CurrencyId> {
+        let mut staking_currency = None;
+        for_tuples!( #(
+            if let Some(asset_id) = Tuple::get_staking_currency() {
+                if staking_currency.as_ref().map_or(false, |c| c != &asset_id) {
+                    return None;
+                }
+                staking_currency = Some(asset_id);
+            }
+        )* );
+        staking_currency
+    }
+
+    fn get_liquid_currency() -> Option<CurrencyId> {
+        let mut liquid_currency = None;
+        for_tuples!( #(
+            if let Some(asset_id) = Tuple::get_liquid_currency() {
+                if liquid_currency.as_ref().map_or(false, |c| c != &asset_id) {
+                    return None;
+                }
+                liquid_currency = Some(asset_id);
+            }
+        )* );
+        liquid_currency
+    }
+
+    fn get_staking_currency_of(liquid_currency: &CurrencyId) -> Option<CurrencyId> {
+        for_tuples!( #(
+            if let Some(asset_id) = Tuple::get_staking_currency_of(liquid_currency) {
+                return Some(asset_id);
+            }
+        )* );
+        None
+    }
 }
 
 pub trait VaultTokenExchangeRateProvider<CurrencyId> {
@@ -139,6 +202,16 @@ pub trait AMM<AccountId, CurrencyId, Balance, BlockNumber> {
     /// Iterate keys of asset pair in AMM Pools
     fn get_pools() -> Result<Vec<(CurrencyId, CurrencyId)>, DispatchError>;
 
+    /// Returns the asset pairs of the pools trading `asset`
+    fn get_pools_of(asset: CurrencyId) -> Result<Vec<(CurrencyId, CurrencyId)>, DispatchError>;
+
+    /// Returns the amounts of `asset_in` and `asset_out` provided to the pool
+    /// of the pair, whichever kind of pool it is
+    fn get_reserves(
+        asset_in: CurrencyId,
+        asset_out: CurrencyId,
+    ) -> Result<(Balance, Balance), DispatchError>;
+
     ///  Returns pool by lp_asset
     fn get_pool_by_lp_asset(
         asset_id: CurrencyId,
@@ -152,6 +225,13 @@ pub trait AMM<AccountId, CurrencyId, Balance, BlockNumber> {
     fn get_pool_by_asset_pair(
         pair: (CurrencyId, CurrencyId),
     ) -> Option<Pool<CurrencyId, Balance, BlockNumber>>;
+
+    /// Returns the time weighted average price of `pair.0` in `pair.1`
+    /// over at least the last `window` blocks
+    fn get_twap(
+        pair: (CurrencyId, CurrencyId),
+        window: BlockNumber,
+    ) -> Result<Price, DispatchError>;
 }
 
 /// Exported traits from StableSwap pallet. These functions are to be used
@@ -185,12 +265,54 @@ pub trait StableSwap<AccountId, CurrencyId, Balance> {
 
     fn get_pools() -> Result<Vec<(CurrencyId, CurrencyId)>, DispatchError>;
 
+    /// Returns the asset pairs of the pools trading `asset`
+    fn get_pools_of(asset: CurrencyId) -> Result<Vec<(CurrencyId, CurrencyId)>, DispatchError>;
+
     fn get_reserves(
         asset_in: CurrencyId,
         asset_out: CurrencyId,
     ) -> Result<(Balance, Balance), DispatchError>;
 }
 
+impl<AccountId, CurrencyId, Balance> StableSwap<AccountId, CurrencyId, Balance> for () {
+    fn get_amounts_out(
+        _amount_in: Balance,
+        _path: Vec<CurrencyId>,
+    ) -> Result<Vec<Balance>, DispatchError> {
+        Err(DispatchError::Other("No stableswap pools"))
+    }
+
+    fn get_amounts_in(
+        _amount_out: Balance,
+        _path: Vec<CurrencyId>,
+    ) -> Result<Vec<Balance>, DispatchError> {
+        Err(DispatchError::Other("No stableswap pools"))
+    }
+
+    fn swap(
+        _who: &AccountId,
+        _pair: (CurrencyId, CurrencyId),
+        _amount_in: Balance,
+    ) -> Result<(), DispatchError> {
+        Err(DispatchError::Other("No stableswap pools"))
+    }
+
+    fn get_pools() -> Result<Vec<(CurrencyId, CurrencyId)>, DispatchError> {
+        Ok(Vec::new())
+    }
+
+    fn get_pools_of(_asset: CurrencyId) -> Result<Vec<(CurrencyId, CurrencyId)>, DispatchError> {
+        Ok(Vec::new())
+    }
+
+    fn get_reserves(
+        _asset_in: CurrencyId,
+        _asset_out: CurrencyId,
+    ) -> Result<(Balance, Balance), DispatchError> {
+        Err(DispatchError::Other("No stableswap pools"))
+    }
+}
+
 pub trait ConvertToBigUint {
     fn get_big_uint(&self) -> BigUint;
 }
@@ -225,6 +347,31 @@ pub trait DistributionStrategy<Balance> {
     ) -> Vec<(DerivativeIndex, Balance)>;
 }
 
+/// Relaychain validator's data used to select the nominations
+#[derive(Default, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ValidatorInfo<Balance> {
+    /// Commission of the validator
+    pub commission: Perbill,
+    /// Whether the validator accepts new nominations
+    pub blocked: bool,
+    /// Validator's own stake in the last proven era
+    pub self_stake: Balance,
+    /// Total stake backing the validator in the last proven era
+    pub total_stake: Balance,
+    /// Reward points of the last eras, the oldest first
+    pub era_points: Vec<(EraIndex, u32)>,
+}
+
+/// Select the validators nominated by liquidstaking multi-accounts
+pub trait NominationStrategy<AccountId, Balance> {
+    /// Returns at most `max_nominations` targets for each of `derivative_indexes`
+    fn get_nominations(
+        validators: Vec<(AccountId, ValidatorInfo<Balance>)>,
+        derivative_indexes: Vec<DerivativeIndex>,
+        max_nominations: u32,
+    ) -> Vec<(DerivativeIndex, Vec<AccountId>)>;
+}
+
 pub trait Streaming<AccountId, CurrencyId, Balance> {
     fn create(
         sender: AccountId,
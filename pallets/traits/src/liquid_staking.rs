@@ -0,0 +1,52 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode};
+use primitives::{EraIndex, Rate};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// ExchangeRateSnapshot contains the state of the liquid staking pool at the
+/// beginning of an era
+#[derive(Default, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExchangeRateSnapshot<Balance> {
+    pub era: EraIndex,
+    pub exchange_rate: Rate,
+    /// Staking amount bonded on the relaychain by all the derivative accounts
+    pub total_bonded: Balance,
+    /// Total issuance of the liquid currency
+    pub total_liquid: Balance,
+}
+
+/// PendingUnstake is a staking amount unbonding on behalf of an account
+#[derive(Default, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingUnstake<Balance> {
+    pub value: Balance,
+    /// Era from which the amount can be claimed
+    pub claimable_era: EraIndex,
+    /// The unstake receipt backing the amount, `None` for the unlockings
+    pub receipt_id: Option<u64>,
+}
+
+/// PendingUnstakes contains the unstakes of an account which are not claimed yet
+#[derive(Default, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingUnstakes<Balance> {
+    /// Liquid amount waiting to be matched by a fast unstake
+    pub fast_unstake: Balance,
+    pub unstakes: Vec<PendingUnstake<Balance>>,
+}
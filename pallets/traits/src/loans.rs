@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use codec::{Decode, Encode};
-use frame_support::dispatch::DispatchError;
+use frame_support::{dispatch::DispatchError, weights::Weight};
 use primitives::{Rate, Ratio};
 use scale_info::TypeInfo;
 use sp_runtime::{FixedU128, RuntimeDebug};
@@ -45,6 +45,44 @@ pub trait Loans<CurrencyId, AccountId, Balance> {
         asset_id: CurrencyId,
         amount: Balance,
     ) -> Result<(), DispatchError>;
+    /// Checks the borrow cap and the cash of the market of `asset_id` for a borrow
+    /// of `amount` by `borrower`, leaving its liquidity out.
+    fn ensure_can_borrow(
+        borrower: &AccountId,
+        asset_id: CurrencyId,
+        amount: Balance,
+    ) -> Result<(), DispatchError>;
+}
+
+/// Hook executed by a flash loan once `amount` of `asset_id` has been lent to
+/// `receiver`. The receiver must hold `amount + fee` when it returns.
+pub trait FlashLoanReceiver<AccountId, CurrencyId, Balance> {
+    /// Weight of the operation described by `data`.
+    fn weight(data: &[u8]) -> Weight;
+
+    fn execute_operation(
+        receiver: &AccountId,
+        asset_id: CurrencyId,
+        amount: Balance,
+        fee: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), DispatchError>;
+}
+
+impl<AccountId, CurrencyId, Balance> FlashLoanReceiver<AccountId, CurrencyId, Balance> for () {
+    fn weight(_data: &[u8]) -> Weight {
+        Weight::zero()
+    }
+
+    fn execute_operation(
+        _receiver: &AccountId,
+        _asset_id: CurrencyId,
+        _amount: Balance,
+        _fee: Balance,
+        _data: Vec<u8>,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
 }
 
 pub trait LoansPositionDataProvider<CurrencyId, AccountId, Balance> {
@@ -87,3 +125,55 @@ pub struct MarketStatus<Balance> {
     pub total_reserves: Balance,
     pub borrow_index: FixedU128,
 }
+
+/// MarketPosition contains the position of an account in a market
+#[derive(Default, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketPosition<Balance> {
+    pub asset_id: primitives::CurrencyId,
+    /// Supplied amount of the underlying asset, interest included
+    pub supply_balance: Balance,
+    pub ptoken_balance: Balance,
+    /// Borrowed amount backed by the collateral of the account, interest included:
+    /// its own borrows and the ones its delegatees drew against its collateral
+    pub borrow_balance: Balance,
+    /// Borrowed amount drawn against the collateral of the delegators of the account,
+    /// interest included
+    pub delegated_borrow_balance: Balance,
+    pub is_collateral: bool,
+    /// Value of the supply, in USD
+    pub supply_value: FixedU128,
+    /// Value of the backed borrow, in USD, as counted by the health factor
+    pub borrow_value: FixedU128,
+    /// Reward earned in this market since the last distribution to the account
+    pub unclaimed_reward: Balance,
+}
+
+/// AccountPositions contains the positions of an account in all the markets
+#[derive(Default, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountPositions<Balance> {
+    pub positions: Vec<MarketPosition<Balance>>,
+    /// Total reward claimable by the account, including the unclaimed reward of
+    /// each market
+    pub unclaimed_reward: Balance,
+    /// Liquidation threshold value of the collaterals over the value of the
+    /// borrows, `None` if the account has no borrow
+    pub health_factor: Option<FixedU128>,
+}
+
+/// LiquidationCandidate contains a liquidatable account and the liquidation
+/// repaying the most of its borrows
+#[derive(Default, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiquidationCandidate<AccountId, Balance> {
+    pub borrower: AccountId,
+    pub shortfall: FixedU128,
+    /// The borrowed asset to repay
+    pub liquidation_asset_id: primitives::CurrencyId,
+    /// The collateral asset to seize
+    pub collateral_asset_id: primitives::CurrencyId,
+    /// Maximum amount of the borrowed asset that can be repaid, bounded by the
+    /// close factor and by the collateral
+    pub max_repay_amount: Balance,
+}
@@ -1,10 +1,10 @@
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Decode, Encode, HasCompact, MaxEncodedLen};
 use frame_support::pallet_prelude::Weight;
 use frame_system::Config;
 use primitives::{AccountId, Balance, BlockNumber, ParaId};
 use scale_info::TypeInfo;
-use sp_runtime::{traits::StaticLookup, MultiSignature, RuntimeDebug};
-use sp_std::{boxed::Box, vec::Vec};
+use sp_runtime::{traits::StaticLookup, MultiSignature, Perbill, RuntimeDebug};
+use sp_std::{boxed::Box, collections::btree_map::BTreeMap, vec::Vec};
 use xcm::latest::MultiLocation;
 
 /// A destination account for payment.
@@ -22,6 +22,50 @@ pub enum RewardDestination<AccountId> {
     None,
 }
 
+/// Validator preferences stored in relaychain staking.validators
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ValidatorPrefs {
+    /// Reward that validator takes up-front; only the rest is split between themselves and
+    /// nominators.
+    #[codec(compact)]
+    pub commission: Perbill,
+    /// Whether or not this validator is accepting more nominations.
+    pub blocked: bool,
+}
+
+/// The amount of exposure (to slashing) than an individual nominator has.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct IndividualExposure<AccountId, Balance: HasCompact> {
+    /// The stash account of the nominator in question.
+    pub who: AccountId,
+    /// Amount of funds exposed.
+    #[codec(compact)]
+    pub value: Balance,
+}
+
+/// A snapshot of the stake backing a single validator in the system, stored in
+/// relaychain staking.erasStakers
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct Exposure<AccountId, Balance: HasCompact> {
+    /// The total balance backing this validator.
+    #[codec(compact)]
+    pub total: Balance,
+    /// The validator's own stash that is exposed.
+    #[codec(compact)]
+    pub own: Balance,
+    /// The portions of nominators stashes that are exposed.
+    pub others: Vec<IndividualExposure<AccountId, Balance>>,
+}
+
+/// Reward points of an era stored in relaychain staking.erasRewardPoints
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct EraRewardPoints<AccountId: Ord> {
+    /// Total number of points. Equals the sum of reward points for each validator.
+    pub total: u32,
+    /// The reward points earned by a given validator.
+    pub individual: BTreeMap<AccountId, u32>,
+}
+
 /// Relaychain staking.bond call arguments
 #[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct StakingBondCall<T: Config> {
@@ -321,16 +365,18 @@ pub enum XcmCall {
     Proxy,
     AddProxy,
     RemoveProxy,
+    PayoutStakers,
+    WithdrawRewards,
 }
 
 #[macro_export]
 macro_rules! switch_relay {
     ({ $( $code:tt )* }) => {
-        if <T as Config>::RelayNetwork::get() == NetworkId::Polkadot {
+        if <T as Config<I>>::RelayNetwork::get() == NetworkId::Polkadot {
             use pallet_traits::ump::PolkadotCall as RelaychainCall;
 
             $( $code )*
-        } else if <T as Config>::RelayNetwork::get() == NetworkId::Kusama {
+        } else if <T as Config<I>>::RelayNetwork::get() == NetworkId::Kusama {
             use pallet_traits::ump::KusamaCall as RelaychainCall;
 
             $( $code )*
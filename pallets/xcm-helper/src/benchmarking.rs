@@ -2,7 +2,7 @@
 
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_benchmarking::{benchmarks_instance_pallet, impl_benchmark_test_suite};
 use frame_system::{self, RawOrigin as SystemOrigin};
 
 const XCM_WEIGHT_FEE: XcmWeightFeeMisc<Weight, Balance> = XcmWeightFeeMisc {
@@ -10,11 +10,11 @@ const XCM_WEIGHT_FEE: XcmWeightFeeMisc<Weight, Balance> = XcmWeightFeeMisc {
     fee: 50000000000u128,
 };
 
-fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::RuntimeEvent) {
     frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
-benchmarks! {
+benchmarks_instance_pallet! {
     where_clause {
         where
             <T as frame_system::Config>::RuntimeOrigin: From<pallet_xcm::Origin>
@@ -23,7 +23,7 @@ benchmarks! {
     update_xcm_weight_fee {
      }: _(SystemOrigin::Root, XcmCall::AddMemo, XCM_WEIGHT_FEE)
     verify {
-        assert_last_event::<T>(Event::XcmWeightFeeUpdated(XCM_WEIGHT_FEE).into())
+        assert_last_event::<T, I>(Event::XcmWeightFeeUpdated(XCM_WEIGHT_FEE).into())
     }
 
 }
@@ -45,10 +45,10 @@ pub mod weights;
 pub use weights::WeightInfo;
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type CallIdOf<T> = <T as pallet_xcm::Config>::RuntimeCall;
-pub type AssetIdOf<T> =
-    <<T as Config>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
-pub type BalanceOf<T> =
-    <<T as Config>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+pub type AssetIdOf<T, I = ()> =
+    <<T as Config<I>>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
+pub type BalanceOf<T, I = ()> =
+    <<T as Config<I>>::Assets as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -57,8 +57,9 @@ pub mod pallet {
     use sp_runtime::traits::{Convert, Zero};
 
     #[pallet::config]
-    pub trait Config: frame_system::Config + pallet_xcm::Config {
-        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+    pub trait Config<I: 'static = ()>: frame_system::Config + pallet_xcm::Config {
+        type RuntimeEvent: From<Event<Self, I>>
+            + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
         /// Assets for deposit/withdraw assets to/from crowdloan account
         type Assets: Transfer<AccountIdOf<Self>, AssetId = CurrencyId, Balance = Balance>
@@ -72,6 +73,10 @@ pub mod pallet {
         #[pallet::constant]
         type RelayNetwork: Get<NetworkId>;
 
+        /// Location of the chain the xcm calls are sent to
+        #[pallet::constant]
+        type RelayLocation: Get<MultiLocation>;
+
         /// Pallet account for collecting xcm fees
         #[pallet::constant]
         type PalletId: Get<PalletId>;
@@ -98,28 +103,28 @@ pub mod pallet {
 
         /// Relay currency
         #[pallet::constant]
-        type RelayCurrency: Get<AssetIdOf<Self>>;
+        type RelayCurrency: Get<AssetIdOf<Self, I>>;
     }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    pub enum Event<T: Config> {
+    pub enum Event<T: Config<I>, I: 'static = ()> {
         /// Xcm fee and weight updated
-        XcmWeightFeeUpdated(XcmWeightFeeMisc<Weight, BalanceOf<T>>),
+        XcmWeightFeeUpdated(XcmWeightFeeMisc<Weight, BalanceOf<T, I>>),
     }
 
     #[pallet::storage]
     #[pallet::getter(fn xcm_weight_fee)]
-    pub type XcmWeightFee<T: Config> =
-        StorageMap<_, Twox64Concat, XcmCall, XcmWeightFeeMisc<Weight, BalanceOf<T>>, ValueQuery>;
+    pub type XcmWeightFee<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, XcmCall, XcmWeightFeeMisc<Weight, BalanceOf<T, I>>, ValueQuery>;
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
     #[pallet::without_storage_info]
-    pub struct Pallet<T>(PhantomData<T>);
+    pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
     #[pallet::error]
-    pub enum Error<T> {
+    pub enum Error<T, I = ()> {
         /// `MultiLocation` value ascend more parents than known ancestors of local location.
         MultiLocationNotInvertible,
         /// XcmWeightMisc cannot have zero value
@@ -136,26 +141,29 @@ pub mod pallet {
     }
 
     #[pallet::call]
-    impl<T: Config> Pallet<T> {
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
         /// Update xcm fees amount to be used in xcm.Withdraw message
         #[pallet::call_index(0)]
-        #[pallet::weight(<T as Config>::WeightInfo::update_xcm_weight_fee())]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::update_xcm_weight_fee())]
         #[transactional]
         pub fn update_xcm_weight_fee(
             origin: OriginFor<T>,
             xcm_call: XcmCall,
-            xcm_weight_fee_misc: XcmWeightFeeMisc<Weight, BalanceOf<T>>,
+            xcm_weight_fee_misc: XcmWeightFeeMisc<Weight, BalanceOf<T, I>>,
         ) -> DispatchResult {
             T::UpdateOrigin::ensure_origin(origin)?;
 
-            ensure!(!xcm_weight_fee_misc.fee.is_zero(), Error::<T>::ZeroXcmFees);
+            ensure!(
+                !xcm_weight_fee_misc.fee.is_zero(),
+                Error::<T, I>::ZeroXcmFees
+            );
             ensure!(
                 !xcm_weight_fee_misc.weight.is_zero(),
-                Error::<T>::ZeroXcmWeightMisc
+                Error::<T, I>::ZeroXcmWeightMisc
             );
 
-            XcmWeightFee::<T>::mutate(xcm_call, |v| *v = xcm_weight_fee_misc);
-            Self::deposit_event(Event::<T>::XcmWeightFeeUpdated(xcm_weight_fee_misc));
+            XcmWeightFee::<T, I>::mutate(xcm_call, |v| *v = xcm_weight_fee_misc);
+            Self::deposit_event(Event::<T, I>::XcmWeightFeeUpdated(xcm_weight_fee_misc));
             Ok(())
         }
     }
@@ -225,12 +233,33 @@ pub trait XcmHelper<T: pallet_xcm::Config, Balance, TAccountId> {
         notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
     ) -> Result<QueryId, DispatchError>;
 
+    fn do_withdraw_unbonded_amount(
+        num_slashing_spans: u32,
+        para_account_id: TAccountId,
+        value: Balance,
+        index: u16,
+        notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+    ) -> Result<QueryId, DispatchError>;
+
+    fn do_withdraw_rewards(
+        value: Balance,
+        para_account_id: TAccountId,
+        index: u16,
+        notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+    ) -> Result<QueryId, DispatchError>;
+
     fn do_nominate(
         targets: Vec<TAccountId>,
         index: u16,
         notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
     ) -> Result<QueryId, DispatchError>;
 
+    fn do_payout_stakers(
+        validator_stash: TAccountId,
+        era: u32,
+        notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+    ) -> Result<QueryId, DispatchError>;
+
     fn do_add_proxy(
         delegate: AccountId,
         proxy_type: Option<ProxyType>,
@@ -246,7 +275,7 @@ pub trait XcmHelper<T: pallet_xcm::Config, Balance, TAccountId> {
     ) -> Result<QueryId, DispatchError>;
 }
 
-impl<T: Config> Pallet<T> {
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
     pub fn account_id() -> AccountIdOf<T> {
         T::PalletId::get().into_account_truncating()
     }
@@ -264,7 +293,7 @@ impl<T: Config> Pallet<T> {
         let responder = responder.into();
         let destination = <T as pallet_xcm::Config>::UniversalLocation::get()
             .invert_target(&responder)
-            .map_err(|()| Error::<T>::MultiLocationNotInvertible)?;
+            .map_err(|()| Error::<T, I>::MultiLocationNotInvertible)?;
         let notify: <T as pallet_xcm::Config>::RuntimeCall = notify.into();
         let max_weight = notify.get_dispatch_info().weight;
         let query_id = pallet_xcm::Pallet::<T>::new_notify_query(responder, notify, timeout, Here);
@@ -292,7 +321,7 @@ impl<T: Config> Pallet<T> {
 
     pub fn get_xcm_weight_fee_to_sibling(
         location: MultiLocation,
-    ) -> XcmWeightFeeMisc<Weight, BalanceOf<T>> {
+    ) -> XcmWeightFeeMisc<Weight, BalanceOf<T, I>> {
         let call = XcmCall::TransferToSiblingchain(Box::new(location));
         Self::xcm_weight_fee(call)
     }
@@ -330,8 +359,8 @@ impl<T: Config> Pallet<T> {
     }
 }
 
-impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
-    fn add_xcm_fees(payer: &AccountIdOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+impl<T: Config<I>, I: 'static> XcmHelper<T, BalanceOf<T, I>, AccountIdOf<T>> for Pallet<T, I> {
+    fn add_xcm_fees(payer: &AccountIdOf<T>, amount: BalanceOf<T, I>) -> DispatchResult {
         T::Assets::transfer(
             T::RelayCurrency::get(),
             payer,
@@ -346,11 +375,11 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
         call: DoubleEncoded<()>,
         weight: Weight,
         beneficiary: MultiLocation,
-        fees: BalanceOf<T>,
+        fees: BalanceOf<T, I>,
     ) -> Result<Xcm<()>, DispatchError> {
         let asset: MultiAsset = (MultiLocation::here(), fees).into();
         T::Assets::burn_from(T::RelayCurrency::get(), &Self::account_id(), fees)
-            .map_err(|_| Error::<T>::InsufficientXcmFees)?;
+            .map_err(|_| Error::<T, I>::InsufficientXcmFees)?;
 
         Ok(Xcm(vec![
             WithdrawAsset(MultiAssets::from(asset.clone())),
@@ -395,13 +424,13 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
             let query_id = Self::report_outcome_notify(
                 &mut msg,
-                MultiLocation::parent(),
+                T::RelayLocation::get(),
                 notify,
                 T::NotifyTimeout::get(),
             )?;
 
-            if let Err(_e) = send_xcm::<T::XcmSender>(MultiLocation::parent(), msg) {
-                return Err(Error::<T>::SendFailure.into());
+            if let Err(_e) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
             }
 
             query_id
@@ -433,13 +462,13 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
             let query_id = Self::report_outcome_notify(
                 &mut msg,
-                MultiLocation::parent(),
+                T::RelayLocation::get(),
                 notify,
                 T::NotifyTimeout::get(),
             )?;
 
-            if let Err(_e) = send_xcm::<T::XcmSender>(MultiLocation::parent(), msg) {
-                return Err(Error::<T>::SendFailure.into());
+            if let Err(_e) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
             }
 
             query_id
@@ -468,13 +497,13 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
             let query_id = Self::report_outcome_notify(
                 &mut msg,
-                MultiLocation::parent(),
+                T::RelayLocation::get(),
                 notify,
                 T::NotifyTimeout::get(),
             )?;
 
-            if let Err(_e) = send_xcm::<T::XcmSender>(MultiLocation::parent(), msg) {
-                return Err(Error::<T>::SendFailure.into());
+            if let Err(_e) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
             }
 
             query_id
@@ -483,7 +512,7 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
     fn do_contribute(
         para_id: ParaId,
-        amount: BalanceOf<T>,
+        amount: BalanceOf<T, I>,
         _who: &AccountIdOf<T>,
         notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
     ) -> Result<QueryId, DispatchError> {
@@ -506,13 +535,13 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
             let query_id = Self::report_outcome_notify(
                 &mut msg,
-                MultiLocation::parent(),
+                T::RelayLocation::get(),
                 notify,
                 T::NotifyTimeout::get(),
             )?;
 
-            if let Err(_e) = send_xcm::<T::XcmSender>(MultiLocation::parent(), msg) {
-                return Err(Error::<T>::SendFailure.into());
+            if let Err(_e) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
             }
 
             query_id
@@ -521,13 +550,13 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
     fn do_proxy_contribute(
         para_id: ParaId,
-        amount: BalanceOf<T>,
+        amount: BalanceOf<T, I>,
         who: &AccountIdOf<T>,
         notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
     ) -> Result<QueryId, DispatchError> {
         let xcm_weight_fee_misc = Self::xcm_weight_fee(XcmCall::Contribute);
-        let real =
-            AccountId::try_from(&who.encode()[..]).map_err(|_| Error::<T>::ConvertAccountError)?;
+        let real = AccountId::try_from(&who.encode()[..])
+            .map_err(|_| Error::<T, I>::ConvertAccountError)?;
         Ok(switch_relay!({
             let call = RelaychainCall::<T>::Balances(BalancesCall::TransferKeepAlive(
                 BalancesTransferKeepAliveCall {
@@ -545,7 +574,7 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
             let query_id = Self::report_outcome_notify(
                 &mut msg,
-                MultiLocation::parent(),
+                T::RelayLocation::get(),
                 notify,
                 T::NotifyTimeout::get(),
             )?;
@@ -563,8 +592,8 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
             })));
             Self::append_transact(&mut msg, call.encode().into(), xcm_weight_fee_misc.weight);
 
-            if let Err(_e) = send_xcm::<T::XcmSender>(MultiLocation::parent(), msg) {
-                return Err(Error::<T>::SendFailure.into());
+            if let Err(_e) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
             }
 
             query_id
@@ -572,7 +601,7 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
     }
 
     fn do_bond(
-        value: BalanceOf<T>,
+        value: BalanceOf<T, I>,
         payee: RewardDestination<AccountIdOf<T>>,
         stash: AccountIdOf<T>,
         index: u16,
@@ -596,7 +625,7 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
             let query_id = Self::report_outcome_notify(
                 &mut msg,
-                MultiLocation::parent(),
+                T::RelayLocation::get(),
                 notify,
                 T::NotifyTimeout::get(),
             )?;
@@ -613,8 +642,8 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
             )));
             Self::append_transact(&mut msg, call.encode().into(), xcm_weight_fee_misc.weight);
 
-            if let Err(_err) = send_xcm::<T::XcmSender>(MultiLocation::parent(), msg) {
-                return Err(Error::<T>::SendFailure.into());
+            if let Err(_err) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
             }
 
             query_id
@@ -622,7 +651,7 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
     }
 
     fn do_bond_extra(
-        value: BalanceOf<T>,
+        value: BalanceOf<T, I>,
         stash: AccountIdOf<T>,
         index: u16,
         notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
@@ -645,7 +674,7 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
             let query_id = Self::report_outcome_notify(
                 &mut msg,
-                MultiLocation::parent(),
+                T::RelayLocation::get(),
                 notify,
                 T::NotifyTimeout::get(),
             )?;
@@ -660,8 +689,8 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
             )));
             Self::append_transact(&mut msg, call.encode().into(), xcm_weight_fee_misc.weight);
 
-            if let Err(_err) = send_xcm::<T::XcmSender>(MultiLocation::parent(), msg) {
-                return Err(Error::<T>::SendFailure.into());
+            if let Err(_err) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
             }
 
             query_id
@@ -669,7 +698,7 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
     }
 
     fn do_unbond(
-        value: BalanceOf<T>,
+        value: BalanceOf<T, I>,
         index: u16,
         notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
     ) -> Result<QueryId, DispatchError> {
@@ -693,13 +722,13 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
             let query_id = Self::report_outcome_notify(
                 &mut msg,
-                MultiLocation::parent(),
+                T::RelayLocation::get(),
                 notify,
                 T::NotifyTimeout::get(),
             )?;
 
-            if let Err(_err) = send_xcm::<T::XcmSender>(MultiLocation::parent(), msg) {
-                return Err(Error::<T>::SendFailure.into());
+            if let Err(_err) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
             }
 
             query_id
@@ -707,7 +736,7 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
     }
 
     fn do_rebond(
-        value: BalanceOf<T>,
+        value: BalanceOf<T, I>,
         index: u16,
         notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
     ) -> Result<QueryId, DispatchError> {
@@ -731,13 +760,13 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
             let query_id = Self::report_outcome_notify(
                 &mut msg,
-                MultiLocation::parent(),
+                T::RelayLocation::get(),
                 notify,
                 T::NotifyTimeout::get(),
             )?;
 
-            if let Err(_err) = send_xcm::<T::XcmSender>(MultiLocation::parent(), msg) {
-                return Err(Error::<T>::SendFailure.into());
+            if let Err(_err) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
             }
 
             query_id
@@ -770,7 +799,7 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
             let query_id = Self::report_outcome_notify(
                 &mut msg,
-                MultiLocation::parent(),
+                T::RelayLocation::get(),
                 notify,
                 T::NotifyTimeout::get(),
             )?;
@@ -788,8 +817,105 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
             )));
             Self::append_transact(&mut msg, call.encode().into(), xcm_weight_fee_misc.weight);
 
-            if let Err(_err) = send_xcm::<T::XcmSender>(MultiLocation::parent(), msg) {
-                return Err(Error::<T>::SendFailure.into());
+            if let Err(_err) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
+            }
+
+            query_id
+        }))
+    }
+
+    fn do_withdraw_unbonded_amount(
+        num_slashing_spans: u32,
+        para_account_id: AccountIdOf<T>,
+        value: BalanceOf<T, I>,
+        index: u16,
+        notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+    ) -> Result<QueryId, DispatchError> {
+        let xcm_weight_fee_misc = Self::xcm_weight_fee(XcmCall::WithdrawUnbonded);
+        Ok(switch_relay!({
+            let call = RelaychainCall::Utility(Box::new(UtilityCall::AsDerivative(
+                UtilityAsDerivativeCall {
+                    index,
+                    call: RelaychainCall::Staking::<T>(StakingCall::WithdrawUnbonded(
+                        StakingWithdrawUnbondedCall { num_slashing_spans },
+                    )),
+                },
+            )));
+
+            let mut msg = Self::do_ump_transact(
+                call.encode().into(),
+                xcm_weight_fee_misc.weight,
+                Self::refund_location(),
+                xcm_weight_fee_misc.fee,
+            )?;
+
+            let query_id = Self::report_outcome_notify(
+                &mut msg,
+                T::RelayLocation::get(),
+                notify,
+                T::NotifyTimeout::get(),
+            )?;
+
+            let call = RelaychainCall::Utility(Box::new(UtilityCall::AsDerivative(
+                UtilityAsDerivativeCall {
+                    index,
+                    // only the unbonded amount, the rewards left in the stash are
+                    // withdrawn by `do_withdraw_rewards`
+                    call: RelaychainCall::Balances::<T>(BalancesCall::TransferKeepAlive(
+                        BalancesTransferKeepAliveCall {
+                            dest: T::Lookup::unlookup(para_account_id),
+                            value,
+                        },
+                    )),
+                },
+            )));
+            Self::append_transact(&mut msg, call.encode().into(), xcm_weight_fee_misc.weight);
+
+            if let Err(_err) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
+            }
+
+            query_id
+        }))
+    }
+
+    fn do_withdraw_rewards(
+        value: BalanceOf<T, I>,
+        para_account_id: AccountIdOf<T>,
+        index: u16,
+        notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+    ) -> Result<QueryId, DispatchError> {
+        let xcm_weight_fee_misc = Self::xcm_weight_fee(XcmCall::WithdrawRewards);
+        Ok(switch_relay!({
+            let call = RelaychainCall::Utility(Box::new(UtilityCall::AsDerivative(
+                UtilityAsDerivativeCall {
+                    index,
+                    call: RelaychainCall::Balances::<T>(BalancesCall::TransferKeepAlive(
+                        BalancesTransferKeepAliveCall {
+                            dest: T::Lookup::unlookup(para_account_id),
+                            value,
+                        },
+                    )),
+                },
+            )));
+
+            let mut msg = Self::do_ump_transact(
+                call.encode().into(),
+                xcm_weight_fee_misc.weight,
+                Self::refund_location(),
+                xcm_weight_fee_misc.fee,
+            )?;
+
+            let query_id = Self::report_outcome_notify(
+                &mut msg,
+                T::RelayLocation::get(),
+                notify,
+                T::NotifyTimeout::get(),
+            )?;
+
+            if let Err(_err) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
             }
 
             query_id
@@ -824,13 +950,50 @@ impl<T: Config> XcmHelper<T, BalanceOf<T>, AccountIdOf<T>> for Pallet<T> {
 
             let query_id = Self::report_outcome_notify(
                 &mut msg,
-                MultiLocation::parent(),
+                T::RelayLocation::get(),
+                notify,
+                T::NotifyTimeout::get(),
+            )?;
+
+            if let Err(_err) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
+            }
+
+            query_id
+        }))
+    }
+
+    fn do_payout_stakers(
+        validator_stash: AccountIdOf<T>,
+        era: u32,
+        notify: impl Into<<T as pallet_xcm::Config>::RuntimeCall>,
+    ) -> Result<QueryId, DispatchError> {
+        let xcm_weight_fee_misc = Self::xcm_weight_fee(XcmCall::PayoutStakers);
+        Ok(switch_relay!({
+            // payout_stakers is permissionless, no need to dispatch it as a derivative
+            let call = RelaychainCall::Staking::<T>(StakingCall::PayoutStakers(
+                StakingPayoutStakersCall {
+                    validator_stash,
+                    era,
+                },
+            ));
+
+            let mut msg = Self::do_ump_transact(
+                call.encode().into(),
+                xcm_weight_fee_misc.weight,
+                Self::refund_location(),
+                xcm_weight_fee_misc.fee,
+            )?;
+
+            let query_id = Self::report_outcome_notify(
+                &mut msg,
+                T::RelayLocation::get(),
                 notify,
                 T::NotifyTimeout::get(),
             )?;
 
-            if let Err(_err) = send_xcm::<T::XcmSender>(MultiLocation::parent(), msg) {
-                return Err(Error::<T>::SendFailure.into());
+            if let Err(_err) = send_xcm::<T::XcmSender>(T::RelayLocation::get(), msg) {
+                return Err(Error::<T, I>::SendFailure.into());
             }
 
             query_id
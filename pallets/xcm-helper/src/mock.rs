@@ -387,6 +387,7 @@ parameter_types! {
     pub const XcmHelperPalletId: PalletId = PalletId(*b"par/fees");
     pub const NotifyTimeout: BlockNumber = 100;
     pub RefundLocation: AccountId = para_a_id().into_account_truncating();
+    pub RelayLocation: MultiLocation = MultiLocation::parent();
 }
 
 impl crate::Config for Test {
@@ -396,6 +397,27 @@ impl crate::Config for Test {
     type XcmSender = XcmRouter;
     type PalletId = XcmHelperPalletId;
     type RelayNetwork = RelayNetwork;
+    type RelayLocation = RelayLocation;
+    type NotifyTimeout = NotifyTimeout;
+    type AccountIdToMultiLocation = AccountIdToMultiLocation;
+    type RefundLocation = RefundLocation;
+    type BlockNumberProvider = frame_system::Pallet<Test>;
+    type WeightInfo = ();
+    type RelayCurrency = RelayCurrency;
+}
+
+parameter_types! {
+    pub const XcmHelperPalletId2: PalletId = PalletId(*b"par/fee2");
+}
+
+impl crate::Config<crate::Instance2> for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type Assets = Assets;
+    type XcmSender = XcmRouter;
+    type PalletId = XcmHelperPalletId2;
+    type RelayNetwork = RelayNetwork;
+    type RelayLocation = RelayLocation;
     type NotifyTimeout = NotifyTimeout;
     type AccountIdToMultiLocation = AccountIdToMultiLocation;
     type RefundLocation = RefundLocation;
@@ -452,6 +474,7 @@ construct_runtime!(
         CumulusXcm: cumulus_pallet_xcm::{Pallet, Event<T>, Origin},
         PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
         XcmHelpers: crate::{Pallet, Storage, Call, Event<T>},
+        XcmHelpers2: crate::<Instance2>::{Pallet, Storage, Call, Event<T>},
     }
 );
 
@@ -44,3 +44,28 @@ fn update_xcm_fees_should_work() {
         );
     });
 }
+
+#[test]
+fn xcm_fees_are_isolated_per_instance() {
+    new_test_ext().execute_with(|| {
+        let xcm_weight_fee_misc = XcmWeightFeeMisc {
+            weight: Weight::from_ref_time(3_000_000_000),
+            fee: dot(1f64),
+        };
+        assert_ok!(XcmHelpers2::update_xcm_weight_fee(
+            frame_system::RawOrigin::Root.into(), // origin
+            XcmCall::Bond,
+            xcm_weight_fee_misc
+        ));
+
+        assert_eq!(
+            XcmWeightFee::<Test, crate::Instance2>::get(XcmCall::Bond),
+            xcm_weight_fee_misc
+        );
+        assert_eq!(
+            XcmWeightFee::<Test>::get(XcmCall::Bond),
+            XcmWeightFeeMisc::default()
+        );
+        assert_ne!(XcmHelpers::account_id(), XcmHelpers2::account_id());
+    });
+}
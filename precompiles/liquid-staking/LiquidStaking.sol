@@ -0,0 +1,97 @@
+pragma solidity ^0.8.0;
+
+    /**
+     * @title Liquid staking interface
+     * @dev The interface through which solidity contracts will interact with pallet-liquid-staking
+     * Address: 0x0000000000000000000000000000000000000803
+     */
+    interface ILiquidStaking {
+
+    /**
+     * @dev Returns the exchange rate between the staking and the liquid asset, with 18 decimals.
+     * Selector: 3ba0b9a9
+     */
+    function exchangeRate() external view returns (uint256);
+
+    /**
+     * @dev Converts an amount of staking asset to liquid asset at the current exchange rate.
+     * Selector: 92f8b83d
+     * @param amount The amount of staking asset.
+     */
+    function stakingToLiquid(uint256 amount) external view returns (uint256);
+
+    /**
+     * @dev Converts an amount of liquid asset to staking asset at the current exchange rate.
+     * Selector: d238e634
+     * @param liquidAmount The amount of liquid asset.
+     */
+    function liquidToStaking(uint256 liquidAmount) external view returns (uint256);
+
+    /**
+     * @dev Returns the current era index.
+     * Selector: 973628f6
+     */
+    function currentEra() external view returns (uint32);
+
+    /**
+     * @dev Returns the relaychain block at which the current era started.
+     * Selector: 59b6ed83
+     */
+    function eraStartBlock() external view returns (uint256);
+
+    /**
+     * @dev Returns the stake and unstake amounts of the current era's matching pool.
+     * Selector: aacef18b
+     */
+    function matchingPool()
+        external view returns (
+            uint256 totalStake,
+            uint256 reservedStake,
+            uint256 totalUnstake,
+            uint256 reservedUnstake
+        );
+
+    /**
+     * @dev Stakes the staking asset and mints the liquid asset to the caller.
+     * Selector: a694fc3a
+     * @param amount The amount of staking asset.
+     */
+    function stake(uint256 amount) external returns (bool);
+
+    /**
+     * @dev Unstakes the liquid asset of the caller.
+     * Selector: bc9699f1
+     * @param liquidAmount The amount of liquid asset.
+     * @param provider 0 for the relaychain, 1 for loans and 2 for the matching pool,
+     * the AMM and the stableswap can only be used through `instantUnstake`.
+     */
+    function unstake(uint256 liquidAmount, uint8 provider) external returns (bool);
+
+    /**
+     * @dev Unstakes the liquid asset of the caller instantly through the provider giving back
+     * the most staking asset, among loans, the matching pool, the AMM and the stableswap.
+     * Selector: bd0461aa
+     * @param liquidAmount The amount of liquid asset.
+     * @param minAmountOut The minimum amount of staking asset to receive.
+     */
+    function instantUnstake(uint256 liquidAmount, uint256 minAmountOut) external returns (bool);
+
+    /**
+     * @dev Returns the best instant unstake quote for the caller.
+     * Selector: 124e100c
+     * @param liquidAmount The amount of liquid asset.
+     * @return provider 1 for loans, 2 for the matching pool, 3 for the AMM
+     * and 4 for the stableswap.
+     * @return amount The amount of staking asset to receive.
+     */
+    function instantUnstakeQuote(uint256 liquidAmount)
+        external view returns (uint8 provider, uint256 amount);
+
+    /**
+     * @dev Claims the unlocked staking asset of an account,
+     * the caller must be allowed to claim on behalf of others.
+     * Selector: ddeae033
+     * @param who The account whose unlocked staking asset is claimed.
+     */
+    function claimFor(address who) external returns (bool);
+}
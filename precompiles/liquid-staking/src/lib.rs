@@ -0,0 +1,365 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use fp_evm::{Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use pallet_evm::AddressMapping;
+use pallet_liquid_staking::{types::UnstakeProvider, BalanceOf};
+use pallet_traits::LiquidStakingConvert;
+use precompile_utils::{
+    generate_function_selector, revert, succeed, Address, EvmDataWriter, EvmResult,
+    FunctionModifier, PrecompileHandleExt, RuntimeHelper,
+};
+use primitives::Balance;
+use sp_core::{H160, U256};
+use sp_runtime::{traits::StaticLookup, FixedPointNumber};
+use sp_std::{
+    convert::{TryFrom, TryInto},
+    marker::PhantomData,
+};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+type LiquidStaking<Runtime, Instance> = pallet_liquid_staking::Pallet<Runtime, Instance>;
+
+#[generate_function_selector]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
+    ExchangeRate = "exchangeRate()",
+    StakingToLiquid = "stakingToLiquid(uint256)",
+    LiquidToStaking = "liquidToStaking(uint256)",
+    CurrentEra = "currentEra()",
+    EraStartBlock = "eraStartBlock()",
+    MatchingPool = "matchingPool()",
+    Stake = "stake(uint256)",
+    Unstake = "unstake(uint256,uint8)",
+    InstantUnstake = "instantUnstake(uint256,uint256)",
+    InstantUnstakeQuote = "instantUnstakeQuote(uint256)",
+    ClaimFor = "claimFor(address)",
+}
+
+/// Precompile exposing pallet_liquid_staking to EVM contracts.
+/// Read methods return the state of the pallet, write methods dispatch
+/// the pallet's calls with the caller's mapped account as origin.
+pub struct LiquidStakingPrecompile<Runtime, Instance: 'static = ()>(
+    PhantomData<(Runtime, Instance)>,
+);
+
+impl<Runtime, Instance> Default for LiquidStakingPrecompile<Runtime, Instance> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<Runtime, Instance> Precompile for LiquidStakingPrecompile<Runtime, Instance>
+where
+    Instance: 'static,
+    Runtime: pallet_liquid_staking::Config<Instance> + pallet_evm::Config,
+    Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+    Runtime::RuntimeCall: From<pallet_liquid_staking::Call<Runtime, Instance>>,
+    <Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+    BalanceOf<Runtime, Instance>: TryFrom<U256> + Into<U256>,
+    Runtime::BlockNumber: Into<U256>,
+{
+    fn execute(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        let selector = handle.read_selector()?;
+
+        handle.check_function_modifier(match selector {
+            Action::Stake | Action::Unstake | Action::InstantUnstake | Action::ClaimFor => {
+                FunctionModifier::NonPayable
+            }
+            _ => FunctionModifier::View,
+        })?;
+
+        match selector {
+            Action::ExchangeRate => Self::exchange_rate(handle),
+            Action::StakingToLiquid => Self::staking_to_liquid(handle),
+            Action::LiquidToStaking => Self::liquid_to_staking(handle),
+            Action::CurrentEra => Self::current_era(handle),
+            Action::EraStartBlock => Self::era_start_block(handle),
+            Action::MatchingPool => Self::matching_pool(handle),
+            Action::Stake => Self::stake(handle),
+            Action::Unstake => Self::unstake(handle),
+            Action::InstantUnstake => Self::instant_unstake(handle),
+            Action::InstantUnstakeQuote => Self::instant_unstake_quote(handle),
+            Action::ClaimFor => Self::claim_for(handle),
+        }
+    }
+}
+
+impl<Runtime, Instance> LiquidStakingPrecompile<Runtime, Instance>
+where
+    Instance: 'static,
+    Runtime: pallet_liquid_staking::Config<Instance> + pallet_evm::Config,
+    Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+    Runtime::RuntimeCall: From<pallet_liquid_staking::Call<Runtime, Instance>>,
+    <Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+    BalanceOf<Runtime, Instance>: TryFrom<U256> + Into<U256>,
+    Runtime::BlockNumber: Into<U256>,
+{
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    /// Returns the inner value of the exchange rate, which has 18 decimals.
+    fn exchange_rate(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+        // Parse input.
+        let input = handle.read_input()?;
+        input.expect_arguments(0)?;
+
+        // Fetch info.
+        let rate: U256 = LiquidStaking::<Runtime, Instance>::exchange_rate()
+            .into_inner()
+            .into();
+
+        // Build output.
+        Ok(succeed(EvmDataWriter::new().write(rate).build()))
+    }
+
+    fn staking_to_liquid(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+        // Parse input.
+        let mut input = handle.read_input()?;
+        input.expect_arguments(1)?;
+
+        let amount = Self::u256_to_balance(input.read()?)?;
+
+        // Fetch info.
+        let liquid_amount: U256 = LiquidStaking::<Runtime, Instance>::staking_to_liquid(amount)
+            .ok_or_else(|| revert("Invalid exchange rate"))?
+            .into();
+
+        // Build output.
+        Ok(succeed(EvmDataWriter::new().write(liquid_amount).build()))
+    }
+
+    fn liquid_to_staking(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+        // Parse input.
+        let mut input = handle.read_input()?;
+        input.expect_arguments(1)?;
+
+        let liquid_amount = Self::u256_to_balance(input.read()?)?;
+
+        // Fetch info.
+        let amount: U256 = LiquidStaking::<Runtime, Instance>::liquid_to_staking(liquid_amount)
+            .ok_or_else(|| revert("Invalid exchange rate"))?
+            .into();
+
+        // Build output.
+        Ok(succeed(EvmDataWriter::new().write(amount).build()))
+    }
+
+    fn current_era(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+        // Parse input.
+        let input = handle.read_input()?;
+        input.expect_arguments(0)?;
+
+        // Fetch info.
+        let era = LiquidStaking::<Runtime, Instance>::current_era();
+
+        // Build output.
+        Ok(succeed(EvmDataWriter::new().write(era).build()))
+    }
+
+    /// Returns the relaychain block at which the current era started.
+    fn era_start_block(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+        // Parse input.
+        let input = handle.read_input()?;
+        input.expect_arguments(0)?;
+
+        // Fetch info.
+        let block: U256 = LiquidStaking::<Runtime, Instance>::era_start_block().into();
+
+        // Build output.
+        Ok(succeed(EvmDataWriter::new().write(block).build()))
+    }
+
+    /// Returns `(totalStake, reservedStake, totalUnstake, reservedUnstake)` of
+    /// the current era's matching pool.
+    fn matching_pool(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+        // Parse input.
+        let input = handle.read_input()?;
+        input.expect_arguments(0)?;
+
+        // Fetch info.
+        let pool = LiquidStaking::<Runtime, Instance>::matching_pool();
+
+        // Build output.
+        Ok(succeed(
+            EvmDataWriter::new()
+                .write::<U256>(pool.total_stake_amount.total.into())
+                .write::<U256>(pool.total_stake_amount.reserved.into())
+                .write::<U256>(pool.total_unstake_amount.total.into())
+                .write::<U256>(pool.total_unstake_amount.reserved.into())
+                .build(),
+        ))
+    }
+
+    fn stake(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        // Parse input.
+        let mut input = handle.read_input()?;
+        input.expect_arguments(1)?;
+
+        let amount = Self::u256_to_amount(input.read()?)?;
+
+        // Dispatch call (if enough gas).
+        let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+        RuntimeHelper::<Runtime>::try_dispatch(
+            handle,
+            Some(origin).into(),
+            pallet_liquid_staking::Call::<Runtime, Instance>::stake { amount },
+        )?;
+
+        // Build output.
+        Ok(succeed(EvmDataWriter::new().write(true).build()))
+    }
+
+    /// Unstakes with the provider encoded as in `UnstakeProvider`:
+    /// 0 for the relaychain, 1 for loans and 2 for the matching pool.
+    /// The AMM and the stableswap can only be used through `instantUnstake`.
+    fn unstake(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        // Parse input.
+        let mut input = handle.read_input()?;
+        input.expect_arguments(2)?;
+
+        let liquid_amount = Self::u256_to_amount(input.read()?)?;
+        let unstake_provider = match input.read::<u8>()? {
+            0 => UnstakeProvider::RelayChain,
+            1 => UnstakeProvider::Loans,
+            2 => UnstakeProvider::MatchingPool,
+            _ => return Err(revert("Invalid unstake provider")),
+        };
+
+        // Dispatch call (if enough gas).
+        let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+        RuntimeHelper::<Runtime>::try_dispatch(
+            handle,
+            Some(origin).into(),
+            pallet_liquid_staking::Call::<Runtime, Instance>::unstake {
+                liquid_amount,
+                unstake_provider,
+            },
+        )?;
+
+        // Build output.
+        Ok(succeed(EvmDataWriter::new().write(true).build()))
+    }
+
+    /// Unstakes instantly through the provider giving back the most staking assets,
+    /// reverting if less than `minAmountOut` would be received.
+    fn instant_unstake(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        // Parse input.
+        let mut input = handle.read_input()?;
+        input.expect_arguments(2)?;
+
+        let liquid_amount = Self::u256_to_amount(input.read()?)?;
+        let min_amount_out = Self::u256_to_amount(input.read()?)?;
+
+        // Dispatch call (if enough gas).
+        let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+        RuntimeHelper::<Runtime>::try_dispatch(
+            handle,
+            Some(origin).into(),
+            pallet_liquid_staking::Call::<Runtime, Instance>::instant_unstake {
+                liquid_amount,
+                min_amount_out,
+            },
+        )?;
+
+        // Build output.
+        Ok(succeed(EvmDataWriter::new().write(true).build()))
+    }
+
+    /// Returns `(provider, amount)` of the best instant unstake quote,
+    /// with the provider encoded as in `UnstakeProvider`, 3 being the AMM and
+    /// 4 the stableswap.
+    fn instant_unstake_quote(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        // The quotes read the loans market, the matching pool, the AMM pool and the
+        // stableswap pool.
+        handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 5)?;
+
+        // Parse input.
+        let mut input = handle.read_input()?;
+        input.expect_arguments(1)?;
+
+        let liquid_amount = Self::u256_to_amount(input.read()?)?;
+
+        // Fetch info.
+        let (provider, amount) =
+            LiquidStaking::<Runtime, Instance>::instant_unstake_quotes(liquid_amount)
+                .into_iter()
+                .max_by_key(|(_, amount)| *amount)
+                .ok_or_else(|| revert("No instant unstake provider"))?;
+
+        // Build output.
+        Ok(succeed(
+            EvmDataWriter::new()
+                .write(provider as u8)
+                .write::<U256>(amount.into())
+                .build(),
+        ))
+    }
+
+    /// Claims the unlocked staking assets of `who`, the caller must be allowed
+    /// to dispatch `claim_for`.
+    fn claim_for(handle: &mut impl PrecompileHandle) -> EvmResult<PrecompileOutput> {
+        // Parse input.
+        let mut input = handle.read_input()?;
+        input.expect_arguments(1)?;
+
+        let who: H160 = input.read::<Address>()?.into();
+        let dest = <Runtime::Lookup as StaticLookup>::unlookup(
+            Runtime::AddressMapping::into_account_id(who),
+        );
+
+        // Dispatch call (if enough gas).
+        let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+        RuntimeHelper::<Runtime>::try_dispatch(
+            handle,
+            Some(origin).into(),
+            pallet_liquid_staking::Call::<Runtime, Instance>::claim_for { dest },
+        )?;
+
+        // Build output.
+        Ok(succeed(EvmDataWriter::new().write(true).build()))
+    }
+
+    fn u256_to_amount(value: U256) -> Result<BalanceOf<Runtime, Instance>, PrecompileFailure> {
+        value
+            .try_into()
+            .map_err(|_| revert("Error processing amount"))
+    }
+
+    fn u256_to_balance(value: U256) -> Result<Balance, PrecompileFailure> {
+        value
+            .try_into()
+            .map_err(|_| revert("Error processing amount"))
+    }
+}
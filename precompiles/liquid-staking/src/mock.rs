@@ -0,0 +1,579 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![allow(unused_imports, dead_code)]
+use super::*;
+
+use frame_support::{
+    construct_runtime,
+    dispatch::Weight,
+    parameter_types,
+    traits::{AsEnsureOriginWithArg, ConstU32, Everything, GenesisBuild, Nothing, SortedMembers},
+    PalletId,
+};
+use frame_system::{EnsureRoot, EnsureSigned};
+use pallet_evm::{AddressMapping, EnsureAddressNever, EnsureAddressRoot, PrecompileSet};
+use pallet_liquid_staking::{distribution::AverageDistribution, nomination::EraPointsNomination};
+use pallet_traits::{DecimalProvider, PriceFeeder, ValidationDataProvider};
+use primitives::{
+    tokens::{HKO, KSM, KSM_U, LP_KSM_SKSM, SKSM},
+    CurrencyId, EraIndex, ParaId, PersistedValidationData, Price, PriceDetail, Rate, Ratio,
+};
+use sp_core::{H160, H256};
+use sp_runtime::{
+    testing::Header,
+    traits::{AccountIdConversion, BlakeTwo256, BlockNumberProvider, Convert, IdentityLookup, One},
+    AccountId32,
+};
+use xcm::latest::prelude::*;
+use xcm_builder::{EnsureXcmOrigin, FixedWeightBounds, SignedToAccountId32};
+
+pub type AccountId = AccountId32;
+pub type BlockNumber = u64;
+pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+pub type Block = frame_system::mocking::MockBlock<Runtime>;
+
+pub const PRECOMPILE_ADDRESS: u64 = 1;
+pub const KSM_DECIMAL: u128 = 10u128.pow(12);
+
+pub fn ksm(n: f64) -> Balance {
+    ((n * 1000000f64) as u128) * KSM_DECIMAL / 1000000
+}
+
+/// A simple account type.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum Account {
+    Alice,
+    Bob,
+    Bogus,
+    Precompile,
+}
+
+impl From<Account> for H160 {
+    fn from(x: Account) -> H160 {
+        match x {
+            Account::Alice => H160::repeat_byte(0xAA),
+            Account::Bob => H160::repeat_byte(0xBB),
+            Account::Bogus => Default::default(),
+            Account::Precompile => H160::from_low_u64_be(PRECOMPILE_ADDRESS),
+        }
+    }
+}
+
+impl From<Account> for AccountId {
+    fn from(x: Account) -> AccountId {
+        MockAddressMapping::into_account_id(x.into())
+    }
+}
+
+/// Maps an EVM address to the account with the address as prefix.
+pub struct MockAddressMapping;
+impl AddressMapping<AccountId> for MockAddressMapping {
+    fn into_account_id(address: H160) -> AccountId {
+        let mut data = [0u8; 32];
+        data[0..20].copy_from_slice(&address[..]);
+        AccountId::from(data)
+    }
+}
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for Runtime {
+    type BaseCallFilter = Everything;
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type RuntimeCall = RuntimeCall;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type BlockWeights = ();
+    type BlockLength = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+    pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Runtime {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u128 = 0;
+}
+
+impl pallet_balances::Config for Runtime {
+    type MaxReserves = ();
+    type ReserveIdentifier = ();
+    type MaxLocks = ();
+    type Balance = Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const AssetDeposit: Balance = 0;
+    pub const ApprovalDeposit: Balance = 0;
+    pub const AssetAccountDeposit: Balance = 0;
+    pub const AssetsStringLimit: u32 = 50;
+    pub const MetadataDepositBase: Balance = 0;
+    pub const MetadataDepositPerByte: Balance = 0;
+}
+
+impl pallet_assets::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type AssetId = CurrencyId;
+    type AssetIdParameter = codec::Compact<CurrencyId>;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type AssetAccountDeposit = AssetAccountDeposit;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = AssetsStringLimit;
+    type Freezer = ();
+    type WeightInfo = ();
+    type Extra = ();
+    type RemoveItemsLimit = ConstU32<1000>;
+    type CallbackHandle = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
+parameter_types! {
+    pub const NativeCurrencyId: CurrencyId = HKO;
+}
+
+impl pallet_currency_adapter::Config for Runtime {
+    type Assets = Assets;
+    type Balances = Balances;
+    type GetNativeCurrencyId = NativeCurrencyId;
+    type LockOrigin = EnsureRoot<AccountId>;
+}
+
+impl pallet_utility::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
+    type PalletsOrigin = OriginCaller;
+    type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+    pub RelayNetwork: NetworkId = NetworkId::Kusama;
+    pub UniversalLocation: InteriorMultiLocation =
+        X2(GlobalConsensus(RelayNetwork::get()), Parachain(2085));
+    pub const UnitWeightCost: u64 = 1;
+    pub const MaxInstructions: u32 = 100;
+}
+
+pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
+
+#[cfg(feature = "runtime-benchmarks")]
+parameter_types! {
+    pub ReachableDest: Option<MultiLocation> = Some(Parent.into());
+}
+
+// XCM is neither sent nor executed by the precompile's calls
+impl pallet_xcm::Config for Runtime {
+    const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+    type XcmRouter = ();
+    type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+    type XcmExecuteFilter = Nothing;
+    type XcmExecutor = ();
+    type XcmTeleportFilter = Nothing;
+    type XcmReserveTransferFilter = Nothing;
+    type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+    type UniversalLocation = UniversalLocation;
+    type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+    type Currency = Balances;
+    type CurrencyMatcher = ();
+    type TrustedLockers = ();
+    type SovereignAccountOf = ();
+    type MaxLockers = ConstU32<8>;
+    type WeightInfo = pallet_xcm::TestWeightInfo;
+    #[cfg(feature = "runtime-benchmarks")]
+    type ReachableDest = ReachableDest;
+}
+
+pub struct AccountIdToMultiLocation;
+impl Convert<AccountId, MultiLocation> for AccountIdToMultiLocation {
+    fn convert(account_id: AccountId) -> MultiLocation {
+        X1(Junction::AccountId32 {
+            network: None,
+            id: account_id.into(),
+        })
+        .into()
+    }
+}
+
+parameter_types! {
+    pub const XcmHelperPalletId: PalletId = PalletId(*b"par/fees");
+    pub const NotifyTimeout: BlockNumber = 100;
+    pub RelayLocation: MultiLocation = MultiLocation::parent();
+    pub RefundLocation: AccountId = ParaId::from(2085).into_account_truncating();
+}
+
+impl pallet_xcm_helper::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type Assets = Assets;
+    type XcmSender = ();
+    type PalletId = XcmHelperPalletId;
+    type RelayNetwork = RelayNetwork;
+    type RelayLocation = RelayLocation;
+    type NotifyTimeout = NotifyTimeout;
+    type AccountIdToMultiLocation = AccountIdToMultiLocation;
+    type RefundLocation = RefundLocation;
+    type BlockNumberProvider = frame_system::Pallet<Runtime>;
+    type WeightInfo = ();
+    type RelayCurrency = StakingCurrency;
+}
+
+pub struct MockPriceFeeder;
+impl PriceFeeder for MockPriceFeeder {
+    fn get_price(_asset_id: &CurrencyId) -> Option<PriceDetail> {
+        Some((Price::one(), 1))
+    }
+}
+
+parameter_types! {
+    pub const LoansPalletId: PalletId = PalletId(*b"par/loan");
+    pub const RewardAssetId: CurrencyId = HKO;
+    pub const LiquidationFreeAssetId: CurrencyId = KSM;
+}
+
+impl pallet_loans::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type PriceFeeder = MockPriceFeeder;
+    type PalletId = LoansPalletId;
+    type ReserveOrigin = EnsureRoot<AccountId>;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type WeightInfo = ();
+    type UnixTime = Timestamp;
+    type Assets = Assets;
+    type RewardAssetId = RewardAssetId;
+    type LiquidationFreeAssetId = LiquidationFreeAssetId;
+    type FlashLoanReceiver = ();
+    type InsuranceAccount = ();
+    type MaxBatchActions = ConstU32<8>;
+    type CollateralDustValue = frame_support::traits::ConstU128<0>;
+}
+
+parameter_types! {
+    pub const AMMPalletId: PalletId = PalletId(*b"par/ammp");
+    pub DefaultLpFee: Ratio = Ratio::from_rational(25u32, 10000u32);
+    pub const MinimumLiquidity: u128 = 1_000u128;
+    pub const MaxObservations: u32 = 4;
+    pub const ObservationPeriod: BlockNumber = 10;
+    pub const MaxTicks: u32 = 8;
+    pub const MinPositionLiquidity: u128 = 1_000_000u128;
+    pub LockAccountId: AccountId = Account::Bogus.into();
+    pub const MaxLengthRoute: u8 = 10;
+}
+
+impl pallet_amm::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Assets = CurrencyAdapter;
+    type PalletId = AMMPalletId;
+    type LockAccountId = LockAccountId;
+    type AMMWeightInfo = ();
+    type CreatePoolOrigin = EnsureRoot<AccountId>;
+    type ProtocolFeeUpdateOrigin = EnsureRoot<AccountId>;
+    type LpFee = DefaultLpFee;
+    type MinimumLiquidity = MinimumLiquidity;
+    type MaxLengthRoute = MaxLengthRoute;
+    type MaxObservations = MaxObservations;
+    type ObservationPeriod = ObservationPeriod;
+    type MaxTicks = MaxTicks;
+    type MinPositionLiquidity = MinPositionLiquidity;
+    type GetNativeCurrencyId = NativeCurrencyId;
+}
+
+pub struct Members;
+impl SortedMembers<AccountId> for Members {
+    fn sorted_members() -> Vec<AccountId> {
+        vec![Account::Alice.into()]
+    }
+}
+
+pub struct Decimal;
+impl DecimalProvider<CurrencyId> for Decimal {
+    fn get_decimal(asset_id: &CurrencyId) -> Option<u8> {
+        match *asset_id {
+            KSM | SKSM => Some(12),
+            _ => None,
+        }
+    }
+}
+
+pub struct RelayChainValidationDataProvider;
+impl BlockNumberProvider for RelayChainValidationDataProvider {
+    type BlockNumber = BlockNumber;
+
+    fn current_block_number() -> Self::BlockNumber {
+        0
+    }
+}
+
+impl ValidationDataProvider for RelayChainValidationDataProvider {
+    fn validation_data() -> Option<PersistedValidationData> {
+        None
+    }
+}
+
+parameter_types! {
+    pub const StakingPalletId: PalletId = PalletId(*b"par/lqsk");
+    pub const EraLength: BlockNumber = 10;
+    pub SelfParaId: ParaId = ParaId::from(2085);
+    pub const MinStake: Balance = 0;
+    pub const MinUnstake: Balance = 0;
+    pub const StakingCurrency: CurrencyId = KSM;
+    pub const LiquidCurrency: CurrencyId = SKSM;
+    pub const CollateralCurrency: CurrencyId = KSM_U;
+    pub const XcmFees: Balance = 0;
+    pub LoansInstantUnstakeFee: Rate = Rate::saturating_from_rational(8u32, 1000u32);
+    pub MatchingPoolFastUnstakeFee: Rate = Rate::saturating_from_rational(1u32, 1000u32);
+    pub const BondingDuration: EraIndex = 3;
+    pub const MinNominatorBond: Balance = 0;
+    pub const NumSlashingSpans: u32 = 0;
+    pub DerivativeIndexList: Vec<u16> = vec![0];
+    pub const ElectionSolutionStoredOffset: BlockNumber = 10;
+    pub ProtocolFeeReceiver: AccountId = Account::Bogus.into();
+    pub const MaxValidators: u32 = 16;
+    pub const MaxNominations: u32 = 16;
+    pub const EraPointsDepth: u32 = 4;
+    pub const ExchangeRateHistoryDepth: u32 = 4;
+    pub const EraAdvanceBounty: Balance = 0;
+    pub const MaxPayoutsPerBlock: u32 = 1;
+    pub const MaxPendingPayouts: u32 = 3;
+}
+
+impl pallet_liquid_staking::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type PalletId = StakingPalletId;
+    type LoansPalletId = LoansPalletId;
+    type SelfParaId = SelfParaId;
+    type WeightInfo = ();
+    type StakingCurrency = StakingCurrency;
+    type LiquidCurrency = LiquidCurrency;
+    type CollateralCurrency = CollateralCurrency;
+    type DerivativeIndexList = DerivativeIndexList;
+    type XcmFees = XcmFees;
+    type LoansInstantUnstakeFee = LoansInstantUnstakeFee;
+    type MatchingPoolFastUnstakeFee = MatchingPoolFastUnstakeFee;
+    type Assets = CurrencyAdapter;
+    type RelayOrigin = EnsureRoot<AccountId>;
+    type EraLength = EraLength;
+    type MinStake = MinStake;
+    type MinUnstake = MinUnstake;
+    type XCM = XcmHelper;
+    type BondingDuration = BondingDuration;
+    type MinNominatorBond = MinNominatorBond;
+    type RelayChainValidationDataProvider = RelayChainValidationDataProvider;
+    type Loans = Loans;
+    type Members = Members;
+    type NumSlashingSpans = NumSlashingSpans;
+    type DistributionStrategy = AverageDistribution;
+    type ElectionSolutionStoredOffset = ElectionSolutionStoredOffset;
+    type ProtocolFeeReceiver = ProtocolFeeReceiver;
+    type Decimal = Decimal;
+    type NominationStrategy = EraPointsNomination;
+    type MaxValidators = MaxValidators;
+    type MaxNominations = MaxNominations;
+    type EraPointsDepth = EraPointsDepth;
+    type ExchangeRateHistoryDepth = ExchangeRateHistoryDepth;
+    type EraAdvanceBounty = EraAdvanceBounty;
+    type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+    type MaxPendingPayouts = MaxPendingPayouts;
+    type AMM = DefaultAMM;
+    type StableSwap = ();
+}
+
+parameter_types! {
+    pub BlockGasLimit: U256 = U256::max_value();
+    pub const PrecompilesValue: Precompiles<Runtime> = Precompiles(PhantomData);
+    pub WeightPerGas: Weight = Weight::from_ref_time(1);
+}
+
+impl pallet_evm::Config for Runtime {
+    type FeeCalculator = ();
+    type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+    type CallOrigin = EnsureAddressRoot<AccountId>;
+    type WithdrawOrigin = EnsureAddressNever<AccountId>;
+    type AddressMapping = MockAddressMapping;
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type Runner = pallet_evm::runner::stack::Runner<Self>;
+    type PrecompilesType = Precompiles<Self>;
+    type PrecompilesValue = PrecompilesValue;
+    type ChainId = ();
+    type OnChargeTransaction = ();
+    type BlockGasLimit = BlockGasLimit;
+    type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+    type FindAuthor = ();
+    type WeightPerGas = WeightPerGas;
+    type OnCreate = ();
+}
+
+// Configure a mock runtime to test the pallet.
+construct_runtime!(
+    pub enum Runtime where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        Utility: pallet_utility::{Pallet, Call, Event},
+        Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+        CurrencyAdapter: pallet_currency_adapter::{Pallet, Call},
+        PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
+        XcmHelper: pallet_xcm_helper::{Pallet, Storage, Call, Event<T>},
+        Loans: pallet_loans::{Pallet, Storage, Call, Event<T>},
+        DefaultAMM: pallet_amm::{Pallet, Call, Storage, Event<T>},
+        LiquidStaking: pallet_liquid_staking::{Pallet, Storage, Call, Event<T>},
+        Evm: pallet_evm::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+#[derive(Default)]
+pub struct Precompiles<R>(PhantomData<R>);
+
+impl<R> PrecompileSet for Precompiles<R>
+where
+    LiquidStakingPrecompile<R>: Precompile,
+{
+    fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<EvmResult<PrecompileOutput>> {
+        match handle.code_address() {
+            a if a == hash(PRECOMPILE_ADDRESS) => {
+                Some(LiquidStakingPrecompile::<R>::execute(handle))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_precompile(&self, address: H160) -> bool {
+        address == hash(PRECOMPILE_ADDRESS)
+    }
+}
+
+fn hash(a: u64) -> H160 {
+    H160::from_low_u64_be(a)
+}
+
+pub(crate) struct ExtBuilder {
+    // endowed accounts with staking assets
+    balances: Vec<(AccountId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> ExtBuilder {
+        ExtBuilder { balances: vec![] }
+    }
+}
+
+impl ExtBuilder {
+    pub(crate) fn with_balances(mut self, balances: Vec<(AccountId, Balance)>) -> Self {
+        self.balances = balances;
+        self
+    }
+
+    pub(crate) fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default()
+            .build_storage::<Runtime>()
+            .expect("Frame system builds valid default genesis config");
+
+        GenesisBuild::<Runtime>::assimilate_storage(
+            &pallet_liquid_staking::GenesisConfig {
+                exchange_rate: Rate::one(),
+                reserve_factor: Ratio::from_perthousand(5),
+            },
+            &mut t,
+        )
+        .expect("Pallet liquid staking storage can be assimilated");
+
+        let mut ext = sp_io::TestExternalities::new(t);
+        ext.execute_with(|| {
+            let owner: AccountId = Account::Alice.into();
+            for asset_id in [KSM, SKSM, KSM_U, LP_KSM_SKSM] {
+                Assets::force_create(
+                    RuntimeOrigin::root(),
+                    asset_id.into(),
+                    owner.clone(),
+                    true,
+                    1,
+                )
+                .expect("Assets can be created");
+            }
+            for (who, amount) in self.balances {
+                Assets::mint(
+                    RuntimeOrigin::signed(owner.clone()),
+                    KSM.into(),
+                    who,
+                    amount,
+                )
+                .expect("Staking assets can be minted");
+            }
+            LiquidStaking::update_staking_ledger_cap(RuntimeOrigin::root(), ksm(10000f64))
+                .expect("Staking ledger cap can be set");
+
+            System::set_block_number(1);
+            Timestamp::set_timestamp(6000);
+        });
+        ext
+    }
+}
+
+pub(crate) fn events() -> Vec<RuntimeEvent> {
+    System::events()
+        .into_iter()
+        .map(|r| r.event)
+        .collect::<Vec<_>>()
+}
@@ -0,0 +1,453 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{assert_ok, traits::fungibles::Inspect};
+use std::str::from_utf8;
+
+use crate::mock::*;
+use crate::*;
+
+use pallet_liquid_staking::{types::UnstakeReceipt, CurrentEra, MatchingPool};
+use precompile_utils::{testing::*, Address, EvmDataWriter};
+use primitives::tokens::{KSM, LP_KSM_SKSM, SKSM};
+
+fn precompiles() -> Precompiles<Runtime> {
+    PrecompilesValue::get()
+}
+
+fn alice() -> AccountId {
+    Account::Alice.into()
+}
+
+fn stake(who: Account, amount: Balance) {
+    precompiles()
+        .prepare_test(
+            who,
+            Account::Precompile,
+            EvmDataWriter::new_with_selector(Action::Stake)
+                .write(U256::from(amount))
+                .build(),
+        )
+        .execute_returns(EvmDataWriter::new().write(true).build());
+}
+
+fn create_amm_pool() {
+    assert_ok!(Assets::mint(
+        RuntimeOrigin::signed(alice()),
+        SKSM.into(),
+        alice(),
+        ksm(50f64)
+    ));
+    // sKSM is sold above the exchange rate in the AMM
+    assert_ok!(DefaultAMM::create_pool(
+        RuntimeOrigin::root(),
+        (SKSM, KSM),
+        (ksm(50f64), ksm(60f64)),
+        alice(),
+        LP_KSM_SKSM,
+    ));
+}
+
+#[test]
+fn selector_less_than_four_bytes() {
+    ExtBuilder::default().build().execute_with(|| {
+        // This selector is only three bytes long when four are required.
+        precompiles()
+            .prepare_test(Account::Alice, Account::Precompile, vec![1u8, 2u8, 3u8])
+            .execute_reverts(|output| output == b"tried to parse selector out of bounds");
+    });
+}
+
+#[test]
+fn no_selector_exists_but_length_is_right() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::Precompile,
+                vec![1u8, 2u8, 3u8, 4u8],
+            )
+            .execute_reverts(|output| output == b"unknown selector");
+    });
+}
+
+#[test]
+fn selectors() {
+    assert_eq!(Action::ExchangeRate as u32, 0x3ba0b9a9);
+    assert_eq!(Action::StakingToLiquid as u32, 0x92f8b83d);
+    assert_eq!(Action::LiquidToStaking as u32, 0xd238e634);
+    assert_eq!(Action::CurrentEra as u32, 0x973628f6);
+    assert_eq!(Action::EraStartBlock as u32, 0x59b6ed83);
+    assert_eq!(Action::MatchingPool as u32, 0xaacef18b);
+    assert_eq!(Action::Stake as u32, 0xa694fc3a);
+    assert_eq!(Action::Unstake as u32, 0xbc9699f1);
+    assert_eq!(Action::InstantUnstake as u32, 0xbd0461aa);
+    assert_eq!(Action::InstantUnstakeQuote as u32, 0x124e100c);
+    assert_eq!(Action::ClaimFor as u32, 0xddeae033);
+}
+
+#[test]
+fn get_exchange_rate() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::Precompile,
+                EvmDataWriter::new_with_selector(Action::ExchangeRate).build(),
+            )
+            .expect_cost(0) // TODO: Test db read/write costs
+            .expect_no_logs()
+            .execute_returns(
+                EvmDataWriter::new()
+                    .write(U256::from(10u128.pow(18)))
+                    .build(),
+            );
+    });
+}
+
+#[test]
+fn get_staking_to_liquid_and_liquid_to_staking() {
+    ExtBuilder::default().build().execute_with(|| {
+        pallet_liquid_staking::ExchangeRate::<Runtime>::put(Rate::saturating_from_rational(
+            2u32, 1u32,
+        ));
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::Precompile,
+                EvmDataWriter::new_with_selector(Action::StakingToLiquid)
+                    .write(U256::from(ksm(10f64)))
+                    .build(),
+            )
+            .expect_cost(0)
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(U256::from(ksm(5f64))).build());
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::Precompile,
+                EvmDataWriter::new_with_selector(Action::LiquidToStaking)
+                    .write(U256::from(ksm(5f64)))
+                    .build(),
+            )
+            .expect_cost(0)
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(U256::from(ksm(10f64))).build());
+    });
+}
+
+#[test]
+fn get_current_era_and_era_start_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        CurrentEra::<Runtime>::put(7);
+        pallet_liquid_staking::EraStartBlock::<Runtime>::put(70);
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::Precompile,
+                EvmDataWriter::new_with_selector(Action::CurrentEra).build(),
+            )
+            .expect_cost(0)
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(7u32).build());
+
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::Precompile,
+                EvmDataWriter::new_with_selector(Action::EraStartBlock).build(),
+            )
+            .expect_cost(0)
+            .expect_no_logs()
+            .execute_returns(EvmDataWriter::new().write(U256::from(70u64)).build());
+    });
+}
+
+#[test]
+fn stake_and_get_matching_pool() {
+    ExtBuilder::default()
+        .with_balances(vec![(alice(), ksm(100f64))])
+        .build()
+        .execute_with(|| {
+            stake(Account::Alice, ksm(10f64));
+
+            // The reserve factor keeps 0.5% of the stake
+            assert_eq!(
+                <Runtime as pallet_liquid_staking::Config>::Assets::balance(SKSM, &alice()),
+                ksm(9.95f64)
+            );
+
+            precompiles()
+                .prepare_test(
+                    Account::Alice,
+                    Account::Precompile,
+                    EvmDataWriter::new_with_selector(Action::MatchingPool).build(),
+                )
+                .expect_cost(0)
+                .expect_no_logs()
+                .execute_returns(
+                    EvmDataWriter::new()
+                        .write(U256::from(ksm(9.95f64)))
+                        .write(U256::zero())
+                        .write(U256::zero())
+                        .write(U256::zero())
+                        .build(),
+                );
+        });
+}
+
+#[test]
+fn stake_fails_without_enough_balance() {
+    ExtBuilder::default()
+        .with_balances(vec![(alice(), ksm(1f64))])
+        .build()
+        .execute_with(|| {
+            precompiles()
+                .prepare_test(
+                    Account::Alice,
+                    Account::Precompile,
+                    EvmDataWriter::new_with_selector(Action::Stake)
+                        .write(U256::from(ksm(10f64)))
+                        .build(),
+                )
+                .execute_reverts(|output| {
+                    from_utf8(output)
+                        .unwrap()
+                        .contains("Dispatched call failed with error: DispatchErrorWithPostInfo")
+                });
+        });
+}
+
+#[test]
+fn unstake_via_relaychain() {
+    ExtBuilder::default()
+        .with_balances(vec![(alice(), ksm(100f64))])
+        .build()
+        .execute_with(|| {
+            stake(Account::Alice, ksm(10f64));
+
+            precompiles()
+                .prepare_test(
+                    Account::Alice,
+                    Account::Precompile,
+                    EvmDataWriter::new_with_selector(Action::Unstake)
+                        .write(U256::from(ksm(5f64)))
+                        .write(0u8)
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+
+            assert_eq!(
+                LiquidStaking::unstake_receipt(0).unwrap(),
+                UnstakeReceipt {
+                    owner: alice(),
+                    value: ksm(5f64),
+                    era: 4
+                }
+            );
+        });
+}
+
+#[test]
+fn unstake_fails_with_invalid_provider() {
+    ExtBuilder::default()
+        .with_balances(vec![(alice(), ksm(100f64))])
+        .build()
+        .execute_with(|| {
+            stake(Account::Alice, ksm(10f64));
+
+            // The AMM can only be used through `instantUnstake`
+            for provider in [3u8, 4u8] {
+                precompiles()
+                    .prepare_test(
+                        Account::Alice,
+                        Account::Precompile,
+                        EvmDataWriter::new_with_selector(Action::Unstake)
+                            .write(U256::from(ksm(5f64)))
+                            .write(provider)
+                            .build(),
+                    )
+                    .execute_reverts(|output| output == b"Invalid unstake provider");
+            }
+        });
+}
+
+#[test]
+fn get_instant_unstake_quote() {
+    ExtBuilder::default()
+        .with_balances(vec![(alice(), ksm(100f64))])
+        .build()
+        .execute_with(|| {
+            stake(Account::Alice, ksm(10f64));
+
+            // Only the matching pool can serve the unstake before the AMM pool exists
+            let matching_pool_amount = ksm(3f64) - ksm(3f64) / 1000;
+            precompiles()
+                .prepare_test(
+                    Account::Alice,
+                    Account::Precompile,
+                    EvmDataWriter::new_with_selector(Action::InstantUnstakeQuote)
+                        .write(U256::from(ksm(3f64)))
+                        .build(),
+                )
+                .expect_cost(0)
+                .expect_no_logs()
+                .execute_returns(
+                    EvmDataWriter::new()
+                        .write(UnstakeProvider::MatchingPool as u8)
+                        .write(U256::from(matching_pool_amount))
+                        .build(),
+                );
+
+            create_amm_pool();
+            let amount_out = *DefaultAMM::get_amounts_out(ksm(3f64), vec![SKSM, KSM])
+                .unwrap()
+                .last()
+                .unwrap();
+            precompiles()
+                .prepare_test(
+                    Account::Alice,
+                    Account::Precompile,
+                    EvmDataWriter::new_with_selector(Action::InstantUnstakeQuote)
+                        .write(U256::from(ksm(3f64)))
+                        .build(),
+                )
+                .expect_cost(0)
+                .expect_no_logs()
+                .execute_returns(
+                    EvmDataWriter::new()
+                        .write(UnstakeProvider::Amm as u8)
+                        .write(U256::from(amount_out))
+                        .build(),
+                );
+        });
+}
+
+#[test]
+fn instant_unstake_via_amm() {
+    ExtBuilder::default()
+        .with_balances(vec![(alice(), ksm(100f64))])
+        .build()
+        .execute_with(|| {
+            stake(Account::Alice, ksm(10f64));
+            create_amm_pool();
+            let amount_out = *DefaultAMM::get_amounts_out(ksm(3f64), vec![SKSM, KSM])
+                .unwrap()
+                .last()
+                .unwrap();
+
+            // Reverts when the best quote is below the minimum
+            precompiles()
+                .prepare_test(
+                    Account::Alice,
+                    Account::Precompile,
+                    EvmDataWriter::new_with_selector(Action::InstantUnstake)
+                        .write(U256::from(ksm(3f64)))
+                        .write(U256::from(amount_out + 1))
+                        .build(),
+                )
+                .execute_reverts(|output| from_utf8(output).unwrap().contains("TooLittleReceived"));
+
+            let balance_before =
+                <Runtime as pallet_liquid_staking::Config>::Assets::balance(KSM, &alice());
+            precompiles()
+                .prepare_test(
+                    Account::Alice,
+                    Account::Precompile,
+                    EvmDataWriter::new_with_selector(Action::InstantUnstake)
+                        .write(U256::from(ksm(3f64)))
+                        .write(U256::from(amount_out))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+            assert_eq!(
+                <Runtime as pallet_liquid_staking::Config>::Assets::balance(KSM, &alice()),
+                balance_before + amount_out
+            );
+        });
+}
+
+#[test]
+fn claim_unlocked_assets() {
+    ExtBuilder::default()
+        .with_balances(vec![(alice(), ksm(100f64))])
+        .build()
+        .execute_with(|| {
+            stake(Account::Alice, ksm(10f64));
+            assert_ok!(LiquidStaking::unstake(
+                RuntimeOrigin::signed(alice()),
+                ksm(4.95f64),
+                Default::default()
+            ));
+
+            // Nothing can be claimed before the target era
+            precompiles()
+                .prepare_test(
+                    Account::Alice,
+                    Account::Precompile,
+                    EvmDataWriter::new_with_selector(Action::ClaimFor)
+                        .write(Address(Account::Alice.into()))
+                        .build(),
+                )
+                .execute_reverts(|output| from_utf8(output).unwrap().contains("NothingToClaim"));
+
+            // The stake was matched with the unstake and the era of the receipt arrived
+            MatchingPool::<Runtime>::kill();
+            CurrentEra::<Runtime>::put(4);
+
+            let balance_before =
+                <Runtime as pallet_liquid_staking::Config>::Assets::balance(KSM, &alice());
+            precompiles()
+                .prepare_test(
+                    Account::Alice,
+                    Account::Precompile,
+                    EvmDataWriter::new_with_selector(Action::ClaimFor)
+                        .write(Address(Account::Alice.into()))
+                        .build(),
+                )
+                .execute_returns(EvmDataWriter::new().write(true).build());
+            assert_eq!(
+                <Runtime as pallet_liquid_staking::Config>::Assets::balance(KSM, &alice()),
+                balance_before + ksm(4.95f64)
+            );
+            assert!(LiquidStaking::unstake_receipt(0).is_none());
+        });
+}
+
+#[test]
+fn claim_for_requires_a_member_and_unlockings() {
+    ExtBuilder::default().build().execute_with(|| {
+        precompiles()
+            .prepare_test(
+                Account::Bob,
+                Account::Precompile,
+                EvmDataWriter::new_with_selector(Action::ClaimFor)
+                    .write(Address(Account::Bob.into()))
+                    .build(),
+            )
+            .execute_reverts(|output| from_utf8(output).unwrap().contains("BadOrigin"));
+        precompiles()
+            .prepare_test(
+                Account::Alice,
+                Account::Precompile,
+                EvmDataWriter::new_with_selector(Action::ClaimFor)
+                    .write(Address(Account::Bob.into()))
+                    .build(),
+            )
+            .execute_reverts(|output| from_utf8(output).unwrap().contains("NoUnlockings"));
+    });
+}
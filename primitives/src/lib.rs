@@ -16,6 +16,7 @@
 
 use codec::{Decode, Encode};
 pub use cumulus_primitives_core::{ParaId, PersistedValidationData};
+use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 pub use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -126,6 +127,16 @@ pub enum DataProviderId {
     Aggregated = 0isize,
 }
 
+/// The market a hop of a route is traded on
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SwapVenue {
+    /// Constant product pools of pallet-amm
+    Amm,
+    /// Stable pools of pallet-stableswap
+    StableSwap,
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 #[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
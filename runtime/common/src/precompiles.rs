@@ -29,6 +29,7 @@ use pallet_evm_precompile_blake2::Blake2F;
 use pallet_evm_precompile_bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
 use pallet_evm_precompile_dispatch::Dispatch;
 use pallet_evm_precompile_ed25519::Ed25519Verify;
+use pallet_evm_precompile_liquid_staking::LiquidStakingPrecompile;
 use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
@@ -49,7 +50,7 @@ where
         Self(Default::default())
     }
     pub fn used_addresses() -> impl Iterator<Item = H160> {
-        sp_std::vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 1024, 1025, 1026, 1027, 2050]
+        sp_std::vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 1024, 1025, 1026, 1027, 2050, 2051]
             .into_iter()
             .map(hash)
     }
@@ -59,6 +60,7 @@ impl<R, M> PrecompileSet for ParallelPrecompiles<R, M>
 where
     Erc20AssetsPrecompileSet<R>: PrecompileSet,
     Erc20BalancesPrecompile<R, M>: Precompile,
+    LiquidStakingPrecompile<R>: Precompile,
     Dispatch<R>: Precompile,
     R: pallet_evm::Config
         + AddressToAssetId<<R as pallet_assets::Config>::AssetId>
@@ -99,6 +101,7 @@ where
             a if a == hash(1027) => Some(Ed25519Verify::execute(handle)),
             //Parallel precompiles:
             a if a == hash(2050) => Some(Erc20BalancesPrecompile::<R, M>::execute(handle)),
+            a if a == hash(2051) => Some(LiquidStakingPrecompile::<R>::execute(handle)),
             a if &a.to_fixed_bytes()[0..4] == ASSET_PRECOMPILE_ADDRESS_PREFIX => {
                 Erc20AssetsPrecompileSet::<R>::new().execute(handle)
             }
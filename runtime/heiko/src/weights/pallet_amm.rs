@@ -29,36 +29,40 @@ use sp_std::marker::PhantomData;
 /// Weight functions for `pallet_amm`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_amm::WeightInfo for WeightInfo<T> {
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: AMM Pools (r:1 w:1)
 	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM Observations (r:1 w:1)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:5 w:5)
 	fn add_liquidity() -> Weight {
-		// Minimum execution time: 172_184 nanoseconds.
 		Weight::from_ref_time(173_791_000)
-			.saturating_add(T::DbWeight::get().reads(10))
-			.saturating_add(T::DbWeight::get().writes(9))
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().writes(10))
 	}
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: AMM Pools (r:1 w:1)
 	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM Observations (r:1 w:1)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:5 w:5)
 	fn remove_liquidity() -> Weight {
-		// Minimum execution time: 188_419 nanoseconds.
 		Weight::from_ref_time(190_666_000)
-			.saturating_add(T::DbWeight::get().reads(10))
-			.saturating_add(T::DbWeight::get().writes(9))
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().writes(10))
 	}
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:0)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:6 w:6)
 	// Storage: System Account (r:2 w:2)
 	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM AssetPools (r:0 w:2)
 	fn create_pool() -> Weight {
-		// Minimum execution time: 214_658 nanoseconds.
 		Weight::from_ref_time(216_739_000)
-			.saturating_add(T::DbWeight::get().reads(13))
-			.saturating_add(T::DbWeight::get().writes(12))
+			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().writes(14))
 	}
 	// Storage: AMM ProtocolFee (r:0 w:1)
 	fn update_protocol_fee() -> Weight {
@@ -72,4 +76,46 @@ impl<T: frame_system::Config> pallet_amm::WeightInfo for WeightInfo<T> {
 		Weight::from_ref_time(26_507_000)
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	// Hand estimate, not benchmarked.
+	// Storage: AMM Pools (r:1 w:0)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM AssetPools (r:0 w:2)
+	fn create_concentrated_pool() -> Weight {
+		Weight::from_ref_time(41_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:1)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn add_concentrated_liquidity() -> Weight {
+		Weight::from_ref_time(173_000_000)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:1)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn remove_concentrated_liquidity() -> Weight {
+		Weight::from_ref_time(166_000_000)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:0)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn collect_fees() -> Weight {
+		Weight::from_ref_time(131_000_000)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
 }
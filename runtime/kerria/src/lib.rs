@@ -109,7 +109,7 @@ use primitives::{
     tokens::{DOT, EUSDC, EUSDT, PARA, SDOT},
     AccountId, AuraId, Balance, BlockNumber, ChainId, CurrencyId, DataProviderId, EraIndex, Hash,
     Index, Liquidity, Moment, PersistedValidationData, Price, Rate, Ratio, Shortfall, Signature,
-    DOT_U,
+    SwapVenue, DOT_U,
 };
 
 use runtime_common::{
@@ -494,6 +494,9 @@ impl pallet_assets::Config for Runtime {
 parameter_types! {
     pub const RewardAssetId: CurrencyId = PARA;
     pub const LiquidationFreeAssetId: CurrencyId = DOT;
+    pub LoansInsuranceAccount: Option<AccountId> = Some(TreasuryAccount::get());
+    pub const LoansMaxBatchActions: u32 = 8;
+    pub const LoansCollateralDustValue: Balance = 10_000_000_000_000_000; // 0.01 USD
 }
 
 impl pallet_loans::Config for Runtime {
@@ -507,6 +510,10 @@ impl pallet_loans::Config for Runtime {
     type Assets = CurrencyAdapter;
     type RewardAssetId = RewardAssetId;
     type LiquidationFreeAssetId = LiquidationFreeAssetId;
+    type FlashLoanReceiver = pallet_loans::FlashLoanCallDispatcher<RuntimeCall>;
+    type InsuranceAccount = LoansInsuranceAccount;
+    type MaxBatchActions = LoansMaxBatchActions;
+    type CollateralDustValue = LoansCollateralDustValue;
 }
 
 parameter_types! {
@@ -526,6 +533,13 @@ parameter_types! {
     pub const NumSlashingSpans: u32 = 0;
     pub DerivativeIndexList: Vec<u16> = vec![0, 1];
     pub const ElectionSolutionStoredOffset: BlockNumber = 18;
+    pub const MaxValidators: u32 = 256;
+    pub const MaxNominations: u32 = 16;
+    pub const EraPointsDepth: u32 = 28;
+    pub const ExchangeRateHistoryDepth: u32 = 30;
+    pub const EraAdvanceBounty: Balance = 10_000_000; // 0.001DOT
+    pub const MaxPayoutsPerBlock: u32 = 8;
+    pub const MaxPendingPayouts: u32 = 512;
 }
 
 impl pallet_liquid_staking::Config for Runtime {
@@ -560,6 +574,16 @@ impl pallet_liquid_staking::Config for Runtime {
     type ElectionSolutionStoredOffset = ElectionSolutionStoredOffset;
     type ProtocolFeeReceiver = DefaultProtocolFeeReceiver;
     type Decimal = Decimal;
+    type NominationStrategy = pallet_liquid_staking::nomination::EraPointsNomination;
+    type MaxValidators = MaxValidators;
+    type MaxNominations = MaxNominations;
+    type EraPointsDepth = EraPointsDepth;
+    type ExchangeRateHistoryDepth = ExchangeRateHistoryDepth;
+    type EraAdvanceBounty = EraAdvanceBounty;
+    type MaxPayoutsPerBlock = MaxPayoutsPerBlock;
+    type MaxPendingPayouts = MaxPendingPayouts;
+    type AMM = AMM;
+    type StableSwap = ();
 }
 
 parameter_types! {
@@ -1775,6 +1799,10 @@ parameter_types! {
     pub DefaultProtocolFee: Ratio = Ratio::from_rational(5u32, 10000u32);   // 0.05%
     pub DefaultProtocolFeeReceiver: AccountId = TreasuryPalletId::get().into_account_truncating();
     pub const MinimumLiquidity: u128 = 1_000u128;
+    pub const MaxObservations: u32 = 48;
+    pub const ObservationPeriod: BlockNumber = 150;
+    pub const MaxTicks: u32 = 100;
+    pub const MinPositionLiquidity: u128 = 1_000_000_000_000u128;
 }
 
 impl pallet_amm::Config for Runtime {
@@ -1788,6 +1816,10 @@ impl pallet_amm::Config for Runtime {
     type LpFee = DefaultLpFee;
     type MinimumLiquidity = MinimumLiquidity;
     type MaxLengthRoute = MaxLengthRoute;
+    type MaxObservations = MaxObservations;
+    type ObservationPeriod = ObservationPeriod;
+    type MaxTicks = MaxTicks;
+    type MinPositionLiquidity = MinPositionLiquidity;
     type GetNativeCurrencyId = NativeCurrencyId;
 }
 
@@ -1881,6 +1913,7 @@ impl pallet_streaming::Config for Runtime {
 parameter_types! {
     pub const XcmHelperPalletId: PalletId = PalletId(*b"par/fees");
     pub const NotifyTimeout: BlockNumber = 100;
+    pub RelayLocation: MultiLocation = MultiLocation::parent();
 }
 
 impl pallet_xcm_helper::Config for Runtime {
@@ -1889,6 +1922,7 @@ impl pallet_xcm_helper::Config for Runtime {
     type Assets = Assets;
     type XcmSender = XcmRouter;
     type RelayNetwork = RelayNetwork;
+    type RelayLocation = RelayLocation;
     type PalletId = XcmHelperPalletId;
     type NotifyTimeout = NotifyTimeout;
     type AccountIdToMultiLocation = AccountIdToMultiLocation<AccountId>;
@@ -1900,6 +1934,10 @@ impl pallet_xcm_helper::Config for Runtime {
 
 parameter_types! {
     pub const MaxLengthRoute: u8 = 10;
+    pub const MaxSplitRoutes: u8 = 3;
+    pub const MaxRoutes: u8 = 16;
+    pub const MaxPools: u32 = 100;
+    pub const MinRouteLiquidity: Balance = 1_000_000;
     pub const RouterPalletId: PalletId = PalletId(*b"ammroute");
 }
 
@@ -1907,8 +1945,13 @@ impl pallet_router::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type PalletId = RouterPalletId;
     type AMM = AMM;
+    type StableSwap = ();
     type AMMRouterWeightInfo = weights::pallet_router::WeightInfo<Runtime>;
     type MaxLengthRoute = MaxLengthRoute;
+    type MaxSplitRoutes = MaxSplitRoutes;
+    type MaxRoutes = MaxRoutes;
+    type MaxPools = MaxPools;
+    type MinRouteLiquidity = MinRouteLiquidity;
     type Assets = CurrencyAdapter;
     type GetNativeCurrencyId = NativeCurrencyId;
 }
@@ -2012,7 +2055,7 @@ construct_runtime!(
 
         // AMM
         AMM: pallet_amm::{Pallet, Call, Storage, Event<T>} = 80,
-        AMMRoute: pallet_router::{Pallet, Call, Event<T>} = 81,
+        AMMRoute: pallet_router::{Pallet, Call, Storage, Event<T>} = 81,
         CurrencyAdapter: pallet_currency_adapter::{Pallet, Call} = 82,
 
         // Others
@@ -2063,6 +2106,15 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic =
     fp_self_contained::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra, H160>;
+/// Migrations to run on the next runtime upgrade, in order.
+pub type Migrations = (
+    pallet_loans::migrations::v7::MigrateToV7<Runtime>,
+    pallet_loans::migrations::v8::MigrateToV8<Runtime>,
+    pallet_loans::migrations::v9::MigrateToV9<Runtime>,
+    pallet_liquid_staking::migrations::v4::MigrateToV4<Runtime>,
+    pallet_amm::migrations::v1::MigrateToV1<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
     Runtime,
@@ -2070,7 +2122,7 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
-    (),
+    Migrations,
 >;
 
 impl fp_self_contained::SelfContainedCall for RuntimeCall {
@@ -2278,12 +2330,52 @@ impl_runtime_apis! {
         fn get_liquidation_threshold_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
             Loans::get_account_liquidation_threshold_liquidity(&account)
         }
+
+        fn get_account_positions(account: AccountId) -> Result<pallet_loans_rpc_runtime_api::AccountPositions<Balance>, DispatchError> {
+            Loans::get_account_positions(&account)
+        }
+
+        fn get_liquidation_candidates(start_key: Option<Vec<u8>>, limit: u32) -> Result<(Vec<pallet_loans_rpc_runtime_api::LiquidationCandidate<AccountId, Balance>>, Option<Vec<u8>>), DispatchError> {
+            Loans::get_liquidation_candidates(start_key, limit)
+        }
+    }
+
+    impl pallet_liquid_staking_rpc_runtime_api::LiquidStakingApi<Block, AccountId, Balance> for Runtime {
+        fn get_apy() -> Option<Rate> {
+            LiquidStaking::get_apy()
+        }
+
+        fn get_exchange_rate_history() -> Vec<pallet_liquid_staking_rpc_runtime_api::ExchangeRateSnapshot<Balance>> {
+            LiquidStaking::exchange_rate_history().into_inner()
+        }
+
+        fn get_pending_unstakes(account: AccountId) -> pallet_liquid_staking_rpc_runtime_api::PendingUnstakes<Balance> {
+            LiquidStaking::pending_unstakes(&account)
+        }
+
+        fn get_bond_distributions(amount: Balance) -> Vec<(u16, Balance)> {
+            LiquidStaking::get_bond_distributions(amount)
+        }
+
+        fn get_unbond_distributions(amount: Balance) -> Vec<(u16, Balance)> {
+            LiquidStaking::get_unbond_distributions(amount)
+        }
     }
 
     impl pallet_router_rpc_runtime_api::RouterApi<Block, Balance> for Runtime {
-        fn get_best_route(amount: Balance, token_in: CurrencyId, token_out: CurrencyId, reversed: bool) -> Result<(Vec<CurrencyId>, Balance), DispatchError> {
-            let (route, amount) = AMMRoute::get_best_route(amount, token_in, token_out, reversed)?;
-            Ok((route, amount))
+        fn get_best_route(amount: Balance, token_in: CurrencyId, token_out: CurrencyId, reversed: bool) -> Result<(Vec<CurrencyId>, Vec<SwapVenue>, Balance), DispatchError> {
+            let (route, venues, amount) = AMMRoute::get_best_route(amount, token_in, token_out, reversed)?;
+            Ok((route, venues, amount))
+        }
+
+        fn get_split_routes(amount: Balance, token_in: CurrencyId, token_out: CurrencyId) -> Result<(Vec<(Vec<CurrencyId>, Balance)>, Balance), DispatchError> {
+            AMMRoute::get_split_routes(amount, token_in, token_out)
+        }
+    }
+
+    impl pallet_amm_rpc_runtime_api::AMMApi<Block, BlockNumber> for Runtime {
+        fn get_twap(asset_in: CurrencyId, asset_out: CurrencyId, window: BlockNumber) -> Result<Price, DispatchError> {
+            AMM::get_twap((asset_in, asset_out), window)
         }
     }
 
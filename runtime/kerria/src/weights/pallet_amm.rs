@@ -28,33 +28,43 @@ use sp_std::marker::PhantomData;
 /// Weight functions for `pallet_amm`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_amm::WeightInfo for WeightInfo<T> {
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM Observations (r:1 w:1)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:5 w:5)
 	fn add_liquidity() -> Weight {
 		Weight::from_ref_time(214_746_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(10 as u64))
-			.saturating_add(T::DbWeight::get().writes(10 as u64))
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
 	}
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM Observations (r:1 w:1)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:5 w:5)
 	fn remove_liquidity() -> Weight {
 		Weight::from_ref_time(233_479_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(10 as u64))
-			.saturating_add(T::DbWeight::get().writes(10 as u64))
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
 	}
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:0)
 	// Storage: Assets Asset (r:3 w:3)
 	// Storage: Assets Account (r:6 w:6)
 	// Storage: System Account (r:2 w:2)
+	// Storage: AMM ProtocolFee (r:1 w:0)
+	// Storage: AMM AssetPools (r:0 w:2)
 	fn create_pool() -> Weight {
 		Weight::from_ref_time(286_985_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(13 as u64))
-			.saturating_add(T::DbWeight::get().writes(13 as u64))
+			.saturating_add(T::DbWeight::get().reads(15 as u64))
+			.saturating_add(T::DbWeight::get().writes(15 as u64))
 	}
 	// Storage: AMM ProtocolFee (r:0 w:1)
 	fn update_protocol_fee() -> Weight {
@@ -64,4 +74,50 @@ impl<T: frame_system::Config> pallet_amm::WeightInfo for WeightInfo<T> {
 	fn update_protocol_fee_receiver() -> Weight {
 		Weight::from_ref_time(4_114_000 as u64).saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM Pools (r:1 w:0)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM AssetPools (r:0 w:2)
+	fn create_concentrated_pool() -> Weight {
+		Weight::from_ref_time(54_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:1)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn add_concentrated_liquidity() -> Weight {
+		Weight::from_ref_time(227_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:1)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn remove_concentrated_liquidity() -> Weight {
+		Weight::from_ref_time(223_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(10 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:1)
+	// Storage: AMM Ticks (r:1 w:0)
+	// Storage: AMM Positions (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn collect_fees() -> Weight {
+		Weight::from_ref_time(175_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
 }
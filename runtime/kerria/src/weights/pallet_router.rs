@@ -28,22 +28,70 @@ use sp_std::marker::PhantomData;
 /// Weight functions for `pallet_router`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_router::WeightInfo for WeightInfo<T> {
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
 	// Storage: Assets Asset (r:2 w:2)
 	// Storage: Assets Account (r:4 w:4)
+	// Storage: AMM ConcentratedPools (r:1 w:0)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM Observations (r:1 w:1)
 	fn swap_exact_tokens_for_tokens() -> Weight {
 		Weight::from_ref_time(204_503_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(8 as u64))
-			.saturating_add(T::DbWeight::get().writes(8 as u64))
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
 	}
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM ConcentratedPools (r:1 w:0)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM Observations (r:1 w:1)
 	// Storage: Assets Account (r:4 w:4)
 	// Storage: Assets Asset (r:2 w:2)
 	fn swap_tokens_for_exact_tokens() -> Weight {
 		Weight::from_ref_time(202_029_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(8 as u64))
-			.saturating_add(T::DbWeight::get().writes(8 as u64))
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM Pools (r:1 w:0)
+	// Storage: AMM ConcentratedPools (r:2 w:1)
+	// Storage: AMM Ticks (r:2 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn swap_through_concentrated_pool() -> Weight {
+		Weight::from_ref_time(1_151_000_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM AssetPools (r:101 w:0)
+	// Storage: AMM ConcentratedPools (r:100 w:0)
+	// Storage: AMM Pools (r:100 w:0)
+	// Storage: AMMRoute RouteCache (r:1 w:1)
+	fn search_routes(p: u32, ) -> Weight {
+		Weight::from_ref_time(64_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(19_000_000 as u64).saturating_mul(p as u64))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().reads((3 as u64).saturating_mul(p as u64)))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: unknown [0x3a7472616e73616374696f6e5f6c6576656c3a] (r:1 w:1)
+	// Storage: AMM AssetPools (r:4 w:0)
+	// Storage: AMM ConcentratedPools (r:10 w:0)
+	// Storage: AMM Pools (r:8 w:5)
+	// Storage: AMM Observations (r:5 w:5)
+	// Storage: AMMRoute RouteCache (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:10 w:10)
+	fn swap_exact_tokens_for_tokens_split(h: u32, ) -> Weight {
+		Weight::from_ref_time(168_000_000 as u64)
+			.saturating_add(Weight::from_ref_time(152_000_000 as u64).saturating_mul(h as u64))
+			.saturating_add(T::DbWeight::get().reads(13 as u64))
+			.saturating_add(T::DbWeight::get().reads((5 as u64).saturating_mul(h as u64)))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+			.saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(h as u64)))
 	}
 }
@@ -343,4 +343,172 @@ impl<T: frame_system::Config> pallet_liquid_staking::WeightInfo for WeightInfo<T
 			.saturating_add(T::DbWeight::get().writes(6))
 			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
 	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn add_validator() -> Weight {
+		Weight::from_ref_time(24_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn remove_validator() -> Weight {
+		Weight::from_ref_time(24_000_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn set_validator_prefs() -> Weight {
+		Weight::from_ref_time(38_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking CurrentEra (r:1 w:0)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn set_validator_exposure() -> Weight {
+		Weight::from_ref_time(48_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking CurrentEra (r:1 w:0)
+	// Storage: LiquidStaking LastRewardedEra (r:1 w:1)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking Validators (r:1 w:1)
+	fn set_era_reward_points(n: u32, ) -> Weight {
+		Weight::from_ref_time(40_000_000)
+			.saturating_add(Weight::from_ref_time(6_000_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking UnstakeReceipts (r:1 w:0)
+	// Storage: LiquidStaking ExchangeRate (r:1 w:0)
+	// Storage: LiquidStaking PendingReceiptTransfers (r:0 w:1)
+	fn transfer_unstake_receipt() -> Weight {
+		Weight::from_ref_time(22_000_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking ExchangeRate (r:1 w:0)
+	// Storage: LiquidStaking UnstakeReserveFactor (r:1 w:0)
+	// Storage: LiquidStaking FastUnstakeRequests (r:1 w:1)
+	// Storage: LiquidStaking MatchingPool (r:1 w:1)
+	// Storage: Loans Markets (r:2 w:0)
+	// Storage: AMM Pools (r:1 w:1)
+	// Storage: Assets Asset (r:3 w:3)
+	// Storage: Assets Account (r:6 w:6)
+	fn instant_unstake() -> Weight {
+		Weight::from_ref_time(303_000_000)
+			.saturating_add(T::DbWeight::get().reads(28))
+			.saturating_add(T::DbWeight::get().writes(16))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking PendingPayouts (r:1 w:1)
+	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: LiquidStaking XcmRequests (r:0 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	fn payout_stakers() -> Weight {
+		Weight::from_ref_time(48_000_000)
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking DistributionWeights (r:1 w:0)
+	// Storage: LiquidStaking CurrentDistributionStrategy (r:0 w:1)
+	/// The range of component `n` is `[1, 16]`.
+	fn set_distribution_strategy(n: u32, ) -> Weight {
+		Weight::from_ref_time(15_000_000)
+			.saturating_add(Weight::from_ref_time(2_000_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(0))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking DistributionWeights (r:0 w:1)
+	fn set_distribution_weights(n: u32, ) -> Weight {
+		Weight::from_ref_time(17_000_000)
+			.saturating_add(Weight::from_ref_time(4_000_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(0))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking PendingReceiptTransfers (r:1 w:1)
+	// Storage: LiquidStaking UnstakeReceipts (r:1 w:1)
+	// Storage: LiquidStaking UnstakeReceiptsOf (r:1 w:2)
+	fn accept_unstake_receipt() -> Weight {
+		Weight::from_ref_time(32_000_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: LiquidStaking CurrentEra (r:1 w:1)
+	// Storage: ParachainSystem ValidationData (r:1 w:0)
+	// Storage: LiquidStaking StakingLedgers (r:3 w:0)
+	// Storage: LiquidStaking MatchingPool (r:1 w:0)
+	// Storage: Assets Asset (r:2 w:1)
+	// Storage: LiquidStaking ExchangeRate (r:1 w:1)
+	// Storage: LiquidStaking ExchangeRateHistory (r:1 w:1)
+	// Storage: LiquidStaking TotalReserves (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: LiquidStaking EraStartBlock (r:0 w:1)
+	// Storage: LiquidStaking IsMatched (r:0 w:1)
+	fn advance_era() -> Weight {
+		Weight::from_ref_time(149_000_000)
+			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking PendingRewards (r:1 w:1)
+	// Storage: XcmHelper XcmWeightFee (r:1 w:0)
+	// Storage: PolkadotXcm QueryCounter (r:1 w:1)
+	// Storage: ParachainSystem HostConfiguration (r:1 w:0)
+	// Storage: ParachainSystem PendingUpwardMessages (r:1 w:1)
+	// Storage: LiquidStaking XcmRequests (r:0 w:1)
+	// Storage: LiquidStaking RewardsWithdrawals (r:0 w:1)
+	// Storage: PolkadotXcm Queries (r:0 w:1)
+	fn withdraw_rewards() -> Weight {
+		Weight::from_ref_time(69_000_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking StakingLedgers (r:1 w:0)
+	// Storage: LiquidStaking PendingRewards (r:1 w:1)
+	// Storage: LiquidStaking RewardsWithdrawals (r:1 w:0)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	fn set_stash_rewards() -> Weight {
+		Weight::from_ref_time(52_000_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: LiquidStaking StakingLedgers (r:1 w:1)
+	// Storage: LiquidStaking IsUpdated (r:1 w:1)
+	// Storage: LiquidStaking XcmRequests (r:1 w:0)
+	// Storage: LiquidStaking ValidationData (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: LiquidStaking CommissionRate (r:1 w:0)
+	// Storage: LiquidStaking TotalReserves (r:1 w:1)
+	// Storage: LiquidStaking MatchingPool (r:1 w:1)
+	// Storage: LiquidStaking CurrentEra (r:1 w:0)
+	// Storage: LiquidStaking Slashes (r:1 w:1)
+	// Storage: LiquidStaking ExchangeRate (r:1 w:1)
+	fn set_staking_ledger() -> Weight {
+		Weight::from_ref_time(76_000_000)
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
 }
@@ -29,22 +29,67 @@ use sp_std::marker::PhantomData;
 /// Weight functions for `pallet_router`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_router::WeightInfo for WeightInfo<T> {
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
 	// Storage: Assets Asset (r:2 w:2)
 	// Storage: Assets Account (r:4 w:4)
+	// Storage: AMM ConcentratedPools (r:1 w:0)
 	// Storage: AMM Pools (r:1 w:1)
+	// Storage: AMM Observations (r:1 w:1)
 	fn swap_exact_tokens_for_tokens() -> Weight {
-		// Minimum execution time: 159_385 nanoseconds.
 		Weight::from_ref_time(161_069_000)
-			.saturating_add(T::DbWeight::get().reads(7))
-			.saturating_add(T::DbWeight::get().writes(7))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(8))
 	}
+	// Hand estimate: the benchmarked execution time with the storage accesses added since.
+	// Storage: AMM ConcentratedPools (r:1 w:0)
 	// Storage: AMM Pools (r:1 w:1)
-	// Storage: Assets Asset (r:2 w:2)
+	// Storage: AMM Observations (r:1 w:1)
 	// Storage: Assets Account (r:4 w:4)
+	// Storage: Assets Asset (r:2 w:2)
 	fn swap_tokens_for_exact_tokens() -> Weight {
-		// Minimum execution time: 159_751 nanoseconds.
 		Weight::from_ref_time(161_736_000)
-			.saturating_add(T::DbWeight::get().reads(7))
-			.saturating_add(T::DbWeight::get().writes(7))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: AMM Pools (r:1 w:0)
+	// Storage: AMM ConcentratedPools (r:2 w:1)
+	// Storage: AMM Ticks (r:2 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn swap_through_concentrated_pool() -> Weight {
+		Weight::from_ref_time(861_000_000)
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: AMM AssetPools (r:101 w:0)
+	// Storage: AMM ConcentratedPools (r:100 w:0)
+	// Storage: AMM Pools (r:100 w:0)
+	// Storage: AMMRoute RouteCache (r:1 w:1)
+	/// The range of component `p` is `[1, 100]`.
+	fn search_routes(p: u32, ) -> Weight {
+		Weight::from_ref_time(48_000_000)
+			.saturating_add(Weight::from_ref_time(15_000_000).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Hand estimate, not benchmarked.
+	// Storage: AMM AssetPools (r:4 w:0)
+	// Storage: AMM ConcentratedPools (r:10 w:0)
+	// Storage: AMM Pools (r:8 w:5)
+	// Storage: AMM Observations (r:5 w:5)
+	// Storage: AMMRoute RouteCache (r:1 w:1)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:10 w:10)
+	/// The range of component `h` is `[1, 5]`.
+	fn swap_exact_tokens_for_tokens_split(h: u32, ) -> Weight {
+		Weight::from_ref_time(128_000_000)
+			.saturating_add(Weight::from_ref_time(117_000_000).saturating_mul(h.into()))
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(h.into())))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(h.into())))
 	}
 }